@@ -1,32 +1,39 @@
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    ensure, to_binary, Addr, Binary, Decimal, Deps, DepsMut, Env, MessageInfo, Order, QueryRequest,
-    Response, StdError, StdResult, Uint128, WasmMsg, WasmQuery,
+    ensure, to_binary, Addr, Attribute, BankMsg, Binary, Coin, CosmosMsg, Decimal, Deps, DepsMut,
+    Env, Event, MessageInfo, Order, QueryRequest, Reply, Response, StdError, StdResult, Storage,
+    SubMsg, SubMsgResult, Uint128, Uint256, WasmMsg, WasmQuery,
 };
 use cw2::set_contract_version;
-use cw_core_interface::{
-    voting::{Query as DaoQuery, VotingPowerAtHeightResponse},
-    ExecuteMsg as DaoExecuteMsg,
-};
+use cw_core_interface::ExecuteMsg as DaoExecuteMsg;
 use cw_storage_plus::Bound;
 use cw_utils::ensure_from_older_version;
 use wynd_stake::hook::MemberDiff;
 
 use crate::msg::{
-    AdapterQueryMsg, AllOptionsResponse, CheckOptionResponse, ExecuteMsg, GaugeConfig,
-    GaugeResponse, InstantiateMsg, ListGaugesResponse, ListOptionsResponse, ListVotesResponse,
-    MigrateMsg, QueryMsg, SampleGaugeMsgsResponse, SelectedSetResponse,
+    AdapterQueryMsg, AllOptionsResponse, CheckOptionResponse, ExecutableGaugesResponse, ExecuteMsg,
+    ExecutionFailuresResponse, GaugeConfig, GaugeResponse, InstantiateMsg, ListGaugesResponse,
+    ListOptionsResponse, ListVotesResponse, MigrateMsg, OptionOrder, ParticipationHistoryResponse,
+    ParticipationResponse, QueryMsg, SampleGaugeMsgsResponse, SelectedSetResponse,
 };
 use crate::state::{
-    fetch_last_id, update_tally, votes, Config, Gauge, GaugeId, CONFIG, GAUGES, OPTION_BY_POINTS,
-    TALLY, TOTAL_CAST,
+    backfill_option_indexes, fetch_last_id, update_tally, votes, Config, EpochPayouts,
+    ExecutionFailure, Gauge, GaugeId, PendingPayout, ABSTAIN_OPTION, CONFIG, DEPRECATED_OPTIONS,
+    EPOCH_PAYOUTS, ESCROW, EXECUTION_FAILURES, GAUGES, GAUGES_BY_ADAPTER, GAUGES_BY_OPTION,
+    GAUGES_BY_TAG, OPTION_ADDED, OPTION_BY_ADDED, OPTION_BY_POINTS, OPTION_LIFETIME_REWARDS,
+    PARTICIPATION_HISTORY, PENDING_PAYOUTS, SYNC_CURSOR, TALLY, TOTAL_ABSTAIN, TOTAL_CAST,
+    VOTER_COUNT, VOTE_NONCES,
 };
 use crate::{error::ContractError, state::Reset};
 
 // version info for migration info
-const CONTRACT_NAME: &str = "crates.io:gauge";
-const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+pub const CONTRACT_NAME: &str = "crates.io:gauge";
+pub const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+// limits for tags, so listing them stays cheap
+const MAX_TAGS: usize = 10;
+const MAX_TAG_LENGTH: usize = 32;
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
@@ -43,17 +50,108 @@ pub fn instantiate(
         voting_powers,
         owner,
         dao_core: info.sender,
+        paused: false,
+        event_namespace: msg.event_namespace,
+        voting_query: msg.voting_query.unwrap_or_default(),
     };
     CONFIG.save(deps.storage, &config)?;
 
+    let mut events = vec![];
     for gauge in msg.gauges.unwrap_or_default() {
-        execute::attach_gauge(deps.branch(), env.clone(), gauge)?;
+        let (gauge_id, _) = execute::attach_gauge(deps.branch(), env.clone(), gauge)?;
+        events.push(gauge_state_change(
+            gauge_id,
+            GAUGE_STATE_NONE,
+            GAUGE_STATE_ACTIVE,
+            &config.dao_core,
+        ));
     }
 
-    Ok(Response::new()
+    let response = Response::new()
         .add_attribute("action", "instantiate")
         .add_attribute("owner", &msg.owner)
-        .add_attribute("voting_powers", &msg.voting_powers))
+        .add_attribute("voting_powers", &msg.voting_powers)
+        .add_events(events);
+    Ok(with_namespace(response, &config.event_namespace))
+}
+
+// lifecycle states reported by the `gauge_state_change` event
+const GAUGE_STATE_NONE: &str = "none";
+const GAUGE_STATE_ACTIVE: &str = "active";
+const GAUGE_STATE_CLOSED: &str = "closed";
+const GAUGE_STATE_STOPPED: &str = "stopped";
+
+/// Basis points making up a weight of 1.0 in `PlaceVotesBps`
+const BPS_PER_UNIT: u16 = 10_000;
+
+/// Reply id of the `post_execute_callback`, payouts of `isolate_failures` count up from zero
+const POST_EXECUTE_CALLBACK_REPLY_ID: u64 = u64::MAX;
+
+/// Fails while all gauges are paused
+fn ensure_not_paused(storage: &dyn Storage) -> Result<(), ContractError> {
+    ensure!(
+        !CONFIG.load(storage)?.paused,
+        ContractError::ContractPaused {}
+    );
+    Ok(())
+}
+
+/// Loads the gauge, failing with `GaugeNotFound` if there is none
+fn load_gauge(storage: &dyn Storage, gauge_id: GaugeId) -> Result<Gauge, ContractError> {
+    GAUGES
+        .may_load(storage, gauge_id)?
+        .ok_or(ContractError::GaugeNotFound { gauge: gauge_id })
+}
+
+/// Fails with `OptionNotFound` unless the gauge has the option
+fn ensure_option_exists(
+    storage: &dyn Storage,
+    gauge_id: GaugeId,
+    option: &str,
+) -> Result<(), ContractError> {
+    ensure!(
+        TALLY.has(storage, (gauge_id, option)),
+        ContractError::OptionNotFound {
+            gauge: gauge_id,
+            option: option.to_owned(),
+        }
+    );
+    Ok(())
+}
+
+/// Lifecycle state of the gauge, as reported in `gauge_state_change` events
+fn gauge_state(gauge: &Gauge) -> &'static str {
+    if gauge.is_stopped {
+        GAUGE_STATE_STOPPED
+    } else if gauge.voting_closed {
+        GAUGE_STATE_CLOSED
+    } else {
+        GAUGE_STATE_ACTIVE
+    }
+}
+
+/// Uniform event emitted on every lifecycle transition of a gauge, so indexers
+/// don't need to know about the individual execute messages
+fn gauge_state_change(gauge_id: GaugeId, from: &str, to: &str, by: &Addr) -> Event {
+    Event::new("gauge_state_change")
+        .add_attribute("gauge_id", gauge_id.to_string())
+        .add_attribute("from", from)
+        .add_attribute("to", to)
+        .add_attribute("by", by)
+}
+
+/// Adds the configured `namespace` attribute to the response and all its events,
+/// so a shared indexer can tell deployments apart
+fn with_namespace(mut response: Response, namespace: &Option<String>) -> Response {
+    if let Some(namespace) = namespace {
+        for event in response.events.iter_mut() {
+            event
+                .attributes
+                .push(Attribute::new("namespace", namespace));
+        }
+        response = response.add_attribute("namespace", namespace);
+    }
+    response
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
@@ -63,17 +161,31 @@ pub fn execute(
     info: MessageInfo,
     msg: ExecuteMsg,
 ) -> Result<Response, ContractError> {
-    match msg {
+    let namespace = CONFIG.load(deps.storage)?.event_namespace;
+    let response = match msg {
         ExecuteMsg::MemberChangedHook(hook_msg) => {
-            execute::member_changed(deps, info.sender, hook_msg.diffs)
+            execute::member_changed(deps, env, info.sender, hook_msg.diffs)
         }
         ExecuteMsg::CreateGauge(options) => execute::create_gauge(deps, env, info.sender, options),
+        ExecuteMsg::CloneGauge {
+            source_gauge,
+            new_adapter,
+            new_title,
+        } => execute::clone_gauge(deps, env, info.sender, source_gauge, new_adapter, new_title),
+        ExecuteMsg::SplitGauge {
+            gauge,
+            options_to_move,
+            new_config,
+        } => execute::split_gauge(deps, env, info.sender, gauge, options_to_move, new_config),
         ExecuteMsg::UpdateGauge {
             gauge_id,
             epoch_size,
             min_percent_selected,
             max_options_selected,
             max_available_percentage,
+            clear_max_available_percentage,
+            tags,
+            max_total_options,
         } => execute::update_gauge(
             deps,
             info.sender,
@@ -82,31 +194,102 @@ pub fn execute(
             min_percent_selected,
             max_options_selected,
             max_available_percentage,
+            clear_max_available_percentage,
+            tags,
+            max_total_options,
         ),
+        ExecuteMsg::FundGauge { gauge } => execute::fund_gauge(deps, info, gauge),
+        ExecuteMsg::CloseVoting { gauge } => execute::close_voting(deps, info.sender, gauge),
+        ExecuteMsg::FreezeOptions { gauge } => {
+            execute::set_options_frozen(deps, info.sender, gauge, true)
+        }
+        ExecuteMsg::UnfreezeOptions { gauge } => {
+            execute::set_options_frozen(deps, info.sender, gauge, false)
+        }
         ExecuteMsg::StopGauge { gauge } => execute::stop_gauge(deps, info.sender, gauge),
+        ExecuteMsg::StopGaugeAndRollover {
+            gauge,
+            successor_gauge,
+        } => execute::stop_gauge_and_rollover(deps, info.sender, gauge, successor_gauge),
+        ExecuteMsg::EmergencyWithdraw {
+            denom,
+            amount,
+            recipient,
+            gauge,
+        } => execute::emergency_withdraw(deps, env, info.sender, denom, amount, recipient, gauge),
         ExecuteMsg::ResetGauge { gauge, batch_size } => {
             execute::reset_gauge(deps, env, gauge, batch_size)
         }
         ExecuteMsg::AddOption { gauge, option } => {
             execute::add_option(deps, info.sender, gauge, option, true)
         }
+        ExecuteMsg::SyncOptions { gauge, limit } => {
+            execute::sync_options(deps, info.sender, gauge, limit)
+        }
+        ExecuteMsg::ValidateOptions { gauge } => {
+            execute::validate_options(deps, env, info.sender, gauge)
+        }
+        ExecuteMsg::SetEpochLabel {
+            gauge,
+            epoch,
+            label,
+        } => execute::set_epoch_label(deps, info.sender, gauge, epoch, label),
+        ExecuteMsg::UpdateExecuteAllowlist { gauge, add, remove } => {
+            execute::update_execute_allowlist(deps, info.sender, gauge, add, remove)
+        }
         ExecuteMsg::RemoveOption { gauge, option } => {
             execute::remove_option(deps, info.sender, gauge, option)
         }
-        ExecuteMsg::PlaceVotes { gauge, votes } => {
-            execute::place_votes(deps, env, info.sender, gauge, votes)
+        ExecuteMsg::RemoveOptions { gauge, options } => {
+            execute::remove_options(deps, info.sender, gauge, options)
         }
-        ExecuteMsg::Execute { gauge } => execute::execute(deps, env, gauge),
-    }
+        ExecuteMsg::PauseAll {} => execute::set_paused(deps, info.sender, true),
+        ExecuteMsg::UnpauseAll {} => execute::set_paused(deps, info.sender, false),
+        ExecuteMsg::DeprecateOption { gauge, option } => {
+            execute::deprecate_option(deps, info.sender, gauge, option)
+        }
+        ExecuteMsg::PlaceVotes {
+            gauge,
+            votes,
+            proof,
+            nonce,
+        } => execute::place_votes(deps, env, info, gauge, votes, proof, nonce),
+        ExecuteMsg::PlaceVotesBps {
+            gauge,
+            votes,
+            proof,
+        } => execute::place_votes_bps(deps, env, info, gauge, votes, proof),
+        ExecuteMsg::Execute { gauge } => execute::execute(deps, env, info.sender, gauge),
+        ExecuteMsg::ExecuteEpoch { gauge, epoch } => {
+            execute::execute_epoch(deps, info.sender, gauge, epoch)
+        }
+    }?;
+    Ok(with_namespace(response, &namespace))
 }
 
 mod execute {
     use super::*;
-    use crate::state::{remove_tally, update_tallies, Reset, Vote};
+    use crate::msg::{
+        DistributionHookMsg, EpochRewardResponse, OracleQueryMsg, PostExecuteCallbackMsg,
+        PriceResponse, RouterExecuteMsg, RouterQueryMsg, SimulateSwapResponse,
+    };
+    use crate::state::{
+        remove_tally, update_tallies, Asset, AssetType, DistributionMode, EpochParticipation,
+        EpochUnit, ExecuteCondition, ExecutePolicy, ExecutionInputs, OptionTallySnapshot, Reset,
+        Rounding, SwapRoute, Vote, VoterActivity, VoterRestriction, AUTO_PRUNE_IDLE_EPOCHS,
+        AUTO_PRUNE_MAX_OPTIONS, AUTO_PRUNE_SCAN_LIMIT, EPOCH_LABELS, EXECUTION_INPUTS,
+        FULL_ALLOCATION_TOLERANCE, LAST_VALIDATION_SWEEP, LAST_VOTED_AT, MAX_EPOCH_LABEL_LENGTH,
+        MISSED_EPOCHS, NEXT_PAYOUT_REPLY_ID, OPTION_TALLY_HISTORY, OPTION_TALLY_HISTORY_EPOCHS,
+        PRUNE_CURSOR, VALIDATE_OPTIONS_BATCH, VALIDATION_CURSOR, VALIDATION_SWEEP_INTERVAL,
+        VOTERS_BY_HEIGHT, VOTER_ALLOWLIST, VOTES_IN_EPOCH, ZERO_TALLY_SINCE,
+    };
+    use sha2::Digest;
+    use std::collections::hash_map::Entry;
     use std::collections::HashMap;
 
     pub fn member_changed(
         deps: DepsMut,
+        env: Env,
         sender: Addr,
         diffs: Vec<MemberDiff>,
     ) -> Result<Response, ContractError> {
@@ -132,30 +315,32 @@ mod execute {
                 let new = diff.new.unwrap_or_default();
 
                 // load gauge if not already loaded
-                let gauge = gauges
-                    .entry(vote.gauge_id)
-                    .or_insert_with(|| GAUGES.load(deps.storage, vote.gauge_id).unwrap());
+                let gauge = match gauges.entry(vote.gauge_id) {
+                    Entry::Occupied(entry) => entry.into_mut(),
+                    Entry::Vacant(entry) => entry.insert(GAUGES.load(deps.storage, vote.gauge_id)?),
+                };
 
                 if vote.is_expired(gauge) {
                     continue;
                 }
 
                 // calculate updates and adjust tallies
-                let updates: Vec<_> = vote
+                let updates = vote
                     .votes
                     .iter()
-                    .map(|v| {
-                        (
+                    .map(|v| -> StdResult<_> {
+                        Ok((
                             v.option.as_str(),
-                            (old * v.weight).u128(),
-                            (new * v.weight).u128(),
-                        )
+                            query::checked_mul_multiplier(old, v.weight)?.u128(),
+                            query::checked_mul_multiplier(new, v.weight)?.u128(),
+                        ))
                     })
-                    .collect();
+                    .collect::<StdResult<Vec<_>>>()?;
                 update_tallies(deps.storage, vote.gauge_id, updates)?;
 
                 // store new vote power for this user
                 vote.power = new;
+                vote.last_updated_block = Some(env.block.height);
                 votes().save(deps.storage, &voter, vote.gauge_id, &vote)?;
             }
         }
@@ -163,6 +348,23 @@ mod execute {
         Ok(response)
     }
 
+    pub fn set_paused(
+        deps: DepsMut,
+        sender: Addr,
+        paused: bool,
+    ) -> Result<Response, ContractError> {
+        let mut config = CONFIG.load(deps.storage)?;
+        if sender != config.owner {
+            return Err(ContractError::Unauthorized {});
+        }
+        config.paused = paused;
+        CONFIG.save(deps.storage, &config)?;
+
+        Ok(Response::new()
+            .add_attribute("action", if paused { "pause_all" } else { "unpause_all" })
+            .add_attribute("sender", &sender))
+    }
+
     pub fn create_gauge(
         deps: DepsMut,
         env: Env,
@@ -173,16 +375,23 @@ mod execute {
         if sender != config.owner {
             return Err(ContractError::Unauthorized {});
         }
+        ensure_not_paused(deps.storage)?;
 
-        let adapter = attach_gauge(deps, env, options)?;
+        let (gauge_id, adapter) = attach_gauge(deps, env, options)?;
 
         Ok(Response::new()
             .add_attribute("action", "create_gauge")
-            .add_attribute("adapter", adapter))
+            .add_attribute("adapter", adapter)
+            .add_event(gauge_state_change(
+                gauge_id,
+                GAUGE_STATE_NONE,
+                GAUGE_STATE_ACTIVE,
+                &sender,
+            )))
     }
 
     pub fn attach_gauge(
-        mut deps: DepsMut,
+        deps: DepsMut,
         env: Env,
         GaugeConfig {
             title,
@@ -192,21 +401,183 @@ mod execute {
             max_options_selected,
             max_available_percentage,
             reset_epoch,
+            to_distribute,
+            reward_schedule,
+            max_voters,
+            validate_option_on_vote,
+            voter_allowlist,
+            voter_merkle_root,
+            tenure_weighting,
+            tenure_curve,
+            bootstrap_equal_split,
+            reclaim_votes_on_remove,
+            adapter_multipliers,
+            adapter_reward,
+            isolate_failures,
+            epoch_unit,
+            rounding,
+            tags,
+            extra_adapters,
+            min_absolute_tally,
+            distribution_mode,
+            boost_source,
+            flashloan_resistant,
+            distribution_hook,
+            auto_prune_zero_options,
+            require_full_allocation,
+            total_budget,
+            vote_change_fee,
+            execute_policy,
+            max_paid_options,
+            deprecated_vote_policy,
+            min_distribution_per_option,
+            max_total_options,
+            post_execute_callback,
+            align_to,
+            max_voter_influence,
+            execute_condition,
+            swap_route,
         }: GaugeConfig,
-    ) -> Result<Addr, ContractError> {
+    ) -> Result<(GaugeId, Addr), ContractError> {
         let adapter = deps.api.addr_validate(&adapter)?;
         // gauge parameter validation
-        ensure!(epoch_size > 60u64, ContractError::EpochSizeTooShort {});
+        validate_epoch_size(&epoch_unit, epoch_size)?;
+        validate_align_to(align_to, epoch_size)?;
+        if let Some(max_voter_influence) = max_voter_influence {
+            ensure!(
+                !max_voter_influence.is_zero() && max_voter_influence <= Decimal::one(),
+                ContractError::InvalidMaxVoterInfluence {}
+            );
+        }
         if let Some(min_percent_selected) = min_percent_selected {
             ensure!(
                 min_percent_selected < Decimal::one(),
                 ContractError::MinPercentSelectedTooBig {}
             );
         }
+        if let DistributionMode::EqualFixed { amount } = distribution_mode {
+            ensure!(!amount.is_zero(), ContractError::InvalidFixedAmount {});
+        }
         ensure!(
             max_options_selected > 0,
             ContractError::MaxOptionsSelectedTooSmall {}
         );
+        ensure!(
+            max_paid_options != Some(0),
+            ContractError::MaxPaidOptionsTooSmall {}
+        );
+        if let Some(max_available_percentage) = max_available_percentage {
+            validate_max_available_percentage(max_available_percentage)?;
+        }
+        ensure!(
+            voter_allowlist.is_none() || voter_merkle_root.is_none(),
+            ContractError::MultipleVoterRestrictions {}
+        );
+        let voter_allowlist = voter_allowlist
+            .map(|list| {
+                list.iter()
+                    .map(|voter| deps.api.addr_validate(voter))
+                    .collect::<StdResult<Vec<_>>>()
+            })
+            .transpose()?;
+        if let Some(root) = &voter_merkle_root {
+            let mut root_buf = [0u8; 32];
+            hex::decode_to_slice(root, &mut root_buf)
+                .map_err(|_| ContractError::InvalidMerkleRoot {})?;
+        }
+        let voter_restriction = match (&voter_allowlist, voter_merkle_root) {
+            (Some(_), _) => Some(VoterRestriction::Allowlist {}),
+            (None, Some(root)) => Some(VoterRestriction::MerkleRoot(root)),
+            (None, None) => None,
+        };
+        let tenure_curve = if tenure_weighting {
+            let curve = tenure_curve.ok_or(ContractError::TenureCurveMissing {})?;
+            deps.api.addr_validate(&curve.source)?;
+            ensure!(
+                curve.max_multiplier >= Decimal::one() && curve.half_life > 0,
+                ContractError::InvalidTenureCurve {}
+            );
+            Some(curve)
+        } else {
+            None
+        };
+        let tags = validate_tags(tags)?;
+        let execute_policy = match execute_policy {
+            ExecutePolicy::Allowlist(allowed) => ExecutePolicy::Allowlist(
+                allowed
+                    .iter()
+                    .map(|addr| deps.api.addr_validate(addr.as_str()))
+                    .collect::<StdResult<_>>()?,
+            ),
+            policy => policy,
+        };
+        let boost_source = boost_source
+            .map(|source| deps.api.addr_validate(&source))
+            .transpose()?;
+        // swaps are paid by the DAO core, which only sends native funds to the router
+        let swap_route = swap_route
+            .map(|route| -> Result<_, ContractError> {
+                ensure!(
+                    to_distribute
+                        .iter()
+                        .chain(reward_schedule.iter().map(|(_, reward)| reward))
+                        .all(|reward| matches!(reward.denom, AssetType::Native(_))),
+                    ContractError::SwapRouteCw20Reward {}
+                );
+                Ok(SwapRoute {
+                    router: deps.api.addr_validate(route.router.as_str())?,
+                    ..route
+                })
+            })
+            .transpose()?;
+        let execute_condition = execute_condition
+            .map(|condition| -> StdResult<_> {
+                Ok(ExecuteCondition {
+                    oracle: deps.api.addr_validate(condition.oracle.as_str())?,
+                    ..condition
+                })
+            })
+            .transpose()?;
+        let extra_adapters = extra_adapters
+            .iter()
+            .map(|adapter| deps.api.addr_validate(adapter))
+            .collect::<StdResult<Vec<_>>>()?;
+        let reward_schedule = reward_schedule.unwrap_or_default();
+        ensure!(
+            reward_schedule.windows(2).all(|w| w[0].0 < w[1].0),
+            ContractError::UnsortedRewardSchedule {}
+        );
+        let distribution_hook = distribution_hook
+            .map(|hook| deps.api.addr_validate(&hook))
+            .transpose()?;
+        let post_execute_callback = post_execute_callback
+            .map(|callback| deps.api.addr_validate(&callback))
+            .transpose()?;
+        if distribution_hook.is_some() {
+            // the hook gets the reward as funds, which doesn't work for cw20 tokens
+            ensure!(
+                to_distribute
+                    .iter()
+                    .chain(reward_schedule.iter().map(|(_, reward)| reward))
+                    .all(|reward| matches!(reward.denom, AssetType::Native(_))),
+                ContractError::DistributionHookCw20Reward {}
+            );
+        }
+        ensure!(
+            !adapter_reward || to_distribute.is_some(),
+            ContractError::AdapterRewardWithoutDistribution {}
+        );
+        if let Some(budget) = &total_budget {
+            // only rewards the gauge pays out itself can be counted against the budget
+            ensure!(
+                to_distribute.is_some()
+                    && to_distribute
+                        .iter()
+                        .chain(reward_schedule.iter().map(|(_, reward)| reward))
+                        .all(|reward| reward.denom == AssetType::Native(budget.denom.clone())),
+                ContractError::BudgetDenomMismatch {}
+            );
+        }
         let gauge = Gauge {
             title,
             adapter: adapter.clone(),
@@ -215,29 +586,312 @@ mod execute {
             max_options_selected,
             max_available_percentage,
             is_stopped: false,
-            next_epoch: env.block.time.seconds() + epoch_size,
+            voting_closed: false,
+            options_frozen: false,
+            next_epoch: epoch_boundary(epoch_unit.now(&env.block), epoch_size, align_to),
             last_executed_set: None,
             reset: reset_epoch.map(|r| Reset {
                 last: None,
                 reset_each: r,
                 next: env.block.time.plus_seconds(r).seconds(),
             }),
+            to_distribute,
+            reward_schedule,
+            executed_epochs: 0,
+            max_voters,
+            validate_option_on_vote,
+            voter_restriction,
+            tenure_curve,
+            bootstrap_equal_split,
+            reclaim_votes_on_remove,
+            adapter_multipliers,
+            adapter_reward,
+            isolate_failures,
+            epoch_unit,
+            rounding,
+            tags,
+            extra_adapters,
+            min_absolute_tally,
+            distribution_mode,
+            boost_source,
+            flashloan_resistant,
+            epoch_start_height: Some(env.block.height),
+            distribution_hook,
+            auto_prune_zero_options,
+            require_full_allocation,
+            total_budget,
+            distributed: Uint128::zero(),
+            vote_change_fee,
+            execute_policy,
+            max_paid_options,
+            deprecated_vote_policy,
+            min_distribution_per_option,
+            max_total_options,
+            post_execute_callback,
+            align_to,
+            max_voter_influence,
+            execute_condition,
+            swap_route,
         };
-        let last_id: GaugeId = fetch_last_id(deps.storage)?;
-        GAUGES.save(deps.storage, last_id, &gauge)?;
+        let gauge_id = save_new_gauge(deps, &gauge, voter_allowlist.unwrap_or_default())?;
 
-        // fetch adapter options
-        let adapter_options: AllOptionsResponse =
-            deps.querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
+        Ok((gauge_id, adapter))
+    }
+
+    /// Stores a new gauge under the next free id, along with its tags, allowlist
+    /// and the adapter's options
+    fn save_new_gauge(
+        mut deps: DepsMut,
+        gauge: &Gauge,
+        voter_allowlist: Vec<Addr>,
+    ) -> Result<GaugeId, ContractError> {
+        let adapter = &gauge.adapter;
+        // fetch adapter options, this also makes sure the adapter is actually a gauge adapter
+        // before anything gets stored
+        let adapter_options: AllOptionsResponse = deps
+            .querier
+            .query(&QueryRequest::Wasm(WasmQuery::Smart {
                 contract_addr: adapter.to_string(),
-                msg: to_binary(&AdapterQueryMsg::AllOptions {})?,
-            }))?;
+                msg: to_binary(&AdapterQueryMsg::AllOptions {
+                    start_after: None,
+                    limit: None,
+                })?,
+            }))
+            .map_err(|_| ContractError::InvalidAdapter(adapter.to_string()))?;
+
+        let last_id: GaugeId = fetch_last_id(deps.storage)?;
+        GAUGES.save(deps.storage, last_id, gauge)?;
+        GAUGES_BY_ADAPTER.save(deps.storage, (adapter, last_id), &1)?;
+        for tag in &gauge.tags {
+            GAUGES_BY_TAG.save(deps.storage, (tag, last_id), &1)?;
+        }
+        for voter in voter_allowlist {
+            VOTER_ALLOWLIST.save(deps.storage, (last_id, &voter), &1)?;
+        }
+
         adapter_options.options.into_iter().try_for_each(|option| {
             execute::add_option(deps.branch(), adapter.clone(), last_id, option, false)?;
             Ok::<_, ContractError>(())
         })?;
 
-        Ok(adapter)
+        Ok(last_id)
+    }
+
+    pub fn clone_gauge(
+        deps: DepsMut,
+        env: Env,
+        sender: Addr,
+        source_gauge: GaugeId,
+        new_adapter: Option<String>,
+        new_title: Option<String>,
+    ) -> Result<Response, ContractError> {
+        let config = CONFIG.load(deps.storage)?;
+        if sender != config.owner {
+            return Err(ContractError::Unauthorized {});
+        }
+        ensure_not_paused(deps.storage)?;
+
+        let source = load_gauge(deps.storage, source_gauge)?;
+        let adapter = new_adapter
+            .map(|adapter| deps.api.addr_validate(&adapter))
+            .transpose()?
+            .unwrap_or_else(|| source.adapter.clone());
+        // only an explicit allowlist is copied, voters verified by merkle proof have to prove again
+        let voter_allowlist = match source.voter_restriction {
+            Some(VoterRestriction::Allowlist {}) => VOTER_ALLOWLIST
+                .prefix(source_gauge)
+                .keys(deps.storage, None, None, Order::Ascending)
+                .collect::<StdResult<Vec<_>>>()?,
+            _ => vec![],
+        };
+        // same config, but starting fresh like a newly created gauge
+        let gauge = Gauge {
+            title: new_title.unwrap_or_else(|| source.title.clone()),
+            adapter: adapter.clone(),
+            is_stopped: false,
+            voting_closed: false,
+            options_frozen: false,
+            next_epoch: epoch_boundary(
+                source.epoch_unit.now(&env.block),
+                source.epoch,
+                source.align_to,
+            ),
+            last_executed_set: None,
+            reset: source.reset.as_ref().map(|reset| Reset {
+                last: None,
+                reset_each: reset.reset_each,
+                next: env.block.time.plus_seconds(reset.reset_each).seconds(),
+            }),
+            executed_epochs: 0,
+            epoch_start_height: Some(env.block.height),
+            distributed: Uint128::zero(),
+            ..source
+        };
+        let gauge_id = save_new_gauge(deps, &gauge, voter_allowlist)?;
+
+        Ok(Response::new()
+            .add_attribute("action", "clone_gauge")
+            .add_attribute("source_gauge", source_gauge.to_string())
+            .add_attribute("gauge_id", gauge_id.to_string())
+            .add_attribute("adapter", adapter)
+            .add_event(gauge_state_change(
+                gauge_id,
+                GAUGE_STATE_NONE,
+                GAUGE_STATE_ACTIVE,
+                &sender,
+            )))
+    }
+
+    pub fn split_gauge(
+        mut deps: DepsMut,
+        env: Env,
+        sender: Addr,
+        source_gauge: GaugeId,
+        mut options_to_move: Vec<String>,
+        new_config: GaugeConfig,
+    ) -> Result<Response, ContractError> {
+        let config = CONFIG.load(deps.storage)?;
+        if sender != config.owner {
+            return Err(ContractError::Unauthorized {});
+        }
+        ensure_not_paused(deps.storage)?;
+
+        let source = load_gauge(deps.storage, source_gauge)?;
+        options_to_move.sort();
+        options_to_move.dedup();
+        ensure!(
+            !options_to_move.is_empty(),
+            ContractError::NoOptionsToMove {}
+        );
+        for option in &options_to_move {
+            ensure_option_exists(deps.storage, source_gauge, option)?;
+        }
+
+        let (gauge_id, adapter) = attach_gauge(deps.branch(), env.clone(), new_config)?;
+
+        // the whole tally of the moved options goes away, it is rebuilt from the moved votes
+        for option in &options_to_move {
+            remove_tally(deps.storage, source_gauge, option)?;
+            if !TALLY.has(deps.storage, (gauge_id, option)) {
+                update_tally(deps.storage, gauge_id, option, 0, 0)?;
+            }
+            if DEPRECATED_OPTIONS.has(deps.storage, (source_gauge, option)) {
+                DEPRECATED_OPTIONS.remove(deps.storage, (source_gauge, option));
+                DEPRECATED_OPTIONS.save(deps.storage, (gauge_id, option), &1)?;
+            }
+        }
+
+        let source_votes = votes()
+            .range_by_gauge(deps.storage, source_gauge)
+            .filter(|vote| match vote {
+                Ok((_, vote)) => !vote.is_expired(&source),
+                Err(_) => true,
+            })
+            .collect::<StdResult<Vec<_>>>()?;
+        let mut moved_voters = 0u64;
+        let mut removed_voters = 0u64;
+        for (voter, vote) in source_votes {
+            let (moved, kept): (Vec<Vote>, Vec<Vote>) = vote
+                .votes
+                .into_iter()
+                .partition(|v| options_to_move.binary_search(&v.option).is_ok());
+            if moved.is_empty() {
+                continue;
+            }
+
+            let updates = moved
+                .iter()
+                .map(|v| (v.option.as_str(), 0, (vote.power * v.weight).u128()))
+                .collect();
+            update_tallies(deps.storage, gauge_id, updates)?;
+            votes().set_votes(deps.storage, &env, &voter, gauge_id, moved, vote.power)?;
+            moved_voters += 1;
+
+            if kept.is_empty() {
+                votes().remove_votes(deps.storage, &voter, source_gauge)?;
+                removed_voters += 1;
+            } else {
+                votes().set_votes(deps.storage, &env, &voter, source_gauge, kept, vote.power)?;
+            }
+        }
+        VOTER_COUNT.save(deps.storage, gauge_id, &moved_voters)?;
+        let voter_count = VOTER_COUNT
+            .may_load(deps.storage, source_gauge)?
+            .unwrap_or_default();
+        VOTER_COUNT.save(
+            deps.storage,
+            source_gauge,
+            &voter_count.saturating_sub(removed_voters),
+        )?;
+
+        Ok(Response::new()
+            .add_attribute("action", "split_gauge")
+            .add_attribute("adapter", adapter)
+            .add_attribute("gauge_id", source_gauge.to_string())
+            .add_attribute("new_gauge_id", gauge_id.to_string())
+            .add_attribute("moved_options", options_to_move.join(","))
+            .add_attribute("moved_voters", moved_voters.to_string())
+            .add_event(gauge_state_change(
+                gauge_id,
+                GAUGE_STATE_NONE,
+                GAUGE_STATE_ACTIVE,
+                &sender,
+            )))
+    }
+
+    /// Checks the number and length of the tags, duplicates are removed
+    fn validate_tags(mut tags: Vec<String>) -> Result<Vec<String>, ContractError> {
+        tags.sort();
+        tags.dedup();
+        ensure!(tags.len() <= MAX_TAGS, ContractError::TooManyTags(MAX_TAGS));
+        if let Some(tag) = tags
+            .iter()
+            .find(|tag| tag.is_empty() || tag.chars().count() > MAX_TAG_LENGTH)
+        {
+            return Err(ContractError::InvalidTag(tag.clone(), MAX_TAG_LENGTH));
+        }
+        Ok(tags)
+    }
+
+    /// A cap of zero would distribute nothing, leave it unset for no cap
+    fn validate_max_available_percentage(percentage: Decimal) -> Result<(), ContractError> {
+        ensure!(
+            !percentage.is_zero(),
+            ContractError::InvalidMaxAvailablePercentage {}
+        );
+        ensure!(
+            percentage <= Decimal::one(),
+            ContractError::MaxAvailablePercentTooBig {}
+        );
+        Ok(())
+    }
+
+    fn validate_epoch_size(unit: &EpochUnit, epoch_size: u64) -> Result<(), ContractError> {
+        match unit {
+            EpochUnit::Time => ensure!(epoch_size > 60u64, ContractError::EpochSizeTooShort {}),
+            EpochUnit::Height => ensure!(epoch_size > 0u64, ContractError::EpochHeightTooShort {}),
+        }
+        Ok(())
+    }
+
+    /// The alignment must be smaller than the epoch, so snapping down never reaches the past
+    fn validate_align_to(align_to: Option<u64>, epoch_size: u64) -> Result<(), ContractError> {
+        if let Some(align_to) = align_to {
+            ensure!(
+                align_to > 0 && align_to <= epoch_size,
+                ContractError::InvalidAlignTo {}
+            );
+        }
+        Ok(())
+    }
+
+    /// End of the epoch starting at `now`, snapped down to a multiple of `align_to` if set
+    fn epoch_boundary(now: u64, epoch_size: u64, align_to: Option<u64>) -> u64 {
+        let boundary = now + epoch_size;
+        match align_to {
+            Some(align_to) => boundary - boundary % align_to,
+            None => boundary,
+        }
     }
 
     pub fn update_gauge(
@@ -248,15 +902,37 @@ mod execute {
         min_percent_selected: Option<Decimal>,
         max_options_selected: Option<u32>,
         max_available_percentage: Option<Decimal>,
+        clear_max_available_percentage: bool,
+        tags: Option<Vec<String>>,
+        max_total_options: Option<u32>,
     ) -> Result<Response, ContractError> {
         let config = CONFIG.load(deps.storage)?;
         if sender != config.owner {
             return Err(ContractError::Unauthorized {});
         }
 
-        let mut gauge = GAUGES.load(deps.storage, gauge_id)?;
+        let mut gauge = load_gauge(deps.storage, gauge_id)?;
+        if let Some(tags) = tags {
+            let tags = validate_tags(tags)?;
+            for tag in &gauge.tags {
+                GAUGES_BY_TAG.remove(deps.storage, (tag, gauge_id));
+            }
+            for tag in &tags {
+                GAUGES_BY_TAG.save(deps.storage, (tag, gauge_id), &1)?;
+            }
+            gauge.tags = tags;
+        }
+        // parameters that change which options get selected (and with which weight)
+        let selection_before = (
+            gauge.min_percent_selected,
+            gauge.max_options_selected,
+            gauge.max_available_percentage,
+        );
         if let Some(epoch_size) = epoch_size {
-            ensure!(epoch_size > 60u64, ContractError::EpochSizeTooShort {});
+            validate_epoch_size(&gauge.epoch_unit, epoch_size)?;
+            validate_align_to(gauge.align_to, epoch_size)?;
+            // `next_epoch` is left untouched, so the new size only applies from the next boundary on.
+            // Recomputing it here could move the end of the current epoch into the past.
             gauge.epoch = epoch_size;
         }
         if let Some(min_percent_selected) = min_percent_selected {
@@ -277,23 +953,74 @@ mod execute {
             );
             gauge.max_options_selected = max_options_selected;
         }
+        if clear_max_available_percentage {
+            ensure!(
+                max_available_percentage.is_none(),
+                ContractError::ConflictingMaxAvailablePercentage {}
+            );
+            gauge.max_available_percentage = None;
+        }
         if let Some(max_available_percentage) = max_available_percentage {
-            if max_available_percentage.is_zero() {
-                gauge.max_available_percentage = None
-            } else {
-                ensure!(
-                    max_available_percentage < Decimal::one(),
-                    ContractError::MaxAvailablePercentTooBig {}
-                );
-                gauge.max_available_percentage = Some(max_available_percentage)
-            };
+            validate_max_available_percentage(max_available_percentage)?;
+            gauge.max_available_percentage = Some(max_available_percentage);
+        }
+        if let Some(max_total_options) = max_total_options {
+            gauge.max_total_options = Some(max_total_options);
         }
         GAUGES.save(deps.storage, gauge_id, &gauge)?;
 
-        Ok(Response::new().add_attribute("action", "update_gauge"))
+        let mut response = Response::new()
+            .add_attribute("action", "update_gauge")
+            .add_attribute("next_epoch", gauge.next_epoch.to_string());
+        // let indexers know that the results of the next epoch are calculated differently
+        if selection_before
+            != (
+                gauge.min_percent_selected,
+                gauge.max_options_selected,
+                gauge.max_available_percentage,
+            )
+        {
+            response = response.add_attribute("selection_behavior_changed", "true");
+        }
+        Ok(response)
     }
 
-    pub fn stop_gauge(
+    pub fn fund_gauge(
+        deps: DepsMut,
+        info: MessageInfo,
+        gauge_id: GaugeId,
+    ) -> Result<Response, ContractError> {
+        let gauge = load_gauge(deps.storage, gauge_id)?;
+        // funds of stopped gauges could never be paid out
+        if gauge.is_stopped {
+            return Err(ContractError::GaugeStopped(gauge_id));
+        }
+        ensure!(!info.funds.is_empty(), ContractError::NoFunds {});
+
+        for coin in &info.funds {
+            ESCROW.update(deps.storage, (gauge_id, &coin.denom), |balance| {
+                balance
+                    .unwrap_or_default()
+                    .checked_add(coin.amount)
+                    .map_err(StdError::from)
+            })?;
+        }
+
+        Ok(Response::new()
+            .add_attribute("action", "fund_gauge")
+            .add_attribute("sender", info.sender)
+            .add_attribute("gauge_id", gauge_id.to_string())
+            .add_attribute(
+                "funds",
+                info.funds
+                    .iter()
+                    .map(Coin::to_string)
+                    .collect::<Vec<_>>()
+                    .join(","),
+            ))
+    }
+
+    pub fn close_voting(
         deps: DepsMut,
         sender: Addr,
         gauge_id: GaugeId,
@@ -303,54 +1030,504 @@ mod execute {
             return Err(ContractError::Unauthorized {});
         }
 
-        let gauge = GAUGES.load(deps.storage, gauge_id)?;
-        let gauge = Gauge {
-            is_stopped: true,
-            ..gauge
-        };
+        let mut gauge = load_gauge(deps.storage, gauge_id)?;
+        if gauge.is_stopped {
+            return Err(ContractError::GaugeStopped(gauge_id));
+        }
+        let from = gauge_state(&gauge);
+        gauge.voting_closed = true;
         GAUGES.save(deps.storage, gauge_id, &gauge)?;
 
-        Ok(Response::new()
-            .add_attribute("action", "stop_gauge")
-            .add_attribute("gauge_id", gauge_id.to_string()))
+        let mut response = Response::new()
+            .add_attribute("action", "close_voting")
+            .add_attribute("gauge_id", gauge_id.to_string());
+        if from != GAUGE_STATE_CLOSED {
+            response = response.add_event(gauge_state_change(
+                gauge_id,
+                from,
+                GAUGE_STATE_CLOSED,
+                &sender,
+            ));
+        }
+        Ok(response)
     }
 
-    pub fn remove_option(
+    pub fn set_options_frozen(
         deps: DepsMut,
         sender: Addr,
         gauge_id: GaugeId,
-        option: String,
+        frozen: bool,
     ) -> Result<Response, ContractError> {
-        // check if such option even exists
-        if !TALLY.has(deps.as_ref().storage, (gauge_id, &option)) {
-            return Err(ContractError::OptionDoesNotExists { option, gauge_id });
-        };
-
-        // only owner can remove option for now
-        if sender != CONFIG.load(deps.storage)?.owner {
+        let config = CONFIG.load(deps.storage)?;
+        if sender != config.owner {
             return Err(ContractError::Unauthorized {});
         }
 
-        remove_tally(deps.storage, gauge_id, &option)?;
+        let mut gauge = load_gauge(deps.storage, gauge_id)?;
+        gauge.options_frozen = frozen;
+        GAUGES.save(deps.storage, gauge_id, &gauge)?;
 
+        let action = if frozen {
+            "freeze_options"
+        } else {
+            "unfreeze_options"
+        };
         Ok(Response::new()
-            .add_attribute("action", "remove_option")
-            .add_attribute("sender", &sender)
-            .add_attribute("gauge_id", gauge_id.to_string())
-            .add_attribute("option", option))
+            .add_attribute("action", action)
+            .add_attribute("gauge_id", gauge_id.to_string()))
     }
 
-    pub fn reset_gauge(
+    pub fn stop_gauge(
         deps: DepsMut,
-        env: Env,
+        sender: Addr,
         gauge_id: GaugeId,
-        batch_size: u32,
     ) -> Result<Response, ContractError> {
-        let mut gauge = GAUGES.load(deps.storage, gauge_id)?;
-        match gauge.reset {
-            Some(ref mut reset) if reset.next <= env.block.time.seconds() => {
-                reset.last = Some(reset.next);
-
+        let config = CONFIG.load(deps.storage)?;
+        if sender != config.owner {
+            return Err(ContractError::Unauthorized {});
+        }
+
+        let gauge = load_gauge(deps.storage, gauge_id)?;
+        let from = gauge_state(&gauge);
+        let gauge = Gauge {
+            is_stopped: true,
+            ..gauge
+        };
+        GAUGES.save(deps.storage, gauge_id, &gauge)?;
+
+        let mut response = Response::new()
+            .add_attribute("action", "stop_gauge")
+            .add_attribute("gauge_id", gauge_id.to_string());
+        if from != GAUGE_STATE_STOPPED {
+            response = response.add_event(gauge_state_change(
+                gauge_id,
+                from,
+                GAUGE_STATE_STOPPED,
+                &sender,
+            ));
+        }
+        Ok(response)
+    }
+
+    pub fn stop_gauge_and_rollover(
+        mut deps: DepsMut,
+        sender: Addr,
+        gauge_id: GaugeId,
+        successor_id: GaugeId,
+    ) -> Result<Response, ContractError> {
+        let successor = load_gauge(deps.storage, successor_id)?;
+        ensure!(
+            successor_id != gauge_id && !successor.is_stopped && !successor.voting_closed,
+            ContractError::InvalidSuccessor(successor_id)
+        );
+        let response = stop_gauge(deps.branch(), sender, gauge_id)?;
+
+        // the funds stay with the contract, they are just earmarked for the successor now
+        let escrow = ESCROW
+            .prefix(gauge_id)
+            .range(deps.storage, None, None, Order::Ascending)
+            .collect::<StdResult<Vec<_>>>()?;
+        for (denom, amount) in &escrow {
+            ESCROW.remove(deps.storage, (gauge_id, denom));
+            ESCROW.update(deps.storage, (successor_id, denom), |balance| {
+                balance
+                    .unwrap_or_default()
+                    .checked_add(*amount)
+                    .map_err(StdError::from)
+            })?;
+        }
+
+        Ok(response
+            .add_attribute("successor_gauge", successor_id.to_string())
+            .add_attribute(
+                "rolled_over",
+                escrow
+                    .iter()
+                    .map(|(denom, amount)| format!("{}{}", amount, denom))
+                    .collect::<Vec<_>>()
+                    .join(","),
+            ))
+    }
+
+    pub fn emergency_withdraw(
+        deps: DepsMut,
+        env: Env,
+        sender: Addr,
+        denom: String,
+        amount: Option<Uint128>,
+        recipient: String,
+        gauge_id: Option<GaugeId>,
+    ) -> Result<Response, ContractError> {
+        if sender != CONFIG.load(deps.storage)?.owner {
+            return Err(ContractError::Unauthorized {});
+        }
+        let recipient = deps.api.addr_validate(&recipient)?;
+        // the escrows of the other gauges are never touched
+        let available = match gauge_id {
+            Some(gauge_id) => {
+                load_gauge(deps.storage, gauge_id)?;
+                ESCROW
+                    .may_load(deps.storage, (gauge_id, &denom))?
+                    .unwrap_or_default()
+            }
+            None => {
+                let balance = deps
+                    .querier
+                    .query_balance(&env.contract.address, &denom)?
+                    .amount;
+                let mut escrowed = Uint128::zero();
+                for item in ESCROW.range(deps.storage, None, None, Order::Ascending) {
+                    let ((_, escrow_denom), escrow) = item?;
+                    if escrow_denom == denom {
+                        escrowed = escrowed.checked_add(escrow)?;
+                    }
+                }
+                balance.saturating_sub(escrowed)
+            }
+        };
+        let amount = amount.unwrap_or(available);
+        ensure!(!amount.is_zero(), ContractError::NoFunds {});
+        ensure!(
+            amount <= available,
+            ContractError::WithdrawExceedsBalance {
+                denom,
+                amount,
+                balance: available,
+            }
+        );
+
+        // keep the escrow in line with what the contract still holds
+        if let Some(gauge_id) = gauge_id {
+            if amount == available {
+                ESCROW.remove(deps.storage, (gauge_id, &denom));
+            } else {
+                ESCROW.save(deps.storage, (gauge_id, &denom), &(available - amount))?;
+            }
+        }
+
+        let mut event = Event::new("emergency_withdraw")
+            .add_attribute("denom", &denom)
+            .add_attribute("amount", amount)
+            .add_attribute("recipient", &recipient)
+            .add_attribute("by", &sender);
+        if let Some(gauge_id) = gauge_id {
+            event = event.add_attribute("gauge_id", gauge_id.to_string());
+        }
+        Ok(Response::new()
+            .add_attribute("action", "emergency_withdraw")
+            .add_event(event)
+            .add_message(BankMsg::Send {
+                to_address: recipient.to_string(),
+                amount: vec![Coin::new(amount.u128(), denom)],
+            }))
+    }
+
+    pub fn sync_options(
+        mut deps: DepsMut,
+        sender: Addr,
+        gauge_id: GaugeId,
+        limit: u32,
+    ) -> Result<Response, ContractError> {
+        let gauge = load_gauge(deps.storage, gauge_id)?;
+        if gauge.is_stopped {
+            return Err(ContractError::GaugeStopped(gauge_id));
+        }
+        if gauge.options_frozen {
+            return Err(ContractError::OptionsFrozen(gauge_id));
+        }
+
+        let start_after = SYNC_CURSOR.may_load(deps.storage, gauge_id)?;
+        let options = deps
+            .querier
+            .query_wasm_smart::<AllOptionsResponse>(
+                gauge.adapter.clone(),
+                &AdapterQueryMsg::AllOptions {
+                    start_after,
+                    limit: Some(limit),
+                },
+            )?
+            .options;
+
+        // the last page was reached, next sync starts from the beginning
+        let finished = (options.len() as u32) < limit;
+        match options.last() {
+            Some(last) if !finished => SYNC_CURSOR.save(deps.storage, gauge_id, last)?,
+            _ => SYNC_CURSOR.remove(deps.storage, gauge_id),
+        }
+
+        // options coming from the adapter are valid, so no need to check them
+        let mut added = 0u32;
+        for option in options {
+            if !TALLY.has(deps.storage, (gauge_id, &option)) {
+                add_option(
+                    deps.branch(),
+                    gauge.adapter.clone(),
+                    gauge_id,
+                    option,
+                    false,
+                )?;
+                added += 1;
+            }
+        }
+
+        Ok(Response::new()
+            .add_attribute("action", "sync_options")
+            .add_attribute("sender", &sender)
+            .add_attribute("gauge_id", gauge_id.to_string())
+            .add_attribute("added", added.to_string())
+            .add_attribute("finished", finished.to_string()))
+    }
+
+    pub fn validate_options(
+        deps: DepsMut,
+        env: Env,
+        sender: Addr,
+        gauge_id: GaugeId,
+    ) -> Result<Response, ContractError> {
+        let gauge = load_gauge(deps.storage, gauge_id)?;
+        if gauge.is_stopped {
+            return Err(ContractError::GaugeStopped(gauge_id));
+        }
+
+        let start_after = VALIDATION_CURSOR.may_load(deps.storage, gauge_id)?;
+        if start_after.is_none() {
+            // anyone can call this, so limit how often all options are queried
+            let now = env.block.time.seconds();
+            if let Some(last) = LAST_VALIDATION_SWEEP.may_load(deps.storage, gauge_id)? {
+                let next = last + VALIDATION_SWEEP_INTERVAL;
+                if now < next {
+                    return Err(ContractError::ValidationSweepTooSoon(gauge_id, next));
+                }
+            }
+            LAST_VALIDATION_SWEEP.save(deps.storage, gauge_id, &now)?;
+        }
+
+        let options = TALLY
+            .prefix(gauge_id)
+            .keys(
+                deps.storage,
+                start_after.as_deref().map(Bound::exclusive),
+                None,
+                Order::Ascending,
+            )
+            .take(VALIDATE_OPTIONS_BATCH)
+            .collect::<StdResult<Vec<_>>>()?;
+
+        // the last page was reached, next sweep starts from the beginning
+        let finished = options.len() < VALIDATE_OPTIONS_BATCH;
+        match options.last() {
+            Some(last) if !finished => VALIDATION_CURSOR.save(deps.storage, gauge_id, last)?,
+            _ => VALIDATION_CURSOR.remove(deps.storage, gauge_id),
+        }
+
+        let mut deprecated = vec![];
+        for option in options {
+            if DEPRECATED_OPTIONS.has(deps.storage, (gauge_id, &option)) {
+                continue;
+            }
+            // an adapter failing on an option doesn't consider it valid either
+            let valid = deps
+                .querier
+                .query_wasm_smart::<CheckOptionResponse>(
+                    gauge.adapter.clone(),
+                    &AdapterQueryMsg::CheckOption {
+                        option: option.clone(),
+                    },
+                )
+                .map(|res| res.valid)
+                .unwrap_or(false);
+            if !valid {
+                DEPRECATED_OPTIONS.save(deps.storage, (gauge_id, &option), &1)?;
+                deprecated.push(option);
+            }
+        }
+
+        Ok(Response::new()
+            .add_attribute("action", "validate_options")
+            .add_attribute("sender", &sender)
+            .add_attribute("gauge_id", gauge_id.to_string())
+            .add_attribute("deprecated", deprecated.join(","))
+            .add_attribute("finished", finished.to_string()))
+    }
+
+    pub fn remove_option(
+        deps: DepsMut,
+        sender: Addr,
+        gauge_id: GaugeId,
+        option: String,
+    ) -> Result<Response, ContractError> {
+        // check if such option even exists
+        ensure_option_exists(deps.storage, gauge_id, &option)?;
+
+        // only owner can remove option for now
+        if sender != CONFIG.load(deps.storage)?.owner {
+            return Err(ContractError::Unauthorized {});
+        }
+
+        let gauge = load_gauge(deps.storage, gauge_id)?;
+        remove_option_state(deps.storage, &gauge, gauge_id, &option)?;
+
+        Ok(Response::new()
+            .add_attribute("action", "remove_option")
+            .add_attribute("sender", &sender)
+            .add_attribute("gauge_id", gauge_id.to_string())
+            .add_attribute("option", option))
+    }
+
+    /// Removes all options without votes, skipping the others
+    pub fn remove_options(
+        deps: DepsMut,
+        sender: Addr,
+        gauge_id: GaugeId,
+        options: Vec<String>,
+    ) -> Result<Response, ContractError> {
+        if sender != CONFIG.load(deps.storage)?.owner {
+            return Err(ContractError::Unauthorized {});
+        }
+
+        let gauge = load_gauge(deps.storage, gauge_id)?;
+        let mut removed = vec![];
+        let mut skipped = vec![];
+        for option in options {
+            let tally = TALLY
+                .may_load(deps.storage, (gauge_id, &option))?
+                .ok_or_else(|| ContractError::OptionNotFound {
+                    gauge: gauge_id,
+                    option: option.clone(),
+                })?;
+            if tally == 0 {
+                remove_option_state(deps.storage, &gauge, gauge_id, &option)?;
+                removed.push(option);
+            } else {
+                skipped.push(option);
+            }
+        }
+
+        Ok(Response::new()
+            .add_attribute("action", "remove_options")
+            .add_attribute("sender", &sender)
+            .add_attribute("gauge_id", gauge_id.to_string())
+            .add_attribute("removed", removed.join(","))
+            .add_attribute("skipped", skipped.join(",")))
+    }
+
+    fn remove_option_state(
+        storage: &mut dyn Storage,
+        gauge: &Gauge,
+        gauge_id: GaugeId,
+        option: &str,
+    ) -> StdResult<()> {
+        remove_tally(storage, gauge_id, option)?;
+        DEPRECATED_OPTIONS.remove(storage, (gauge_id, option));
+
+        if gauge.reclaim_votes_on_remove {
+            // the tally of the option is gone already, so only the votes need to be updated
+            let removed = votes().remove_option_from_votes(storage, gauge_id, option)?;
+            let voter_count = VOTER_COUNT.may_load(storage, gauge_id)?.unwrap_or_default();
+            VOTER_COUNT.save(storage, gauge_id, &voter_count.saturating_sub(removed))?;
+        }
+        Ok(())
+    }
+
+    pub fn deprecate_option(
+        deps: DepsMut,
+        sender: Addr,
+        gauge_id: GaugeId,
+        option: String,
+    ) -> Result<Response, ContractError> {
+        ensure_option_exists(deps.storage, gauge_id, &option)?;
+        if sender != CONFIG.load(deps.storage)?.owner {
+            return Err(ContractError::Unauthorized {});
+        }
+
+        // the tally stays, it is just left out when selecting
+        DEPRECATED_OPTIONS.save(deps.storage, (gauge_id, &option), &1)?;
+
+        Ok(Response::new()
+            .add_attribute("action", "deprecate_option")
+            .add_attribute("sender", &sender)
+            .add_attribute("gauge_id", gauge_id.to_string())
+            .add_attribute("option", option))
+    }
+
+    pub fn set_epoch_label(
+        deps: DepsMut,
+        sender: Addr,
+        gauge_id: GaugeId,
+        epoch: u64,
+        label: String,
+    ) -> Result<Response, ContractError> {
+        if sender != CONFIG.load(deps.storage)?.owner {
+            return Err(ContractError::Unauthorized {});
+        }
+        // make sure the gauge exists
+        load_gauge(deps.storage, gauge_id)?;
+        ensure!(
+            label.chars().count() <= MAX_EPOCH_LABEL_LENGTH,
+            ContractError::InvalidEpochLabel(MAX_EPOCH_LABEL_LENGTH)
+        );
+
+        if label.is_empty() {
+            EPOCH_LABELS.remove(deps.storage, (gauge_id, epoch));
+        } else {
+            EPOCH_LABELS.save(deps.storage, (gauge_id, epoch), &label)?;
+        }
+
+        Ok(Response::new()
+            .add_attribute("action", "set_epoch_label")
+            .add_attribute("sender", &sender)
+            .add_attribute("gauge_id", gauge_id.to_string())
+            .add_attribute("epoch", epoch.to_string())
+            .add_attribute("label", label))
+    }
+
+    pub fn update_execute_allowlist(
+        deps: DepsMut,
+        sender: Addr,
+        gauge_id: GaugeId,
+        add: Vec<String>,
+        remove: Vec<String>,
+    ) -> Result<Response, ContractError> {
+        if sender != CONFIG.load(deps.storage)?.owner {
+            return Err(ContractError::Unauthorized {});
+        }
+        let mut gauge = load_gauge(deps.storage, gauge_id)?;
+        let allowed = match &mut gauge.execute_policy {
+            ExecutePolicy::Allowlist(allowed) => allowed,
+            _ => return Err(ContractError::NoExecuteAllowlist(gauge_id)),
+        };
+
+        for addr in &remove {
+            let addr = deps.api.addr_validate(addr)?;
+            allowed.retain(|allowed| *allowed != addr);
+        }
+        for addr in &add {
+            let addr = deps.api.addr_validate(addr)?;
+            if !allowed.contains(&addr) {
+                allowed.push(addr);
+            }
+        }
+        GAUGES.save(deps.storage, gauge_id, &gauge)?;
+
+        Ok(Response::new()
+            .add_attribute("action", "update_execute_allowlist")
+            .add_attribute("sender", &sender)
+            .add_attribute("gauge_id", gauge_id.to_string())
+            .add_attribute("added", add.join(","))
+            .add_attribute("removed", remove.join(",")))
+    }
+
+    pub fn reset_gauge(
+        deps: DepsMut,
+        env: Env,
+        gauge_id: GaugeId,
+        batch_size: u32,
+    ) -> Result<Response, ContractError> {
+        let mut gauge = load_gauge(deps.storage, gauge_id)?;
+        match gauge.reset {
+            Some(ref mut reset) if reset.next <= env.block.time.seconds() => {
+                reset.last = Some(reset.next);
+
                 // remove all options from the gauge
                 let keys = OPTION_BY_POINTS
                     .sub_prefix(gauge_id)
@@ -367,6 +1544,9 @@ mod execute {
                 if (keys.len() as u32) < batch_size {
                     // removing total cast only once at the end to save gas
                     TOTAL_CAST.save(deps.storage, gauge_id, &0)?;
+                    TOTAL_ABSTAIN.save(deps.storage, gauge_id, &0)?;
+                    // all votes are expired now
+                    VOTER_COUNT.save(deps.storage, gauge_id, &0)?;
                     reset.next += reset.reset_each;
                 }
             }
@@ -393,15 +1573,40 @@ mod execute {
         // must be true if option is added by execute message
         check_option: bool,
     ) -> Result<Response, ContractError> {
+        if option == ABSTAIN_OPTION {
+            return Err(ContractError::ReservedOption(option));
+        }
         // check is such option already exists
         if TALLY.has(deps.as_ref().storage, (gauge_id, &option)) {
             return Err(ContractError::OptionAlreadyExists { option, gauge_id });
         };
+        // this also applies to options of the adapter when creating the gauge
+        if let Some(max_total_options) = load_gauge(deps.storage, gauge_id)?.max_total_options {
+            let options = TALLY
+                .prefix(gauge_id)
+                .keys(deps.storage, None, None, Order::Ascending)
+                .take(max_total_options as usize)
+                .count();
+            ensure!(
+                options < max_total_options as usize,
+                ContractError::OptionLimitReached {
+                    gauge_id,
+                    max_total_options,
+                }
+            );
+        }
 
         // only options added from gauge creation level should not be validated and can
         // have 0 points as assigned voting power.
         if check_option {
-            let gauge = GAUGES.load(deps.storage, gauge_id)?;
+            ensure_not_paused(deps.storage)?;
+            let gauge = load_gauge(deps.storage, gauge_id)?;
+            if gauge.voting_closed {
+                return Err(ContractError::VotingClosed(gauge_id));
+            }
+            if gauge.options_frozen {
+                return Err(ContractError::OptionsFrozen(gauge_id));
+            }
             // query gauge adapter if it is valid
             let adapter_option: CheckOptionResponse = deps
                 .querier
@@ -420,16 +1625,13 @@ mod execute {
             }
             // If it is a user adding option, query him for voting power in order to prevent
             // spam from nonvoting users
-            let voting_power = deps
-                .querier
-                .query::<VotingPowerAtHeightResponse>(&QueryRequest::Wasm(WasmQuery::Smart {
-                    contract_addr: CONFIG.load(deps.storage)?.voting_powers.to_string(),
-                    msg: to_binary(&DaoQuery::VotingPowerAtHeight {
-                        address: sender.to_string(),
-                        height: None,
-                    })?,
-                }))?
-                .power;
+            let config = CONFIG.load(deps.storage)?;
+            let voting_power = config.voting_query.power(
+                &deps.querier,
+                &config.voting_powers,
+                sender.as_str(),
+                None,
+            )?;
             if voting_power.is_zero() {
                 return Err(ContractError::NoVotingPower(sender.to_string()));
             }
@@ -444,40 +1646,108 @@ mod execute {
             .add_attribute("option", option))
     }
 
+    pub fn place_votes_bps(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        gauge_id: GaugeId,
+        votes: Vec<(String, u16)>,
+        proof: Option<Vec<String>>,
+    ) -> Result<Response, ContractError> {
+        // the total weight is checked like for `PlaceVotes`
+        let votes = votes
+            .into_iter()
+            .map(|(option, bps)| Vote {
+                option,
+                weight: Decimal::from_ratio(bps, BPS_PER_UNIT),
+            })
+            .collect();
+        place_votes(deps, env, info, gauge_id, Some(votes), proof, None)
+    }
+
     pub fn place_votes(
         deps: DepsMut,
         env: Env,
-        sender: Addr,
+        info: MessageInfo,
         gauge_id: GaugeId,
         new_votes: Option<Vec<Vote>>,
+        proof: Option<Vec<String>>,
+        nonce: Option<u64>,
     ) -> Result<Response, ContractError> {
-        let gauge = match GAUGES.may_load(deps.storage, gauge_id)? {
-            Some(gauge) => gauge,
-            None => return Err(ContractError::GaugeMissing(gauge_id)),
-        };
+        ensure_not_paused(deps.storage)?;
+        let sender = info.sender;
+        let gauge = load_gauge(deps.storage, gauge_id)?;
 
+        if gauge.voting_closed {
+            return Err(ContractError::VotingClosed(gauge_id));
+        }
         if gauge.is_resetting() {
             return Err(ContractError::GaugeResetting(gauge_id));
         }
 
+        // make sure the sender is allowed to vote on gauges with restricted voters
+        match &gauge.voter_restriction {
+            Some(restriction) if !VOTER_ALLOWLIST.has(deps.storage, (gauge_id, &sender)) => {
+                match (restriction, proof) {
+                    (VoterRestriction::MerkleRoot(root), Some(proof)) => {
+                        verify_voter_proof(root, &sender, proof)?;
+                        // remember the voter, so the proof is not needed for later votes
+                        VOTER_ALLOWLIST.save(deps.storage, (gauge_id, &sender), &1)?;
+                    }
+                    _ => return Err(ContractError::VoterNotAllowed(sender.to_string())),
+                }
+            }
+            _ => {}
+        }
+
+        // a resubmission of the last call is not applied again, any attached funds are sent back.
+        // It still has to pass the checks above, like any other vote
+        if let Some(nonce) = nonce {
+            if VOTE_NONCES.may_load(deps.storage, (gauge_id, &sender))? == Some(nonce) {
+                let mut response = Response::new()
+                    .add_attribute("action", "place_vote")
+                    .add_attribute("sender", &sender)
+                    .add_attribute("gauge_id", gauge_id.to_string())
+                    .add_attribute("duplicate_nonce", nonce.to_string());
+                if !info.funds.is_empty() {
+                    response = response.add_message(BankMsg::Send {
+                        to_address: sender.to_string(),
+                        amount: info.funds,
+                    });
+                }
+                return Ok(response);
+            }
+            VOTE_NONCES.save(deps.storage, (gauge_id, &sender), &nonce)?;
+        }
+
+        // zero weight votes are meaningless, don't store them.
+        // If all votes have zero weight, this removes the vote
+        let new_votes: Vec<Vote> = new_votes
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|v| !v.weight.is_zero())
+            .collect();
+
         // make sure sums work out
-        let new_votes = new_votes.unwrap_or_default();
         let total_weight = new_votes.iter().map(|v| v.weight).sum();
         if total_weight > Decimal::one() {
             return Err(ContractError::TooMuchVotingWeight(total_weight));
         }
+        if gauge.require_full_allocation
+            && !new_votes.is_empty()
+            && total_weight + FULL_ALLOCATION_TOLERANCE < Decimal::one()
+        {
+            return Err(ContractError::IncompleteAllocation(total_weight));
+        }
 
         // load voter power from voting powers contract (DAO)
-        let voting_power = deps
-            .querier
-            .query::<VotingPowerAtHeightResponse>(&QueryRequest::Wasm(WasmQuery::Smart {
-                contract_addr: CONFIG.load(deps.storage)?.voting_powers.to_string(),
-                msg: to_binary(&DaoQuery::VotingPowerAtHeight {
-                    address: sender.to_string(),
-                    height: None,
-                })?,
-            }))?
-            .power;
+        let config = CONFIG.load(deps.storage)?;
+        let voting_power = config.voting_query.power(
+            &deps.querier,
+            &config.voting_powers,
+            sender.as_str(),
+            None,
+        )?;
         if voting_power.is_zero() {
             return Err(ContractError::NoVotingPower(sender.to_string()));
         }
@@ -492,6 +1762,44 @@ mod execute {
             return Err(ContractError::CannotRemoveNonexistingVote {});
         }
 
+        // the first vote of every epoch is free, voting again costs the fee. Removing is free
+        let mut charged_fee = None;
+        if let (Some(fee), false) = (&gauge.vote_change_fee, new_votes.is_empty()) {
+            let count = match VOTES_IN_EPOCH.may_load(deps.storage, (gauge_id, &sender))? {
+                Some((epoch, count)) if epoch == gauge.executed_epochs => count,
+                _ => 0,
+            };
+            if count > 0 {
+                ensure!(
+                    info.funds
+                        .iter()
+                        .any(|coin| coin.denom == fee.denom && coin.amount >= fee.amount),
+                    ContractError::VoteChangeFeeRequired(fee.to_string())
+                );
+                charged_fee = Some(fee.clone());
+            }
+            VOTES_IN_EPOCH.save(
+                deps.storage,
+                (gauge_id, &sender),
+                &(gauge.executed_epochs, count + 1),
+            )?;
+        }
+
+        // keep track of the number of voters, new voters cannot vote above the limit
+        let voter_count = VOTER_COUNT
+            .may_load(deps.storage, gauge_id)?
+            .unwrap_or_default();
+        if previous_vote.is_none() {
+            if let Some(max_voters) = gauge.max_voters {
+                if voter_count >= max_voters {
+                    return Err(ContractError::VoterLimitReached(gauge_id));
+                }
+            }
+            VOTER_COUNT.save(deps.storage, gauge_id, &(voter_count + 1))?;
+        } else if new_votes.is_empty() {
+            VOTER_COUNT.save(deps.storage, gauge_id, &voter_count.saturating_sub(1))?;
+        }
+
         // first, calculate a diff between new_vote and previous_vote (option -> (old, new))
         let previous_vote = previous_vote.unwrap_or_default();
         let power = previous_vote.power;
@@ -511,19 +1819,49 @@ mod execute {
 
         // second, test any new options are valid,
         // only for those voted for first time (others have already been checked)
+        // abstaining is always valid
         for new_opt in diff
             .iter()
-            .filter(|(_, (old, _))| *old == 0)
+            .filter(|(&k, (old, _))| *old == 0 && k != ABSTAIN_OPTION)
             .map(|(&k, _)| k)
         {
-            if !TALLY.has(deps.storage, (gauge_id, new_opt)) {
-                return Err(ContractError::OptionDoesNotExists {
+            ensure_option_exists(deps.storage, gauge_id, new_opt)?;
+            if DEPRECATED_OPTIONS.has(deps.storage, (gauge_id, new_opt)) {
+                return Err(ContractError::OptionDeprecated {
                     option: new_opt.to_string(),
                     gauge_id,
                 });
             }
         }
 
+        // optionally, make sure the adapter still considers all voted options valid
+        if gauge.validate_option_on_vote {
+            for option in new_votes
+                .iter()
+                .map(|v| &v.option)
+                .filter(|&o| o != ABSTAIN_OPTION)
+            {
+                let adapter_option: CheckOptionResponse = deps
+                    .querier
+                    .query_wasm_smart(
+                        gauge.adapter.clone(),
+                        &AdapterQueryMsg::CheckOption {
+                            option: option.clone(),
+                        },
+                    )
+                    .map_err(|_| ContractError::OptionInvalidByAdapter {
+                        option: option.clone(),
+                        gauge_id,
+                    })?;
+                if !adapter_option.valid {
+                    return Err(ContractError::OptionInvalidByAdapter {
+                        option: option.clone(),
+                        gauge_id,
+                    });
+                }
+            }
+        }
+
         // third, update tally based on diff
         let updates: Vec<(&str, u128, u128)> = diff
             .iter()
@@ -547,15 +1885,90 @@ mod execute {
             )?;
         }
 
-        let response = Response::new()
+        if let Some(last) = LAST_VOTED_AT.may_load(deps.storage, (gauge_id, &sender))? {
+            VOTERS_BY_HEIGHT.remove(deps.storage, (gauge_id, last.height, &sender));
+        }
+        VOTERS_BY_HEIGHT.save(deps.storage, (gauge_id, env.block.height, &sender), &1)?;
+        LAST_VOTED_AT.save(
+            deps.storage,
+            (gauge_id, &sender),
+            &VoterActivity {
+                height: env.block.height,
+                time: env.block.time,
+            },
+        )?;
+
+        let mut response = Response::new()
             .add_attribute("action", "place_vote")
             .add_attribute("sender", &sender)
-            .add_attribute("gauge_id", gauge_id.to_string());
+            .add_attribute("gauge_id", gauge_id.to_string())
+            .add_attribute("last_voted_at", env.block.time.seconds().to_string());
+        if let Some(fee) = &charged_fee {
+            response = response.add_message(BankMsg::Send {
+                to_address: config.dao_core.to_string(),
+                amount: vec![fee.clone()],
+            });
+        }
+        // only the fee is kept, everything else that was attached is sent back
+        let refund: Vec<Coin> = info
+            .funds
+            .into_iter()
+            .filter_map(|mut coin| {
+                if let Some(fee) = charged_fee.as_ref().filter(|fee| fee.denom == coin.denom) {
+                    coin.amount -= fee.amount;
+                }
+                (!coin.amount.is_zero()).then_some(coin)
+            })
+            .collect();
+        if !refund.is_empty() {
+            response = response.add_message(BankMsg::Send {
+                to_address: sender.to_string(),
+                amount: refund,
+            });
+        }
         Ok(response)
     }
 
-    pub fn execute(deps: DepsMut, env: Env, gauge_id: u64) -> Result<Response, ContractError> {
-        let mut gauge = GAUGES.load(deps.storage, gauge_id)?;
+    /// Verifies that the sha256 hash of `voter` is a leaf of the merkle tree with the given root
+    fn verify_voter_proof(
+        merkle_root: &str,
+        voter: &Addr,
+        proof: Vec<String>,
+    ) -> Result<(), ContractError> {
+        let hash: [u8; 32] = sha2::Sha256::digest(voter.as_bytes())
+            .as_slice()
+            .try_into()
+            .map_err(|_| ContractError::InvalidMerkleProof {})?;
+
+        let hash = proof.into_iter().try_fold(hash, |hash, p| {
+            let mut proof_buf = [0; 32];
+            hex::decode_to_slice(p, &mut proof_buf)
+                .map_err(|_| ContractError::InvalidMerkleProof {})?;
+            let mut hashes = [hash, proof_buf];
+            hashes.sort_unstable();
+            sha2::Sha256::digest(&hashes.concat())
+                .as_slice()
+                .try_into()
+                .map_err(|_| ContractError::InvalidMerkleProof {})
+        })?;
+
+        let mut root_buf: [u8; 32] = [0; 32];
+        hex::decode_to_slice(merkle_root, &mut root_buf)
+            .map_err(|_| ContractError::InvalidMerkleRoot {})?;
+        if root_buf != hash {
+            return Err(ContractError::InvalidMerkleProof {});
+        }
+        Ok(())
+    }
+
+    pub fn execute(
+        mut deps: DepsMut,
+        env: Env,
+        sender: Addr,
+        gauge_id: u64,
+    ) -> Result<Response, ContractError> {
+        ensure_not_paused(deps.storage)?;
+        let mut gauge = load_gauge(deps.storage, gauge_id)?;
 
         if gauge.is_stopped {
             return Err(ContractError::GaugeStopped(gauge_id));
@@ -563,8 +1976,17 @@ mod execute {
         if gauge.is_resetting() {
             return Err(ContractError::GaugeResetting(gauge_id));
         }
+        if !gauge
+            .execute_policy
+            .allows(&sender, &CONFIG.load(deps.storage)?.owner)
+        {
+            return Err(ContractError::ExecutorNotAllowed(
+                sender.to_string(),
+                gauge_id,
+            ));
+        }
 
-        let current_epoch = env.block.time.seconds();
+        let current_epoch = gauge.epoch_unit.now(&env.block);
         if current_epoch < gauge.next_epoch {
             return Err(ContractError::EpochNotReached {
                 gauge_id,
@@ -572,97 +1994,1084 @@ mod execute {
                 next_epoch: gauge.next_epoch,
             });
         }
-        gauge.next_epoch = env.block.time.plus_seconds(gauge.epoch).seconds();
+        // below the price floor, the epoch is skipped: nothing is selected or paid
+        if let Some(condition) = &gauge.execute_condition {
+            let PriceResponse { price } = deps
+                .querier
+                .query_wasm_smart(&condition.oracle, &OracleQueryMsg::Price {})?;
+            if price < condition.min_price {
+                gauge.next_epoch = epoch_boundary(current_epoch, gauge.epoch, gauge.align_to);
+                GAUGES.save(deps.storage, gauge_id, &gauge)?;
+                return Ok(Response::new()
+                    .add_attribute("action", "skip_execution")
+                    .add_attribute("gauge_id", gauge_id.to_string())
+                    .add_attribute("price", price.to_string())
+                    .add_attribute("next_epoch", gauge.next_epoch.to_string()));
+            }
+        }
+        // whole epochs that passed without an execution, they can be paid with `ExecuteEpoch`
+        let missed = (current_epoch - gauge.next_epoch) / gauge.epoch;
+        if missed > 0 {
+            MISSED_EPOCHS.save(deps.storage, (gauge_id, gauge.executed_epochs), &missed)?;
+        }
+        gauge.next_epoch = epoch_boundary(current_epoch, gauge.epoch, gauge.align_to);
+
+        // all voted options with their power, this is stored for the epoch along with the config
+        let tallies = query::execution_tallies(deps.as_ref(), &env, gauge_id, &gauge)?;
+        gauge.epoch_start_height = Some(env.block.height);
 
         // this set contains tuple (option, total_voted_power)
         // for adapter query, this needs to be transformed into (option, voted_weight)
-        let selected_set_with_powers = query::selected_set(deps.as_ref(), gauge_id)?.votes;
-        let selected_powers_sum = selected_set_with_powers
-            .iter()
-            .map(|(_, power)| power.u128())
-            .sum::<u128>();
+        let (max_options_selected, max_available_percentage) = gauge.selection_limits();
+        let selected_set_with_powers = query::preview_selected_set(
+            tallies.clone(),
+            gauge.min_percent_selected,
+            gauge.min_absolute_tally,
+            max_options_selected,
+            max_available_percentage,
+        )?
+        .votes;
 
         // save the selected options and their powers for the frontend to display
         gauge.last_executed_set = Some(selected_set_with_powers.clone());
+        let state_before = gauge_state(&gauge);
+        // this was the final execution after voting was closed
+        if gauge.voting_closed {
+            gauge.is_stopped = true;
+        }
 
-        // calculate "local" ratios of voted options per total power of all selected options
-        let selected = selected_set_with_powers
-            .into_iter()
-            .map(|(option, power)| Ok((option, Decimal::from_ratio(power, selected_powers_sum))))
-            .collect::<StdResult<Vec<(String, Decimal)>>>()?;
+        let total_cast = TOTAL_CAST
+            .may_load(deps.storage, gauge_id)?
+            .unwrap_or_default();
+        let paid_options = gauge.max_paid_options.unwrap_or(u32::MAX);
+        let distribution_mode = gauge.distribution_mode.clone();
+        let rounding = gauge.rounding.clone();
+        let selected = if gauge.bootstrap_equal_split && total_cast == 0 {
+            // nobody voted yet, so split equally to bootstrap the gauge
+            query::bootstrap_weights(
+                deps.as_ref(),
+                gauge_id,
+                gauge.max_options_selected.min(paid_options),
+            )?
+        } else {
+            // only the top of the selected set is paid, the weights are normalized among them
+            query::to_weights(
+                selected_set_with_powers
+                    .into_iter()
+                    .take(paid_options as usize)
+                    .collect(),
+            )
+        };
+        let selected = if gauge.adapter_multipliers {
+            query::apply_multipliers(deps.as_ref(), &gauge.adapter, selected)?
+        } else {
+            selected
+        };
+
+        let config = CONFIG.load(deps.storage)?;
+        let reward = if gauge.adapter_reward {
+            adapter_epoch_reward(deps.as_ref(), gauge_id, &gauge, &config.dao_core)?
+        } else {
+            gauge.epoch_reward()
+        };
+        let epoch = gauge.executed_epochs;
+        let callback_msg = gauge
+            .post_execute_callback
+            .as_ref()
+            .map(|callback| post_execute_callback_msg(callback, gauge_id, epoch, selected.clone()))
+            .transpose()?;
+        let mut response = distribute(
+            deps.branch(),
+            gauge_id,
+            &mut gauge,
+            epoch,
+            selected,
+            &reward,
+            &distribution_mode,
+            &rounding,
+            &config.dao_core,
+            true,
+            Response::new()
+                .add_attribute("action", "execute_tally")
+                .add_attribute("executed_by", &sender),
+        )?;
+        // sent after the distribution, a failing callback is only logged in the reply
+        if let Some(msg) = callback_msg {
+            response = response
+                .add_submessage(SubMsg::reply_on_error(msg, POST_EXECUTE_CALLBACK_REPLY_ID));
+        }
+        if missed > 0 {
+            response = response.add_attribute("missed_epochs", missed.to_string());
+        }
 
-        // query gauge adapter for execute messages for DAO
-        let execute_messages: SampleGaugeMsgsResponse = deps.querier.query_wasm_smart(
-            gauge.adapter.clone(),
-            &AdapterQueryMsg::SampleGaugeMsgs { selected },
+        // record participation and inputs of this epoch for analytics and audits
+        let participation = query::participation(deps.as_ref(), gauge_id)?;
+        if participation.votes_exceed_power {
+            response = response.add_attribute("votes_exceed_power", "true");
+        }
+        if gauge.is_stopped {
+            response = response.add_event(gauge_state_change(
+                gauge_id,
+                state_before,
+                GAUGE_STATE_STOPPED,
+                &sender,
+            ));
+        }
+        // snapshot the selected options, so their popularity can be charted over time
+        for (option, _) in gauge.last_executed_set.iter().flatten() {
+            let tally = tallies
+                .iter()
+                .find(|(o, _)| o == option)
+                .map(|(_, tally)| *tally)
+                .unwrap_or_default();
+            OPTION_TALLY_HISTORY.save(
+                deps.storage,
+                (
+                    gauge_id,
+                    option,
+                    gauge.executed_epochs % OPTION_TALLY_HISTORY_EPOCHS,
+                ),
+                &OptionTallySnapshot {
+                    epoch: gauge.executed_epochs,
+                    tally,
+                },
+            )?;
+        }
+        EXECUTION_INPUTS.save(
+            deps.storage,
+            (gauge_id, gauge.executed_epochs),
+            &ExecutionInputs {
+                tallies,
+                total_power: participation.total_power,
+                min_percent_selected: gauge.min_percent_selected,
+                min_absolute_tally: gauge.min_absolute_tally,
+                max_options_selected,
+                max_available_percentage,
+                to_distribute: reward,
+                executed_by: Some(sender.clone()),
+                max_paid_options: gauge.max_paid_options,
+                distribution_mode,
+                rounding,
+            },
+        )?;
+        PARTICIPATION_HISTORY.save(
+            deps.storage,
+            (gauge_id, gauge.executed_epochs),
+            &EpochParticipation {
+                epoch: gauge.executed_epochs,
+                votes_power: participation
+                    .votes_cast
+                    .checked_add(participation.abstained)?,
+                total_power: participation.total_power,
+            },
         )?;
 
+        if gauge.auto_prune_zero_options {
+            let pruned = prune_zero_options(deps.storage, &gauge, gauge_id)?;
+            if !pruned.is_empty() {
+                response = response.add_attribute("pruned", pruned.join(","));
+            }
+        }
+
+        gauge.executed_epochs += 1;
+        GAUGES.save(deps.storage, gauge_id, &gauge)?;
+
+        Ok(response)
+    }
+
+    pub fn execute_epoch(
+        mut deps: DepsMut,
+        sender: Addr,
+        gauge_id: GaugeId,
+        epoch: u64,
+    ) -> Result<Response, ContractError> {
         let config = CONFIG.load(deps.storage)?;
-        let execute_msg = WasmMsg::Execute {
-            contract_addr: config.dao_core.to_string(),
-            msg: to_binary(&DaoExecuteMsg::ExecuteProposalHook {
-                msgs: execute_messages.execute,
+        if sender != config.owner {
+            return Err(ContractError::Unauthorized {});
+        }
+        ensure_not_paused(deps.storage)?;
+        let mut gauge = load_gauge(deps.storage, gauge_id)?;
+        if gauge.is_stopped {
+            return Err(ContractError::GaugeStopped(gauge_id));
+        }
+        if gauge.is_resetting() {
+            return Err(ContractError::GaugeResetting(gauge_id));
+        }
+
+        // every missed epoch is paid only once
+        let missed = MISSED_EPOCHS
+            .may_load(deps.storage, (gauge_id, epoch))?
+            .unwrap_or_default();
+        ensure!(missed > 0, ContractError::NoMissedEpoch { gauge_id, epoch });
+        if missed > 1 {
+            MISSED_EPOCHS.save(deps.storage, (gauge_id, epoch), &(missed - 1))?;
+        } else {
+            MISSED_EPOCHS.remove(deps.storage, (gauge_id, epoch));
+        }
+
+        // select and pay like the execution of `epoch` did
+        let inputs = EXECUTION_INPUTS.load(deps.storage, (gauge_id, epoch))?;
+        let selected_set = query::preview_selected_set(
+            inputs.tallies,
+            inputs.min_percent_selected,
+            inputs.min_absolute_tally,
+            inputs.max_options_selected,
+            inputs.max_available_percentage,
+        )?
+        .votes;
+        let selected = query::to_weights(
+            selected_set
+                .into_iter()
+                .take(inputs.max_paid_options.unwrap_or(u32::MAX) as usize)
+                .collect(),
+        );
+        // the budget may have been used up since
+        let reward = inputs.to_distribute.map(|reward| Asset {
+            amount: match gauge.remaining_budget() {
+                Some(remaining) => reward.amount.min(remaining),
+                None => reward.amount,
+            },
+            ..reward
+        });
+
+        let state_before = gauge_state(&gauge);
+        // the payouts of the original execution of `epoch` stay recorded
+        let mut response = distribute(
+            deps.branch(),
+            gauge_id,
+            &mut gauge,
+            epoch,
+            selected,
+            &reward,
+            &inputs.distribution_mode,
+            &inputs.rounding,
+            &config.dao_core,
+            false,
+            Response::new()
+                .add_attribute("action", "execute_epoch")
+                .add_attribute("gauge_id", gauge_id.to_string())
+                .add_attribute("epoch", epoch.to_string())
+                .add_attribute("missed_left", (missed - 1).to_string()),
+        )?;
+        if gauge.is_stopped {
+            response = response.add_event(gauge_state_change(
+                gauge_id,
+                state_before,
+                GAUGE_STATE_STOPPED,
+                &sender,
+            ));
+        }
+        GAUGES.save(deps.storage, gauge_id, &gauge)?;
+
+        Ok(response)
+    }
+
+    /// Removes options that had a zero tally for more than `AUTO_PRUNE_IDLE_EPOCHS`.
+    /// Only a window of the zero tally options is checked per call, continuing where the
+    /// last call stopped. Returns the removed options
+    fn prune_zero_options(
+        storage: &mut dyn Storage,
+        gauge: &Gauge,
+        gauge_id: GaugeId,
+    ) -> StdResult<Vec<String>> {
+        let cursor = PRUNE_CURSOR.may_load(storage, gauge_id)?;
+        let options = OPTION_BY_POINTS
+            .prefix((gauge_id, 0))
+            .keys(
+                storage,
+                cursor.as_deref().map(Bound::exclusive),
+                None,
+                Order::Ascending,
+            )
+            .take(AUTO_PRUNE_SCAN_LIMIT)
+            .collect::<StdResult<Vec<_>>>()?;
+        match options.last() {
+            Some(last) if options.len() == AUTO_PRUNE_SCAN_LIMIT => {
+                PRUNE_CURSOR.save(storage, gauge_id, last)?
+            }
+            _ => PRUNE_CURSOR.remove(storage, gauge_id),
+        }
+
+        let epoch = gauge.executed_epochs;
+        let mut pruned = vec![];
+        for option in options {
+            match ZERO_TALLY_SINCE.may_load(storage, (gauge_id, &option))? {
+                Some(since)
+                    if epoch - since > AUTO_PRUNE_IDLE_EPOCHS
+                        && pruned.len() < AUTO_PRUNE_MAX_OPTIONS =>
+                {
+                    remove_option_state(storage, gauge, gauge_id, &option)?;
+                    pruned.push(option);
+                }
+                Some(_) => {}
+                None => ZERO_TALLY_SINCE.save(storage, (gauge_id, &option), &epoch)?,
+            }
+        }
+        Ok(pruned)
+    }
+
+    /// Pays `reward` to the `selected` options of `epoch`, or asks the adapters (or the
+    /// distribution hook) for the messages without a reward. They are added to `response`,
+    /// to be executed by the DAO core unless the escrow covers the reward.
+    /// With a swap route, the swap of the reward comes first. With `record_payouts`, what the
+    /// options are paid is recorded as the payouts of `epoch`
+    #[allow(clippy::too_many_arguments)]
+    fn distribute(
+        deps: DepsMut,
+        gauge_id: GaugeId,
+        gauge: &mut Gauge,
+        epoch: u64,
+        selected: Vec<(String, Decimal)>,
+        reward: &Option<Asset>,
+        distribution_mode: &DistributionMode,
+        rounding: &Rounding,
+        dao_core: &Addr,
+        record_payouts: bool,
+        mut response: Response,
+    ) -> Result<Response, ContractError> {
+        let mut from_escrow = false;
+        let mut swap = None;
+        let payouts;
+        // rewards paid by each of the messages, in the same order
+        let mut msg_rewards: Vec<Vec<(String, Asset)>> = vec![];
+        // fixed grants ignore the proportions, the options only keep their rank
+        let fixed_amount = match distribution_mode {
+            DistributionMode::EqualFixed { amount } => Some(*amount),
+            _ => None,
+        };
+        let selected = match fixed_amount {
+            Some(_) => query::equal_weights(selected.into_iter().map(|(option, _)| option)),
+            None => selected,
+        };
+        let mut msgs = match reward {
+            // gauge distributes the reward itself, options are the recipients
+            Some(reward) => {
+                // fail clearly instead of with the error of the transfers
+                let ensure_available = |denom: &AssetType| -> Result<(), ContractError> {
+                    ensure!(
+                        !denom.query_balance(&deps.querier, dao_core)?.is_zero(),
+                        ContractError::MissingDenom {
+                            denom: denom.denom().to_owned(),
+                        }
+                    );
+                    Ok(())
+                };
+                // the DAO core swaps the reward first, the options are paid what the router returns
+                let offered = reward.amount;
+                let reward = match &gauge.swap_route {
+                    Some(route) if !reward.amount.is_zero() => {
+                        ensure_available(&reward.denom)?;
+                        let (swapped, msg) = swap_msg(deps.as_ref(), route, reward)?;
+                        swap = Some(msg);
+                        swapped
+                    }
+                    _ => reward.clone(),
+                };
+                let (selected, shares) = match fixed_amount {
+                    // the best ranked options get their grant, as long as the reward lasts
+                    Some(amount) => {
+                        let covered = (reward.amount / amount).u128();
+                        let selected = query::equal_weights(
+                            selected
+                                .into_iter()
+                                .map(|(option, _)| option)
+                                .take(covered.try_into().unwrap_or(usize::MAX)),
+                        );
+                        let shares = selected
+                            .iter()
+                            .map(|(option, _)| (option.clone(), amount))
+                            .collect();
+                        (selected, shares)
+                    }
+                    None => query::split_reward_with_floor(
+                        &reward,
+                        selected,
+                        rounding,
+                        gauge.min_distribution_per_option,
+                    )?,
+                };
+                let shares: Vec<_> = shares
+                    .into_iter()
+                    .filter(|(_, amount)| !amount.is_zero())
+                    .collect();
+                let total = shares.iter().map(|(_, amount)| *amount).sum();
+                if swap.is_none() {
+                    from_escrow = pay_from_escrow(deps.storage, gauge_id, &reward.denom, total)?;
+                    if !from_escrow && !total.is_zero() {
+                        ensure_available(&reward.denom)?;
+                    }
+                }
+                for (option, amount) in &shares {
+                    add_lifetime_reward(deps.storage, gauge_id, option, &reward.denom, *amount)?;
+                }
+                payouts = EpochPayouts::Reward(shares.clone());
+                let paid: Vec<_> = shares
+                    .iter()
+                    .map(|(option, amount)| {
+                        let paid = Asset {
+                            denom: reward.denom.clone(),
+                            amount: *amount,
+                        };
+                        (option.clone(), paid)
+                    })
+                    .collect();
+                if let Some(budget) = &gauge.total_budget {
+                    // the budget is in the denom before the swap
+                    gauge.distributed = gauge.distributed.checked_add(if swap.is_some() {
+                        offered
+                    } else {
+                        total
+                    })?;
+                    // the campaign is over once its budget is paid out
+                    if gauge.distributed >= budget.amount {
+                        gauge.is_stopped = true;
+                    }
+                }
+                match (&gauge.distribution_hook, &reward.denom) {
+                    // the hook gets the whole reward and splits it by itself
+                    (Some(hook), AssetType::Native(denom)) => {
+                        msg_rewards.push(paid);
+                        vec![distribution_hook_msg(
+                            hook,
+                            gauge_id,
+                            epoch,
+                            selected,
+                            vec![Coin::new(total.u128(), denom)],
+                        )?]
+                    }
+                    _ => {
+                        msg_rewards = paid.into_iter().map(|paid| vec![paid]).collect();
+                        shares
+                            .into_iter()
+                            .map(|(option, amount)| {
+                                let recipient = deps.api.addr_validate(&option)?;
+                                reward.denom.transfer_msg(&recipient, amount)
+                            })
+                            .collect::<StdResult<Vec<CosmosMsg>>>()?
+                    }
+                }
+            }
+            None => {
+                payouts = EpochPayouts::Weights(selected.clone());
+                match &gauge.distribution_hook {
+                    Some(hook) => vec![distribution_hook_msg(
+                        hook,
+                        gauge_id,
+                        epoch,
+                        selected,
+                        vec![],
+                    )?],
+                    // query gauge adapters for execute messages for DAO
+                    None => query::adapter_msgs(deps.as_ref(), gauge, selected)?,
+                }
+            }
+        };
+        if record_payouts {
+            EPOCH_PAYOUTS.save(deps.storage, (gauge_id, epoch), &payouts)?;
+        }
+        // with a swap, the budget is charged what was offered, not what the options get
+        let counts_to_budget = gauge.total_budget.is_some() && swap.is_none();
+        if let Some(swap) = swap {
+            msgs.insert(0, swap);
+            msg_rewards.insert(0, vec![]);
+        }
+        // the messages of the adapters pay nothing from the gauge's records
+        msg_rewards.resize(msgs.len(), vec![]);
+
+        if from_escrow {
+            // the gauge holds the reward itself, no need to involve the DAO core
+            response = response
+                .add_attribute("paid_from", "escrow")
+                .add_messages(msgs);
+        } else if gauge.isolate_failures {
+            // send every message on its own, so a failing one doesn't revert the others
+            for (index, (msg, rewards)) in msgs.into_iter().zip(msg_rewards).enumerate() {
+                let id = NEXT_PAYOUT_REPLY_ID
+                    .may_load(deps.storage)?
+                    .unwrap_or_default();
+                NEXT_PAYOUT_REPLY_ID.save(deps.storage, &(id + 1))?;
+                PENDING_PAYOUTS.save(
+                    deps.storage,
+                    id,
+                    &PendingPayout {
+                        gauge_id,
+                        epoch,
+                        index: index as u64,
+                        msg: msg.clone(),
+                        rewards,
+                        counts_to_budget,
+                        recorded: record_payouts,
+                    },
+                )?;
+                response = response.add_submessage(SubMsg::reply_always(
+                    proposal_hook(dao_core, vec![msg])?,
+                    id,
+                ));
+            }
+        } else {
+            response = response.add_message(proposal_hook(dao_core, msgs)?);
+        }
+
+        Ok(response)
+    }
+
+    /// Simulates swapping the native `reward` with the router of `route`.
+    /// Returns the swapped reward along with the swap message, which fails on a worse return
+    fn swap_msg(
+        deps: Deps,
+        route: &SwapRoute,
+        reward: &Asset,
+    ) -> Result<(Asset, CosmosMsg), ContractError> {
+        let offer = Coin {
+            denom: reward.denom.denom().to_owned(),
+            amount: reward.amount,
+        };
+        let SimulateSwapResponse { return_amount } = deps.querier.query_wasm_smart(
+            &route.router,
+            &RouterQueryMsg::SimulateSwap {
+                offer: offer.clone(),
+                to_denom: route.to_denom.clone(),
+            },
+        )?;
+        let msg = WasmMsg::Execute {
+            contract_addr: route.router.to_string(),
+            msg: to_binary(&RouterExecuteMsg::Swap {
+                to_denom: route.to_denom.clone(),
+                minimum_receive: return_amount,
             })?,
-            funds: vec![],
+            funds: vec![offer],
         };
+        let swapped = Asset {
+            denom: AssetType::Native(route.to_denom.clone()),
+            amount: return_amount,
+        };
+        Ok((swapped, msg.into()))
+    }
 
-        GAUGES.save(deps.storage, gauge_id, &gauge)?;
+    /// Adds a payout to the rewards the option received over its lifetime
+    fn add_lifetime_reward(
+        storage: &mut dyn Storage,
+        gauge_id: GaugeId,
+        option: &str,
+        denom: &AssetType,
+        amount: Uint128,
+    ) -> StdResult<()> {
+        OPTION_LIFETIME_REWARDS.update(storage, (gauge_id, option), |rewards| -> StdResult<_> {
+            let mut rewards = rewards.unwrap_or_default();
+            match rewards.iter_mut().find(|reward| &reward.denom == denom) {
+                Some(reward) => reward.amount = reward.amount.checked_add(amount)?,
+                None => rewards.push(Asset {
+                    denom: denom.clone(),
+                    amount,
+                }),
+            }
+            Ok(rewards)
+        })?;
+        Ok(())
+    }
 
-        Ok(Response::new()
-            .add_attribute("action", "execute_tally")
-            .add_message(execute_msg))
+    /// Takes `total` from the gauge's escrow if it holds enough of the native `denom`.
+    /// Returns `false` if the reward has to be paid by the DAO core instead
+    fn pay_from_escrow(
+        storage: &mut dyn Storage,
+        gauge_id: GaugeId,
+        denom: &AssetType,
+        total: Uint128,
+    ) -> StdResult<bool> {
+        let denom = match denom {
+            AssetType::Native(denom) => denom,
+            AssetType::Cw20(_) => return Ok(false),
+        };
+        let balance = ESCROW
+            .may_load(storage, (gauge_id, denom))?
+            .unwrap_or_default();
+        if balance < total {
+            return Ok(false);
+        }
+        ESCROW.save(storage, (gauge_id, denom), &(balance - total))?;
+        Ok(true)
+    }
+
+    /// Reward of the current epoch of a gauge with `adapter_reward`.
+    /// The adapter's reward replaces the gauge's own one, as long as the escrow or the DAO core
+    /// hold enough of it to pay it out
+    fn adapter_epoch_reward(
+        deps: Deps,
+        gauge_id: GaugeId,
+        gauge: &Gauge,
+        dao_core: &Addr,
+    ) -> Result<Option<Asset>, ContractError> {
+        let res: EpochRewardResponse = deps.querier.query_wasm_smart(
+            &gauge.adapter,
+            &AdapterQueryMsg::EpochReward {
+                epoch: gauge.executed_epochs,
+            },
+        )?;
+        let mut reward = match res.reward {
+            Some(reward) => reward,
+            None => return Ok(gauge.epoch_reward()),
+        };
+
+        if let Some(budget) = &gauge.total_budget {
+            ensure!(
+                reward.denom == AssetType::Native(budget.denom.clone()),
+                ContractError::BudgetDenomMismatch {}
+            );
+            reward.amount = reward
+                .amount
+                .min(gauge.remaining_budget().unwrap_or_default());
+        }
+        let (denom, available) = match &reward.denom {
+            AssetType::Native(denom) => {
+                // either paid out from the escrow or by the DAO core, never both
+                let escrow = ESCROW
+                    .may_load(deps.storage, (gauge_id, denom))?
+                    .unwrap_or_default();
+                let core = reward.denom.query_balance(&deps.querier, dao_core)?;
+                (denom, escrow.max(core))
+            }
+            AssetType::Cw20(token) => {
+                ensure!(
+                    gauge.distribution_hook.is_none(),
+                    ContractError::DistributionHookCw20Reward {}
+                );
+                (token, reward.denom.query_balance(&deps.querier, dao_core)?)
+            }
+        };
+        ensure!(
+            available >= reward.amount,
+            ContractError::AdapterRewardUnavailable {
+                denom: denom.clone(),
+                amount: reward.amount,
+                available,
+            }
+        );
+        Ok(Some(reward))
+    }
+
+    /// Message handing the selected set of an epoch over to the gauge's distribution hook
+    fn distribution_hook_msg(
+        hook: &Addr,
+        gauge_id: GaugeId,
+        epoch: u64,
+        selected: Vec<(String, Decimal)>,
+        funds: Vec<Coin>,
+    ) -> StdResult<CosmosMsg> {
+        Ok(WasmMsg::Execute {
+            contract_addr: hook.to_string(),
+            msg: to_binary(&DistributionHookMsg::Distribute {
+                gauge_id,
+                epoch,
+                selected,
+            })?,
+            funds,
+        }
+        .into())
+    }
+
+    fn post_execute_callback_msg(
+        callback: &Addr,
+        gauge_id: GaugeId,
+        epoch: u64,
+        selected: Vec<(String, Decimal)>,
+    ) -> StdResult<WasmMsg> {
+        Ok(WasmMsg::Execute {
+            contract_addr: callback.to_string(),
+            msg: to_binary(&PostExecuteCallbackMsg::GaugeExecuted {
+                gauge_id,
+                epoch,
+                selected,
+            })?,
+            funds: vec![],
+        })
+    }
+
+    /// Message making the DAO core execute `msgs`
+    fn proposal_hook(dao_core: &Addr, msgs: Vec<CosmosMsg>) -> StdResult<WasmMsg> {
+        Ok(WasmMsg::Execute {
+            contract_addr: dao_core.to_string(),
+            msg: to_binary(&DaoExecuteMsg::ExecuteProposalHook { msgs })?,
+            funds: vec![],
+        })
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractError> {
+    let response = if msg.id == POST_EXECUTE_CALLBACK_REPLY_ID {
+        // the callback only replies on error, which must not revert the execution
+        let mut response = Response::new().add_attribute("action", "post_execute_callback_failure");
+        if let SubMsgResult::Err(error) = msg.result {
+            response = response.add_attribute("error", error);
+        }
+        response
+    } else {
+        payout_reply(deps.storage, msg)?
+    };
+    Ok(with_namespace(
+        response,
+        &CONFIG.load(deps.storage)?.event_namespace,
+    ))
+}
+
+/// Records the failure of a payout sent on its own, for executions with `isolate_failures`
+fn payout_reply(storage: &mut dyn Storage, msg: Reply) -> Result<Response, ContractError> {
+    let payout = PENDING_PAYOUTS.load(storage, msg.id)?;
+    PENDING_PAYOUTS.remove(storage, msg.id);
+
+    match msg.result {
+        SubMsgResult::Ok(_) => Ok(Response::new()),
+        SubMsgResult::Err(error) => {
+            revert_payout(storage, &payout)?;
+            EXECUTION_FAILURES.save(
+                storage,
+                (payout.gauge_id, payout.epoch, payout.index),
+                &ExecutionFailure {
+                    epoch: payout.epoch,
+                    msg: payout.msg,
+                    error: error.clone(),
+                },
+            )?;
+            Ok(Response::new()
+                .add_attribute("action", "execution_failure")
+                .add_attribute("gauge_id", payout.gauge_id.to_string())
+                .add_attribute("epoch", payout.epoch.to_string())
+                .add_attribute("error", error))
+        }
+    }
+}
+
+/// Takes the rewards of a failed payout back from the lifetime rewards of the options, the
+/// recorded payouts of its epoch and the distributed budget of its gauge
+fn revert_payout(storage: &mut dyn Storage, payout: &PendingPayout) -> Result<(), ContractError> {
+    if payout.rewards.is_empty() {
+        return Ok(());
+    }
+    let gauge_id = payout.gauge_id;
+    for (option, reward) in &payout.rewards {
+        OPTION_LIFETIME_REWARDS.update(storage, (gauge_id, option), |rewards| -> StdResult<_> {
+            let mut rewards = rewards.unwrap_or_default();
+            if let Some(lifetime) = rewards.iter_mut().find(|r| r.denom == reward.denom) {
+                lifetime.amount = lifetime.amount.checked_sub(reward.amount)?;
+            }
+            rewards.retain(|r| !r.amount.is_zero());
+            Ok(rewards)
+        })?;
+    }
+    if payout.recorded {
+        if let Some(EpochPayouts::Reward(shares)) =
+            EPOCH_PAYOUTS.may_load(storage, (gauge_id, payout.epoch))?
+        {
+            let shares = shares
+                .into_iter()
+                .filter(|(option, _)| !payout.rewards.iter().any(|(failed, _)| failed == option))
+                .collect();
+            EPOCH_PAYOUTS.save(
+                storage,
+                (gauge_id, payout.epoch),
+                &EpochPayouts::Reward(shares),
+            )?;
+        }
+    }
+    if payout.counts_to_budget {
+        let mut gauge = GAUGES.load(storage, gauge_id)?;
+        let failed = payout
+            .rewards
+            .iter()
+            .try_fold(Uint128::zero(), |sum, (_, reward)| {
+                sum.checked_add(reward.amount)
+            })?;
+        gauge.distributed = gauge.distributed.checked_sub(failed)?;
+        // the campaign is only over once its budget was actually paid out
+        if let Some(budget) = &gauge.total_budget {
+            if gauge.is_stopped && !gauge.voting_closed && gauge.distributed < budget.amount {
+                gauge.is_stopped = false;
+            }
+        }
+        GAUGES.save(storage, gauge_id, &gauge)?;
     }
+    Ok(())
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
         QueryMsg::Info {} => Ok(to_binary(&query::info(deps)?)?),
+        QueryMsg::Config {} => Ok(to_binary(&query::config(deps)?)?),
         QueryMsg::Gauge { id } => Ok(to_binary(&query::gauge(deps, id)?)?),
         QueryMsg::ListGauges { start_after, limit } => {
             Ok(to_binary(&query::list_gauges(deps, start_after, limit)?)?)
         }
-        QueryMsg::Vote { gauge, voter } => Ok(to_binary(&query::vote(deps, gauge, voter)?)?),
-        QueryMsg::ListVotes {
-            gauge,
+        QueryMsg::NextExecution { gauge } => Ok(to_binary(&query::next_execution(deps, gauge)?)?),
+        QueryMsg::ExecutableGauges { limit } => {
+            Ok(to_binary(&query::executable_gauges(deps, env, limit)?)?)
+        }
+        QueryMsg::ExecutableGaugesWithPreview { limit } => Ok(to_binary(
+            &query::executable_gauges_with_preview(deps, env, limit)?,
+        )?),
+        QueryMsg::GaugesByTitle { title } => Ok(to_binary(&query::gauges_by_title(deps, title)?)?),
+        QueryMsg::ListGaugesByTag {
+            tag,
             start_after,
             limit,
-        } => Ok(to_binary(&query::list_votes(
+        } => Ok(to_binary(&query::list_gauges_by_tag(
             deps,
-            gauge,
+            tag,
             start_after,
             limit,
         )?)?),
-        QueryMsg::ListOptions {
-            gauge,
+        QueryMsg::GaugesForAdapter { adapter } => {
+            Ok(to_binary(&query::gauges_for_adapter(deps, adapter)?)?)
+        }
+        QueryMsg::GaugesForOption {
+            option,
             start_after,
             limit,
-        } => Ok(to_binary(&query::list_options(
+        } => Ok(to_binary(&query::gauges_for_option(
             deps,
-            gauge,
+            option,
             start_after,
             limit,
         )?)?),
-        QueryMsg::SelectedSet { gauge } => Ok(to_binary(&query::selected_set(deps, gauge)?)?),
-        QueryMsg::LastExecutedSet { gauge } => {
-            Ok(to_binary(&query::last_executed_set(deps, gauge)?)?)
+        QueryMsg::Vote { gauge, voter } => Ok(to_binary(&query::vote(deps, gauge, voter)?)?),
+        QueryMsg::CanVote { gauge, voter } => Ok(to_binary(&query::can_vote(deps, gauge, voter)?)?),
+        QueryMsg::VoterMissedGauges { voter, limit } => {
+            Ok(to_binary(&query::voter_missed_gauges(deps, voter, limit)?)?)
         }
+        QueryMsg::VoterActivity { gauge, voter } => {
+            Ok(to_binary(&query::voter_activity(deps, gauge, voter)?)?)
+        }
+        QueryMsg::RecentVoters {
+            gauge,
+            start_after,
+            limit,
+        } => Ok(to_binary(&query::recent_voters(
+            deps,
+            gauge,
+            start_after,
+            limit,
+        )?)?),
+        QueryMsg::Participation { gauge } => Ok(to_binary(&query::participation(deps, gauge)?)?),
+        QueryMsg::ParticipationHistory {
+            gauge,
+            start_after,
+            limit,
+        } => Ok(to_binary(&query::participation_history(
+            deps,
+            gauge,
+            start_after,
+            limit,
+        )?)?),
+        QueryMsg::OptionTallyHistory {
+            gauge,
+            option,
+            start_after,
+            limit,
+        } => Ok(to_binary(&query::option_tally_history(
+            deps,
+            gauge,
+            option,
+            start_after,
+            limit,
+        )?)?),
+        QueryMsg::OptionLifetimeRewards { gauge, option } => Ok(to_binary(
+            &query::option_lifetime_rewards(deps, gauge, option)?,
+        )?),
+        QueryMsg::VotesToOvertake {
+            gauge,
+            option,
+            target_option,
+        } => Ok(to_binary(&query::votes_to_overtake(
+            deps,
+            gauge,
+            option,
+            target_option,
+        )?)?),
+        QueryMsg::OptionVoters {
+            gauge,
+            option,
+            start_after,
+            limit,
+        } => Ok(to_binary(&query::option_voters(
+            deps,
+            gauge,
+            option,
+            start_after,
+            limit,
+        )?)?),
+        QueryMsg::ListVotes {
+            gauge,
+            start_after,
+            limit,
+            only_active,
+        } => Ok(to_binary(&query::list_votes(
+            deps,
+            gauge,
+            start_after,
+            limit,
+            only_active,
+        )?)?),
+        QueryMsg::VotesChangedSince {
+            gauge,
+            since_block,
+            start_after,
+            limit,
+        } => Ok(to_binary(&query::votes_changed_since(
+            deps,
+            gauge,
+            since_block,
+            start_after,
+            limit,
+        )?)?),
+        QueryMsg::ListOptions {
+            gauge,
+            start_after,
+            limit,
+            order_by,
+        } => Ok(to_binary(&query::list_options(
+            deps,
+            gauge,
+            start_after,
+            limit,
+            order_by,
+        )?)?),
+        QueryMsg::SelectedSet { gauge } => Ok(to_binary(&query::selected_set(deps, &env, gauge)?)?),
+        QueryMsg::OptionLeaderboard { gauge, limit } => {
+            Ok(to_binary(&query::option_leaderboard(deps, gauge, limit)?)?)
+        }
+        QueryMsg::LastExecutedSet { gauge } => {
+            Ok(to_binary(&query::last_executed_set(deps, gauge)?)?)
+        }
+        QueryMsg::ShutOutOptions { gauge } => {
+            Ok(to_binary(&query::shut_out_options(deps, gauge)?)?)
+        }
+        QueryMsg::ExecutionFailures { gauge } => {
+            Ok(to_binary(&query::execution_failures(deps, gauge)?)?)
+        }
+        QueryMsg::ExecutionInputs { gauge, epoch } => {
+            Ok(to_binary(&query::execution_inputs(deps, gauge, epoch)?)?)
+        }
+        QueryMsg::PreviewSelectedSet {
+            tallies,
+            min_percent_selected,
+            min_absolute_tally,
+            max_options_selected,
+            max_available_percentage,
+        } => Ok(to_binary(&query::preview_selected_set(
+            tallies,
+            min_percent_selected,
+            min_absolute_tally,
+            max_options_selected,
+            max_available_percentage,
+        )?)?),
+        QueryMsg::RewardEfficiency { gauge } => {
+            Ok(to_binary(&query::reward_efficiency(deps, env, gauge)?)?)
+        }
+        QueryMsg::VoterProjectedImpact { gauge, voter } => Ok(to_binary(
+            &query::voter_projected_impact(deps, gauge, voter)?,
+        )?),
+        QueryMsg::SetDelta { gauge } => Ok(to_binary(&query::set_delta(deps, env, gauge)?)?),
+        QueryMsg::ExecutionComplexity { gauge } => {
+            Ok(to_binary(&query::execution_complexity(deps, env, gauge)?)?)
+        }
+        QueryMsg::AdapterSampleMsgs { gauge, selected } => Ok(to_binary(
+            &query::adapter_sample_msgs(deps, gauge, selected)?,
+        )?),
+        QueryMsg::AggregateSelectedSet { denom } => Ok(to_binary(&query::aggregate_selected_set(
+            deps, env, denom,
+        )?)?),
+        QueryMsg::RemainingBudget { gauge } => {
+            Ok(to_binary(&query::remaining_budget(deps, gauge)?)?)
+        }
+        QueryMsg::QualificationThreshold { gauge } => {
+            Ok(to_binary(&query::qualification_threshold(deps, gauge)?)?)
+        }
+        QueryMsg::SelectionParams { gauge } => {
+            Ok(to_binary(&query::selection_params(deps, gauge)?)?)
+        }
+        QueryMsg::VoteDistribution { gauge, buckets } => {
+            Ok(to_binary(&query::vote_distribution(deps, gauge, buckets)?)?)
+        }
+        QueryMsg::UnvotedOptions {
+            gauge,
+            voter,
+            start_after,
+            limit,
+        } => Ok(to_binary(&query::unvoted_options(
+            deps,
+            gauge,
+            voter,
+            start_after,
+            limit,
+        )?)?),
     }
 }
 
 mod query {
     use super::*;
 
-    use crate::msg::{LastExecutedSetResponse, VoteInfo, VoteResponse};
+    use crate::msg::{
+        AggregateSelectedSetResponse, BoostQueryMsg, BoostResponse, CanVoteResponse,
+        ConfigResponse, ExecutableGaugePreview, ExecutableGaugesWithPreviewResponse,
+        ExecutionComplexityResponse, ExecutionInputsResponse, GaugesForAdapterResponse,
+        GaugesForOptionResponse, LastExecutedSetResponse, LeaderboardEntry, MemberSinceResponse,
+        MembershipQueryMsg, NextExecutionResponse, OptionDelta, OptionLeaderboardResponse,
+        OptionLifetimeRewardsResponse, OptionMultipliersResponse, OptionRewardEfficiency,
+        OptionTallyHistoryResponse, OptionVotersResponse, PowerBucket,
+        QualificationThresholdResponse, RecentVotersResponse, RemainingBudgetResponse,
+        RewardEfficiencyResponse, SelectionParamsResponse, SetDeltaResponse,
+        ShutOutOptionsResponse, UnvotedOptionsResponse, VoteDistributionResponse, VoteInfo,
+        VoteResponse, VoterActivityResponse, VoterMissedGaugesResponse, VoterOptionImpact,
+        VoterProjectedImpactResponse, VotesChangedSinceResponse, VotesToOvertakeResponse,
+    };
+    use crate::state::{
+        Asset, AssetType, DeprecatedVotePolicy, Rounding, TenureCurve, EPOCH_LABELS,
+        EXECUTION_INPUTS, LAST_VOTED_AT, MAX_BOOST_MULTIPLIER, OPTION_LIFETIME_REWARDS,
+        OPTION_TALLY_HISTORY, OPTION_TALLY_HISTORY_EPOCHS, VOTERS_BY_HEIGHT, VOTER_ALLOWLIST,
+    };
     use cw_core_interface::voting::InfoResponse;
+    use std::collections::{BTreeMap, HashMap};
+
+    /// Loads the gauge for a query, failing with the message of `GaugeNotFound`
+    fn query_gauge(storage: &dyn Storage, gauge_id: GaugeId) -> StdResult<Gauge> {
+        load_gauge(storage, gauge_id).map_err(|err| StdError::generic_err(err.to_string()))
+    }
+
+    /// Fails with the message of `OptionNotFound` unless the gauge has the option
+    fn query_option(storage: &dyn Storage, gauge_id: GaugeId, option: &str) -> StdResult<()> {
+        query_gauge(storage, gauge_id)?;
+        ensure_option_exists(storage, gauge_id, option)
+            .map_err(|err| StdError::generic_err(err.to_string()))
+    }
 
     pub fn info(deps: Deps) -> StdResult<InfoResponse> {
         let info = cw2::get_contract_version(deps.storage)?;
         Ok(InfoResponse { info })
     }
 
-    fn to_gauge_response(gauge_id: GaugeId, gauge: Gauge) -> GaugeResponse {
-        GaugeResponse {
+    pub fn config(deps: Deps) -> StdResult<ConfigResponse> {
+        let config = CONFIG.load(deps.storage)?;
+        let version = cw2::get_contract_version(deps.storage)?;
+        Ok(ConfigResponse {
+            voting_powers: config.voting_powers.into_string(),
+            owner: config.owner.into_string(),
+            dao_core: config.dao_core.into_string(),
+            paused: config.paused,
+            event_namespace: config.event_namespace,
+            voting_query: config.voting_query,
+            contract: version.contract,
+            version: version.version,
+        })
+    }
+
+    fn to_gauge_response(deps: Deps, gauge_id: GaugeId, gauge: Gauge) -> StdResult<GaugeResponse> {
+        let escrow_balance = ESCROW
+            .prefix(gauge_id)
+            .range(deps.storage, None, None, Order::Ascending)
+            .map(|item| item.map(|(denom, amount)| Coin { denom, amount }))
+            .collect::<StdResult<_>>()?;
+        let epoch_label = EPOCH_LABELS.may_load(deps.storage, (gauge_id, gauge.executed_epochs))?;
+        Ok(GaugeResponse {
             id: gauge_id,
             title: gauge.title,
             adapter: gauge.adapter.to_string(),
@@ -671,20 +3080,71 @@ mod query {
             max_options_selected: gauge.max_options_selected,
             max_available_percentage: gauge.max_available_percentage,
             is_stopped: gauge.is_stopped,
+            voting_closed: gauge.voting_closed,
+            options_frozen: gauge.options_frozen,
             next_epoch: gauge.next_epoch,
             reset: gauge.reset,
-        }
+            to_distribute: gauge.to_distribute,
+            reward_schedule: gauge.reward_schedule,
+            executed_epochs: gauge.executed_epochs,
+            max_voters: gauge.max_voters,
+            validate_option_on_vote: gauge.validate_option_on_vote,
+            voter_restriction: gauge.voter_restriction,
+            tenure_curve: gauge.tenure_curve,
+            bootstrap_equal_split: gauge.bootstrap_equal_split,
+            reclaim_votes_on_remove: gauge.reclaim_votes_on_remove,
+            adapter_multipliers: gauge.adapter_multipliers,
+            adapter_reward: gauge.adapter_reward,
+            isolate_failures: gauge.isolate_failures,
+            epoch_unit: gauge.epoch_unit,
+            rounding: gauge.rounding,
+            tags: gauge.tags,
+            escrow_balance,
+            extra_adapters: gauge
+                .extra_adapters
+                .iter()
+                .map(|adapter| adapter.to_string())
+                .collect(),
+            min_absolute_tally: gauge.min_absolute_tally,
+            distribution_mode: gauge.distribution_mode,
+            boost_source: gauge.boost_source.map(|source| source.to_string()),
+            flashloan_resistant: gauge.flashloan_resistant,
+            distribution_hook: gauge.distribution_hook.map(|hook| hook.to_string()),
+            auto_prune_zero_options: gauge.auto_prune_zero_options,
+            require_full_allocation: gauge.require_full_allocation,
+            total_budget: gauge.total_budget,
+            vote_change_fee: gauge.vote_change_fee,
+            execute_policy: gauge.execute_policy,
+            max_paid_options: gauge.max_paid_options,
+            deprecated_vote_policy: gauge.deprecated_vote_policy,
+            min_distribution_per_option: gauge.min_distribution_per_option,
+            max_total_options: gauge.max_total_options,
+            post_execute_callback: gauge
+                .post_execute_callback
+                .map(|callback| callback.to_string()),
+            align_to: gauge.align_to,
+            max_voter_influence: gauge.max_voter_influence,
+            execute_condition: gauge.execute_condition,
+            swap_route: gauge.swap_route,
+            epoch_label,
+        })
     }
 
     pub fn gauge(deps: Deps, gauge_id: GaugeId) -> StdResult<GaugeResponse> {
-        let gauge = GAUGES.load(deps.storage, gauge_id)?;
-        Ok(to_gauge_response(gauge_id, gauge))
+        let gauge = query_gauge(deps.storage, gauge_id)?;
+        to_gauge_response(deps, gauge_id, gauge)
     }
 
     // settings for pagination
     pub const MAX_LIMIT: u32 = 100;
     pub const DEFAULT_LIMIT: u32 = 30;
 
+    // bounds of `VoteDistribution`
+    const MAX_DISTRIBUTION_BUCKETS: u32 = 100;
+    const MAX_DISTRIBUTION_VOTERS: usize = 1000;
+    // gauges checked by `VoterMissedGauges`
+    const MAX_MISSED_GAUGES_SCAN: usize = 100;
+
     pub fn list_gauges(
         deps: Deps,
         start_after: Option<u64>,
@@ -698,16 +3158,145 @@ mod query {
                 .range(deps.storage, start, None, Order::Ascending)
                 .map(|item| {
                     let (id, gauge) = item?;
-                    Ok(to_gauge_response(id, gauge))
+                    to_gauge_response(deps, id, gauge)
+                })
+                .take(limit)
+                .collect::<StdResult<Vec<GaugeResponse>>>()?,
+        })
+    }
+
+    pub fn next_execution(deps: Deps, gauge_id: u64) -> StdResult<NextExecutionResponse> {
+        let gauge = query_gauge(deps.storage, gauge_id)?;
+        Ok(NextExecutionResponse {
+            next: gauge.epoch_unit.expiration(gauge.next_epoch),
+            epoch: gauge.executed_epochs,
+            epoch_label: EPOCH_LABELS.may_load(deps.storage, (gauge_id, gauge.executed_epochs))?,
+        })
+    }
+
+    pub fn executable_gauges(
+        deps: Deps,
+        env: Env,
+        limit: Option<u32>,
+    ) -> StdResult<ExecutableGaugesResponse> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        // `Execute` fails for every gauge until `UnpauseAll`
+        if CONFIG.load(deps.storage)?.paused {
+            return Ok(ExecutableGaugesResponse { gauges: vec![] });
+        }
+
+        Ok(ExecutableGaugesResponse {
+            gauges: GAUGES
+                .range(deps.storage, None, None, Order::Ascending)
+                .filter(|item| match item {
+                    Ok((_, gauge)) => {
+                        !gauge.is_stopped
+                            && !gauge.is_resetting()
+                            && gauge.next_epoch <= gauge.epoch_unit.now(&env.block)
+                    }
+                    Err(_) => true,
+                })
+                .map(|item| item.map(|(id, _)| id))
+                .take(limit)
+                .collect::<StdResult<Vec<u64>>>()?,
+        })
+    }
+
+    pub fn executable_gauges_with_preview(
+        deps: Deps,
+        env: Env,
+        limit: Option<u32>,
+    ) -> StdResult<ExecutableGaugesWithPreviewResponse> {
+        let gauges = executable_gauges(deps, env.clone(), limit)?
+            .gauges
+            .into_iter()
+            .map(|gauge_id| {
+                let gauge = query_gauge(deps.storage, gauge_id)?;
+                let selected_set = selected_set(deps, &env, gauge_id)?.votes;
+                // without votes, bootstrapping gauges split the reward between all options
+                let bootstraps = gauge.bootstrap_equal_split
+                    && TOTAL_CAST
+                        .may_load(deps.storage, gauge_id)?
+                        .unwrap_or_default()
+                        == 0;
+                Ok(ExecutableGaugePreview {
+                    gauge_id,
+                    is_noop: selected_set.is_empty() && !bootstraps,
+                    selected_set,
+                })
+            })
+            .collect::<StdResult<_>>()?;
+        Ok(ExecutableGaugesWithPreviewResponse { gauges })
+    }
+
+    pub fn gauges_by_title(deps: Deps, title: String) -> StdResult<ListGaugesResponse> {
+        Ok(ListGaugesResponse {
+            gauges: GAUGES
+                .range(deps.storage, None, None, Order::Ascending)
+                .filter_map(|item| match item {
+                    Ok((id, gauge)) if gauge.title == title => {
+                        Some(to_gauge_response(deps, id, gauge))
+                    }
+                    Ok(_) => None,
+                    Err(err) => Some(Err(err)),
                 })
+                .collect::<StdResult<Vec<GaugeResponse>>>()?,
+        })
+    }
+
+    pub fn list_gauges_by_tag(
+        deps: Deps,
+        tag: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    ) -> StdResult<ListGaugesResponse> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let start = start_after.map(Bound::exclusive);
+
+        Ok(ListGaugesResponse {
+            gauges: GAUGES_BY_TAG
+                .prefix(&tag)
+                .keys(deps.storage, start, None, Order::Ascending)
                 .take(limit)
+                .map(|id| {
+                    let id = id?;
+                    to_gauge_response(deps, id, GAUGES.load(deps.storage, id)?)
+                })
                 .collect::<StdResult<Vec<GaugeResponse>>>()?,
         })
     }
 
+    pub fn gauges_for_adapter(deps: Deps, adapter: String) -> StdResult<GaugesForAdapterResponse> {
+        let adapter = deps.api.addr_validate(&adapter)?;
+        Ok(GaugesForAdapterResponse {
+            gauge_ids: GAUGES_BY_ADAPTER
+                .prefix(&adapter)
+                .keys(deps.storage, None, None, Order::Ascending)
+                .collect::<StdResult<_>>()?,
+        })
+    }
+
+    pub fn gauges_for_option(
+        deps: Deps,
+        option: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    ) -> StdResult<GaugesForOptionResponse> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let start = start_after.map(Bound::exclusive);
+
+        Ok(GaugesForOptionResponse {
+            gauge_ids: GAUGES_BY_OPTION
+                .prefix(&option)
+                .keys(deps.storage, start, None, Order::Ascending)
+                .take(limit)
+                .collect::<StdResult<_>>()?,
+        })
+    }
+
     pub fn vote(deps: Deps, gauge_id: u64, voter: String) -> StdResult<VoteResponse> {
         let voter_addr = deps.api.addr_validate(&voter)?;
-        let gauge = GAUGES.load(deps.storage, gauge_id)?;
+        let gauge = query_gauge(deps.storage, gauge_id)?;
 
         let vote = votes()
             .may_load(deps.storage, &voter_addr, gauge_id)?
@@ -720,102 +3309,1408 @@ mod query {
         Ok(VoteResponse { vote })
     }
 
-    pub fn list_votes(
+    pub fn can_vote(deps: Deps, gauge_id: u64, voter: String) -> StdResult<CanVoteResponse> {
+        let voter = deps.api.addr_validate(&voter)?;
+        let reason = voting_blocked_by(deps, gauge_id, &voter)?.map(|err| err.to_string());
+        Ok(CanVoteResponse {
+            can_vote: reason.is_none(),
+            reason,
+        })
+    }
+
+    pub fn voter_missed_gauges(
         deps: Deps,
-        gauge_id: u64,
-        start_after: Option<String>,
+        voter: String,
         limit: Option<u32>,
-    ) -> StdResult<ListVotesResponse> {
-        Ok(ListVotesResponse {
-            votes: votes().query_votes_by_gauge(deps, gauge_id, start_after, limit)?,
-        })
+    ) -> StdResult<VoterMissedGaugesResponse> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let voter = deps.api.addr_validate(&voter)?;
+
+        let mut gauge_ids = vec![];
+        for item in GAUGES
+            .range(deps.storage, None, None, Order::Ascending)
+            .take(MAX_MISSED_GAUGES_SCAN)
+        {
+            let (gauge_id, gauge) = item?;
+            if gauge.is_stopped {
+                continue;
+            }
+            let has_vote = votes()
+                .may_load(deps.storage, &voter, gauge_id)?
+                .map_or(false, |vote| !vote.is_expired(&gauge));
+            if has_vote || voting_blocked_by(deps, gauge_id, &voter)?.is_some() {
+                continue;
+            }
+            gauge_ids.push(gauge_id);
+            if gauge_ids.len() == limit {
+                break;
+            }
+        }
+        Ok(VoterMissedGaugesResponse { gauge_ids })
     }
 
-    pub fn list_options(
+    pub fn voter_activity(
+        deps: Deps,
+        gauge_id: u64,
+        voter: String,
+    ) -> StdResult<VoterActivityResponse> {
+        let voter = deps.api.addr_validate(&voter)?;
+        let last_voted_at = LAST_VOTED_AT.may_load(deps.storage, (gauge_id, &voter))?;
+        Ok(VoterActivityResponse { last_voted_at })
+    }
+
+    pub fn recent_voters(
         deps: Deps,
         gauge_id: u64,
         start_after: Option<String>,
         limit: Option<u32>,
-    ) -> StdResult<ListOptionsResponse> {
+    ) -> StdResult<RecentVotersResponse> {
         let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
-        let start_after = start_after.as_ref().map(|s| Bound::exclusive(s.as_str()));
+        // the current epoch started with the last execution
+        let epoch_start = query_gauge(deps.storage, gauge_id)?
+            .epoch_start_height
+            .unwrap_or_default();
 
-        Ok(ListOptionsResponse {
-            options: TALLY
-                .prefix(gauge_id)
-                .range(deps.storage, start_after, None, Order::Ascending)
-                .map(|option| {
-                    let (option, power) = option?;
-                    Ok((option, Uint128::new(power)))
-                })
-                .take(limit)
-                .collect::<StdResult<Vec<(String, Uint128)>>>()?,
-        })
+        let first = Addr::unchecked("");
+        let start_after = start_after
+            .map(|voter| deps.api.addr_validate(&voter))
+            .transpose()?;
+        let start = match &start_after {
+            Some(voter) => match LAST_VOTED_AT.may_load(deps.storage, (gauge_id, voter))? {
+                Some(activity) if activity.height >= epoch_start => {
+                    Bound::exclusive((activity.height, voter))
+                }
+                // not a recent voter, so there is nothing after it
+                _ => return Ok(RecentVotersResponse { voters: vec![] }),
+            },
+            None => Bound::inclusive((epoch_start, &first)),
+        };
+
+        let voters = VOTERS_BY_HEIGHT
+            .sub_prefix(gauge_id)
+            .keys(deps.storage, Some(start), None, Order::Ascending)
+            .take(limit)
+            .map(|item| {
+                let (_, voter) = item?;
+                let activity = LAST_VOTED_AT.load(deps.storage, (gauge_id, &voter))?;
+                Ok((voter.into_string(), activity))
+            })
+            .collect::<StdResult<_>>()?;
+        Ok(RecentVotersResponse { voters })
     }
 
-    pub fn selected_set(deps: Deps, gauge_id: u64) -> StdResult<SelectedSetResponse> {
-        let gauge = GAUGES.load(deps.storage, gauge_id)?;
-        let total_cast = TOTAL_CAST.load(deps.storage, gauge_id)?;
+    /// Runs the checks of `PlaceVotes` that don't depend on the votes themselves.
+    /// Addresses which still have to provide a merkle proof are reported as not allowed,
+    /// as the proof cannot be checked without it.
+    fn voting_blocked_by(
+        deps: Deps,
+        gauge_id: u64,
+        voter: &Addr,
+    ) -> StdResult<Option<ContractError>> {
+        let gauge = match load_gauge(deps.storage, gauge_id) {
+            Ok(gauge) => gauge,
+            Err(err) => return Ok(Some(err)),
+        };
+        if gauge.voting_closed {
+            return Ok(Some(ContractError::VotingClosed(gauge_id)));
+        }
+        if gauge.is_resetting() {
+            return Ok(Some(ContractError::GaugeResetting(gauge_id)));
+        }
+        if gauge.voter_restriction.is_some()
+            && !VOTER_ALLOWLIST.has(deps.storage, (gauge_id, voter))
+        {
+            return Ok(Some(ContractError::VoterNotAllowed(voter.to_string())));
+        }
 
-        if gauge.is_resetting() || total_cast == 0 {
-            return Ok(SelectedSetResponse { votes: vec![] });
+        let config = CONFIG.load(deps.storage)?;
+        let voting_power = config.voting_query.power(
+            &deps.querier,
+            &config.voting_powers,
+            voter.as_str(),
+            None,
+        )?;
+        if voting_power.is_zero() {
+            return Ok(Some(ContractError::NoVotingPower(voter.to_string())));
         }
 
-        // This is sorted index, but requires manual filtering - cannot be prefixed
-        // given our requirements
-        let votes = OPTION_BY_POINTS
-            .sub_prefix(gauge_id)
-            .range(deps.storage, None, None, Order::Descending)
-            .filter(|o| {
-                let ((power, _), _) = o.as_ref().unwrap();
-                if let Some(min_percent_selected) = gauge.min_percent_selected {
-                    Decimal::from_ratio(*power, total_cast) >= min_percent_selected
-                } else {
-                    // filter out options without a vote
-                    *power != 0u128
-                }
-            })
-            .map(|o| {
-                let ((power, option), _) = o?;
-                // If gauge has max_available_percentage set, discard all power
-                // above that percentage
-                if let Some(max_available_percentage) = gauge.max_available_percentage {
-                    if Decimal::from_ratio(power, total_cast) > max_available_percentage {
-                        // If power is above available percentage, cut power down to max available
-                        return Ok((option, Uint128::new(total_cast) * max_available_percentage));
-                    }
-                }
-                Ok((option, Uint128::new(power)))
-            })
-            .take(gauge.max_options_selected as usize)
-            .collect::<StdResult<Vec<(String, Uint128)>>>()?;
+        // existing voters can always change their votes
+        let has_vote = votes()
+            .may_load(deps.storage, voter, gauge_id)?
+            .map_or(false, |vote| !vote.is_expired(&gauge));
+        if let Some(max_voters) = gauge.max_voters {
+            let voter_count = VOTER_COUNT
+                .may_load(deps.storage, gauge_id)?
+                .unwrap_or_default();
+            if !has_vote && voter_count >= max_voters {
+                return Ok(Some(ContractError::VoterLimitReached(gauge_id)));
+            }
+        }
 
-        Ok(SelectedSetResponse { votes })
+        Ok(None)
     }
 
-    pub fn last_executed_set(deps: Deps, gauge_id: u64) -> StdResult<LastExecutedSetResponse> {
-        let gauge = GAUGES.load(deps.storage, gauge_id)?;
-        Ok(LastExecutedSetResponse {
-            votes: gauge.last_executed_set,
+    pub fn participation(deps: Deps, gauge_id: u64) -> StdResult<ParticipationResponse> {
+        // make sure the gauge exists
+        query_gauge(deps.storage, gauge_id)?;
+        let votes_cast = TOTAL_CAST
+            .may_load(deps.storage, gauge_id)?
+            .unwrap_or_default();
+        let abstained = TOTAL_ABSTAIN
+            .may_load(deps.storage, gauge_id)?
+            .unwrap_or_default();
+
+        let config = CONFIG.load(deps.storage)?;
+        let total_power =
+            config
+                .voting_query
+                .total_power(&deps.querier, &config.voting_powers, None)?;
+        // votes are only updated when the voting power of their voter changes,
+        // so a total power shrinking without those updates can leave more votes than power
+        let votes_power = Uint128::new(votes_cast).checked_add(Uint128::new(abstained))?;
+        let votes_exceed_power = votes_power > total_power;
+        let participation = if total_power.is_zero() {
+            Decimal::zero()
+        } else {
+            Decimal::from_ratio(votes_power, total_power).min(Decimal::one())
+        };
+
+        Ok(ParticipationResponse {
+            votes_cast: votes_cast.into(),
+            abstained: abstained.into(),
+            total_power,
+            participation,
+            votes_exceed_power,
         })
     }
-}
 
-#[cfg_attr(not(feature = "library"), entry_point)]
-pub fn migrate(deps: DepsMut, env: Env, msg: MigrateMsg) -> Result<Response, ContractError> {
-    ensure_from_older_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    pub fn participation_history(
+        deps: Deps,
+        gauge_id: u64,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    ) -> StdResult<ParticipationHistoryResponse> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let start = start_after.map(Bound::exclusive);
 
-    for (gauge_id, config) in msg.gauge_config.unwrap_or_default() {
-        GAUGES.update(deps.storage, gauge_id, |gauge| -> StdResult<_> {
-            let mut gauge = gauge.ok_or(StdError::NotFound {
-                kind: format!("Gauge with id {}", gauge_id),
-            })?;
-            if let Some(next_epoch) = config.next_epoch {
-                if next_epoch < env.block.time.seconds() {
-                    return Err(StdError::GenericErr {
-                        msg: "Next epoch value cannot be earlier then current epoch!".to_owned(),
-                    });
-                }
+        Ok(ParticipationHistoryResponse {
+            history: PARTICIPATION_HISTORY
+                .prefix(gauge_id)
+                .range(deps.storage, start, None, Order::Ascending)
+                .map(|item| item.map(|(_, participation)| participation))
+                .take(limit)
+                .collect::<StdResult<_>>()?,
+        })
+    }
+
+    pub fn option_tally_history(
+        deps: Deps,
+        gauge_id: u64,
+        option: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    ) -> StdResult<OptionTallyHistoryResponse> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let executed_epochs = query_gauge(deps.storage, gauge_id)?.executed_epochs;
+        query_option(deps.storage, gauge_id, &option)?;
+
+        // the slots are a ring buffer, so they are not ordered by epoch
+        let mut history = OPTION_TALLY_HISTORY
+            .prefix((gauge_id, option.as_str()))
+            .range(deps.storage, None, None, Order::Ascending)
+            .map(|item| item.map(|(_, snapshot)| snapshot))
+            .collect::<StdResult<Vec<_>>>()?;
+        history.retain(|snapshot| {
+            // slots not overwritten since the option was last selected can be outdated
+            snapshot.epoch + OPTION_TALLY_HISTORY_EPOCHS >= executed_epochs
+                && start_after.map_or(true, |start| snapshot.epoch > start)
+        });
+        history.sort_by_key(|snapshot| snapshot.epoch);
+        history.truncate(limit);
+
+        Ok(OptionTallyHistoryResponse { history })
+    }
+
+    pub fn option_lifetime_rewards(
+        deps: Deps,
+        gauge_id: u64,
+        option: String,
+    ) -> StdResult<OptionLifetimeRewardsResponse> {
+        // removed options keep their rewards, so only the gauge has to exist
+        query_gauge(deps.storage, gauge_id)?;
+        let rewards = OPTION_LIFETIME_REWARDS
+            .may_load(deps.storage, (gauge_id, &option))?
+            .unwrap_or_default();
+        Ok(OptionLifetimeRewardsResponse { rewards })
+    }
+
+    pub fn votes_to_overtake(
+        deps: Deps,
+        gauge_id: u64,
+        option: String,
+        target_option: String,
+    ) -> StdResult<VotesToOvertakeResponse> {
+        query_option(deps.storage, gauge_id, &option)?;
+        query_option(deps.storage, gauge_id, &target_option)?;
+        let tally = TALLY.load(deps.storage, (gauge_id, &option))?;
+        let target_tally = TALLY.load(deps.storage, (gauge_id, &target_option))?;
+        // one more than the target is needed to exceed it
+        let votes_needed = target_tally.saturating_add(1).saturating_sub(tally);
+        Ok(VotesToOvertakeResponse {
+            votes_needed: votes_needed.into(),
+        })
+    }
+
+    pub fn option_voters(
+        deps: Deps,
+        gauge_id: u64,
+        option: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<OptionVotersResponse> {
+        query_option(deps.storage, gauge_id, &option)?;
+        let voters = votes()
+            .query_voters_by_option(deps, gauge_id, &option, start_after, limit)?
+            .into_iter()
+            .map(|(voter, weight)| (voter.into_string(), weight))
+            .collect();
+        Ok(OptionVotersResponse { voters })
+    }
+
+    pub fn list_votes(
+        deps: Deps,
+        gauge_id: u64,
+        start_after: Option<String>,
+        limit: Option<u32>,
+        only_active: bool,
+    ) -> StdResult<ListVotesResponse> {
+        let mut votes = votes().query_votes_by_gauge(deps, gauge_id, start_after, limit)?;
+        if only_active {
+            let config = CONFIG.load(deps.storage)?;
+            let mut active = Vec::with_capacity(votes.len());
+            for vote in votes {
+                let voter = Addr::unchecked(&vote.voter);
+                if !power_at_height(deps, &config, &voter, None)?.is_zero() {
+                    active.push(vote);
+                }
+            }
+            votes = active;
+        }
+        Ok(ListVotesResponse { votes })
+    }
+
+    pub fn votes_changed_since(
+        deps: Deps,
+        gauge_id: u64,
+        since_block: u64,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<VotesChangedSinceResponse> {
+        Ok(VotesChangedSinceResponse {
+            votes: votes().query_votes_changed_since(
+                deps,
+                gauge_id,
+                since_block,
+                start_after,
+                limit,
+            )?,
+        })
+    }
+
+    pub fn list_options(
+        deps: Deps,
+        gauge_id: u64,
+        start_after: Option<String>,
+        limit: Option<u32>,
+        order_by: OptionOrder,
+    ) -> StdResult<ListOptionsResponse> {
+        query_gauge(deps.storage, gauge_id)?;
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+
+        let options = match order_by {
+            OptionOrder::Key => {
+                let start_after = start_after.as_deref().map(Bound::exclusive);
+                TALLY
+                    .prefix(gauge_id)
+                    .range(deps.storage, start_after, None, Order::Ascending)
+                    .map(|option| {
+                        let (option, power) = option?;
+                        Ok((option, Uint128::new(power)))
+                    })
+                    .take(limit)
+                    .collect::<StdResult<Vec<(String, Uint128)>>>()?
+            }
+            OptionOrder::Tally => {
+                // continue below the position of `start_after` in the sorted index
+                let end_before = match start_after.as_deref() {
+                    Some(option) => {
+                        let points = TALLY.load(deps.storage, (gauge_id, option))?;
+                        Some(Bound::exclusive((points, option)))
+                    }
+                    None => None,
+                };
+                OPTION_BY_POINTS
+                    .sub_prefix(gauge_id)
+                    .keys(deps.storage, None, end_before, Order::Descending)
+                    .map(|option| {
+                        let (power, option) = option?;
+                        Ok((option, Uint128::new(power)))
+                    })
+                    .take(limit)
+                    .collect::<StdResult<Vec<(String, Uint128)>>>()?
+            }
+            OptionOrder::AddedAt => {
+                let start_after = match start_after.as_deref() {
+                    Some(option) => Some(Bound::exclusive(
+                        OPTION_ADDED.load(deps.storage, (gauge_id, option))?,
+                    )),
+                    None => None,
+                };
+                OPTION_BY_ADDED
+                    .prefix(gauge_id)
+                    .range(deps.storage, start_after, None, Order::Ascending)
+                    .map(|option| {
+                        let (_, option) = option?;
+                        let power = TALLY.load(deps.storage, (gauge_id, &option))?;
+                        Ok((option, Uint128::new(power)))
+                    })
+                    .take(limit)
+                    .collect::<StdResult<Vec<(String, Uint128)>>>()?
+            }
+        };
+        let deprecated = options
+            .iter()
+            .filter(|(option, _)| DEPRECATED_OPTIONS.has(deps.storage, (gauge_id, option)))
+            .map(|(option, _)| option.clone())
+            .collect();
+        Ok(ListOptionsResponse {
+            options,
+            deprecated,
+        })
+    }
+
+    pub fn unvoted_options(
+        deps: Deps,
+        gauge_id: u64,
+        voter: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<UnvotedOptionsResponse> {
+        let gauge = query_gauge(deps.storage, gauge_id)?;
+        let voter = deps.api.addr_validate(&voter)?;
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let start_after = start_after.as_ref().map(|s| Bound::exclusive(s.as_str()));
+
+        let voted: Vec<String> = votes()
+            .may_load(deps.storage, &voter, gauge_id)?
+            .filter(|vote| !vote.is_expired(&gauge))
+            .map(|vote| vote.votes.into_iter().map(|v| v.option).collect())
+            .unwrap_or_default();
+
+        let options = TALLY
+            .prefix(gauge_id)
+            .keys(deps.storage, start_after, None, Order::Ascending)
+            .filter(|option| match option {
+                Ok(option) => {
+                    !voted.contains(option)
+                        && !DEPRECATED_OPTIONS.has(deps.storage, (gauge_id, option))
+                }
+                Err(_) => true,
+            })
+            .take(limit)
+            .collect::<StdResult<Vec<_>>>()?;
+        Ok(UnvotedOptionsResponse { options })
+    }
+
+    /// Total power the selection thresholds are applied to.
+    /// Deprecated options are left out like on execution, including their votes in the total
+    fn selection_total(deps: Deps, gauge_id: u64) -> StdResult<u128> {
+        let deprecated_power = DEPRECATED_OPTIONS
+            .prefix(gauge_id)
+            .keys(deps.storage, None, None, Order::Ascending)
+            .try_fold(0u128, |sum, option| -> StdResult<_> {
+                let tally = TALLY
+                    .may_load(deps.storage, (gauge_id, &option?))?
+                    .unwrap_or_default();
+                Ok(Uint128::new(sum).checked_add(Uint128::new(tally))?.u128())
+            })?;
+        let total_cast = TOTAL_CAST
+            .may_load(deps.storage, gauge_id)?
+            .unwrap_or_default();
+        Ok(Uint128::new(total_cast)
+            .checked_sub(Uint128::new(deprecated_power))?
+            .u128())
+    }
+
+    /// The set `Execute` would select right now, computed from the same tallies
+    pub fn selected_set(deps: Deps, env: &Env, gauge_id: u64) -> StdResult<SelectedSetResponse> {
+        let gauge = query_gauge(deps.storage, gauge_id)?;
+        if gauge.is_resetting() {
+            return Ok(SelectedSetResponse { votes: vec![] });
+        }
+
+        let (max_options_selected, max_available_percentage) = gauge.selection_limits();
+        preview_selected_set(
+            execution_tallies(deps, env, gauge_id, &gauge)?,
+            gauge.min_percent_selected,
+            gauge.min_absolute_tally,
+            max_options_selected,
+            max_available_percentage,
+        )
+    }
+
+    pub fn selection_params(deps: Deps, gauge_id: u64) -> StdResult<SelectionParamsResponse> {
+        let gauge = query_gauge(deps.storage, gauge_id)?;
+        let (effective_max_options_selected, effective_max_available_percentage) =
+            gauge.selection_limits();
+        Ok(SelectionParamsResponse {
+            min_percent_selected: gauge.min_percent_selected,
+            min_absolute_tally: gauge.min_absolute_tally,
+            max_options_selected: gauge.max_options_selected,
+            max_available_percentage: gauge.max_available_percentage,
+            distribution_mode: gauge.distribution_mode,
+            effective_max_options_selected,
+            effective_max_available_percentage,
+            max_paid_options: gauge.max_paid_options,
+            deprecated_vote_policy: gauge.deprecated_vote_policy,
+            bootstrap_equal_split: gauge.bootstrap_equal_split,
+        })
+    }
+
+    pub fn qualification_threshold(
+        deps: Deps,
+        gauge_id: u64,
+    ) -> StdResult<QualificationThresholdResponse> {
+        let gauge = query_gauge(deps.storage, gauge_id)?;
+        let total_cast = selection_total(deps, gauge_id)?;
+
+        let percent_threshold = match gauge.min_percent_selected {
+            // smallest tally with a `tally_fraction` of at least `min_percent_selected`
+            Some(min_percent_selected) => {
+                let fractional = Uint256::from(Decimal::one().atomics());
+                let product =
+                    Uint256::from(total_cast) * Uint256::from(min_percent_selected.atomics());
+                let threshold = (product + fractional - Uint256::one()) / fractional;
+                Uint128::try_from(threshold)?
+            }
+            // options need at least one vote
+            None => Uint128::one(),
+        };
+        let threshold = gauge
+            .min_absolute_tally
+            .map(|min| min.max(percent_threshold))
+            .unwrap_or(percent_threshold);
+
+        Ok(QualificationThresholdResponse {
+            threshold,
+            total_tally: Uint128::new(total_cast),
+        })
+    }
+
+    /// Applies the selection rules to arbitrary tallies, in any order
+    pub fn preview_selected_set(
+        mut tallies: Vec<(String, Uint128)>,
+        min_percent_selected: Option<Decimal>,
+        min_absolute_tally: Option<Uint128>,
+        max_options_selected: u32,
+        max_available_percentage: Option<Decimal>,
+    ) -> StdResult<SelectedSetResponse> {
+        let total_cast = tallies
+            .iter()
+            .try_fold(Uint128::zero(), |sum, (_, power)| sum.checked_add(*power))?
+            .u128();
+        if total_cast == 0 {
+            return Ok(SelectedSetResponse { votes: vec![] });
+        }
+
+        // same order as `OPTION_BY_POINTS` in descending order
+        tallies.sort_unstable_by(|(a_option, a_power), (b_option, b_power)| {
+            (b_power, b_option).cmp(&(a_power, a_option))
+        });
+        let votes = select_options(
+            min_percent_selected,
+            min_absolute_tally,
+            max_options_selected,
+            max_available_percentage,
+            total_cast,
+            tallies
+                .into_iter()
+                .map(|(option, power)| Ok((power.u128(), option))),
+        )?;
+
+        Ok(SelectedSetResponse { votes })
+    }
+
+    /// All voted options of the gauge with their power, sorted by power descending
+    pub fn option_leaderboard(
+        deps: Deps,
+        gauge_id: u64,
+        limit: Option<u32>,
+    ) -> StdResult<OptionLeaderboardResponse> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let total_cast = TOTAL_CAST
+            .may_load(deps.storage, gauge_id)?
+            .unwrap_or_default();
+
+        let mut options: Vec<LeaderboardEntry> = Vec::new();
+        for item in OPTION_BY_POINTS
+            .sub_prefix(gauge_id)
+            .keys(deps.storage, None, None, Order::Descending)
+            .take_while(|o| !matches!(o, Ok((0, _))))
+            .take(limit)
+        {
+            let (points, option) = item?;
+            let rank = match options.last() {
+                Some(last) if last.tally.u128() == points => last.rank,
+                _ => options.len() as u32 + 1,
+            };
+            options.push(LeaderboardEntry {
+                rank,
+                option,
+                tally: Uint128::new(points),
+                tally_fraction: tally_fraction(points, total_cast),
+            });
+        }
+        Ok(OptionLeaderboardResponse { options })
+    }
+
+    /// The tallies `Execute` selects from: all voted options with their (weighted, if the gauge
+    /// requires it) power, sorted by power descending. Deprecated options are left out
+    pub fn execution_tallies(
+        deps: Deps,
+        env: &Env,
+        gauge_id: u64,
+        gauge: &Gauge,
+    ) -> StdResult<Vec<(String, Uint128)>> {
+        let tallies = if gauge.tenure_curve.is_some()
+            || gauge.boost_source.is_some()
+            || gauge.flashloan_resistant
+            || gauge.deprecated_vote_policy == DeprecatedVotePolicy::Redistribute
+            || gauge.max_voter_influence.is_some()
+        {
+            weighted_tallies(deps, env, gauge_id, gauge)?
+        } else {
+            sorted_tallies(deps, gauge_id)?
+        };
+        // deprecated options keep their votes, but are not selected anymore
+        Ok(tallies
+            .into_iter()
+            .filter(|(option, _)| !DEPRECATED_OPTIONS.has(deps.storage, (gauge_id, option)))
+            .collect())
+    }
+
+    pub fn sorted_tallies(deps: Deps, gauge_id: u64) -> StdResult<Vec<(String, Uint128)>> {
+        OPTION_BY_POINTS
+            .sub_prefix(gauge_id)
+            .keys(deps.storage, None, None, Order::Descending)
+            .filter(|o| !matches!(o, Ok((0, _))))
+            .map(|o| {
+                let (power, option) = o?;
+                Ok((option, Uint128::new(power)))
+            })
+            .collect()
+    }
+
+    /// Same as `sorted_tallies`, but every voter's power is capped (if flashloan resistant) and
+    /// multiplied by their tenure multiplier and boost. Depending on the `deprecated_vote_policy`,
+    /// the power given to deprecated options is moved to the voter's other options.
+    /// This has to iterate over all votes of the gauge, so it is only done for gauges that need it.
+    pub fn weighted_tallies(
+        deps: Deps,
+        env: &Env,
+        gauge_id: u64,
+        gauge: &Gauge,
+    ) -> StdResult<Vec<(String, Uint128)>> {
+        let config = CONFIG.load(deps.storage)?;
+        // the power every voter contributes to each option
+        let mut contributions: Vec<Vec<(String, u128)>> = vec![];
+        for vote in votes().range_by_gauge(deps.storage, gauge_id) {
+            let (voter, vote) = vote?;
+            if vote.is_expired(gauge) {
+                continue;
+            }
+            let mut power = vote.power;
+            if gauge.flashloan_resistant {
+                // the lowest power since the epoch started
+                power = power.min(power_at_height(deps, &config, &voter, None)?);
+                if let Some(height) = gauge.epoch_start_height {
+                    power = power.min(power_at_height(deps, &config, &voter, Some(height))?);
+                }
+            }
+            if let Some(curve) = &gauge.tenure_curve {
+                power =
+                    checked_mul_multiplier(power, tenure_multiplier(deps, env, curve, &voter)?)?;
+            }
+            if let Some(source) = &gauge.boost_source {
+                power = checked_mul_multiplier(power, boost_multiplier(deps, source, &voter)?)?;
+            }
+            let (deprecated, active): (Vec<_>, Vec<_>) = vote
+                .votes
+                .iter()
+                .filter(|v| v.option != ABSTAIN_OPTION)
+                .partition(|v| DEPRECATED_OPTIONS.has(deps.storage, (gauge_id, &v.option)));
+            let mut contribution = vec![];
+            for v in &active {
+                contribution.push((v.option.clone(), (power * v.weight).u128()));
+            }
+            match gauge.deprecated_vote_policy {
+                DeprecatedVotePolicy::Redistribute if !active.is_empty() => {
+                    // the other options share the power, in the ratio of their weights
+                    let moved = power * deprecated.iter().map(|v| v.weight).sum::<Decimal>();
+                    let active_weight = active.iter().map(|v| v.weight).sum::<Decimal>();
+                    for v in &active {
+                        let share =
+                            moved.multiply_ratio(v.weight.atomics(), active_weight.atomics());
+                        contribution.push((v.option.clone(), share.u128()));
+                    }
+                }
+                // left out of the selection later on anyway
+                _ => {
+                    for v in &deprecated {
+                        contribution.push((v.option.clone(), (power * v.weight).u128()));
+                    }
+                }
+            }
+            contributions.push(contribution);
+        }
+        if let Some(max_influence) = gauge.max_voter_influence {
+            cap_voter_influence(&mut contributions, max_influence);
+        }
+        // summed up in 256 bits, only the tallies themselves have to fit
+        let mut tally: HashMap<String, Uint256> = HashMap::new();
+        for (option, power) in contributions.into_iter().flatten() {
+            *tally.entry(option).or_default() += Uint256::from(power);
+        }
+
+        let mut tallies: Vec<(String, Uint128)> = tally
+            .into_iter()
+            .filter(|(_, power)| !power.is_zero())
+            .map(|(option, power)| Ok((option, power.try_into()?)))
+            .collect::<StdResult<_>>()?;
+        // same order as `OPTION_BY_POINTS` in descending order
+        tallies.sort_unstable_by(|(a_option, a_power), (b_option, b_power)| {
+            (b_power, b_option).cmp(&(a_power, a_option))
+        });
+        Ok(tallies)
+    }
+
+    /// Scales down the contributions of the biggest voters, so none of them has more than
+    /// `max_influence` of the resulting total. If there are too few voters for that, all of them
+    /// are capped to the smallest voter, giving everyone the same influence
+    pub fn cap_voter_influence(contributions: &mut [Vec<(String, u128)>], max_influence: Decimal) {
+        let totals: Vec<u128> = contributions
+            .iter()
+            .map(|contribution| contribution.iter().map(|(_, power)| power).sum())
+            .collect();
+        let mut sorted = totals.clone();
+        sorted.sort_unstable_by(|a, b| b.cmp(a));
+
+        // with `capped` voters at `level`, each of them has `max_influence` of the total for
+        // `level = max_influence * rest / (1 - capped * max_influence)`
+        let mut rest = sorted
+            .iter()
+            .fold(Uint256::zero(), |rest, &total| rest + Uint256::from(total));
+        let mut level = sorted.last().copied();
+        for (capped, &total) in sorted.iter().enumerate() {
+            let capped_share = max_influence * Decimal::from_ratio(capped as u128, 1u128);
+            let uncapped_share = match Decimal::one().checked_sub(capped_share) {
+                Ok(share) if !share.is_zero() => share,
+                // the remaining voters can't make up for the capped ones
+                _ => break,
+            };
+            let max_total = rest.multiply_ratio(max_influence.atomics(), uncapped_share.atomics());
+            if Uint256::from(total) <= max_total {
+                // smaller than the total of the last capped voter, so it fits
+                level = (capped > 0)
+                    .then(|| Uint128::try_from(max_total).ok())
+                    .flatten()
+                    .map(Uint128::u128);
+                break;
+            }
+            rest -= Uint256::from(total);
+        }
+
+        if let Some(level) = level {
+            for (contribution, total) in contributions.iter_mut().zip(totals) {
+                if total > level {
+                    for (_, power) in contribution.iter_mut() {
+                        *power = Uint128::new(*power).multiply_ratio(level, total).u128();
+                    }
+                }
+            }
+        }
+    }
+
+    fn power_at_height(
+        deps: Deps,
+        config: &Config,
+        voter: &Addr,
+        height: Option<u64>,
+    ) -> StdResult<Uint128> {
+        config
+            .voting_query
+            .power(&deps.querier, &config.voting_powers, voter.as_str(), height)
+    }
+
+    /// `power` times `multiplier`, failing instead of panicking if the result does not fit
+    pub fn checked_mul_multiplier(power: Uint128, multiplier: Decimal) -> StdResult<Uint128> {
+        let product = Uint256::from(power) * Uint256::from(multiplier.atomics())
+            / Uint256::from(Decimal::one().atomics());
+        Ok(product.try_into()?)
+    }
+
+    fn tenure_multiplier(
+        deps: Deps,
+        env: &Env,
+        curve: &TenureCurve,
+        voter: &Addr,
+    ) -> StdResult<Decimal> {
+        let since = deps
+            .querier
+            .query_wasm_smart::<MemberSinceResponse>(
+                &curve.source,
+                &MembershipQueryMsg::MemberSince {
+                    address: voter.to_string(),
+                },
+            )?
+            .since;
+        // non-members get no bonus
+        let tenure = since
+            .map(|since| env.block.time.seconds().saturating_sub(since))
+            .unwrap_or_default();
+        Ok(curve.multiplier(tenure))
+    }
+
+    /// Boost of the voter, kept between 1.0 and `MAX_BOOST_MULTIPLIER`
+    fn boost_multiplier(deps: Deps, source: &Addr, voter: &Addr) -> StdResult<Decimal> {
+        let multiplier = deps
+            .querier
+            .query_wasm_smart::<BoostResponse>(
+                source,
+                &BoostQueryMsg::Boost {
+                    address: voter.to_string(),
+                },
+            )?
+            .multiplier;
+        Ok(multiplier.clamp(Decimal::one(), MAX_BOOST_MULTIPLIER))
+    }
+
+    /// Share of `power` in `total`, rounded down to the 18 decimal places of `Decimal`.
+    /// All thresholds are compared to this, so `Execute` and the selection queries agree
+    /// exactly on options right at a threshold
+    pub fn tally_fraction(power: u128, total: u128) -> Decimal {
+        if total == 0 {
+            Decimal::zero()
+        } else {
+            Decimal::from_ratio(power, total)
+        }
+    }
+
+    /// Applies the selection rules to `(power, option)` pairs, sorted by power descending
+    fn select_options(
+        min_percent_selected: Option<Decimal>,
+        min_absolute_tally: Option<Uint128>,
+        max_options_selected: u32,
+        max_available_percentage: Option<Decimal>,
+        total_cast: u128,
+        options: impl Iterator<Item = StdResult<(u128, String)>>,
+    ) -> StdResult<Vec<(String, Uint128)>> {
+        options
+            .filter(|o| match o {
+                Ok((power, _)) => {
+                    // both thresholds have to be reached
+                    let above_absolute = min_absolute_tally
+                        .map(|min| Uint128::new(*power) >= min)
+                        .unwrap_or(true);
+                    let above_percent = if let Some(min_percent_selected) = min_percent_selected {
+                        tally_fraction(*power, total_cast) >= min_percent_selected
+                    } else {
+                        // filter out options without a vote
+                        *power != 0u128
+                    };
+                    above_absolute && above_percent
+                }
+                // keep the error
+                Err(_) => true,
+            })
+            .map(|o| {
+                let (power, option) = o?;
+                // If gauge has max_available_percentage set, discard all power
+                // above that percentage
+                if let Some(max_available_percentage) = max_available_percentage {
+                    if tally_fraction(power, total_cast) > max_available_percentage {
+                        // If power is above available percentage, cut power down to max available
+                        return Ok((option, Uint128::new(total_cast) * max_available_percentage));
+                    }
+                }
+                Ok((option, Uint128::new(power)))
+            })
+            .take(max_options_selected as usize)
+            .collect()
+    }
+
+    /// Calculates the "local" ratios of voted options per total power of all selected options.
+    /// Options without power get no weight and are left out, so only powers are ever divided
+    /// by a non-zero sum
+    pub fn to_weights(selected_set: Vec<(String, Uint128)>) -> Vec<(String, Decimal)> {
+        let selected_set: Vec<_> = selected_set
+            .into_iter()
+            .filter(|(_, power)| !power.is_zero())
+            .collect();
+        // weighted tallies may sum up to more than fits into 128 bits
+        let selected_powers_sum = selected_set
+            .iter()
+            .fold(Uint256::zero(), |sum, (_, power)| {
+                sum + Uint256::from(*power)
+            });
+        selected_set
+            .into_iter()
+            .map(|(option, power)| {
+                let weight = Uint256::from(power)
+                    .multiply_ratio(Decimal::one().atomics(), selected_powers_sum);
+                // every power is part of the sum, so the weight is at most 1.0
+                let weight = Uint128::try_from(weight).unwrap_or(Decimal::one().atomics());
+                (option, Decimal::new(weight))
+            })
+            .collect()
+    }
+
+    /// Multiplies the weights by the adapter's multipliers and normalizes them again
+    pub fn apply_multipliers(
+        deps: Deps,
+        adapter: &Addr,
+        selected: Vec<(String, Decimal)>,
+    ) -> StdResult<Vec<(String, Decimal)>> {
+        if selected.is_empty() {
+            return Ok(selected);
+        }
+        let multipliers: HashMap<String, Decimal> = deps
+            .querier
+            .query_wasm_smart::<OptionMultipliersResponse>(
+                adapter,
+                &AdapterQueryMsg::OptionMultipliers {
+                    options: selected.iter().map(|(option, _)| option.clone()).collect(),
+                },
+            )?
+            .multipliers
+            .into_iter()
+            .collect();
+
+        let boosted: Vec<(String, Decimal)> = selected
+            .into_iter()
+            .map(|(option, weight)| {
+                let multiplier = multipliers
+                    .get(&option)
+                    .copied()
+                    .unwrap_or_else(Decimal::one);
+                (option, weight * multiplier)
+            })
+            .collect();
+        let sum = boosted
+            .iter()
+            .map(|(_, weight)| *weight)
+            .fold(Decimal::zero(), |acc, weight| acc + weight);
+        if sum.is_zero() {
+            return Ok(boosted);
+        }
+        Ok(boosted
+            .into_iter()
+            .map(|(option, weight)| (option, Decimal::from_ratio(weight.atomics(), sum.atomics())))
+            .collect())
+    }
+
+    /// Equal weights for the first `max_options_selected` options, for gauges without votes
+    pub fn bootstrap_weights(
+        deps: Deps,
+        gauge_id: u64,
+        max_options_selected: u32,
+    ) -> StdResult<Vec<(String, Decimal)>> {
+        let options = TALLY
+            .prefix(gauge_id)
+            .keys(deps.storage, None, None, Order::Ascending)
+            .filter(|option| match option {
+                Ok(option) => !DEPRECATED_OPTIONS.has(deps.storage, (gauge_id, option)),
+                Err(_) => true,
+            })
+            .take(max_options_selected as usize)
+            .collect::<StdResult<Vec<String>>>()?;
+        Ok(equal_weights(options.into_iter()))
+    }
+
+    /// The same weight for each of the options
+    pub fn equal_weights(options: impl Iterator<Item = String>) -> Vec<(String, Decimal)> {
+        let options: Vec<String> = options.collect();
+        let count = options.len() as u128;
+        options
+            .into_iter()
+            .map(|option| (option, Decimal::from_ratio(1u128, count)))
+            .collect()
+    }
+
+    /// Splits the reward like `split_reward`, but options whose share is below `floor` are
+    /// dropped and the reward is split again among the others, in the ratio of their weights.
+    /// Returns the remaining options with their new weights, along with the shares
+    pub fn split_reward_with_floor(
+        reward: &Asset,
+        selected: Vec<(String, Decimal)>,
+        rounding: &Rounding,
+        floor: Option<Uint128>,
+    ) -> StdResult<(Vec<(String, Decimal)>, Vec<(String, Uint128)>)> {
+        let shares = split_reward(reward, &selected, rounding)?;
+        let floor = match floor {
+            Some(floor) if shares.iter().any(|(_, amount)| *amount < floor) => floor,
+            _ => return Ok((selected, shares)),
+        };
+
+        // the shares only grow with less options, so splitting once more is enough
+        let kept: Vec<_> = selected
+            .into_iter()
+            .zip(shares)
+            .filter(|(_, (_, amount))| *amount >= floor)
+            .map(|(option, _)| option)
+            .collect();
+        let sum = kept
+            .iter()
+            .map(|(_, weight)| *weight)
+            .fold(Decimal::zero(), |acc, weight| acc + weight);
+        if sum.is_zero() {
+            return Ok((vec![], vec![]));
+        }
+        let kept: Vec<_> = kept
+            .into_iter()
+            .map(|(option, weight)| (option, Decimal::from_ratio(weight.atomics(), sum.atomics())))
+            .collect();
+        let shares = split_reward(reward, &kept, rounding)?;
+        Ok((kept, shares))
+    }
+
+    /// Splits the reward between the options according to their weights.
+    /// The sum of the shares never exceeds the reward
+    pub fn split_reward(
+        reward: &Asset,
+        selected: &[(String, Decimal)],
+        rounding: &Rounding,
+    ) -> StdResult<Vec<(String, Uint128)>> {
+        let mut shares = selected
+            .iter()
+            .map(|(option, weight)| Ok((option.clone(), rounding.share(reward.amount, *weight)?)))
+            .collect::<StdResult<Vec<_>>>()?;
+
+        // rounding up can distribute a bit too much, take that from the biggest shares
+        let total: Uint128 = shares.iter().map(|(_, amount)| *amount).sum();
+        let mut overshoot = total.saturating_sub(reward.amount);
+        let mut biggest: Vec<usize> = (0..shares.len()).collect();
+        biggest.sort_by(|&a, &b| shares[b].1.cmp(&shares[a].1));
+        for i in biggest {
+            if overshoot.is_zero() {
+                break;
+            }
+            let cut = overshoot.min(shares[i].1);
+            shares[i].1 -= cut;
+            overshoot -= cut;
+        }
+        Ok(shares)
+    }
+
+    pub fn reward_efficiency(
+        deps: Deps,
+        env: Env,
+        gauge_id: u64,
+    ) -> StdResult<RewardEfficiencyResponse> {
+        let gauge = query_gauge(deps.storage, gauge_id)?;
+        let selected_set = selected_set(deps, &env, gauge_id)?.votes;
+
+        // only gauges distributing the reward themselves know the reward of an option
+        let options = match gauge.current_reward() {
+            Some(reward) => split_reward(reward, &to_weights(selected_set), &gauge.rounding)?
+                .into_iter()
+                .map(|(option, projected_reward)| {
+                    // use the full tally, options above `max_available_percentage` are less efficient
+                    let tally = Uint128::new(TALLY.load(deps.storage, (gauge_id, &option))?);
+                    Ok(OptionRewardEfficiency {
+                        reward_per_power: if tally.is_zero() {
+                            Decimal::zero()
+                        } else {
+                            Decimal::from_ratio(projected_reward, tally)
+                        },
+                        option,
+                        projected_reward,
+                        tally,
+                    })
+                })
+                .collect::<StdResult<_>>()?,
+            None => vec![],
+        };
+
+        Ok(RewardEfficiencyResponse { options })
+    }
+
+    pub fn aggregate_selected_set(
+        deps: Deps,
+        env: Env,
+        denom: String,
+    ) -> StdResult<AggregateSelectedSetResponse> {
+        let gauges = GAUGES
+            .range(deps.storage, None, None, Order::Ascending)
+            .filter(|gauge| match gauge {
+                Ok((_, gauge)) => {
+                    !gauge.is_stopped
+                        && gauge
+                            .current_reward()
+                            .map_or(false, |reward| match &reward.denom {
+                                AssetType::Native(d) | AssetType::Cw20(d) => *d == denom,
+                            })
+                }
+                Err(_) => true,
+            })
+            .take(MAX_LIMIT as usize)
+            .collect::<StdResult<Vec<_>>>()?;
+
+        let mut rewards: BTreeMap<String, Uint128> = BTreeMap::new();
+        for (gauge_id, gauge) in &gauges {
+            let selected = to_weights(selected_set(deps, &env, *gauge_id)?.votes);
+            // filtered for gauges with a reward above
+            if let Some(reward) = gauge.current_reward() {
+                for (option, amount) in split_reward(reward, &selected, &gauge.rounding)? {
+                    let total = rewards.entry(option).or_default();
+                    *total = total.checked_add(amount)?;
+                }
+            }
+        }
+
+        Ok(AggregateSelectedSetResponse {
+            rewards: rewards.into_iter().collect(),
+            gauge_ids: gauges.into_iter().map(|(gauge_id, _)| gauge_id).collect(),
+        })
+    }
+
+    pub fn vote_distribution(
+        deps: Deps,
+        gauge_id: u64,
+        buckets: u32,
+    ) -> StdResult<VoteDistributionResponse> {
+        let gauge = query_gauge(deps.storage, gauge_id)?;
+
+        let mut powers = votes()
+            .range_by_gauge(deps.storage, gauge_id)
+            .filter(|vote| match vote {
+                Ok((_, vote)) => !vote.is_expired(&gauge),
+                Err(_) => true,
+            })
+            .map(|vote| vote.map(|(_, vote)| vote.power.u128()))
+            .take(MAX_DISTRIBUTION_VOTERS + 1)
+            .collect::<StdResult<Vec<_>>>()?;
+        let truncated = powers.len() > MAX_DISTRIBUTION_VOTERS;
+        powers.truncate(MAX_DISTRIBUTION_VOTERS);
+
+        let max_power = match powers.iter().max() {
+            Some(max_power) => *max_power,
+            None => {
+                return Ok(VoteDistributionResponse {
+                    buckets: vec![],
+                    truncated,
+                })
+            }
+        };
+        // split 0..=max_power into ranges of equal size, rounded up. This can leave fewer
+        // buckets than requested, eg. when there are less distinct powers
+        let buckets = buckets.clamp(1, MAX_DISTRIBUTION_BUCKETS) as u128;
+        let size = (max_power + buckets) / buckets;
+        let buckets = (max_power + size) / size;
+        let mut counts = vec![0u32; buckets as usize];
+        for power in powers {
+            counts[(power / size) as usize] += 1;
+        }
+
+        Ok(VoteDistributionResponse {
+            buckets: counts
+                .into_iter()
+                .enumerate()
+                .map(|(i, voters)| {
+                    let min = i as u128 * size;
+                    PowerBucket {
+                        min: Uint128::new(min),
+                        max: Uint128::new((min + size - 1).min(max_power)),
+                        voters,
+                    }
+                })
+                .collect(),
+            truncated,
+        })
+    }
+
+    pub fn remaining_budget(deps: Deps, gauge_id: u64) -> StdResult<RemainingBudgetResponse> {
+        let gauge = query_gauge(deps.storage, gauge_id)?;
+        let remaining = gauge.remaining_budget().zip(gauge.total_budget.as_ref());
+        Ok(RemainingBudgetResponse {
+            remaining: remaining.map(|(amount, budget)| Coin::new(amount.u128(), &budget.denom)),
+            distributed: gauge.distributed,
+            total_budget: gauge.total_budget,
+        })
+    }
+
+    pub fn voter_projected_impact(
+        deps: Deps,
+        gauge_id: u64,
+        voter: String,
+    ) -> StdResult<VoterProjectedImpactResponse> {
+        let voter = deps.api.addr_validate(&voter)?;
+        let gauge = query_gauge(deps.storage, gauge_id)?;
+        let vote = votes()
+            .may_load(deps.storage, &voter, gauge_id)?
+            .filter(|v| !v.is_expired(&gauge));
+        let vote = match vote {
+            // only gauges distributing the reward themselves know the reward of an option
+            Some(vote) if gauge.current_reward().is_some() => vote,
+            _ => return Ok(VoterProjectedImpactResponse { options: vec![] }),
+        };
+        let efficiency = reward_efficiency(deps, gauge_id)?.options;
+
+        let options = vote
+            .votes
+            .into_iter()
+            .filter(|v| v.option != ABSTAIN_OPTION)
+            .map(|v| {
+                let voter_power = vote.power * v.weight;
+                // options outside of the selected set don't get anything
+                let (projected_reward, voter_reward) = efficiency
+                    .iter()
+                    .find(|e| e.option == v.option)
+                    .map(|e| {
+                        (
+                            e.projected_reward,
+                            e.projected_reward.multiply_ratio(voter_power, e.tally),
+                        )
+                    })
+                    .unwrap_or_default();
+                VoterOptionImpact {
+                    option: v.option,
+                    projected_reward,
+                    voter_power,
+                    voter_reward,
+                }
+            })
+            .collect();
+        Ok(VoterProjectedImpactResponse { options })
+    }
+
+    pub fn execution_failures(deps: Deps, gauge_id: u64) -> StdResult<ExecutionFailuresResponse> {
+        let failures = EXECUTION_FAILURES
+            .sub_prefix(gauge_id)
+            .range(deps.storage, None, None, Order::Ascending)
+            .map(|item| item.map(|(_, failure)| failure))
+            .collect::<StdResult<Vec<_>>>()?;
+        Ok(ExecutionFailuresResponse { failures })
+    }
+
+    pub fn execution_inputs(
+        deps: Deps,
+        gauge_id: u64,
+        epoch: u64,
+    ) -> StdResult<ExecutionInputsResponse> {
+        Ok(ExecutionInputsResponse {
+            inputs: EXECUTION_INPUTS.may_load(deps.storage, (gauge_id, epoch))?,
+        })
+    }
+
+    pub fn last_executed_set(deps: Deps, gauge_id: u64) -> StdResult<LastExecutedSetResponse> {
+        let gauge = query_gauge(deps.storage, gauge_id)?;
+        let executed_by = match gauge.executed_epochs.checked_sub(1) {
+            Some(epoch) => EXECUTION_INPUTS
+                .may_load(deps.storage, (gauge_id, epoch))?
+                .and_then(|inputs| inputs.executed_by),
+            None => None,
+        };
+        Ok(LastExecutedSetResponse {
+            votes: gauge.last_executed_set,
+            executed_by,
+        })
+    }
+
+    pub fn shut_out_options(deps: Deps, gauge_id: u64) -> StdResult<ShutOutOptionsResponse> {
+        let gauge = query_gauge(deps.storage, gauge_id)?;
+        let last = match gauge.executed_epochs.checked_sub(1) {
+            Some(epoch) => match (
+                EXECUTION_INPUTS.may_load(deps.storage, (gauge_id, epoch))?,
+                EPOCH_PAYOUTS.may_load(deps.storage, (gauge_id, epoch))?,
+            ) {
+                (Some(inputs), Some(payouts)) => Some((epoch, inputs, payouts)),
+                _ => None,
+            },
+            None => None,
+        };
+        let (epoch, inputs, payouts) = match last {
+            Some(last) => last,
+            None => {
+                return Ok(ShutOutOptionsResponse {
+                    epoch: None,
+                    options: vec![],
+                })
+            }
+        };
+
+        // the options the execution actually paid
+        let paid: Vec<String> = match payouts {
+            EpochPayouts::Reward(shares) => shares
+                .into_iter()
+                .filter(|(_, amount)| !amount.is_zero())
+                .map(|(option, _)| option)
+                .collect(),
+            EpochPayouts::Weights(weights) => weights
+                .into_iter()
+                .filter(|(_, weight)| !weight.is_zero())
+                .map(|(option, _)| option)
+                .collect(),
+        };
+
+        let options = inputs
+            .tallies
+            .into_iter()
+            .filter(|(option, tally)| !tally.is_zero() && !paid.contains(option))
+            .collect();
+        Ok(ShutOutOptionsResponse {
+            epoch: Some(epoch),
+            options,
+        })
+    }
+
+    fn option_delta(
+        option: String,
+        last_amount: Uint128,
+        projected_amount: Uint128,
+    ) -> OptionDelta {
+        let (delta, decreased) = if projected_amount >= last_amount {
+            (projected_amount - last_amount, false)
+        } else {
+            (last_amount - projected_amount, true)
+        };
+        OptionDelta {
+            option,
+            last_amount,
+            projected_amount,
+            delta,
+            decreased,
+        }
+    }
+
+    pub fn set_delta(deps: Deps, env: Env, gauge_id: u64) -> StdResult<SetDeltaResponse> {
+        let last = query_gauge(deps.storage, gauge_id)?
+            .last_executed_set
+            .unwrap_or_default();
+        let projected = selected_set(deps, &env, gauge_id)?.votes;
+
+        let mut deltas: Vec<OptionDelta> = projected
+            .iter()
+            .map(|(option, projected_amount)| {
+                let last_amount = last
+                    .iter()
+                    .find(|(o, _)| o == option)
+                    .map(|(_, amount)| *amount)
+                    .unwrap_or_default();
+                option_delta(option.clone(), last_amount, *projected_amount)
+            })
+            .collect();
+        // options which were executed last time, but are not selected anymore
+        deltas.extend(
+            last.into_iter()
+                .filter(|(option, _)| !projected.iter().any(|(o, _)| o == option))
+                .map(|(option, last_amount)| option_delta(option, last_amount, Uint128::zero())),
+        );
+
+        Ok(SetDeltaResponse { deltas })
+    }
+
+    pub fn execution_complexity(
+        deps: Deps,
+        env: Env,
+        gauge_id: u64,
+    ) -> StdResult<ExecutionComplexityResponse> {
+        let gauge = query_gauge(deps.storage, gauge_id)?;
+        let voter_count = VOTER_COUNT
+            .may_load(deps.storage, gauge_id)?
+            .unwrap_or_default();
+        let option_count = TALLY
+            .prefix(gauge_id)
+            .keys(deps.storage, None, None, Order::Ascending)
+            .count() as u64;
+
+        let selected = selected_set(deps, &env, gauge_id)?.votes.len() as u64;
+        let msgs = if gauge.distribution_hook.is_some() {
+            // the hook gets everything in one message
+            1
+        } else if gauge.current_reward().is_some() {
+            // one transfer per option
+            selected
+        } else {
+            // adapters usually create one message per option
+            selected * gauge.adapters().count() as u64
+        };
+        let estimated_submsgs = if gauge.isolate_failures { msgs } else { 1 };
+
+        Ok(ExecutionComplexityResponse {
+            voter_count,
+            option_count,
+            estimated_submsgs,
+        })
+    }
+
+    /// Queries all adapters of the gauge for the messages executing the weighted selection.
+    /// They all get the same selected set
+    pub fn adapter_msgs(
+        deps: Deps,
+        gauge: &Gauge,
+        selected: Vec<(String, Decimal)>,
+    ) -> StdResult<Vec<CosmosMsg>> {
+        let mut msgs = vec![];
+        for adapter in gauge.adapters() {
+            msgs.extend(
+                deps.querier
+                    .query_wasm_smart::<SampleGaugeMsgsResponse>(
+                        adapter,
+                        &AdapterQueryMsg::SampleGaugeMsgs {
+                            selected: selected.clone(),
+                        },
+                    )?
+                    .execute,
+            );
+        }
+        Ok(msgs)
+    }
+
+    pub fn adapter_sample_msgs(
+        deps: Deps,
+        gauge_id: u64,
+        selected: Vec<(String, Uint128)>,
+    ) -> StdResult<SampleGaugeMsgsResponse> {
+        let gauge = query_gauge(deps.storage, gauge_id)?;
+        // same conversion as in `Execute`
+        let selected = to_weights(selected);
+        let selected = if gauge.adapter_multipliers {
+            apply_multipliers(deps, &gauge.adapter, selected)?
+        } else {
+            selected
+        };
+        Ok(SampleGaugeMsgsResponse {
+            execute: adapter_msgs(deps, &gauge, selected)?,
+        })
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(deps: DepsMut, env: Env, msg: MigrateMsg) -> Result<Response, ContractError> {
+    ensure_from_older_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    for (gauge_id, config) in msg.gauge_config.unwrap_or_default() {
+        GAUGES.update(deps.storage, gauge_id, |gauge| -> StdResult<_> {
+            let mut gauge = gauge.ok_or(StdError::NotFound {
+                kind: format!("Gauge with id {}", gauge_id),
+            })?;
+            if let Some(next_epoch) = config.next_epoch {
+                if next_epoch < gauge.epoch_unit.now(&env.block) {
+                    return Err(StdError::GenericErr {
+                        msg: "Next epoch value cannot be earlier then current epoch!".to_owned(),
+                    });
+                }
                 gauge.next_epoch = next_epoch;
             }
             if let Some(reset_config) = config.reset {
@@ -834,5 +4729,28 @@ pub fn migrate(deps: DepsMut, env: Env, msg: MigrateMsg) -> Result<Response, Con
         })?;
     }
 
-    Ok(Response::new())
+    // index gauges created before the adapter index existed, there are only a few of them
+    let gauges = GAUGES
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|item| item.map(|(id, gauge)| (id, gauge.adapter)))
+        .collect::<StdResult<Vec<_>>>()?;
+    for (gauge_id, adapter) in gauges {
+        GAUGES_BY_ADAPTER.save(deps.storage, (&adapter, gauge_id), &1)?;
+    }
+    let mut response = Response::new();
+    if let Some(batch_size) = msg.backfill_vote_index {
+        let (indexed, finished) = votes().backfill_option_index(deps.storage, batch_size)?;
+        response = response
+            .add_attribute("indexed_votes", indexed.to_string())
+            .add_attribute("finished", finished.to_string());
+    }
+    // options are many more than gauges, so they are indexed in batches as well
+    if let Some(batch_size) = msg.backfill_option_index {
+        let (indexed, finished) = backfill_option_indexes(deps.storage, batch_size)?;
+        response = response
+            .add_attribute("indexed_options", indexed.to_string())
+            .add_attribute("options_finished", finished.to_string());
+    }
+
+    Ok(response)
 }
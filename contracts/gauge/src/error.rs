@@ -1,20 +1,31 @@
-use cosmwasm_std::{Decimal, StdError};
+use cosmwasm_std::{Decimal, OverflowError, StdError, Uint128};
 use thiserror::Error;
 
 #[derive(Error, Debug, PartialEq)]
 pub enum ContractError {
     #[error("{0}")]
-    Std(#[from] StdError),
+    Std(StdError),
+
+    #[error("{0}")]
+    Overflow(#[from] OverflowError),
 
     #[error("Unauthorized")]
     Unauthorized {},
 
-    #[error("Gauge with ID {0} does not exists")]
-    GaugeMissing(u64),
+    #[error("Gauge {gauge} not found")]
+    GaugeNotFound { gauge: u64 },
+
+    #[error("Address {0} is not a valid gauge adapter")]
+    InvalidAdapter(String),
 
     #[error("Voted for {0} times total voting power. Limit 1.0")]
     TooMuchVotingWeight(Decimal),
 
+    #[error(
+        "Voted for {0} times total voting power, but the gauge requires all of it to be allocated"
+    )]
+    IncompleteAllocation(Decimal),
+
     #[error("User {0} has no voting power")]
     NoVotingPower(String),
 
@@ -27,8 +38,11 @@ pub enum ContractError {
     #[error("Option {option} has been judged as valid by gauge adapter of gauge ID {gauge_id} and cannot be removed")]
     OptionValidByAdapter { option: String, gauge_id: u64 },
 
-    #[error("Option {option} does not exists for gauge ID {gauge_id}")]
-    OptionDoesNotExists { option: String, gauge_id: u64 },
+    #[error("Option {option} not found in gauge {gauge}")]
+    OptionNotFound { gauge: u64, option: String },
+
+    #[error("Option {option} is deprecated for gauge ID {gauge_id}")]
+    OptionDeprecated { option: String, gauge_id: u64 },
 
     #[error("Gauge ID {gauge_id} cannot execute because next_epoch is not yet reached: current {current_epoch}, next_epoch: {next_epoch}")]
     EpochNotReached {
@@ -43,21 +57,162 @@ pub enum ContractError {
     #[error("Gauge ID {0} cannot execute because it is stopped")]
     GaugeStopped(u64),
 
+    #[error("Option {0} is reserved and cannot be added")]
+    ReservedOption(String),
+
+    #[error("Gauge ID {0} is closed for voting")]
+    VotingClosed(u64),
+
+    #[error("Options of gauge ID {0} are frozen, no new options can be added")]
+    OptionsFrozen(u64),
+
+    #[error("All gauges are paused")]
+    ContractPaused {},
+
     #[error("Gauge ID {0} is currently resetting, please try again later")]
     GaugeResetting(u64),
 
+    #[error("Gauge ID {0} reached the maximum number of voters")]
+    VoterLimitReached(u64),
+
+    #[error("Address {0} is not allowed to vote on this gauge")]
+    VoterNotAllowed(String),
+
+    #[error("Voter allowlist and merkle root cannot be used together")]
+    MultipleVoterRestrictions {},
+
+    #[error("Merkle root must be a hex encoded 32 byte hash")]
+    InvalidMerkleRoot {},
+
+    #[error("Merkle proof verification failed")]
+    InvalidMerkleProof {},
+
+    #[error("Tenure weighting requires a tenure curve")]
+    TenureCurveMissing {},
+
+    #[error(
+        "Tenure curve needs a maximum multiplier of at least 1.0 and a half life bigger then 0"
+    )]
+    InvalidTenureCurve {},
+
     #[error("Trying to remove vote that does not exists")]
     CannotRemoveNonexistingVote {},
 
     #[error("Epoch size must be bigger then 60 seconds")]
     EpochSizeTooShort {},
 
+    #[error("Epoch size must be at least one block")]
+    EpochHeightTooShort {},
+
     #[error("Minimum percent selected parameter needs to be smaller then 1.0")]
     MinPercentSelectedTooBig {},
 
     #[error("Maximum options selected parameter needs to be bigger then 0")]
     MaxOptionsSelectedTooSmall {},
 
-    #[error("Maximum percentage available parameter needs to be smaller then 1.0")]
+    #[error("Maximum paid options parameter needs to be bigger then 0")]
+    MaxPaidOptionsTooSmall {},
+
+    #[error("Maximum percentage available parameter must not be bigger then 1.0")]
     MaxAvailablePercentTooBig {},
+
+    #[error(
+        "Maximum percentage available parameter must not be zero, leave it unset for no limit"
+    )]
+    InvalidMaxAvailablePercentage {},
+
+    #[error("Maximum percentage available parameter cannot be set and cleared at once")]
+    ConflictingMaxAvailablePercentage {},
+
+    #[error("Reward schedule must be sorted by epoch, without duplicates")]
+    UnsortedRewardSchedule {},
+
+    #[error("No funds sent")]
+    NoFunds {},
+
+    #[error("{0} is not allowed to execute gauge {1}")]
+    ExecutorNotAllowed(String, u64),
+
+    #[error("Gauge {0} has no execute allowlist")]
+    NoExecuteAllowlist(u64),
+
+    #[error("No options to move given")]
+    NoOptionsToMove {},
+
+    #[error("A gauge can have at most {0} tags")]
+    TooManyTags(usize),
+
+    #[error("Tag \"{0}\" must not be empty or longer than {1} characters")]
+    InvalidTag(String, usize),
+
+    #[error("Distribution hooks only support native rewards")]
+    DistributionHookCw20Reward {},
+
+    #[error("Swap routes only support native rewards")]
+    SwapRouteCw20Reward {},
+
+    #[error("The total budget must be in the native denom of the distributed rewards")]
+    BudgetDenomMismatch {},
+
+    #[error("Options of gauge {0} were validated recently, the next sweep can start at {1}")]
+    ValidationSweepTooSoon(u64, u64),
+
+    #[error("Epoch label must not be longer than {0} characters")]
+    InvalidEpochLabel(usize),
+
+    #[error("Voting again in this epoch requires a fee of {0}")]
+    VoteChangeFeeRequired(String),
+
+    #[error("Rewards of the adapter require to_distribute as fallback")]
+    AdapterRewardWithoutDistribution {},
+
+    #[error(
+        "Reward of {amount} {denom} returned by the adapter is not available, only {available}"
+    )]
+    AdapterRewardUnavailable {
+        denom: String,
+        amount: Uint128,
+        available: Uint128,
+    },
+
+    #[error("Gauge {gauge_id} missed no epoch before executing epoch {epoch}")]
+    NoMissedEpoch { gauge_id: u64, epoch: u64 },
+
+    #[error("Gauge {0} cannot be the successor, it must be another active gauge")]
+    InvalidSuccessor(u64),
+
+    #[error("The DAO core holds no {denom} to distribute")]
+    MissingDenom { denom: String },
+
+    #[error("Gauge {gauge_id} already has the maximum of {max_total_options} options")]
+    OptionLimitReached {
+        gauge_id: u64,
+        max_total_options: u32,
+    },
+
+    #[error("align_to must be bigger than 0 and at most the epoch size")]
+    InvalidAlignTo {},
+
+    #[error("Maximum voter influence must be bigger than 0 and at most 1.0")]
+    InvalidMaxVoterInfluence {},
+
+    #[error("The fixed amount per option must be bigger than 0")]
+    InvalidFixedAmount {},
+
+    #[error("Cannot withdraw {amount} {denom}, only {balance} are available")]
+    WithdrawExceedsBalance {
+        denom: String,
+        amount: Uint128,
+        balance: Uint128,
+    },
+}
+
+impl From<StdError> for ContractError {
+    fn from(err: StdError) -> Self {
+        // overflows in the state helpers are reported like the ones in the contract
+        match err {
+            StdError::Overflow { source, .. } => ContractError::Overflow(source),
+            err => ContractError::Std(err),
+        }
+    }
 }
@@ -1,7 +1,12 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::{CosmosMsg, Decimal, Uint128};
+use cosmwasm_std::{Addr, Coin, CosmosMsg, Decimal, Uint128};
+use cw_utils::Expiration;
 
-use crate::state::{Reset, Vote};
+use crate::state::{
+    Asset, DeprecatedVotePolicy, DistributionMode, EpochParticipation, EpochUnit, ExecuteCondition,
+    ExecutePolicy, ExecutionFailure, ExecutionInputs, OptionTallySnapshot, Reset, Rounding,
+    SwapRoute, TenureCurve, Vote, VoterActivity, VoterRestriction, VotingQuery,
+};
 use wynd_stake::hook::MemberChangedHookMsg;
 
 type GaugeId = u64;
@@ -16,6 +21,11 @@ pub struct InstantiateMsg {
     /// Important, as instantiation and CreateGauge both come from DAO proposals
     /// and without this argument, you need 2 cycles to create and configure a gauge
     pub gauges: Option<Vec<GaugeConfig>>,
+    /// Optional label added as `namespace` attribute to all emitted events,
+    /// so indexers shared by several deployments can route them
+    pub event_namespace: Option<String>,
+    /// Queries to send to `voting_powers`, `VotingPowerAtHeight` of DAO voting modules by default
+    pub voting_query: Option<VotingQuery>,
 }
 
 #[cw_serde]
@@ -24,7 +34,7 @@ pub struct GaugeConfig {
     pub title: String,
     /// Address of contract to serve gauge-specific info (AdapterQueryMsg)
     pub adapter: String,
-    /// Frequency (in seconds) the gauge executes messages, typically something like 7*86400
+    /// Frequency (in `epoch_unit`) the gauge executes messages, typically something like 7*86400 seconds
     pub epoch_size: u64,
     /// Minimum percentage of votes needed by a given option to be in the selected set.
     /// If unset, there is no minimum percentage, just the `max_options_selected` limit.
@@ -36,6 +46,148 @@ pub struct GaugeConfig {
     pub max_available_percentage: Option<Decimal>,
     /// If set, the gauge can be reset periodically, every `reset_epoch` seconds.
     pub reset_epoch: Option<u64>,
+    /// If set, the gauge distributes this asset (native or cw20) to the selected options
+    /// every epoch by itself, instead of asking the adapter for the messages.
+    /// Options must be valid addresses in that case.
+    pub to_distribute: Option<Asset>,
+    /// Optional schedule of `(epoch, reward)` pairs, sorted by epoch. Each entry replaces
+    /// `to_distribute` from the given epoch on (epochs are counted by executions, starting at 0).
+    /// Before the first entry, `to_distribute` is used.
+    pub reward_schedule: Option<Vec<(u64, Asset)>>,
+    /// If set, at most this many distinct voters can vote on the gauge.
+    /// Existing voters can still change their votes once the limit is reached.
+    pub max_voters: Option<u64>,
+    /// If set, every voted option is checked with the adapter again when voting,
+    /// so options which were delisted in the meantime cannot receive votes anymore.
+    #[serde(default)]
+    pub validate_option_on_vote: bool,
+    /// If set, only these addresses can vote on the gauge. Good for small, fixed voter sets
+    pub voter_allowlist: Option<Vec<String>>,
+    /// If set, only addresses proving to be part of the merkle tree with this (hex encoded) root
+    /// can vote. Leaves are the sha256 hashes of the addresses. Use it for large voter sets,
+    /// it cannot be combined with `voter_allowlist`.
+    pub voter_merkle_root: Option<String>,
+    /// If set, every voter's power is multiplied by a multiplier growing with the time
+    /// they have been a member (queried from `tenure_curve.source`) when executing.
+    /// Note that this iterates over all votes of the gauge on every execution.
+    #[serde(default)]
+    pub tenure_weighting: bool,
+    /// Parameters of the tenure multiplier, required if `tenure_weighting` is set
+    pub tenure_curve: Option<TenureCurve>,
+    /// If set, executing without any votes splits the reward equally between the first
+    /// `max_options_selected` options (in alphabetical order), instead of distributing nothing.
+    /// Useful to bootstrap new gauges.
+    #[serde(default)]
+    pub bootstrap_equal_split: bool,
+    /// If set, removing an option also drops it from the votes of everyone who voted for it.
+    /// The rest of their split stays as it is, the weight of the removed option is left unused.
+    /// Note that this relies on the voter-by-option index, so it has to be fully backfilled.
+    #[serde(default)]
+    pub reclaim_votes_on_remove: bool,
+    /// If set, the adapter is asked for a multiplier per selected option (`OptionMultipliers`)
+    /// when executing, eg. to boost strategic options. Each option's weight is multiplied by it,
+    /// then the weights are normalized again, so the whole reward is still distributed.
+    #[serde(default)]
+    pub adapter_multipliers: bool,
+    /// If set, the adapter is asked for the reward of every epoch (`EpochReward`) when executing,
+    /// eg. to switch from a bootstrap token to the final one. It falls back to `to_distribute`,
+    /// which is required. The DAO core (or the gauge's escrow) has to hold the returned reward.
+    #[serde(default)]
+    pub adapter_reward: bool,
+    /// If set, every message of an execution is sent to the DAO core on its own, so a failing
+    /// payout doesn't revert the others. Failures are recorded, see `ExecutionFailures`.
+    #[serde(default)]
+    pub isolate_failures: bool,
+    /// Unit of `epoch_size`, time (seconds) by default. With `Height`, the epochs are measured
+    /// in blocks instead, for chains with a variable block time.
+    #[serde(default)]
+    pub epoch_unit: EpochUnit,
+    /// Rounding of the per-option amounts when the gauge distributes `to_distribute` itself.
+    /// Rounds down by default. The total distributed never exceeds the reward.
+    #[serde(default)]
+    pub rounding: Rounding,
+    /// Tags to group gauges by, eg. a category. At most 10 tags of up to 32 characters
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Further adapters distributing the selected set, eg. an LP incentive adapter next to
+    /// a bank payout one. Each is queried with the same selected set and all their messages
+    /// are executed. Options are only managed by `adapter`. Ignored with `to_distribute`
+    #[serde(default)]
+    pub extra_adapters: Vec<String>,
+    /// Minimum power an option needs to be selected. Options have to reach both this and
+    /// `min_percent_selected`, which prevents tiny tallies from being selected in epochs
+    /// with little participation.
+    pub min_absolute_tally: Option<Uint128>,
+    /// With `WinnerTakeAll`, only the qualifying option with the highest tally is selected.
+    /// With `EqualFixed`, the selected options get a fixed grant each instead of a share.
+    /// Proportional by default
+    #[serde(default)]
+    pub distribution_mode: DistributionMode,
+    /// If set, every voter's power is multiplied by the boost this contract reports for them
+    /// (`BoostQueryMsg::Boost`) when executing, eg. for locked tokens.
+    /// Multipliers are kept between 1.0 and 2.5
+    pub boost_source: Option<String>,
+    /// If set, every voter's power is reduced to the lowest of their power when the epoch started,
+    /// when voting and when executing. Power acquired just for the vote (eg. by a flash loan)
+    /// is not counted. This queries the historical power of every voter, so it is only done
+    /// when executing.
+    #[serde(default)]
+    pub flashloan_resistant: bool,
+    /// If set, `Execute` sends the complete selected set to this contract in a single
+    /// `DistributionHookMsg::Distribute`, instead of the adapter messages or reward transfers.
+    /// The hook fans out the distribution itself, eg. to track receipts. A native reward is
+    /// attached as funds, cw20 rewards are not supported.
+    pub distribution_hook: Option<String>,
+    /// If set, every `Execute` removes options that had no votes for more than 4 executed epochs.
+    /// At most 10 options are removed and 50 options without votes are checked per execution,
+    /// to keep the gas usage bounded.
+    #[serde(default)]
+    pub auto_prune_zero_options: bool,
+    /// If set, votes must allocate the voter's full power, their weights have to sum up to 1.0.
+    /// Removing the vote is still possible
+    #[serde(default)]
+    pub require_full_allocation: bool,
+    /// Total amount the gauge may ever distribute, for campaigns with a finite budget.
+    /// It must be in the (native) denom of `to_distribute`. The last epoch only pays out what
+    /// is left of the budget, after which the gauge is stopped
+    pub total_budget: Option<Coin>,
+    /// If set, every vote after the first one of a voter in the same epoch requires attaching
+    /// this fee, which is sent to the DAO core. Removing votes is free
+    pub vote_change_fee: Option<Coin>,
+    /// Who may call `Execute` on the gauge, eg. only a trusted keeper to avoid timing games.
+    /// Anyone by default. Listed addresses can be changed with `UpdateExecuteAllowlist`
+    #[serde(default)]
+    pub execute_policy: ExecutePolicy,
+    /// If set, only this many of the top options in the selected set are paid on `Execute`,
+    /// sharing the whole reward among them. The others are still part of the selected set
+    pub max_paid_options: Option<u32>,
+    /// With `redistribute`, the power voters give to deprecated options is moved to their other
+    /// options on `Execute`, instead of being left out (`ignore`, default)
+    #[serde(default)]
+    pub deprecated_vote_policy: DeprecatedVotePolicy,
+    /// If set, options whose share of `to_distribute` is below this amount are not paid on
+    /// `Execute`, the reward is split among the remaining options instead
+    pub min_distribution_per_option: Option<Uint128>,
+    /// If set, no more options can be added once the gauge has this many.
+    /// Can be raised with `UpdateGauge`
+    pub max_total_options: Option<u32>,
+    /// If set, this contract is sent a `PostExecuteCallbackMsg::GaugeExecuted` with the results
+    /// after every `Execute`, eg. for monitoring. Its failure doesn't revert the execution
+    pub post_execute_callback: Option<String>,
+    /// If set, every epoch boundary is snapped down to a multiple of it (in `epoch_unit`),
+    /// eg. 86400 to execute at midnight. Must be at most `epoch_size`
+    pub align_to: Option<u64>,
+    /// If set, the power of the biggest voters is scaled down on `Execute`, so no voter makes up
+    /// more than this share of the tallies the selection is done with (anti-whale).
+    /// Like `tenure_weighting`, this needs to go over all votes of the gauge
+    pub max_voter_influence: Option<Decimal>,
+    /// If set, `Execute` queries the price of the oracle (`OracleQueryMsg::Price`) and only
+    /// distributes if it is at least `min_price`. Otherwise the epoch is skipped without payout
+    pub execute_condition: Option<ExecuteCondition>,
+    /// If set, `Execute` has the DAO core swap the native reward to `to_denom` with the router
+    /// first, and splits the amount the router returns (`RouterQueryMsg::SimulateSwap`).
+    /// The swap is not paid from the gauge's escrow
+    pub swap_route: Option<SwapRoute>,
 }
 
 #[cw_serde]
@@ -46,20 +198,74 @@ pub enum ExecuteMsg {
     /// This creates a new Gauge, returns CreateGaugeReply JSON-encoded in the data field.
     /// Can only be called by owner
     CreateGauge(GaugeConfig),
+    /// Creates a new gauge with the same config as `source_gauge`, optionally with another adapter
+    /// or title. Votes, options and execution history are not copied, the options are fetched from
+    /// the adapter like for `CreateGauge`.
+    /// Can only be called by owner
+    CloneGauge {
+        source_gauge: u64,
+        new_adapter: Option<String>,
+        new_title: Option<String>,
+    },
+    /// Splits an overloaded gauge: creates a new gauge with `new_config` and moves the listed
+    /// options, including the votes for them, over from `gauge`. Voters keep their weights,
+    /// their split is divided between the two gauges. Expired votes stay with `gauge`.
+    /// This goes over all votes of `gauge`. Can only be called by owner
+    SplitGauge {
+        gauge: u64,
+        options_to_move: Vec<String>,
+        new_config: GaugeConfig,
+    },
     /// Allows owner to update certain parameters of GaugeConfig.
     /// If you want to change next_epoch value, you need to use migration.
+    /// The `next_epoch` the gauge can be executed at is returned as attribute.
     UpdateGauge {
         gauge_id: u64,
+        /// Takes effect from the next epoch boundary on, the current epoch keeps its end
         epoch_size: Option<u64>,
         // Some<0> would set min_percent_selected to None
         min_percent_selected: Option<Decimal>,
         max_options_selected: Option<u32>,
+        /// Must be bigger than 0 and at most 1.0, where 1.0 imposes no limit
         max_available_percentage: Option<Decimal>,
+        /// Removes the cap of `max_available_percentage`, which must not be given along with it
+        #[serde(default)]
+        clear_max_available_percentage: bool,
+        /// Replaces all tags of the gauge
+        tags: Option<Vec<String>>,
+        /// New cap on the number of options, existing options are kept if it is lowered
+        max_total_options: Option<u32>,
     },
+    /// Adds the sent native funds to the gauge's escrow. Can be called by anyone.
+    /// Gauges with `to_distribute` pay out from their escrow instead of the DAO core,
+    /// as long as it holds enough of the reward denom.
+    FundGauge { gauge: u64 },
+    /// Closes voting on a given gauge. No more votes or options will be accepted,
+    /// but the gauge can still be executed once more, after which it is stopped.
+    /// All data stays queryable.
+    CloseVoting { gauge: u64 },
+    /// Freezes the set of options of a gauge, so `AddOption` and `SyncOptions` don't add any
+    /// new ones. Voting on the existing options continues. Owner only
+    FreezeOptions { gauge: u64 },
+    /// Allows adding options again after `FreezeOptions`. Owner only
+    UnfreezeOptions { gauge: u64 },
     /// Stops a given gauge, meaning it will not execute any more messages,
     /// Or receive any more updates on MemberChangedHook.
     /// Ideally, this will allow for eventual deletion of all data on that gauge
     StopGauge { gauge: u64 },
+    /// Stops the gauge like `StopGauge` and moves everything left in its escrow to the escrow
+    /// of `successor_gauge`, which must be another active gauge. Owner only
+    StopGaugeAndRollover { gauge: u64, successor_gauge: u64 },
+    /// Safety hatch for funds stuck in the contract, eg. in the escrow of a stopped gauge.
+    /// Sends `amount` of the native `denom` to `recipient`. With `gauge`, it is taken from the
+    /// escrow of that gauge (all of it by default). Without, only the balance of the contract
+    /// that is not held in any escrow can be withdrawn (all of it by default). Owner only
+    EmergencyWithdraw {
+        denom: String,
+        amount: Option<Uint128>,
+        recipient: String,
+        gauge: Option<u64>,
+    },
     /// Resets all votes on a given gauge if it is configured to be periodically reset and the epoch has passed.
     /// One call to this will only clear `batch_size` votes to prevent gas exhaustion. Call repeatedly to clear all votes.
     ResetGauge { gauge: u64, batch_size: u32 },
@@ -72,16 +278,74 @@ pub enum ExecuteMsg {
     /// Allows the owner to remove an option. This is useful if the option is no longer valid
     /// or if the owner wants to remove all votes from a valid option.
     RemoveOption { gauge: u64, option: String },
+    /// Allows the owner to remove many options at once, eg. to clean up after a campaign.
+    /// Options with votes are skipped, they are listed in the `skipped` attribute.
+    RemoveOptions { gauge: u64, options: Vec<String> },
+    /// Allows the owner to freeze all gauges at once, eg. during an upgrade.
+    /// While paused, no gauges can be created or executed and no votes or options can be added.
+    PauseAll {},
+    /// Allows the owner to lift a `PauseAll`
+    UnpauseAll {},
+    /// Allows the owner to deprecate an option. It is never selected again, but keeps its votes,
+    /// so voters can move them elsewhere whenever they like. New votes for it are rejected.
+    DeprecateOption { gauge: u64, option: String },
+    /// Adds the options of the adapter that are not in the gauge yet.
+    /// It processes at most `limit` options of the adapter per call, continuing where the last call
+    /// stopped. Call repeatedly until the `finished` attribute is `true` to sync all options.
+    /// Can be called by anyone, not just owner
+    SyncOptions { gauge: u64, limit: u32 },
+    /// Checks the options of the gauge with the adapter and deprecates the invalid ones,
+    /// eg. delisted pools. It checks at most 30 options per call, continuing where the last call
+    /// stopped. Call repeatedly until the `finished` attribute is `true` to check all options.
+    /// Can be called by anyone, but a new sweep can only be started once a day
+    ValidateOptions { gauge: u64 },
+    /// Allows the owner to name an epoch of the gauge, eg. after a campaign.
+    /// `epoch` is the number of the executed epoch, starting at 0. An empty label removes it
+    SetEpochLabel {
+        gauge: u64,
+        epoch: u64,
+        label: String,
+    },
+    /// Allows the owner to change who may execute a gauge with an `Allowlist` execute policy
+    UpdateExecuteAllowlist {
+        gauge: u64,
+        add: Vec<String>,
+        remove: Vec<String>,
+    },
     /// Place your vote on the gauge. Can be updated anytime
     PlaceVotes {
         /// Gauge to vote on
         gauge: u64,
         /// The options to put my vote on, along with proper weights (must sum up to 1.0)
-        /// "None" means remove existing votes and abstain
+        /// "None" means remove existing votes and abstain.
+        /// Options with zero weight are ignored, so only zero weights also remove the votes
         votes: Option<Vec<Vote>>,
+        /// Merkle proof (hex encoded hashes) that the sender is allowed to vote on the gauge.
+        /// Only needed the first time the sender votes on a gauge with `voter_merkle_root`.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        proof: Option<Vec<String>>,
+        /// Idempotency key against accidental resubmission. If it equals the nonce of the
+        /// sender's last `PlaceVotes` on this gauge, the call succeeds without changing anything,
+        /// as long as the sender could vote on the gauge at all.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        nonce: Option<u64>,
+    },
+    /// Same as `PlaceVotes`, but with weights in basis points (10000 = 100%), which must sum up
+    /// to at most 10000. An empty list removes existing votes
+    PlaceVotesBps {
+        gauge: u64,
+        votes: Vec<(String, u16)>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        proof: Option<Vec<String>>,
     },
     /// Takes a sample of the current tally and execute the proper messages to make it work
     Execute { gauge: u64 },
+    /// Owner only. Pays out one of the epochs missed before `epoch` was executed (eg. because
+    /// all gauges were paused). The missed epochs have no tallies of their own, they are all paid
+    /// with the inputs recorded by the execution of `epoch` which ended the gap: the same
+    /// tallies, selection rules, paid options, distribution mode, rounding and reward.
+    /// Every missed epoch can only be paid out once
+    ExecuteEpoch { gauge: u64, epoch: u64 },
 }
 
 #[cw_serde]
@@ -90,12 +354,33 @@ pub struct CreateGaugeReply {
     pub id: u64,
 }
 
+/// Order of the options returned by `QueryMsg::ListOptions`
+#[cw_serde]
+pub enum OptionOrder {
+    /// By option name
+    Key,
+    /// By tally, highest first. Options with the same tally come in reverse key order
+    Tally,
+    /// In the order the options were added to the gauge. Options of older versions are only
+    /// listed once the migration backfilled them, appended in key order
+    AddedAt,
+}
+
+impl Default for OptionOrder {
+    fn default() -> Self {
+        OptionOrder::Key
+    }
+}
+
 /// Queries the gauge exposes
 #[cw_serde]
 #[derive(QueryResponses)]
 pub enum QueryMsg {
     #[returns(cw_core_interface::voting::InfoResponse)]
     Info {},
+    /// Settings of the gauge contract itself, along with its stored version
+    #[returns(ConfigResponse)]
+    Config {},
     #[returns(GaugeResponse)]
     Gauge { id: u64 },
     #[returns(ListGaugesResponse)]
@@ -103,24 +388,234 @@ pub enum QueryMsg {
         start_after: Option<u64>,
         limit: Option<u32>,
     },
+    /// Returns when the gauge can be executed next
+    #[returns(NextExecutionResponse)]
+    NextExecution { gauge: u64 },
+    /// Returns ids of gauges that can be executed right now, that is: the epoch has passed,
+    /// and they are neither stopped nor resetting. Empty while all gauges are paused.
+    #[returns(ExecutableGaugesResponse)]
+    ExecutableGauges { limit: Option<u32> },
+    /// Same as `ExecutableGauges`, along with the set each gauge would select if executed now.
+    /// Lets keepers skip executions that would not distribute anything
+    #[returns(ExecutableGaugesWithPreviewResponse)]
+    ExecutableGaugesWithPreview { limit: Option<u32> },
+    /// Returns all gauges with the given title. Titles are not unique, so this can be more than one
+    #[returns(ListGaugesResponse)]
+    GaugesByTitle { title: String },
+    /// Returns the gauges with the given tag, ordered by ID
+    #[returns(ListGaugesResponse)]
+    ListGaugesByTag {
+        tag: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// Returns the ids of all gauges using the given contract as their `adapter`, ordered by ID
+    #[returns(GaugesForAdapterResponse)]
+    GaugesForAdapter { adapter: String },
+    /// Returns the ids of all gauges having the given option, ordered by ID
+    #[returns(GaugesForOptionResponse)]
+    GaugesForOption {
+        option: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
     #[returns(VoteResponse)]
     Vote { gauge: u64, voter: String },
+    /// Checks if the voter could place a vote on the gauge right now.
+    /// Note that stopped gauges still accept votes, they just don't execute anymore.
+    #[returns(CanVoteResponse)]
+    CanVote { gauge: u64, voter: String },
+    /// Returns the ids of gauges that are not stopped and the voter could vote on, but has no
+    /// vote on yet, ordered by ID. Only a limited number of gauges is checked per query
+    #[returns(VoterMissedGaugesResponse)]
+    VoterMissedGauges { voter: String, limit: Option<u32> },
+    /// Returns when the voter last placed, changed or removed their vote on the gauge
+    #[returns(VoterActivityResponse)]
+    VoterActivity { gauge: u64, voter: String },
+    /// Returns the voters who placed, changed or removed their vote in the current epoch, ie.
+    /// since the last `Execute` (or the creation of the gauge), ordered by the block of their
+    /// last vote, then by address. `start_after` is the last voter of the previous page.
+    /// Gauges of older versions list all voters until their next `Execute`
+    #[returns(RecentVotersResponse)]
+    RecentVoters {
+        gauge: u64,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Returns how much of the total voting power took part in the given gauge,
+    /// including abstained votes
+    #[returns(ParticipationResponse)]
+    Participation { gauge: u64 },
+    /// Returns the participation recorded at each execution of the gauge, ordered by epoch
+    #[returns(ParticipationHistoryResponse)]
+    ParticipationHistory {
+        gauge: u64,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// Returns the option's tally at each execution it was selected in, ordered by epoch.
+    /// Only the last `OPTION_TALLY_HISTORY_EPOCHS` epochs are kept
+    #[returns(OptionTallyHistoryResponse)]
+    OptionTallyHistory {
+        gauge: u64,
+        option: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// Returns the rewards the gauge distributed to the option over all epochs, per denom.
+    /// Only rewards of `to_distribute` are tracked, not the messages of adapters
+    #[returns(OptionLifetimeRewardsResponse)]
+    OptionLifetimeRewards { gauge: u64, option: String },
+    /// Returns how much more voting power `option` needs to exceed the current tally of
+    /// `target_option`, zero if it is already ahead
+    #[returns(VotesToOvertakeResponse)]
+    VotesToOvertake {
+        gauge: u64,
+        option: String,
+        target_option: String,
+    },
+    /// Returns the voters who voted for the given option, along with the weight they gave it.
+    /// Ordered by voter address. This includes votes expired by a reset
+    #[returns(OptionVotersResponse)]
+    OptionVoters {
+        gauge: u64,
+        option: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// With `only_active`, votes of voters without voting power left are omitted.
+    /// This queries the voting power of every voter on the page, so it costs a lot more gas,
+    /// and a page may contain fewer than `limit` votes
     #[returns(ListVotesResponse)]
     ListVotes {
         gauge: u64,
         start_after: Option<String>,
         limit: Option<u32>,
+        #[serde(default)]
+        only_active: bool,
+    },
+    /// Lists the votes changed (placed or updated by a voting power change) at or after
+    /// `since_block`, ordered by the block they were changed at, for indexers syncing incrementally.
+    /// Votes of older versions are only listed once they change again
+    #[returns(VotesChangedSinceResponse)]
+    VotesChangedSince {
+        gauge: u64,
+        since_block: u64,
+        /// Voter of the last vote of the previous page
+        start_after: Option<String>,
+        limit: Option<u32>,
     },
     #[returns(ListOptionsResponse)]
     ListOptions {
         gauge: u64,
+        /// Option to continue after, in the chosen order
         start_after: Option<String>,
         limit: Option<u32>,
+        #[serde(default)]
+        order_by: OptionOrder,
     },
     #[returns(SelectedSetResponse)]
     SelectedSet { gauge: u64 },
+    /// Returns the voted options ranked by tally, highest first.
+    /// This reads the sorted tally index, so the gas cost only grows with `limit`
+    /// (at most 100), not with the number of options of the gauge.
+    #[returns(OptionLeaderboardResponse)]
+    OptionLeaderboard { gauge: u64, limit: Option<u32> },
     #[returns(LastExecutedSetResponse)]
     LastExecutedSet { gauge: u64 },
+    /// Returns the options that had votes in the last executed epoch, but received no reward,
+    /// eg. because of `min_distribution_per_option` or the selection limits.
+    /// Based on the payouts recorded by the execution, so later config changes don't matter
+    #[returns(ShutOutOptionsResponse)]
+    ShutOutOptions { gauge: u64 },
+    /// Returns the messages that failed during executions of a gauge with `isolate_failures`
+    #[returns(ExecutionFailuresResponse)]
+    ExecutionFailures { gauge: u64 },
+    /// Returns the inputs an epoch (counted by executions, starting at 0) was executed with
+    #[returns(ExecutionInputsResponse)]
+    ExecutionInputs { gauge: u64, epoch: u64 },
+    /// Applies the selection rules to the given tallies, without touching any gauge.
+    /// Feeding it the `ExecutionInputs` of an epoch reproduces the set executed in that epoch.
+    #[returns(SelectedSetResponse)]
+    PreviewSelectedSet {
+        tallies: Vec<(String, Uint128)>,
+        min_percent_selected: Option<Decimal>,
+        min_absolute_tally: Option<Uint128>,
+        max_options_selected: u32,
+        max_available_percentage: Option<Decimal>,
+    },
+    /// Returns the projected reward per voting power of each option in the selected set,
+    /// in order to find under-voted options. Empty if the gauge does not distribute the reward itself
+    #[returns(RewardEfficiencyResponse)]
+    RewardEfficiency { gauge: u64 },
+    /// Returns the projected reward of each option the voter votes for, and the part of it
+    /// coming from the voter's own power. Empty if the gauge does not distribute the reward itself
+    #[returns(VoterProjectedImpactResponse)]
+    VoterProjectedImpact { gauge: u64, voter: String },
+    /// Compares the current selected set with the last executed one
+    #[returns(SetDeltaResponse)]
+    SetDelta { gauge: u64 },
+    /// Returns the sizes `Execute` of the gauge scales with, so keepers can check if
+    /// the execution fits into the block gas limit
+    #[returns(ExecutionComplexityResponse)]
+    ExecutionComplexity { gauge: u64 },
+    /// Returns the messages the gauge's adapters would create for the given set of
+    /// `(option, power)`, without executing them. Only meant for debugging adapters
+    #[returns(SampleGaugeMsgsResponse)]
+    AdapterSampleMsgs {
+        gauge: u64,
+        selected: Vec<(String, Uint128)>,
+    },
+    /// Sums up the projected rewards per option of all active gauges distributing `denom`
+    /// (a native denom or cw20 address) with `to_distribute`, eg. for a treasury view.
+    /// At most 100 gauges are aggregated, they are listed in the response
+    #[returns(AggregateSelectedSetResponse)]
+    AggregateSelectedSet { denom: String },
+    /// Returns how much of the gauge's `total_budget` is distributed and what is left of it
+    #[returns(RemainingBudgetResponse)]
+    RemainingBudget { gauge: u64 },
+    /// Returns the tally an option needs right now to reach `min_percent_selected` and
+    /// `min_absolute_tally`. It is based on the current tallies, so it changes with every vote,
+    /// and an option reaching it can still be left out by `max_options_selected`
+    #[returns(QualificationThresholdResponse)]
+    QualificationThreshold { gauge: u64 },
+    /// Returns all parameters deciding which options are selected on `Execute`, and with which
+    /// weight, for re-implementing the selection off-chain
+    #[returns(SelectionParamsResponse)]
+    SelectionParams { gauge: u64 },
+    /// Histogram of the voters' powers, split into up to `buckets` (at most 100) ranges of equal
+    /// size from zero up to the highest power. Only the first 1000 voters (by address) are counted
+    #[returns(VoteDistributionResponse)]
+    VoteDistribution { gauge: u64, buckets: u32 },
+    /// Returns the options of the gauge the voter gave no weight to, ordered by option.
+    /// Deprecated options are left out, as they cannot be voted for
+    #[returns(UnvotedOptionsResponse)]
+    UnvotedOptions {
+        gauge: u64,
+        voter: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+}
+
+#[cw_serde]
+pub struct ConfigResponse {
+    /// Contract with the voting powers
+    pub voting_powers: String,
+    /// Address that can add new gauges or stop them
+    pub owner: String,
+    /// DAO core module executing the messages
+    pub dao_core: String,
+    /// True if all gauges are paused
+    pub paused: bool,
+    /// Label added to all emitted events
+    pub event_namespace: Option<String>,
+    /// Queries sent to `voting_powers`
+    pub voting_query: VotingQuery,
+    /// Contract name stored with `cw2`
+    pub contract: String,
+    /// Contract version stored with `cw2`, updated on migration
+    pub version: String,
 }
 
 /// Information about one gauge
@@ -131,7 +626,7 @@ pub struct GaugeResponse {
     pub title: String,
     /// Address of contract to serve gauge-specific info (AdapterQueryMsg)
     pub adapter: String,
-    /// Frequency (in seconds) the gauge executes messages, typically something like 7*86400
+    /// Frequency (in `epoch_unit`) the gauge executes messages
     pub epoch_size: u64,
     /// Minimum percentage of votes needed by a given option to be in the selected set.
     /// If unset, there is no minimum percentage, just the `max_options_selected` limit.
@@ -143,10 +638,95 @@ pub struct GaugeResponse {
     pub max_available_percentage: Option<Decimal>,
     /// True if the gauge is stopped
     pub is_stopped: bool,
-    /// UNIX time (seconds) when next epoch may be executed. May be future or past
+    /// True if voting is closed and the gauge will stop after the next execution
+    pub voting_closed: bool,
+    /// True if no new options can be added, see `ExecuteMsg::FreezeOptions`
+    pub options_frozen: bool,
+    /// UNIX time (seconds) or block height (depending on `epoch_unit`) when next epoch
+    /// may be executed. May be future or past
     pub next_epoch: u64,
     /// Set this in migration if the gauge should be periodically reset
     pub reset: Option<Reset>,
+    /// Asset distributed by the gauge itself every epoch, `None` if the adapter creates the messages
+    pub to_distribute: Option<Asset>,
+    /// Schedule of `(epoch, reward)` pairs overriding `to_distribute` from the given epoch on
+    pub reward_schedule: Vec<(u64, Asset)>,
+    /// Number of times the gauge was executed, this is the number of the current epoch.
+    /// New gauges are in epoch 0 until their first `Execute`
+    pub executed_epochs: u64,
+    /// Maximum number of distinct voters, `None` if unlimited
+    pub max_voters: Option<u64>,
+    /// True if voted options are checked with the adapter again when voting
+    pub validate_option_on_vote: bool,
+    /// Restricts who can vote, `None` if everyone with voting power can
+    pub voter_restriction: Option<VoterRestriction>,
+    /// Tenure multiplier applied to voting powers when executing, `None` if not weighted
+    pub tenure_curve: Option<TenureCurve>,
+    /// True if the reward is split equally between the options when executing without any votes
+    pub bootstrap_equal_split: bool,
+    /// True if removing an option also removes it from the votes of its voters
+    pub reclaim_votes_on_remove: bool,
+    /// True if the weights of the selected options are multiplied by the adapter's multipliers
+    pub adapter_multipliers: bool,
+    /// True if the adapter may override the reward of every epoch
+    pub adapter_reward: bool,
+    /// True if failing messages of an execution are recorded instead of reverting it
+    pub isolate_failures: bool,
+    /// Unit of `epoch_size` and `next_epoch`
+    pub epoch_unit: EpochUnit,
+    /// Rounding of the per-option reward amounts
+    pub rounding: Rounding,
+    pub tags: Vec<String>,
+    /// Funds held by the gauge itself, see `FundGauge`
+    pub escrow_balance: Vec<Coin>,
+    pub extra_adapters: Vec<String>,
+    /// Minimum power an option needs to be selected, along with `min_percent_selected`
+    pub min_absolute_tally: Option<Uint128>,
+    pub distribution_mode: DistributionMode,
+    /// Contract boosting the voting powers when executing, `None` if not boosted
+    pub boost_source: Option<String>,
+    /// True if powers are capped at the voter's power when the epoch started
+    pub flashloan_resistant: bool,
+    /// Contract every execution is routed through, `None` if distributed directly
+    pub distribution_hook: Option<String>,
+    /// True if long idle options are removed when executing
+    pub auto_prune_zero_options: bool,
+    /// True if votes must allocate the voter's full power
+    pub require_full_allocation: bool,
+    /// Total amount the gauge may ever distribute, see `RemainingBudget` for what is left
+    pub total_budget: Option<Coin>,
+    /// Fee for changing votes within an epoch, `None` if free
+    pub vote_change_fee: Option<Coin>,
+    pub execute_policy: ExecutePolicy,
+    /// Maximum number of selected options that are paid, `None` if all of them
+    pub max_paid_options: Option<u32>,
+    pub deprecated_vote_policy: DeprecatedVotePolicy,
+    /// Smallest amount paid to an option, `None` if there is no minimum
+    pub min_distribution_per_option: Option<Uint128>,
+    /// Maximum number of options, `None` if unlimited
+    pub max_total_options: Option<u32>,
+    /// Contract notified after every `Execute`
+    pub post_execute_callback: Option<String>,
+    /// Epoch boundaries are multiples of this, `None` if not aligned
+    pub align_to: Option<u64>,
+    /// Maximum share of a single voter in the tallies on `Execute`, `None` if unlimited
+    pub max_voter_influence: Option<Decimal>,
+    /// Oracle price condition for distributing on `Execute`, `None` if unconditional
+    pub execute_condition: Option<ExecuteCondition>,
+    /// Router the reward is swapped with before distribution, `None` if paid as is
+    pub swap_route: Option<SwapRoute>,
+    /// Label of the current epoch, if the owner set one with `SetEpochLabel`
+    pub epoch_label: Option<String>,
+}
+
+#[cw_serde]
+pub struct GaugesForAdapterResponse {
+    pub gauge_ids: Vec<u64>,
+}
+
+#[cw_serde]
+pub struct GaugesForOptionResponse {
+    pub gauge_ids: Vec<u64>,
 }
 
 /// Information about one gauge
@@ -155,6 +735,36 @@ pub struct ListGaugesResponse {
     pub gauges: Vec<GaugeResponse>,
 }
 
+/// Ids of gauges that are due for execution, ordered by id
+#[cw_serde]
+pub struct ExecutableGaugesResponse {
+    pub gauges: Vec<u64>,
+}
+
+#[cw_serde]
+pub struct ExecutableGaugesWithPreviewResponse {
+    pub gauges: Vec<ExecutableGaugePreview>,
+}
+
+#[cw_serde]
+pub struct ExecutableGaugePreview {
+    pub gauge_id: u64,
+    /// Same as the `SelectedSet` of the gauge
+    pub selected_set: Vec<(String, Uint128)>,
+    /// True if nothing would be distributed, executing only starts the next epoch
+    pub is_noop: bool,
+}
+
+#[cw_serde]
+pub struct NextExecutionResponse {
+    /// Either a time or a height, depending on the `epoch_unit` of the gauge
+    pub next: Expiration,
+    /// Number of the epoch executed next, starting at 0. Same as `executed_epochs` of the gauge
+    pub epoch: u64,
+    /// Label of the epoch that is executed next, if the owner set one
+    pub epoch_label: Option<String>,
+}
+
 /// Information about a vote that was cast.
 #[cw_serde]
 pub struct VoteInfo {
@@ -174,18 +784,197 @@ pub struct VoteResponse {
     pub vote: Option<VoteInfo>,
 }
 
+#[cw_serde]
+pub struct CanVoteResponse {
+    pub can_vote: bool,
+    /// The first check that failed, same as the error `PlaceVotes` would return
+    pub reason: Option<String>,
+}
+
+#[cw_serde]
+pub struct VoterMissedGaugesResponse {
+    pub gauge_ids: Vec<u64>,
+}
+
+#[cw_serde]
+pub struct VoterActivityResponse {
+    /// `None` if the voter never voted on this gauge
+    pub last_voted_at: Option<VoterActivity>,
+}
+
+#[cw_serde]
+pub struct RecentVotersResponse {
+    /// Voters along with when they voted last
+    pub voters: Vec<(String, VoterActivity)>,
+}
+
 /// Information about all votes on the gauge
 #[cw_serde]
 pub struct ListVotesResponse {
     pub votes: Vec<VoteInfo>,
 }
 
+#[cw_serde]
+pub struct ChangedVote {
+    pub voter: String,
+    pub votes: Vec<Vote>,
+    /// The voting power behind the vote
+    pub power: Uint128,
+    /// Block the vote was changed at last
+    pub last_updated_block: u64,
+}
+
+#[cw_serde]
+pub struct VotesChangedSinceResponse {
+    pub votes: Vec<ChangedVote>,
+}
+
+/// Participation of the voters in a gauge
+#[cw_serde]
+pub struct ParticipationResponse {
+    /// Total power cast on options (the part that can be selected)
+    pub votes_cast: Uint128,
+    /// Total power that abstained
+    pub abstained: Uint128,
+    /// Total voting power of the DAO
+    pub total_power: Uint128,
+    /// `(votes_cast + abstained) / total_power`, capped at 1.0
+    pub participation: Decimal,
+    /// True if more power was cast than the DAO has in total, which happens if voting powers
+    /// shrank without the gauge being notified. The participation is capped in that case.
+    pub votes_exceed_power: bool,
+}
+
+/// Participation of the voters at past executions of a gauge
+#[cw_serde]
+pub struct ParticipationHistoryResponse {
+    pub history: Vec<EpochParticipation>,
+}
+
+#[cw_serde]
+pub struct RemainingBudgetResponse {
+    /// `None` if the gauge has no budget
+    pub total_budget: Option<Coin>,
+    /// Amount distributed so far
+    pub distributed: Uint128,
+    /// `total_budget - distributed`, `None` if the gauge has no budget
+    pub remaining: Option<Coin>,
+}
+
+#[cw_serde]
+pub struct QualificationThresholdResponse {
+    /// Minimum tally to qualify
+    pub threshold: Uint128,
+    /// Total tally the percentage is applied to, excluding deprecated options
+    pub total_tally: Uint128,
+}
+
+#[cw_serde]
+pub struct SelectionParamsResponse {
+    pub min_percent_selected: Option<Decimal>,
+    pub min_absolute_tally: Option<Uint128>,
+    pub max_options_selected: u32,
+    pub max_available_percentage: Option<Decimal>,
+    pub distribution_mode: DistributionMode,
+    /// Limits the selection is done with, taking the `distribution_mode` into account
+    pub effective_max_options_selected: u32,
+    pub effective_max_available_percentage: Option<Decimal>,
+    /// Number of top selected options that are paid, `None` if all of them
+    pub max_paid_options: Option<u32>,
+    pub deprecated_vote_policy: DeprecatedVotePolicy,
+    /// True if the reward is split equally while nobody voted
+    pub bootstrap_equal_split: bool,
+}
+
+#[cw_serde]
+pub struct UnvotedOptionsResponse {
+    pub options: Vec<String>,
+}
+
+#[cw_serde]
+pub struct VoteDistributionResponse {
+    /// Ordered by power, empty if nobody voted
+    pub buckets: Vec<PowerBucket>,
+    /// True if there are more voters than were counted
+    pub truncated: bool,
+}
+
+#[cw_serde]
+pub struct PowerBucket {
+    /// Lowest power in the bucket
+    pub min: Uint128,
+    /// Highest power in the bucket, inclusive
+    pub max: Uint128,
+    /// Number of voters with a power in this range
+    pub voters: u32,
+}
+
+#[cw_serde]
+pub struct AggregateSelectedSetResponse {
+    /// `(option, projected reward)` summed over all gauges, ordered by option
+    pub rewards: Vec<(String, Uint128)>,
+    /// Gauges included in the sum, ordered by ID
+    pub gauge_ids: Vec<u64>,
+}
+
+#[cw_serde]
+pub struct ExecutionComplexityResponse {
+    /// Number of voters with a current vote
+    pub voter_count: u64,
+    /// Number of options in the gauge
+    pub option_count: u64,
+    /// Estimated number of messages dispatched by `Execute`. One per paid option with
+    /// `isolate_failures`, otherwise they are wrapped into a single proposal hook
+    pub estimated_submsgs: u64,
+}
+
+#[cw_serde]
+pub struct OptionTallyHistoryResponse {
+    pub history: Vec<OptionTallySnapshot>,
+}
+
+#[cw_serde]
+pub struct OptionLifetimeRewardsResponse {
+    /// Total amount received, one entry per denom in the order they were first paid
+    pub rewards: Vec<Asset>,
+}
+
+#[cw_serde]
+pub struct VotesToOvertakeResponse {
+    /// Additional tally `option` needs to have more votes than `target_option`
+    pub votes_needed: Uint128,
+}
+
+/// Voters of one option along with the weight of their vote
+#[cw_serde]
+pub struct OptionVotersResponse {
+    pub voters: Vec<(String, Decimal)>,
+}
+
 /// List all available options ordered by the option string.
 /// Also returns the current voting power assigned to that option.
 /// You will need to paginate to collect them all.
 #[cw_serde]
 pub struct ListOptionsResponse {
     pub options: Vec<(String, Uint128)>,
+    /// Those of `options` that are deprecated and cannot be selected anymore
+    pub deprecated: Vec<String>,
+}
+
+#[cw_serde]
+pub struct OptionLeaderboardResponse {
+    pub options: Vec<LeaderboardEntry>,
+}
+
+#[cw_serde]
+pub struct LeaderboardEntry {
+    /// Position in the leaderboard, starting at 1.
+    /// Options with the same tally share the rank of the first of them
+    pub rank: u32,
+    pub option: String,
+    pub tally: Uint128,
+    /// Share of all votes cast on options
+    pub tally_fraction: Decimal,
 }
 
 /// List the options that were selected in the last executed set.
@@ -193,6 +982,30 @@ pub struct ListOptionsResponse {
 pub struct LastExecutedSetResponse {
     /// `None` if no vote has been executed yet
     pub votes: Option<Vec<(String, Uint128)>>,
+    /// Address which executed the set, `None` before the first execution or if it was executed
+    /// by an older version
+    pub executed_by: Option<Addr>,
+}
+
+#[cw_serde]
+pub struct ShutOutOptionsResponse {
+    /// Number of the last executed epoch, `None` if the gauge was not executed yet
+    /// or its last execution was done by an older version
+    pub epoch: Option<u64>,
+    /// `(option, tally)` in the order of the tallies of the epoch, highest first
+    pub options: Vec<(String, Uint128)>,
+}
+
+#[cw_serde]
+pub struct ExecutionFailuresResponse {
+    /// Sorted by epoch
+    pub failures: Vec<ExecutionFailure>,
+}
+
+#[cw_serde]
+pub struct ExecutionInputsResponse {
+    /// `None` if the epoch was not executed (yet)
+    pub inputs: Option<ExecutionInputs>,
 }
 
 /// List the top options by power that would make it into the selected set.
@@ -202,12 +1015,77 @@ pub struct SelectedSetResponse {
     pub votes: Vec<(String, Uint128)>,
 }
 
+/// Projected reward of one option in the selected set
+#[cw_serde]
+pub struct OptionRewardEfficiency {
+    pub option: String,
+    /// Reward the option would get if the gauge was executed now
+    pub projected_reward: Uint128,
+    /// Total power voted for the option, including power above `max_available_percentage`
+    pub tally: Uint128,
+    /// `projected_reward / tally`
+    pub reward_per_power: Decimal,
+}
+
+/// Projected rewards of the selected set, ordered from highest votes to lowest
+#[cw_serde]
+pub struct RewardEfficiencyResponse {
+    pub options: Vec<OptionRewardEfficiency>,
+}
+
+/// Projected reward of one option a voter votes for
+#[cw_serde]
+pub struct VoterOptionImpact {
+    pub option: String,
+    /// Reward the option would get if the gauge was executed now, zero if it is not selected
+    pub projected_reward: Uint128,
+    /// Power the voter put on this option
+    pub voter_power: Uint128,
+    /// Part of `projected_reward` proportional to `voter_power` in the option's tally
+    pub voter_reward: Uint128,
+}
+
+/// Projected impact of a voter, in the order of their votes
+#[cw_serde]
+pub struct VoterProjectedImpactResponse {
+    pub options: Vec<VoterOptionImpact>,
+}
+
+/// Difference between the last executed and the currently selected power of one option
+#[cw_serde]
+pub struct OptionDelta {
+    pub option: String,
+    /// Power the option had in the last executed set, zero if it was not selected
+    pub last_amount: Uint128,
+    /// Power the option has in the current selected set, zero if it is not selected
+    pub projected_amount: Uint128,
+    /// Absolute difference between `projected_amount` and `last_amount`
+    pub delta: Uint128,
+    /// True if `projected_amount` is lower than `last_amount`
+    pub decreased: bool,
+}
+
+/// List of changes between the last executed set and the current selected set.
+/// Options in the current selected set come first (ordered from highest votes to lowest),
+/// followed by the options that are only in the last executed set.
+#[cw_serde]
+pub struct SetDeltaResponse {
+    pub deltas: Vec<OptionDelta>,
+}
+
 /// Queries the gauge requires from the adapter contract in order to function
 #[cw_serde]
 #[derive(QueryResponses)]
 pub enum AdapterQueryMsg {
+    /// Pagination is optional for adapters. It is only used by `SyncOptions`, and the fields
+    /// are left out if `None`, so adapters without pagination support keep working.
     #[returns(AllOptionsResponse)]
-    AllOptions {},
+    AllOptions {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        start_after: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        limit: Option<u32>,
+    },
     #[returns(CheckOptionResponse)]
     CheckOption { option: String },
     #[returns(SampleGaugeMsgsResponse)]
@@ -216,6 +1094,13 @@ pub enum AdapterQueryMsg {
         /// sum of all weights should be 1.0 (within rounding error)
         selected: Vec<(String, Decimal)>,
     },
+    /// Only used by gauges with `adapter_multipliers` set
+    #[returns(OptionMultipliersResponse)]
+    OptionMultipliers { options: Vec<String> },
+    /// Only used by gauges with `adapter_reward` set.
+    /// `epoch` is the number of the executed epoch, starting at 0
+    #[returns(EpochRewardResponse)]
+    EpochReward { epoch: u64 },
 }
 
 #[cw_serde]
@@ -228,15 +1113,151 @@ pub struct CheckOptionResponse {
     pub valid: bool,
 }
 
+#[cw_serde]
+pub struct OptionMultipliersResponse {
+    /// Multiplier per option, options left out get a multiplier of 1.0
+    pub multipliers: Vec<(String, Decimal)>,
+}
+
+#[cw_serde]
+pub struct EpochRewardResponse {
+    /// Reward to distribute in the epoch, `None` to distribute the gauge's own reward
+    pub reward: Option<Asset>,
+}
+
 #[cw_serde]
 pub struct SampleGaugeMsgsResponse {
     // NOTE: I think we will never need CustomMsg here, any reason we should include??
     pub execute: Vec<CosmosMsg>,
 }
 
+/// Queries the gauge requires from the membership source used for tenure weighting
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum MembershipQueryMsg {
+    #[returns(MemberSinceResponse)]
+    MemberSince { address: String },
+}
+
+#[cw_serde]
+pub struct MemberSinceResponse {
+    /// UNIX time (seconds) since the address is a member, `None` if it is no member
+    pub since: Option<u64>,
+}
+
+/// Queries the gauge requires from a staking contract with `VotingQuery::StakedBalanceAtHeight`
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum StakingQueryMsg {
+    #[returns(StakedBalanceAtHeightResponse)]
+    StakedBalanceAtHeight {
+        address: String,
+        height: Option<u64>,
+    },
+    #[returns(TotalStakedAtHeightResponse)]
+    TotalStakedAtHeight { height: Option<u64> },
+}
+
+#[cw_serde]
+pub struct StakedBalanceAtHeightResponse {
+    pub balance: Uint128,
+    pub height: u64,
+}
+
+#[cw_serde]
+pub struct TotalStakedAtHeightResponse {
+    pub total: Uint128,
+    pub height: u64,
+}
+
+/// Queries the gauge requires from the boost source, eg. a token locking contract
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum BoostQueryMsg {
+    #[returns(BoostResponse)]
+    Boost { address: String },
+}
+
+#[cw_serde]
+pub struct BoostResponse {
+    /// Multiplier applied to the address' voting power, 1.0 for no boost
+    pub multiplier: Decimal,
+}
+
+/// Queries the gauge requires from the oracle of an `ExecuteCondition`
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum OracleQueryMsg {
+    #[returns(PriceResponse)]
+    Price {},
+}
+
+#[cw_serde]
+pub struct PriceResponse {
+    pub price: Decimal,
+}
+
+/// Queries the gauge requires from the router of a `SwapRoute`
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum RouterQueryMsg {
+    #[returns(SimulateSwapResponse)]
+    SimulateSwap { offer: Coin, to_denom: String },
+}
+
+#[cw_serde]
+pub struct SimulateSwapResponse {
+    /// Amount of `to_denom` the offer is swapped for
+    pub return_amount: Uint128,
+}
+
+/// Message the DAO core sends to the router of a `SwapRoute` along with the reward.
+/// The router sends the swapped funds back to the sender
+#[cw_serde]
+pub enum RouterExecuteMsg {
+    Swap {
+        to_denom: String,
+        minimum_receive: Uint128,
+    },
+}
+
+/// Message the gauge sends to its distribution hook on every execution
+#[cw_serde]
+pub enum DistributionHookMsg {
+    Distribute {
+        gauge_id: GaugeId,
+        /// Number of the executed epoch, starting at 0
+        epoch: u64,
+        /// All selected options with their weight, summing up to 1.0
+        selected: Vec<(String, Decimal)>,
+    },
+}
+
+/// Message the gauge sends to its `post_execute_callback` after every execution
+#[cw_serde]
+pub enum PostExecuteCallbackMsg {
+    GaugeExecuted {
+        gauge_id: GaugeId,
+        /// Number of the executed epoch, starting at 0
+        epoch: u64,
+        /// Options paid in this epoch with their weight, summing up to 1.0
+        selected: Vec<(String, Decimal)>,
+    },
+}
+
 #[cw_serde]
 pub struct MigrateMsg {
     pub gauge_config: Option<Vec<(GaugeId, GaugeMigrationConfig)>>,
+    /// If set, adds this many of the existing votes to the voter-by-option index.
+    /// Migrate repeatedly (with the same code) until the `finished` attribute is `true`
+    /// to index all votes of older versions.
+    #[serde(default)]
+    pub backfill_vote_index: Option<u32>,
+    /// If set, adds this many of the existing options to the gauge-by-option index and the
+    /// insertion order of `ListOptions`.
+    /// Like `backfill_vote_index`, migrate repeatedly until `options_finished` is `true`.
+    #[serde(default)]
+    pub backfill_option_index: Option<u32>,
 }
 
 #[cw_serde]
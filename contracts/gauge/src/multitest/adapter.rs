@@ -8,12 +8,14 @@ use cosmwasm_std::{
     Order, Response, StdError, StdResult,
 };
 use cw_multi_test::{Contract, ContractWrapper};
-use cw_storage_plus::{Item, Map};
+use cw_storage_plus::{Bound, Item, Map};
 use serde::{Deserialize, Serialize};
 
 use crate::msg::{
-    AdapterQueryMsg, AllOptionsResponse, CheckOptionResponse, SampleGaugeMsgsResponse,
+    AdapterQueryMsg, AllOptionsResponse, CheckOptionResponse, EpochRewardResponse,
+    OptionMultipliersResponse, SampleGaugeMsgsResponse,
 };
+use crate::state::Asset;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct InstantiateMsg {
@@ -25,6 +27,8 @@ pub struct InstantiateMsg {
 pub enum ExecuteMsg {
     InvalidateOption { option: String },
     AddValidOption { option: String },
+    SetMultiplier { option: String, multiplier: Decimal },
+    SetEpochReward { epoch: u64, reward: Asset },
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -32,6 +36,8 @@ struct EmptyMsg {}
 
 const OPTIONS: Map<String, bool> = Map::new("options");
 const TO_DISTRIBUTE: Item<Coin> = Item::new("to_spend");
+const MULTIPLIERS: Map<String, Decimal> = Map::new("multipliers");
+const EPOCH_REWARDS: Map<u64, Asset> = Map::new("epoch_rewards");
 
 fn instantiate(
     deps: DepsMut,
@@ -59,20 +65,47 @@ fn execute(
         ExecuteMsg::AddValidOption { option } => {
             OPTIONS.save(deps.storage, option, &true)?;
         }
+        ExecuteMsg::SetMultiplier { option, multiplier } => {
+            MULTIPLIERS.save(deps.storage, option, &multiplier)?;
+        }
+        ExecuteMsg::SetEpochReward { epoch, reward } => {
+            EPOCH_REWARDS.save(deps.storage, epoch, &reward)?;
+        }
     }
     Ok(Response::new())
 }
 
 fn query(deps: Deps, _env: Env, msg: AdapterQueryMsg) -> Result<Binary, StdError> {
     match msg {
-        AdapterQueryMsg::AllOptions {} => to_binary(&AllOptionsResponse {
+        AdapterQueryMsg::AllOptions { start_after, limit } => to_binary(&AllOptionsResponse {
             options: OPTIONS
-                .keys(deps.storage, None, None, Order::Ascending)
+                .keys(
+                    deps.storage,
+                    start_after.map(Bound::exclusive),
+                    None,
+                    Order::Ascending,
+                )
+                .take(limit.map(|l| l as usize).unwrap_or(usize::MAX))
                 .collect::<StdResult<Vec<_>>>()?,
         }),
         AdapterQueryMsg::CheckOption { option } => to_binary(&CheckOptionResponse {
             valid: OPTIONS.has(deps.storage, option),
         }),
+        AdapterQueryMsg::OptionMultipliers { options } => to_binary(&OptionMultipliersResponse {
+            multipliers: options
+                .into_iter()
+                .map(|option| {
+                    let multiplier = MULTIPLIERS.may_load(deps.storage, option.clone())?;
+                    Ok(multiplier.map(|multiplier| (option, multiplier)))
+                })
+                .collect::<StdResult<Vec<_>>>()?
+                .into_iter()
+                .flatten()
+                .collect(),
+        }),
+        AdapterQueryMsg::EpochReward { epoch } => to_binary(&EpochRewardResponse {
+            reward: EPOCH_REWARDS.may_load(deps.storage, epoch)?,
+        }),
         AdapterQueryMsg::SampleGaugeMsgs { selected } => {
             let to_distribute = TO_DISTRIBUTE.load(deps.storage)?;
             let mut weights_sum = Decimal::zero();
@@ -0,0 +1,51 @@
+//! Boost source contract to mock in tests.
+//! InstantiateMsg contains the boosted addresses along with their multiplier.
+
+use cosmwasm_std::{
+    to_binary, Binary, Decimal, Deps, DepsMut, Empty, Env, MessageInfo, Response, StdError,
+    StdResult,
+};
+use cw_multi_test::{Contract, ContractWrapper};
+use cw_storage_plus::Map;
+use serde::{Deserialize, Serialize};
+
+use crate::msg::{BoostQueryMsg, BoostResponse};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InstantiateMsg {
+    /// `(address, multiplier)` pairs, everyone else has no boost
+    pub boosts: Vec<(String, Decimal)>,
+}
+
+const BOOSTS: Map<String, Decimal> = Map::new("boosts");
+
+fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, StdError> {
+    msg.boosts
+        .into_iter()
+        .try_for_each(|(address, multiplier)| BOOSTS.save(deps.storage, address, &multiplier))?;
+    Ok(Response::default())
+}
+
+fn execute(_deps: DepsMut, _env: Env, _info: MessageInfo, _msg: Empty) -> StdResult<Response> {
+    Ok(Response::new())
+}
+
+fn query(deps: Deps, _env: Env, msg: BoostQueryMsg) -> Result<Binary, StdError> {
+    match msg {
+        BoostQueryMsg::Boost { address } => to_binary(&BoostResponse {
+            multiplier: BOOSTS
+                .may_load(deps.storage, address)?
+                .unwrap_or_else(Decimal::one),
+        }),
+    }
+}
+
+pub fn contract() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new_with_empty(execute, instantiate, query);
+    Box::new(contract)
+}
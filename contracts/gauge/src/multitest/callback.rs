@@ -0,0 +1,64 @@
+//! Post execute callback contract to mock in tests.
+//! It records every notification it receives, or fails if instantiated with `fail: true`.
+
+use cosmwasm_std::{
+    to_binary, Binary, Deps, DepsMut, Empty, Env, MessageInfo, Response, StdError, StdResult,
+};
+use cw_multi_test::{Contract, ContractWrapper};
+use cw_storage_plus::Item;
+use serde::{Deserialize, Serialize};
+
+use crate::msg::PostExecuteCallbackMsg;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InstantiateMsg {
+    pub fail: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    /// Returns all received notifications as `Vec<PostExecuteCallbackMsg>`
+    Notifications {},
+}
+
+const FAIL: Item<bool> = Item::new("fail");
+const NOTIFICATIONS: Item<Vec<PostExecuteCallbackMsg>> = Item::new("notifications");
+
+fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, StdError> {
+    FAIL.save(deps.storage, &msg.fail)?;
+    NOTIFICATIONS.save(deps.storage, &vec![])?;
+    Ok(Response::default())
+}
+
+fn execute(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: PostExecuteCallbackMsg,
+) -> StdResult<Response> {
+    if FAIL.load(deps.storage)? {
+        return Err(StdError::generic_err("callback failed"));
+    }
+    NOTIFICATIONS.update(deps.storage, |mut notifications| -> StdResult<_> {
+        notifications.push(msg);
+        Ok(notifications)
+    })?;
+    Ok(Response::new())
+}
+
+fn query(deps: Deps, _env: Env, msg: QueryMsg) -> Result<Binary, StdError> {
+    match msg {
+        QueryMsg::Notifications {} => to_binary(&NOTIFICATIONS.load(deps.storage)?),
+    }
+}
+
+pub fn contract() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new_with_empty(execute, instantiate, query);
+    Box::new(contract)
+}
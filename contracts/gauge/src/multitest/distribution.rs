@@ -0,0 +1,1862 @@
+use cosmwasm_std::{coin, coins, Addr, BankMsg, CosmosMsg, Decimal, Event, Uint128};
+
+use super::suite::{init_gauge, SuiteBuilder};
+use crate::error::ContractError;
+use crate::msg::{
+    GaugeConfig, OptionRewardEfficiency, PostExecuteCallbackMsg, RemainingBudgetResponse,
+    VoterOptionImpact,
+};
+use crate::state::{Asset, DistributionMode, ExecuteCondition, Rounding, SwapRoute};
+
+const EPOCH: u64 = 7 * 86_400;
+
+#[test]
+fn distribute_cw20_reward() {
+    let voter1 = "voter1";
+    let voter2 = "voter2";
+    let mut suite = SuiteBuilder::new()
+        .with_voting_members(&[(voter1, 100), (voter2, 100)])
+        .build();
+
+    let gauge_contract = init_gauge(&mut suite, &[voter1, voter2]);
+
+    // reward token is held by the DAO core, which executes the transfers
+    let core = suite.core.to_string();
+    let token = suite.instantiate_cw20(&[(core.as_str(), 2000)]).unwrap();
+
+    let mut gauge_config = suite
+        .instantiate_adapter_and_return_config(&[voter1, voter2], (0, "ujuno"), None, None)
+        .unwrap();
+    gauge_config.to_distribute = Some(Asset::new_cw20(token.as_str(), 1000));
+    suite.create_gauge(&gauge_contract, gauge_config).unwrap();
+    let gauge_id = 0;
+
+    suite
+        .place_votes(
+            &gauge_contract,
+            voter1,
+            gauge_id,
+            vec![
+                (voter1.to_owned(), Decimal::percent(60)),
+                (voter2.to_owned(), Decimal::percent(40)),
+            ],
+        )
+        .unwrap();
+    suite
+        .place_vote(&gauge_contract, voter2, gauge_id, Some(voter2.to_owned()))
+        .unwrap();
+
+    suite.advance_time(EPOCH);
+    suite
+        .execute_options(&gauge_contract, voter1, gauge_id)
+        .unwrap();
+
+    // voter1 option got 60 out of 200 votes, voter2 option 140 out of 200
+    assert_eq!(suite.query_cw20_balance(&token, voter1).unwrap(), 300);
+    assert_eq!(suite.query_cw20_balance(&token, voter2).unwrap(), 700);
+    assert_eq!(suite.query_cw20_balance(&token, &core).unwrap(), 1000);
+}
+
+#[test]
+fn distribute_native_reward_by_gauge() {
+    let voter1 = "voter1";
+    let voter2 = "voter2";
+    let mut suite = SuiteBuilder::new()
+        .with_voting_members(&[(voter1, 100), (voter2, 300)])
+        .with_core_balance((1000, "ujuno"))
+        .build();
+
+    let gauge_contract = init_gauge(&mut suite, &[voter1, voter2]);
+
+    let mut gauge_config = suite
+        .instantiate_adapter_and_return_config(&[voter1, voter2], (0, "ujuno"), None, None)
+        .unwrap();
+    gauge_config.to_distribute = Some(Asset::new_native("ujuno", 1000));
+    suite.create_gauge(&gauge_contract, gauge_config).unwrap();
+    let gauge_id = 0;
+
+    suite
+        .place_vote(&gauge_contract, voter1, gauge_id, Some(voter1.to_owned()))
+        .unwrap();
+    suite
+        .place_vote(&gauge_contract, voter2, gauge_id, Some(voter2.to_owned()))
+        .unwrap();
+
+    suite.advance_time(EPOCH);
+    suite
+        .execute_options(&gauge_contract, voter1, gauge_id)
+        .unwrap();
+
+    assert_eq!(suite.query_balance(voter1, "ujuno").unwrap(), 250);
+    assert_eq!(suite.query_balance(voter2, "ujuno").unwrap(), 750);
+}
+
+#[test]
+fn reward_schedule_changes_amount_per_epoch() {
+    let voter1 = "voter1";
+    let mut suite = SuiteBuilder::new()
+        .with_voting_members(&[(voter1, 100)])
+        .with_core_balance((10_000, "ujuno"))
+        .build();
+
+    let gauge_contract = init_gauge(&mut suite, &[voter1]);
+
+    let mut gauge_config = suite
+        .instantiate_adapter_and_return_config(&[voter1], (0, "ujuno"), None, None)
+        .unwrap();
+    gauge_config.to_distribute = Some(Asset::new_native("ujuno", 1000));
+
+    // schedule has to be sorted
+    let bad_config = GaugeConfig {
+        reward_schedule: Some(vec![
+            (3, Asset::new_native("ujuno", 500)),
+            (1, Asset::new_native("ujuno", 200)),
+        ]),
+        ..gauge_config.clone()
+    };
+    let err = suite.create_gauge(&gauge_contract, bad_config).unwrap_err();
+    assert_eq!(
+        ContractError::UnsortedRewardSchedule {},
+        err.downcast().unwrap()
+    );
+
+    // 1000 per epoch for the first two epochs, then 500
+    gauge_config.reward_schedule = Some(vec![(2, Asset::new_native("ujuno", 500))]);
+    suite.create_gauge(&gauge_contract, gauge_config).unwrap();
+    let gauge_id = 0;
+
+    suite
+        .place_vote(&gauge_contract, voter1, gauge_id, Some(voter1.to_owned()))
+        .unwrap();
+
+    let mut received = vec![];
+    for _ in 0..4 {
+        suite.advance_time(EPOCH);
+        suite
+            .execute_options(&gauge_contract, voter1, gauge_id)
+            .unwrap();
+        received.push(suite.query_balance(voter1, "ujuno").unwrap());
+    }
+    assert_eq!(received, vec![1000, 2000, 2500, 3000]);
+
+    let gauge = suite.query_gauge(gauge_contract, gauge_id).unwrap();
+    assert_eq!(gauge.executed_epochs, 4);
+}
+
+#[test]
+fn reward_efficiency_per_option() {
+    let voter1 = "voter1";
+    let voter2 = "voter2";
+    let mut suite = SuiteBuilder::new()
+        .with_voting_members(&[(voter1, 100), (voter2, 300)])
+        .build();
+
+    let gauge_contract = init_gauge(&mut suite, &[voter1, voter2]);
+
+    // votes above 50% are discarded
+    let mut gauge_config = suite
+        .instantiate_adapter_and_return_config(
+            &[voter1, voter2],
+            (0, "ujuno"),
+            Decimal::percent(50),
+            None,
+        )
+        .unwrap();
+    gauge_config.to_distribute = Some(Asset::new_native("ujuno", 1000));
+    suite.create_gauge(&gauge_contract, gauge_config).unwrap();
+    let gauge_id = 0;
+
+    suite
+        .place_vote(&gauge_contract, voter1, gauge_id, Some(voter1.to_owned()))
+        .unwrap();
+    suite
+        .place_vote(&gauge_contract, voter2, gauge_id, Some(voter2.to_owned()))
+        .unwrap();
+
+    // voter2 option is capped at 200 power, so it gets 2/3 of the reward for 300 votes
+    assert_eq!(
+        suite
+            .query_reward_efficiency(&gauge_contract, gauge_id)
+            .unwrap(),
+        vec![
+            OptionRewardEfficiency {
+                option: voter2.to_owned(),
+                projected_reward: Uint128::new(666),
+                tally: Uint128::new(300),
+                reward_per_power: Decimal::percent(222),
+            },
+            OptionRewardEfficiency {
+                option: voter1.to_owned(),
+                projected_reward: Uint128::new(333),
+                tally: Uint128::new(100),
+                reward_per_power: Decimal::percent(333),
+            },
+        ]
+    );
+}
+
+#[test]
+fn bootstrap_equal_split_without_votes() {
+    let voter1 = "voter1";
+    let options = ["option1", "option2", "option3"];
+    let mut suite = SuiteBuilder::new()
+        .with_voting_members(&[(voter1, 100)])
+        .with_core_balance((900, "ujuno"))
+        .build();
+
+    let gauge_contract = init_gauge(&mut suite, &[voter1]);
+
+    let mut gauge_config = suite
+        .instantiate_adapter_and_return_config(&options, (0, "ujuno"), None, None)
+        .unwrap();
+    gauge_config.to_distribute = Some(Asset::new_native("ujuno", 900));
+    gauge_config.bootstrap_equal_split = true;
+    suite.create_gauge(&gauge_contract, gauge_config).unwrap();
+    let gauge_id = 0;
+
+    // nobody votes
+    suite.advance_time(EPOCH);
+    suite
+        .execute_options(&gauge_contract, voter1, gauge_id)
+        .unwrap();
+
+    for option in options {
+        assert_eq!(suite.query_balance(option, "ujuno").unwrap(), 300);
+    }
+    assert_eq!(
+        suite.query_balance(suite.core.as_str(), "ujuno").unwrap(),
+        0
+    );
+}
+
+#[test]
+fn adapter_multiplier_boosts_option_share() {
+    let voter1 = "voter1";
+    let voter2 = "voter2";
+    let mut suite = SuiteBuilder::new()
+        .with_voting_members(&[(voter1, 100), (voter2, 100)])
+        .with_core_balance((1000, "ujuno"))
+        .build();
+
+    let gauge_contract = init_gauge(&mut suite, &[voter1, voter2]);
+
+    let mut gauge_config = suite
+        .instantiate_adapter_and_return_config(&[voter1, voter2], (0, "ujuno"), None, None)
+        .unwrap();
+    gauge_config.to_distribute = Some(Asset::new_native("ujuno", 1000));
+    gauge_config.adapter_multipliers = true;
+    let adapter = Addr::unchecked(&gauge_config.adapter);
+    suite.create_gauge(&gauge_contract, gauge_config).unwrap();
+    let gauge_id = 0;
+
+    // voter2 option is a strategic one
+    suite
+        .set_option_multiplier(&adapter, voter2, Decimal::percent(150))
+        .unwrap();
+
+    suite
+        .place_vote(&gauge_contract, voter1, gauge_id, Some(voter1.to_owned()))
+        .unwrap();
+    suite
+        .place_vote(&gauge_contract, voter2, gauge_id, Some(voter2.to_owned()))
+        .unwrap();
+
+    suite.advance_time(EPOCH);
+    suite
+        .execute_options(&gauge_contract, voter1, gauge_id)
+        .unwrap();
+
+    // equal votes, but 0.5 * 1.5 against 0.5 * 1.0, normalized to the whole reward
+    let reward1 = suite.query_balance(voter1, "ujuno").unwrap();
+    let reward2 = suite.query_balance(voter2, "ujuno").unwrap();
+    assert_eq!(reward1, 400);
+    assert_eq!(reward2, 600);
+    assert_eq!(reward1 + reward2, 1000);
+}
+
+#[test]
+fn adapter_overrides_epoch_reward() {
+    let voter1 = "voter1";
+    let voter2 = "voter2";
+    let funder = "funder";
+    let mut suite = SuiteBuilder::new()
+        .with_voting_members(&[(voter1, 100), (voter2, 300)])
+        .with_core_balance((1000, "ujuno"))
+        .with_balance(funder, (400, "uwynd"))
+        .build();
+
+    let gauge_contract = init_gauge(&mut suite, &[voter1, voter2]);
+
+    let mut gauge_config = suite
+        .instantiate_adapter_and_return_config(&[voter1, voter2], (0, "ujuno"), None, None)
+        .unwrap();
+    gauge_config.adapter_reward = true;
+    let err = suite
+        .create_gauge(&gauge_contract, gauge_config.clone())
+        .unwrap_err();
+    assert_eq!(
+        ContractError::AdapterRewardWithoutDistribution {},
+        err.downcast().unwrap()
+    );
+    gauge_config.to_distribute = Some(Asset::new_native("ujuno", 1000));
+    let adapter = Addr::unchecked(&gauge_config.adapter);
+    suite.create_gauge(&gauge_contract, gauge_config).unwrap();
+    let gauge_id = 0;
+
+    // the bootstrap token is replaced by the final one from the second epoch on
+    suite
+        .set_adapter_epoch_reward(&adapter, 1, Asset::new_native("uwynd", 400))
+        .unwrap();
+    suite
+        .set_adapter_epoch_reward(&adapter, 2, Asset::new_native("uwynd", 400))
+        .unwrap();
+
+    suite
+        .place_vote(&gauge_contract, voter1, gauge_id, Some(voter1.to_owned()))
+        .unwrap();
+    suite
+        .place_vote(&gauge_contract, voter2, gauge_id, Some(voter2.to_owned()))
+        .unwrap();
+
+    // no reward from the adapter, the gauge's own reward is distributed
+    suite.advance_time(EPOCH);
+    suite
+        .execute_options(&gauge_contract, voter1, gauge_id)
+        .unwrap();
+    assert_eq!(suite.query_balance(voter1, "ujuno").unwrap(), 250);
+    assert_eq!(suite.query_balance(voter2, "ujuno").unwrap(), 750);
+
+    // the DAO core doesn't hold the new token yet
+    suite.advance_time(EPOCH);
+    let err = suite
+        .execute_options(&gauge_contract, voter1, gauge_id)
+        .unwrap_err();
+    assert_eq!(
+        ContractError::AdapterRewardUnavailable {
+            denom: "uwynd".to_owned(),
+            amount: Uint128::new(400),
+            available: Uint128::zero(),
+        },
+        err.downcast().unwrap()
+    );
+
+    let core = suite.core.clone();
+    suite
+        .app
+        .send_tokens(Addr::unchecked(funder), core, &coins(400, "uwynd"))
+        .unwrap();
+    suite
+        .execute_options(&gauge_contract, voter1, gauge_id)
+        .unwrap();
+    assert_eq!(suite.query_balance(voter1, "uwynd").unwrap(), 100);
+    assert_eq!(suite.query_balance(voter2, "uwynd").unwrap(), 300);
+    // nothing more of the old token was distributed
+    assert_eq!(suite.query_balance(voter1, "ujuno").unwrap(), 250);
+
+    let inputs = suite
+        .query_execution_inputs(&gauge_contract, gauge_id, 1)
+        .unwrap()
+        .unwrap();
+    assert_eq!(inputs.to_distribute, Some(Asset::new_native("uwynd", 400)));
+}
+
+#[test]
+fn isolated_payout_failure_does_not_block_others() {
+    let voter1 = "voter1";
+    let voter2 = "voter2";
+    // not enough to pay both options
+    let mut suite = SuiteBuilder::new()
+        .with_voting_members(&[(voter1, 100), (voter2, 150)])
+        .with_core_balance((700, "ujuno"))
+        .build();
+
+    let gauge_contract = init_gauge(&mut suite, &[voter1, voter2]);
+
+    let mut gauge_config = suite
+        .instantiate_adapter_and_return_config(&[voter1, voter2], (0, "ujuno"), None, None)
+        .unwrap();
+    gauge_config.to_distribute = Some(Asset::new_native("ujuno", 1000));
+    gauge_config.isolate_failures = true;
+    suite.create_gauge(&gauge_contract, gauge_config).unwrap();
+    let gauge_id = 0;
+
+    suite
+        .place_vote(&gauge_contract, voter1, gauge_id, Some(voter1.to_owned()))
+        .unwrap();
+    suite
+        .place_vote(&gauge_contract, voter2, gauge_id, Some(voter2.to_owned()))
+        .unwrap();
+
+    suite.advance_time(EPOCH);
+    let res = suite
+        .execute_options(&gauge_contract, voter1, gauge_id)
+        .unwrap();
+    assert!(res.has_event(&Event::new("wasm").add_attribute("action", "execution_failure")));
+
+    // voter2 option is paid first, there is not enough left for voter1 option
+    assert_eq!(suite.query_balance(voter2, "ujuno").unwrap(), 600);
+    assert_eq!(suite.query_balance(voter1, "ujuno").unwrap(), 0);
+    assert_eq!(
+        suite.query_balance(suite.core.as_str(), "ujuno").unwrap(),
+        100
+    );
+
+    let failures = suite
+        .query_execution_failures(&gauge_contract, gauge_id)
+        .unwrap();
+    assert_eq!(failures.len(), 1);
+    assert_eq!(failures[0].epoch, 0);
+    assert_eq!(
+        failures[0].msg,
+        CosmosMsg::Bank(BankMsg::Send {
+            to_address: voter1.to_owned(),
+            amount: coins(400, "ujuno"),
+        })
+    );
+
+    // the epoch is executed nevertheless
+    let gauge = suite.query_gauge(gauge_contract, gauge_id).unwrap();
+    assert_eq!(gauge.executed_epochs, 1);
+}
+
+#[test]
+fn failed_isolated_payout_is_not_recorded() {
+    let voter1 = "voter1";
+    let voter2 = "voter2";
+    // not enough to pay both options
+    let mut suite = SuiteBuilder::new()
+        .with_voting_members(&[(voter1, 100), (voter2, 150)])
+        .with_core_balance((700, "ujuno"))
+        .build();
+
+    let gauge_contract = init_gauge(&mut suite, &[voter1, voter2]);
+
+    let mut gauge_config = suite
+        .instantiate_adapter_and_return_config(&[voter1, voter2], (0, "ujuno"), None, None)
+        .unwrap();
+    gauge_config.to_distribute = Some(Asset::new_native("ujuno", 1000));
+    gauge_config.total_budget = Some(coin(1000, "ujuno"));
+    gauge_config.isolate_failures = true;
+    suite.create_gauge(&gauge_contract, gauge_config).unwrap();
+    let gauge_id = 0;
+
+    suite
+        .place_vote(&gauge_contract, voter1, gauge_id, Some(voter1.to_owned()))
+        .unwrap();
+    suite
+        .place_vote(&gauge_contract, voter2, gauge_id, Some(voter2.to_owned()))
+        .unwrap();
+
+    suite.advance_time(EPOCH);
+    suite
+        .execute_options(&gauge_contract, voter1, gauge_id)
+        .unwrap();
+    assert_eq!(suite.query_balance(voter1, "ujuno").unwrap(), 0);
+
+    // only what was actually sent counts as paid
+    assert!(suite
+        .query_option_lifetime_rewards(&gauge_contract, gauge_id, voter1)
+        .unwrap()
+        .is_empty());
+    assert_eq!(
+        suite
+            .query_option_lifetime_rewards(&gauge_contract, gauge_id, voter2)
+            .unwrap(),
+        vec![Asset::new_native("ujuno", 600)]
+    );
+    assert_eq!(
+        suite
+            .query_remaining_budget(&gauge_contract, gauge_id)
+            .unwrap()
+            .distributed,
+        Uint128::new(600)
+    );
+    // the budget is not used up, so the gauge keeps running
+    assert!(
+        !suite
+            .query_gauge(gauge_contract.clone(), gauge_id)
+            .unwrap()
+            .is_stopped
+    );
+    assert_eq!(
+        suite
+            .query_shut_out_options(&gauge_contract, gauge_id)
+            .unwrap()
+            .options,
+        vec![(voter1.to_owned(), Uint128::new(100))]
+    );
+}
+
+/// Runs one execution distributing `reward` ujuno to options voted by voters with the given powers,
+/// returns what each voter's option received
+fn distribute_with_rounding(powers: &[u64], reward: u128, rounding: Rounding) -> Vec<u128> {
+    let voters: Vec<String> = (0..powers.len()).map(|i| format!("voter{}", i)).collect();
+    let members: Vec<(&str, u64)> = voters
+        .iter()
+        .map(String::as_str)
+        .zip(powers.iter().copied())
+        .collect();
+    let options: Vec<&str> = voters.iter().map(String::as_str).collect();
+    let mut suite = SuiteBuilder::new()
+        .with_voting_members(&members)
+        .with_core_balance((reward, "ujuno"))
+        .build();
+
+    let gauge_contract = init_gauge(&mut suite, &options);
+
+    let mut gauge_config = suite
+        .instantiate_adapter_and_return_config(&options, (0, "ujuno"), None, None)
+        .unwrap();
+    gauge_config.to_distribute = Some(Asset::new_native("ujuno", reward));
+    gauge_config.min_percent_selected = None;
+    gauge_config.rounding = rounding;
+    suite.create_gauge(&gauge_contract, gauge_config).unwrap();
+    let gauge_id = 0;
+
+    for voter in &voters {
+        suite
+            .place_vote(&gauge_contract, voter, gauge_id, Some(voter.clone()))
+            .unwrap();
+    }
+
+    suite.advance_time(EPOCH);
+    suite
+        .execute_options(&gauge_contract, &voters[0], gauge_id)
+        .unwrap();
+
+    voters
+        .iter()
+        .map(|voter| suite.query_balance(voter, "ujuno").unwrap())
+        .collect()
+}
+
+#[test]
+fn rounding_never_exceeds_reward() {
+    // 666.67 and 333.33
+    assert_eq!(
+        distribute_with_rounding(&[200, 100], 1000, Rounding::Down),
+        vec![666, 333]
+    );
+    assert_eq!(
+        distribute_with_rounding(&[200, 100], 1000, Rounding::Nearest),
+        vec![667, 333]
+    );
+
+    // 0.67 each, rounding all of them up would pay out 3
+    assert_eq!(
+        distribute_with_rounding(&[100, 100, 100], 2, Rounding::Down),
+        vec![0, 0, 0]
+    );
+    let received = distribute_with_rounding(&[100, 100, 100], 2, Rounding::Nearest);
+    assert_eq!(received.iter().sum::<u128>(), 2);
+
+    // 0.6 each, the overshoot is bigger than the top share
+    let received = distribute_with_rounding(&[100; 5], 3, Rounding::Nearest);
+    assert_eq!(received.iter().sum::<u128>(), 3);
+    assert!(received.iter().all(|&r| r <= 1));
+
+    for rounding in [Rounding::Down, Rounding::Nearest] {
+        let received = distribute_with_rounding(&[7, 11, 13, 17], 1001, rounding);
+        assert!(received.iter().sum::<u128>() <= 1001);
+    }
+}
+
+#[test]
+fn distribute_from_gauge_escrow() {
+    let voter1 = "voter1";
+    let voter2 = "voter2";
+    let funder = "funder";
+    let mut suite = SuiteBuilder::new()
+        .with_voting_members(&[(voter1, 100), (voter2, 300)])
+        .with_core_balance((1000, "ujuno"))
+        .with_balance(funder, (1500, "ujuno"))
+        .build();
+
+    let gauge_contract = init_gauge(&mut suite, &[voter1, voter2]);
+
+    let mut gauge_config = suite
+        .instantiate_adapter_and_return_config(&[voter1, voter2], (0, "ujuno"), None, None)
+        .unwrap();
+    gauge_config.to_distribute = Some(Asset::new_native("ujuno", 1000));
+    suite.create_gauge(&gauge_contract, gauge_config).unwrap();
+    let gauge_id = 0;
+
+    let err = suite
+        .fund_gauge(funder, &gauge_contract, gauge_id, &[])
+        .unwrap_err();
+    assert_eq!(ContractError::NoFunds {}, err.downcast().unwrap());
+    let err = suite
+        .fund_gauge(funder, &gauge_contract, 1, &coins(100, "ujuno"))
+        .unwrap_err();
+    assert_eq!(
+        ContractError::GaugeNotFound { gauge: 1 },
+        err.downcast().unwrap()
+    );
+
+    suite
+        .fund_gauge(funder, &gauge_contract, gauge_id, &coins(1500, "ujuno"))
+        .unwrap();
+    let gauge = suite.query_gauge(gauge_contract.clone(), gauge_id).unwrap();
+    assert_eq!(gauge.escrow_balance, coins(1500, "ujuno"));
+
+    suite
+        .place_vote(&gauge_contract, voter1, gauge_id, Some(voter1.to_owned()))
+        .unwrap();
+    suite
+        .place_vote(&gauge_contract, voter2, gauge_id, Some(voter2.to_owned()))
+        .unwrap();
+
+    // the escrow covers the reward, the core balance is not touched
+    suite.advance_time(EPOCH);
+    let res = suite
+        .execute_options(&gauge_contract, voter1, gauge_id)
+        .unwrap();
+    assert!(res.has_event(&Event::new("wasm").add_attribute("paid_from", "escrow")));
+    assert_eq!(suite.query_balance(voter1, "ujuno").unwrap(), 250);
+    assert_eq!(suite.query_balance(voter2, "ujuno").unwrap(), 750);
+    assert_eq!(
+        suite.query_balance(suite.core.as_str(), "ujuno").unwrap(),
+        1000
+    );
+    assert_eq!(
+        suite
+            .query_balance(gauge_contract.as_str(), "ujuno")
+            .unwrap(),
+        500
+    );
+    let gauge = suite.query_gauge(gauge_contract.clone(), gauge_id).unwrap();
+    assert_eq!(gauge.escrow_balance, coins(500, "ujuno"));
+
+    // not enough left in the escrow, so the core pays
+    suite.advance_time(EPOCH);
+    suite
+        .execute_options(&gauge_contract, voter1, gauge_id)
+        .unwrap();
+    assert_eq!(suite.query_balance(voter1, "ujuno").unwrap(), 500);
+    assert_eq!(suite.query_balance(voter2, "ujuno").unwrap(), 1500);
+    assert_eq!(
+        suite.query_balance(suite.core.as_str(), "ujuno").unwrap(),
+        0
+    );
+    let gauge = suite.query_gauge(gauge_contract, gauge_id).unwrap();
+    assert_eq!(gauge.escrow_balance, coins(500, "ujuno"));
+}
+
+#[test]
+fn multiple_adapters_distribute_selected_set() {
+    let voter1 = "voter1";
+    let voter2 = "voter2";
+    let mut suite = SuiteBuilder::new()
+        .with_voting_members(&[(voter1, 100), (voter2, 300)])
+        .with_core_balance((1500, "ujuno"))
+        .build();
+
+    let gauge_contract = init_gauge(&mut suite, &[voter1, voter2]);
+
+    // eg. a bank payout and an incentive adapter, both paying out of the DAO core
+    let lp_adapter = suite
+        .instantiate_adapter(&[voter1, voter2], (500, "ujuno"))
+        .unwrap();
+    let mut gauge_config = suite
+        .instantiate_adapter_and_return_config(&[voter1, voter2], (1000, "ujuno"), None, None)
+        .unwrap();
+    gauge_config.extra_adapters = vec![lp_adapter.to_string()];
+    suite.create_gauge(&gauge_contract, gauge_config).unwrap();
+    let gauge_id = 0;
+
+    let gauge = suite.query_gauge(gauge_contract.clone(), gauge_id).unwrap();
+    assert_eq!(gauge.extra_adapters, vec![lp_adapter.to_string()]);
+
+    suite
+        .place_vote(&gauge_contract, voter1, gauge_id, Some(voter1.to_owned()))
+        .unwrap();
+    suite
+        .place_vote(&gauge_contract, voter2, gauge_id, Some(voter2.to_owned()))
+        .unwrap();
+
+    suite.advance_time(EPOCH);
+    suite
+        .execute_options(&gauge_contract, voter1, gauge_id)
+        .unwrap();
+
+    // both adapters split their amount by the same selected set (25% / 75%)
+    assert_eq!(suite.query_balance(voter1, "ujuno").unwrap(), 250 + 125);
+    assert_eq!(suite.query_balance(voter2, "ujuno").unwrap(), 750 + 375);
+    assert_eq!(
+        suite.query_balance(suite.core.as_str(), "ujuno").unwrap(),
+        0
+    );
+}
+
+#[test]
+fn voter_projected_impact_per_option() {
+    let voter1 = "voter1";
+    let voter2 = "voter2";
+    let voter3 = "voter3";
+    let mut suite = SuiteBuilder::new()
+        .with_voting_members(&[(voter1, 200), (voter2, 300), (voter3, 100)])
+        .build();
+
+    let gauge_contract = init_gauge(&mut suite, &[voter1, voter2, voter3]);
+
+    let mut gauge_config = suite
+        .instantiate_adapter_and_return_config(&[voter1, voter2, voter3], (0, "ujuno"), None, None)
+        .unwrap();
+    gauge_config.to_distribute = Some(Asset::new_native("ujuno", 1200));
+    suite.create_gauge(&gauge_contract, gauge_config).unwrap();
+    let gauge_id = 0;
+
+    // voter1 backs two options, sharing both of them with another voter
+    suite
+        .place_votes(
+            &gauge_contract,
+            voter1,
+            gauge_id,
+            vec![
+                (voter1.to_owned(), Decimal::percent(25)),
+                (voter2.to_owned(), Decimal::percent(75)),
+            ],
+        )
+        .unwrap();
+    suite
+        .place_vote(&gauge_contract, voter2, gauge_id, Some(voter2.to_owned()))
+        .unwrap();
+    suite
+        .place_vote(&gauge_contract, voter3, gauge_id, Some(voter1.to_owned()))
+        .unwrap();
+
+    // option voter1 has 50 + 100 = 150 power, option voter2 has 150 + 300 = 450 power
+    assert_eq!(
+        suite
+            .query_voter_projected_impact(&gauge_contract, gauge_id, voter1)
+            .unwrap(),
+        vec![
+            VoterOptionImpact {
+                option: voter1.to_owned(),
+                projected_reward: Uint128::new(300),
+                voter_power: Uint128::new(50),
+                voter_reward: Uint128::new(100),
+            },
+            VoterOptionImpact {
+                option: voter2.to_owned(),
+                projected_reward: Uint128::new(900),
+                voter_power: Uint128::new(150),
+                voter_reward: Uint128::new(300),
+            },
+        ]
+    );
+
+    // voter3 only backs one of them, nothing for voters without vote
+    let impact = suite
+        .query_voter_projected_impact(&gauge_contract, gauge_id, voter3)
+        .unwrap();
+    assert_eq!(impact.len(), 1);
+    assert_eq!(impact[0].voter_reward, Uint128::new(200));
+    assert!(suite
+        .query_voter_projected_impact(&gauge_contract, gauge_id, "nobody")
+        .unwrap()
+        .is_empty());
+}
+
+#[test]
+fn winner_takes_all_reward() {
+    let voter1 = "voter1";
+    let voter2 = "voter2";
+    let voter3 = "voter3";
+    let mut suite = SuiteBuilder::new()
+        .with_voting_members(&[(voter1, 100), (voter2, 300), (voter3, 200)])
+        .with_core_balance((1000, "ujuno"))
+        .build();
+
+    let gauge_contract = init_gauge(&mut suite, &[voter1, voter2, voter3]);
+
+    // the cap would normally limit voter2 to 40% of the votes
+    let mut gauge_config = suite
+        .instantiate_adapter_and_return_config(
+            &[voter1, voter2, voter3],
+            (0, "ujuno"),
+            Decimal::percent(40),
+            None,
+        )
+        .unwrap();
+    gauge_config.to_distribute = Some(Asset::new_native("ujuno", 1000));
+    gauge_config.distribution_mode = DistributionMode::WinnerTakeAll;
+    suite.create_gauge(&gauge_contract, gauge_config).unwrap();
+    let gauge_id = 0;
+
+    for voter in [voter1, voter2, voter3] {
+        suite
+            .place_vote(&gauge_contract, voter, gauge_id, Some(voter.to_owned()))
+            .unwrap();
+    }
+    assert_eq!(
+        suite.query_selected_set(&gauge_contract, gauge_id).unwrap(),
+        vec![(voter2.to_owned(), Uint128::new(300))]
+    );
+
+    suite.advance_time(EPOCH);
+    suite
+        .execute_options(&gauge_contract, voter1, gauge_id)
+        .unwrap();
+
+    assert_eq!(suite.query_balance(voter1, "ujuno").unwrap(), 0);
+    assert_eq!(suite.query_balance(voter2, "ujuno").unwrap(), 1000);
+    assert_eq!(suite.query_balance(voter3, "ujuno").unwrap(), 0);
+}
+
+#[test]
+fn equal_fixed_grants() {
+    let voter1 = "voter1";
+    let voter2 = "voter2";
+    let voter3 = "voter3";
+    let voter4 = "voter4";
+    let mut suite = SuiteBuilder::new()
+        .with_voting_members(&[(voter1, 100), (voter2, 300), (voter3, 200), (voter4, 50)])
+        .with_core_balance((2000, "ujuno"))
+        .build();
+
+    let gauge_contract = init_gauge(&mut suite, &[voter1, voter2, voter3]);
+
+    let mut gauge_config = suite
+        .instantiate_adapter_and_return_config(
+            &[voter1, voter2, voter3, voter4],
+            (0, "ujuno"),
+            None,
+            None,
+        )
+        .unwrap();
+    gauge_config.distribution_mode = DistributionMode::EqualFixed {
+        amount: Uint128::zero(),
+    };
+    let err = suite
+        .create_gauge(&gauge_contract, gauge_config.clone())
+        .unwrap_err();
+    assert_eq!(
+        ContractError::InvalidFixedAmount {},
+        err.downcast().unwrap()
+    );
+
+    // the reward covers three grants, so the option with the lowest tally gets nothing
+    gauge_config.to_distribute = Some(Asset::new_native("ujuno", 1000));
+    gauge_config.distribution_mode = DistributionMode::EqualFixed {
+        amount: Uint128::new(300),
+    };
+    suite.create_gauge(&gauge_contract, gauge_config).unwrap();
+    let gauge_id = 0;
+
+    for voter in [voter1, voter2, voter3, voter4] {
+        suite
+            .place_vote(&gauge_contract, voter, gauge_id, Some(voter.to_owned()))
+            .unwrap();
+    }
+
+    suite.advance_time(EPOCH);
+    suite
+        .execute_options(&gauge_contract, voter1, gauge_id)
+        .unwrap();
+
+    assert_eq!(suite.query_balance(voter1, "ujuno").unwrap(), 300);
+    assert_eq!(suite.query_balance(voter2, "ujuno").unwrap(), 300);
+    assert_eq!(suite.query_balance(voter3, "ujuno").unwrap(), 300);
+    assert_eq!(suite.query_balance(voter4, "ujuno").unwrap(), 0);
+    assert_eq!(
+        suite.query_balance(suite.core.as_str(), "ujuno").unwrap(),
+        1100
+    );
+    // the recorded payouts tell which option got no grant
+    let shut_out = suite
+        .query_shut_out_options(&gauge_contract, gauge_id)
+        .unwrap();
+    assert_eq!(shut_out.epoch, Some(0));
+    assert_eq!(
+        shut_out.options,
+        vec![(voter4.to_owned(), Uint128::new(50))]
+    );
+}
+
+#[test]
+fn distribution_hook_receives_selected_set() {
+    let voter1 = "voter1";
+    let voter2 = "voter2";
+    let mut suite = SuiteBuilder::new()
+        .with_voting_members(&[(voter1, 100), (voter2, 300)])
+        .with_core_balance((1000, "ujuno"))
+        .build();
+
+    let gauge_contract = init_gauge(&mut suite, &[voter1, voter2]);
+    let hook = suite.instantiate_distribution_hook().unwrap();
+
+    let mut gauge_config = suite
+        .instantiate_adapter_and_return_config(&[voter1, voter2], (0, "ujuno"), None, None)
+        .unwrap();
+    gauge_config.distribution_hook = Some(hook.to_string());
+
+    // the hook couldn't receive a cw20 reward as funds
+    gauge_config.to_distribute = Some(Asset::new_cw20("token", 1000));
+    let err = suite
+        .create_gauge(&gauge_contract, gauge_config.clone())
+        .unwrap_err();
+    assert_eq!(
+        ContractError::DistributionHookCw20Reward {},
+        err.downcast().unwrap()
+    );
+
+    gauge_config.to_distribute = Some(Asset::new_native("ujuno", 1000));
+    suite.create_gauge(&gauge_contract, gauge_config).unwrap();
+    let gauge_id = 0;
+    assert_eq!(
+        suite
+            .query_gauge(gauge_contract.clone(), gauge_id)
+            .unwrap()
+            .distribution_hook,
+        Some(hook.to_string())
+    );
+
+    suite
+        .place_vote(&gauge_contract, voter1, gauge_id, Some(voter1.to_owned()))
+        .unwrap();
+    suite
+        .place_vote(&gauge_contract, voter2, gauge_id, Some(voter2.to_owned()))
+        .unwrap();
+
+    suite.advance_time(EPOCH);
+    suite
+        .execute_options(&gauge_contract, voter1, gauge_id)
+        .unwrap();
+
+    // the hook got the whole selected set and reward in one message and fanned it out
+    let distributions = suite.query_hook_distributions(&hook).unwrap();
+    assert_eq!(distributions.len(), 1);
+    assert_eq!(distributions[0].gauge_id, gauge_id);
+    assert_eq!(distributions[0].epoch, 0);
+    assert_eq!(
+        distributions[0].selected,
+        vec![
+            (voter2.to_owned(), Decimal::percent(75)),
+            (voter1.to_owned(), Decimal::percent(25)),
+        ]
+    );
+    assert_eq!(distributions[0].funds, coins(1000, "ujuno"));
+    assert_eq!(suite.query_balance(voter1, "ujuno").unwrap(), 250);
+    assert_eq!(suite.query_balance(voter2, "ujuno").unwrap(), 750);
+    assert_eq!(suite.query_balance(hook.as_str(), "ujuno").unwrap(), 0);
+}
+
+#[test]
+fn aggregate_selected_set_over_gauges() {
+    let voter1 = "voter1";
+    let voter2 = "voter2";
+    let voter3 = "voter3";
+    let mut suite = SuiteBuilder::new()
+        .with_voting_members(&[(voter1, 100), (voter2, 100), (voter3, 300)])
+        .build();
+
+    let gauge_contract = init_gauge(&mut suite, &[voter1, voter2, voter3]);
+
+    // two gauges distribute ujuno, both to voter2
+    for (options, reward) in [
+        ([voter1, voter2], Asset::new_native("ujuno", 1000)),
+        ([voter2, voter3], Asset::new_native("ujuno", 600)),
+        ([voter1, voter3], Asset::new_native("uother", 1000)),
+    ] {
+        let mut gauge_config = suite
+            .instantiate_adapter_and_return_config(&options, (0, "ujuno"), None, None)
+            .unwrap();
+        gauge_config.to_distribute = Some(reward);
+        suite.create_gauge(&gauge_contract, gauge_config).unwrap();
+    }
+
+    for (voter, gauge_id, option) in [
+        (voter1, 0, voter1),
+        (voter2, 0, voter2),
+        (voter2, 1, voter2),
+        (voter3, 1, voter3),
+        (voter1, 2, voter1),
+    ] {
+        suite
+            .place_vote(&gauge_contract, voter, gauge_id, Some(option.to_owned()))
+            .unwrap();
+    }
+
+    // voter2 gets half of gauge 0 and a quarter of gauge 1
+    let aggregate = suite
+        .query_aggregate_selected_set(&gauge_contract, "ujuno")
+        .unwrap();
+    assert_eq!(aggregate.gauge_ids, vec![0, 1]);
+    assert_eq!(
+        aggregate.rewards,
+        vec![
+            (voter1.to_owned(), Uint128::new(500)),
+            (voter2.to_owned(), Uint128::new(650)),
+            (voter3.to_owned(), Uint128::new(450)),
+        ]
+    );
+
+    // stopped gauges are left out
+    suite
+        .stop_gauge(&gauge_contract, suite.owner.clone(), 1)
+        .unwrap();
+    let aggregate = suite
+        .query_aggregate_selected_set(&gauge_contract, "ujuno")
+        .unwrap();
+    assert_eq!(aggregate.gauge_ids, vec![0]);
+    assert_eq!(
+        aggregate.rewards,
+        vec![
+            (voter1.to_owned(), Uint128::new(500)),
+            (voter2.to_owned(), Uint128::new(500)),
+        ]
+    );
+}
+
+#[test]
+fn total_budget_stops_gauge_when_exhausted() {
+    let voter1 = "voter1";
+    let voter2 = "voter2";
+    let mut suite = SuiteBuilder::new()
+        .with_voting_members(&[(voter1, 100), (voter2, 300)])
+        .with_core_balance((3000, "ujuno"))
+        .build();
+
+    let gauge_contract = init_gauge(&mut suite, &[voter1, voter2]);
+
+    let mut gauge_config = suite
+        .instantiate_adapter_and_return_config(&[voter1, voter2], (0, "ujuno"), None, None)
+        .unwrap();
+    gauge_config.to_distribute = Some(Asset::new_native("ujuno", 1000));
+    gauge_config.total_budget = Some(coin(2500, "uother"));
+    let err = suite
+        .create_gauge(&gauge_contract, gauge_config.clone())
+        .unwrap_err();
+    assert_eq!(
+        ContractError::BudgetDenomMismatch {},
+        err.downcast().unwrap()
+    );
+
+    gauge_config.total_budget = Some(coin(2500, "ujuno"));
+    suite.create_gauge(&gauge_contract, gauge_config).unwrap();
+    let gauge_id = 0;
+    assert_eq!(
+        suite
+            .query_remaining_budget(&gauge_contract, gauge_id)
+            .unwrap(),
+        RemainingBudgetResponse {
+            total_budget: Some(coin(2500, "ujuno")),
+            distributed: Uint128::zero(),
+            remaining: Some(coin(2500, "ujuno")),
+        }
+    );
+
+    suite
+        .place_vote(&gauge_contract, voter1, gauge_id, Some(voter1.to_owned()))
+        .unwrap();
+    suite
+        .place_vote(&gauge_contract, voter2, gauge_id, Some(voter2.to_owned()))
+        .unwrap();
+
+    for _ in 0..2 {
+        suite.advance_time(EPOCH);
+        suite
+            .execute_options(&gauge_contract, voter1, gauge_id)
+            .unwrap();
+    }
+    assert_eq!(
+        suite
+            .query_remaining_budget(&gauge_contract, gauge_id)
+            .unwrap()
+            .remaining,
+        Some(coin(500, "ujuno"))
+    );
+    assert!(
+        !suite
+            .query_gauge(gauge_contract.clone(), gauge_id)
+            .unwrap()
+            .is_stopped
+    );
+
+    // the last epoch only pays out what is left and stops the gauge
+    suite.advance_time(EPOCH);
+    let res = suite
+        .execute_options(&gauge_contract, voter1, gauge_id)
+        .unwrap();
+    assert!(res.has_event(
+        &Event::new("wasm-gauge_state_change")
+            .add_attribute("gauge_id", gauge_id.to_string())
+            .add_attribute("from", "active")
+            .add_attribute("to", "stopped")
+    ));
+    assert_eq!(
+        suite
+            .query_remaining_budget(&gauge_contract, gauge_id)
+            .unwrap(),
+        RemainingBudgetResponse {
+            total_budget: Some(coin(2500, "ujuno")),
+            distributed: Uint128::new(2500),
+            remaining: Some(coin(0, "ujuno")),
+        }
+    );
+    assert!(
+        suite
+            .query_gauge(gauge_contract.clone(), gauge_id)
+            .unwrap()
+            .is_stopped
+    );
+    assert_eq!(suite.query_balance(voter1, "ujuno").unwrap(), 625);
+    assert_eq!(suite.query_balance(voter2, "ujuno").unwrap(), 1875);
+    assert_eq!(
+        suite.query_balance(suite.core.as_str(), "ujuno").unwrap(),
+        500
+    );
+
+    suite.advance_time(EPOCH);
+    let err = suite
+        .execute_options(&gauge_contract, voter1, gauge_id)
+        .unwrap_err();
+    assert_eq!(
+        ContractError::GaugeStopped(gauge_id),
+        err.downcast().unwrap()
+    );
+}
+
+#[test]
+fn only_top_selected_options_are_paid() {
+    // voter1 to voter5 have 100 votes, voter6 to voter15 have 200
+    let voters: Vec<String> = (1..=15).map(|i| format!("voter{}", i)).collect();
+    let voters: Vec<&str> = voters.iter().map(String::as_str).collect();
+    let members: Vec<(&str, u64)> = voters
+        .iter()
+        .enumerate()
+        .map(|(i, voter)| (*voter, if i < 5 { 100 } else { 200 }))
+        .collect();
+    let mut suite = SuiteBuilder::new()
+        .with_voting_members(&members)
+        .with_core_balance((1000, "ujuno"))
+        .build();
+
+    let gauge_contract = init_gauge(&mut suite, &voters);
+
+    let mut gauge_config = suite
+        .instantiate_adapter_and_return_config(&voters, (0, "ujuno"), None, None)
+        .unwrap();
+    gauge_config.to_distribute = Some(Asset::new_native("ujuno", 1000));
+    gauge_config.min_percent_selected = None;
+    gauge_config.max_options_selected = 20;
+    gauge_config.max_paid_options = Some(10);
+    let bad_config = GaugeConfig {
+        max_paid_options: Some(0),
+        ..gauge_config.clone()
+    };
+    let err = suite.create_gauge(&gauge_contract, bad_config).unwrap_err();
+    assert_eq!(
+        ContractError::MaxPaidOptionsTooSmall {},
+        err.downcast().unwrap()
+    );
+    suite.create_gauge(&gauge_contract, gauge_config).unwrap();
+    let gauge_id = 0;
+
+    for voter in &voters {
+        suite
+            .place_vote(&gauge_contract, *voter, gauge_id, Some(voter.to_string()))
+            .unwrap();
+    }
+    suite.advance_time(EPOCH);
+    suite
+        .execute_options(&gauge_contract, voters[0], gauge_id)
+        .unwrap();
+
+    // all 15 options qualify and are selected
+    let selected = suite
+        .query_last_executed_set(&gauge_contract, gauge_id)
+        .unwrap()
+        .unwrap();
+    assert_eq!(selected.len(), 15);
+    // but only the top 10 share the reward
+    for voter in &voters[..5] {
+        assert_eq!(suite.query_balance(voter, "ujuno").unwrap(), 0);
+    }
+    for voter in &voters[5..] {
+        assert_eq!(suite.query_balance(voter, "ujuno").unwrap(), 100);
+    }
+    assert_eq!(
+        suite.query_balance(suite.core.as_str(), "ujuno").unwrap(),
+        0
+    );
+}
+
+#[test]
+fn option_lifetime_rewards_add_up_over_epochs() {
+    let voter1 = "voter1";
+    let voter2 = "voter2";
+    let mut suite = SuiteBuilder::new()
+        .with_voting_members(&[(voter1, 100), (voter2, 100)])
+        .with_core_balance((2000, "ujuno"))
+        .build();
+
+    let gauge_contract = init_gauge(&mut suite, &[voter1, voter2]);
+
+    let mut gauge_config = suite
+        .instantiate_adapter_and_return_config(&[voter1, voter2], (0, "ujuno"), None, None)
+        .unwrap();
+    gauge_config.to_distribute = Some(Asset::new_native("ujuno", 1000));
+    suite.create_gauge(&gauge_contract, gauge_config).unwrap();
+    let gauge_id = 0;
+
+    assert!(suite
+        .query_option_lifetime_rewards(&gauge_contract, gauge_id, voter1)
+        .unwrap()
+        .is_empty());
+
+    // everybody votes for voter1
+    suite
+        .place_vote(&gauge_contract, voter1, gauge_id, Some(voter1.to_owned()))
+        .unwrap();
+    suite
+        .place_vote(&gauge_contract, voter2, gauge_id, Some(voter1.to_owned()))
+        .unwrap();
+
+    suite.advance_time(EPOCH);
+    suite
+        .execute_options(&gauge_contract, voter1, gauge_id)
+        .unwrap();
+    assert_eq!(
+        suite
+            .query_option_lifetime_rewards(&gauge_contract, gauge_id, voter1)
+            .unwrap(),
+        vec![Asset::new_native("ujuno", 1000)]
+    );
+
+    suite.advance_time(EPOCH);
+    suite
+        .execute_options(&gauge_contract, voter1, gauge_id)
+        .unwrap();
+    assert_eq!(
+        suite
+            .query_option_lifetime_rewards(&gauge_contract, gauge_id, voter1)
+            .unwrap(),
+        vec![Asset::new_native("ujuno", 2000)]
+    );
+    assert_eq!(suite.query_balance(voter1, "ujuno").unwrap(), 2000);
+    // the other option never got anything
+    assert!(suite
+        .query_option_lifetime_rewards(&gauge_contract, gauge_id, voter2)
+        .unwrap()
+        .is_empty());
+}
+
+#[test]
+fn backfill_epochs_missed_while_paused() {
+    let voter1 = "voter1";
+    let mut suite = SuiteBuilder::new()
+        .with_voting_members(&[(voter1, 100)])
+        .with_core_balance((3000, "ujuno"))
+        .build();
+    let owner = suite.owner.clone();
+
+    let gauge_contract = init_gauge(&mut suite, &[voter1]);
+    let mut gauge_config = suite
+        .instantiate_adapter_and_return_config(&[voter1], (0, "ujuno"), None, None)
+        .unwrap();
+    gauge_config.to_distribute = Some(Asset::new_native("ujuno", 1000));
+    suite.create_gauge(&gauge_contract, gauge_config).unwrap();
+    let gauge_id = 0;
+    suite
+        .place_vote(&gauge_contract, voter1, gauge_id, Some(voter1.to_owned()))
+        .unwrap();
+
+    // nothing was missed yet
+    suite.advance_time(EPOCH);
+    suite
+        .execute_options(&gauge_contract, voter1, gauge_id)
+        .unwrap();
+    let err = suite
+        .execute_epoch(&gauge_contract, &owner, gauge_id, 0)
+        .unwrap_err();
+    assert_eq!(
+        ContractError::NoMissedEpoch { gauge_id, epoch: 0 },
+        err.downcast().unwrap()
+    );
+
+    // the execution of the second epoch is late by one epoch
+    suite.pause_all(&gauge_contract, &owner).unwrap();
+    suite.advance_time(2 * EPOCH);
+    suite.unpause_all(&gauge_contract, &owner).unwrap();
+    suite
+        .execute_options(&gauge_contract, voter1, gauge_id)
+        .unwrap();
+    assert_eq!(suite.query_balance(voter1, "ujuno").unwrap(), 2000);
+
+    let err = suite
+        .execute_epoch(&gauge_contract, voter1, gauge_id, 1)
+        .unwrap_err();
+    assert_eq!(ContractError::Unauthorized {}, err.downcast().unwrap());
+    suite
+        .execute_epoch(&gauge_contract, &owner, gauge_id, 1)
+        .unwrap();
+    assert_eq!(suite.query_balance(voter1, "ujuno").unwrap(), 3000);
+
+    // the missed epoch cannot be paid twice
+    let err = suite
+        .execute_epoch(&gauge_contract, &owner, gauge_id, 1)
+        .unwrap_err();
+    assert_eq!(
+        ContractError::NoMissedEpoch { gauge_id, epoch: 1 },
+        err.downcast().unwrap()
+    );
+}
+
+#[test]
+fn backfilled_epochs_use_recorded_inputs() {
+    let voter1 = "voter1";
+    let voter2 = "voter2";
+    let mut suite = SuiteBuilder::new()
+        .with_voting_members(&[(voter1, 200), (voter2, 100)])
+        .with_core_balance((2000, "ujuno"))
+        .build();
+    let owner = suite.owner.clone();
+
+    let gauge_contract = init_gauge(&mut suite, &[voter1, voter2]);
+    let mut gauge_config = suite
+        .instantiate_adapter_and_return_config(&[voter1, voter2], (0, "ujuno"), None, None)
+        .unwrap();
+    gauge_config.to_distribute = Some(Asset::new_native("ujuno", 1000));
+    gauge_config.max_paid_options = Some(1);
+    gauge_config.rounding = Rounding::Nearest;
+    suite.create_gauge(&gauge_contract, gauge_config).unwrap();
+    let gauge_id = 0;
+    suite
+        .place_vote(&gauge_contract, voter1, gauge_id, Some(voter1.to_owned()))
+        .unwrap();
+    suite
+        .place_vote(&gauge_contract, voter2, gauge_id, Some(voter2.to_owned()))
+        .unwrap();
+
+    suite.pause_all(&gauge_contract, &owner).unwrap();
+    suite.advance_time(2 * EPOCH);
+    suite.unpause_all(&gauge_contract, &owner).unwrap();
+    suite
+        .execute_options(&gauge_contract, voter1, gauge_id)
+        .unwrap();
+    let inputs = suite
+        .query_execution_inputs(&gauge_contract, gauge_id, 0)
+        .unwrap()
+        .unwrap();
+    assert_eq!(inputs.max_paid_options, Some(1));
+    assert_eq!(inputs.distribution_mode, DistributionMode::Proportional);
+    assert_eq!(inputs.rounding, Rounding::Nearest);
+
+    // the missed epoch is paid like the execution which ended the gap, only the top option
+    suite
+        .execute_epoch(&gauge_contract, &owner, gauge_id, 0)
+        .unwrap();
+    assert_eq!(suite.query_balance(voter1, "ujuno").unwrap(), 2000);
+    assert_eq!(suite.query_balance(voter2, "ujuno").unwrap(), 0);
+}
+
+#[test]
+fn dust_shares_go_to_other_options() {
+    let voter1 = "voter1";
+    let voter2 = "voter2";
+    let voter3 = "voter3";
+    let mut suite = SuiteBuilder::new()
+        .with_voting_members(&[(voter1, 495), (voter2, 500), (voter3, 5)])
+        .with_core_balance((1000, "ujuno"))
+        .build();
+
+    let gauge_contract = init_gauge(&mut suite, &[voter1, voter2, voter3]);
+    let mut gauge_config = suite
+        .instantiate_adapter_and_return_config(&[voter1, voter2, voter3], (0, "ujuno"), None, None)
+        .unwrap();
+    gauge_config.to_distribute = Some(Asset::new_native("ujuno", 1000));
+    gauge_config.min_percent_selected = None;
+    gauge_config.min_distribution_per_option = Some(Uint128::new(10));
+    suite.create_gauge(&gauge_contract, gauge_config).unwrap();
+    let gauge_id = 0;
+
+    for voter in [voter1, voter2, voter3] {
+        suite
+            .place_vote(&gauge_contract, voter, gauge_id, Some(voter.to_owned()))
+            .unwrap();
+    }
+    suite.advance_time(EPOCH);
+    suite
+        .execute_options(&gauge_contract, voter1, gauge_id)
+        .unwrap();
+
+    // voter3 would only get 5, so its share is split between the others
+    assert_eq!(suite.query_balance(voter1, "ujuno").unwrap(), 497);
+    assert_eq!(suite.query_balance(voter2, "ujuno").unwrap(), 502);
+    assert_eq!(suite.query_balance(voter3, "ujuno").unwrap(), 0);
+    // it is still part of the selected set
+    let selected = suite
+        .query_last_executed_set(&gauge_contract, gauge_id)
+        .unwrap()
+        .unwrap();
+    assert_eq!(selected.len(), 3);
+}
+
+#[test]
+fn stop_gauge_and_rollover_escrow() {
+    let voter1 = "voter1";
+    let funder = "funder";
+    let mut suite = SuiteBuilder::new()
+        .with_voting_members(&[(voter1, 100)])
+        .with_balance(funder, (1500, "ujuno"))
+        .build();
+    let owner = suite.owner.clone();
+
+    let gauge_contract = init_gauge(&mut suite, &[voter1]);
+    let mut gauge_config = suite
+        .instantiate_adapter_and_return_config(&[voter1], (0, "ujuno"), None, None)
+        .unwrap();
+    gauge_config.to_distribute = Some(Asset::new_native("ujuno", 1000));
+    suite
+        .create_gauge(&gauge_contract, gauge_config.clone())
+        .unwrap();
+    suite
+        .create_gauge(&gauge_contract, gauge_config.clone())
+        .unwrap();
+    suite.create_gauge(&gauge_contract, gauge_config).unwrap();
+    let (source, successor, stopped) = (0, 1, 2);
+    suite
+        .fund_gauge(funder, &gauge_contract, source, &coins(1000, "ujuno"))
+        .unwrap();
+    suite
+        .fund_gauge(funder, &gauge_contract, successor, &coins(500, "ujuno"))
+        .unwrap();
+    suite.stop_gauge(&gauge_contract, &owner, stopped).unwrap();
+
+    let err = suite
+        .stop_gauge_and_rollover(&gauge_contract, voter1, source, successor)
+        .unwrap_err();
+    assert_eq!(ContractError::Unauthorized {}, err.downcast().unwrap());
+    // the successor must be another active gauge
+    for invalid in [source, stopped] {
+        let err = suite
+            .stop_gauge_and_rollover(&gauge_contract, &owner, source, invalid)
+            .unwrap_err();
+        assert_eq!(
+            ContractError::InvalidSuccessor(invalid),
+            err.downcast().unwrap()
+        );
+    }
+
+    suite
+        .stop_gauge_and_rollover(&gauge_contract, &owner, source, successor)
+        .unwrap();
+    let gauge = suite.query_gauge(gauge_contract.clone(), source).unwrap();
+    assert!(gauge.is_stopped);
+    assert!(gauge.escrow_balance.is_empty());
+    let gauge = suite
+        .query_gauge(gauge_contract.clone(), successor)
+        .unwrap();
+    assert_eq!(gauge.escrow_balance, coins(1500, "ujuno"));
+    // the funds never left the contract
+    assert_eq!(
+        suite
+            .query_balance(gauge_contract.as_str(), "ujuno")
+            .unwrap(),
+        1500
+    );
+}
+
+#[test]
+fn execute_fails_without_reward_denom() {
+    let voter1 = "voter1";
+    let mut suite = SuiteBuilder::new()
+        .with_voting_members(&[(voter1, 100)])
+        .with_core_balance((1000, "uwynd"))
+        .build();
+
+    let gauge_contract = init_gauge(&mut suite, &[voter1]);
+    let mut gauge_config = suite
+        .instantiate_adapter_and_return_config(&[voter1], (0, "ujuno"), None, None)
+        .unwrap();
+    gauge_config.to_distribute = Some(Asset::new_native("ujuno", 1000));
+    suite.create_gauge(&gauge_contract, gauge_config).unwrap();
+    let gauge_id = 0;
+    suite
+        .place_vote(&gauge_contract, voter1, gauge_id, Some(voter1.to_owned()))
+        .unwrap();
+
+    suite.advance_time(EPOCH);
+    let err = suite
+        .execute_options(&gauge_contract, voter1, gauge_id)
+        .unwrap_err();
+    assert_eq!(
+        ContractError::MissingDenom {
+            denom: "ujuno".to_owned()
+        },
+        err.downcast().unwrap()
+    );
+}
+
+#[test]
+fn post_execute_callback() {
+    let voter1 = "voter1";
+    let voter2 = "voter2";
+    let mut suite = SuiteBuilder::new()
+        .with_voting_members(&[(voter1, 100), (voter2, 300)])
+        .with_core_balance((1000, "ujuno"))
+        .build();
+
+    let gauge_contract = init_gauge(&mut suite, &[voter1, voter2]);
+    let callback = suite.instantiate_post_execute_callback(false).unwrap();
+    let failing_callback = suite.instantiate_post_execute_callback(true).unwrap();
+
+    for callback in [&callback, &failing_callback] {
+        let mut gauge_config = suite
+            .instantiate_adapter_and_return_config(&[voter1, voter2], (0, "ujuno"), None, None)
+            .unwrap();
+        gauge_config.to_distribute = Some(Asset::new_native("ujuno", 500));
+        gauge_config.post_execute_callback = Some(callback.to_string());
+        suite.create_gauge(&gauge_contract, gauge_config).unwrap();
+    }
+    for gauge_id in [0, 1] {
+        suite
+            .place_vote(&gauge_contract, voter1, gauge_id, Some(voter1.to_owned()))
+            .unwrap();
+        suite
+            .place_vote(&gauge_contract, voter2, gauge_id, Some(voter2.to_owned()))
+            .unwrap();
+    }
+
+    suite.advance_time(EPOCH);
+    suite.execute_options(&gauge_contract, voter1, 0).unwrap();
+    assert_eq!(
+        suite.query_callback_notifications(&callback).unwrap(),
+        vec![PostExecuteCallbackMsg::GaugeExecuted {
+            gauge_id: 0,
+            epoch: 0,
+            selected: vec![
+                (voter2.to_owned(), Decimal::percent(75)),
+                (voter1.to_owned(), Decimal::percent(25)),
+            ],
+        }]
+    );
+
+    // the failure of the callback is only logged, the reward is still paid
+    let res = suite.execute_options(&gauge_contract, voter1, 1).unwrap();
+    assert!(
+        res.has_event(&Event::new("wasm").add_attribute("action", "post_execute_callback_failure"))
+    );
+    assert!(suite
+        .query_callback_notifications(&failing_callback)
+        .unwrap()
+        .is_empty());
+    assert_eq!(suite.query_balance(voter1, "ujuno").unwrap(), 250);
+    assert_eq!(suite.query_balance(voter2, "ujuno").unwrap(), 750);
+}
+
+#[test]
+fn emergency_withdraw_from_escrow() {
+    let voter1 = "voter1";
+    let funder = "funder";
+    let rescuer = "rescuer";
+    let mut suite = SuiteBuilder::new()
+        .with_voting_members(&[(voter1, 100)])
+        .with_balance(funder, (1500, "ujuno"))
+        .build();
+    let owner = suite.owner.clone();
+
+    let gauge_contract = init_gauge(&mut suite, &[voter1]);
+    for _ in 0..2 {
+        let mut gauge_config = suite
+            .instantiate_adapter_and_return_config(&[voter1], (0, "ujuno"), None, None)
+            .unwrap();
+        gauge_config.to_distribute = Some(Asset::new_native("ujuno", 1000));
+        suite.create_gauge(&gauge_contract, gauge_config).unwrap();
+    }
+    suite
+        .fund_gauge(funder, &gauge_contract, 0, &coins(1000, "ujuno"))
+        .unwrap();
+    suite
+        .fund_gauge(funder, &gauge_contract, 1, &coins(500, "ujuno"))
+        .unwrap();
+    // the funds of a stopped gauge can't be paid out anymore
+    suite.stop_gauge(&gauge_contract, &owner, 0).unwrap();
+
+    let err = suite
+        .emergency_withdraw(voter1, &gauge_contract, "ujuno", None, rescuer, 0)
+        .unwrap_err();
+    assert_eq!(ContractError::Unauthorized {}, err.downcast().unwrap());
+    // only the escrow of the given gauge can be withdrawn
+    let err = suite
+        .emergency_withdraw(&owner, &gauge_contract, "ujuno", 1200, rescuer, 0)
+        .unwrap_err();
+    assert_eq!(
+        ContractError::WithdrawExceedsBalance {
+            denom: "ujuno".to_owned(),
+            amount: Uint128::new(1200),
+            balance: Uint128::new(1000),
+        },
+        err.downcast().unwrap()
+    );
+    // everything the contract holds belongs to an escrow
+    let err = suite
+        .emergency_withdraw(&owner, &gauge_contract, "ujuno", None, rescuer, None)
+        .unwrap_err();
+    assert_eq!(ContractError::NoFunds {}, err.downcast().unwrap());
+
+    let res = suite
+        .emergency_withdraw(&owner, &gauge_contract, "ujuno", 600, rescuer, 0)
+        .unwrap();
+    assert!(res.has_event(
+        &Event::new("wasm-emergency_withdraw")
+            .add_attribute("denom", "ujuno")
+            .add_attribute("amount", "600")
+            .add_attribute("recipient", rescuer)
+            .add_attribute("by", &owner)
+            .add_attribute("gauge_id", "0")
+    ));
+    assert_eq!(suite.query_balance(rescuer, "ujuno").unwrap(), 600);
+    let gauge = suite.query_gauge(gauge_contract.clone(), 0).unwrap();
+    assert_eq!(gauge.escrow_balance, coins(400, "ujuno"));
+    // the escrow of the other gauge is left untouched
+    let gauge = suite.query_gauge(gauge_contract.clone(), 1).unwrap();
+    assert_eq!(gauge.escrow_balance, coins(500, "ujuno"));
+
+    // the rest of the escrow by default
+    suite
+        .emergency_withdraw(&owner, &gauge_contract, "ujuno", None, rescuer, 0)
+        .unwrap();
+    assert_eq!(suite.query_balance(rescuer, "ujuno").unwrap(), 1000);
+    let gauge = suite.query_gauge(gauge_contract.clone(), 0).unwrap();
+    assert!(gauge.escrow_balance.is_empty());
+    let gauge = suite.query_gauge(gauge_contract.clone(), 1).unwrap();
+    assert_eq!(gauge.escrow_balance, coins(500, "ujuno"));
+    assert_eq!(
+        suite
+            .query_balance(gauge_contract.as_str(), "ujuno")
+            .unwrap(),
+        500
+    );
+    let err = suite
+        .emergency_withdraw(&owner, &gauge_contract, "ujuno", None, rescuer, 0)
+        .unwrap_err();
+    assert_eq!(ContractError::NoFunds {}, err.downcast().unwrap());
+}
+
+#[test]
+fn max_voter_influence_caps_whale() {
+    let whale = "whale";
+    let voters = ["voter1", "voter2", "voter3", "voter4"];
+    let mut suite = SuiteBuilder::new()
+        .with_voting_members(&[
+            (whale, 600),
+            (voters[0], 100),
+            (voters[1], 100),
+            (voters[2], 100),
+            (voters[3], 100),
+        ])
+        .with_core_balance((2000, "ujuno"))
+        .build();
+
+    let gauge_contract = init_gauge(&mut suite, &[whale, voters[0]]);
+    let mut gauge_config = suite
+        .instantiate_adapter_and_return_config(&voters[..2], (0, "ujuno"), None, None)
+        .unwrap();
+    gauge_config.to_distribute = Some(Asset::new_native("ujuno", 1000));
+    gauge_config.max_voter_influence = Some(Decimal::zero());
+    let err = suite
+        .create_gauge(&gauge_contract, gauge_config.clone())
+        .unwrap_err();
+    assert_eq!(
+        ContractError::InvalidMaxVoterInfluence {},
+        err.downcast().unwrap()
+    );
+    gauge_config.max_voter_influence = Some(Decimal::percent(20));
+    suite.create_gauge(&gauge_contract, gauge_config).unwrap();
+
+    // the whale alone would get 60% to voter1
+    suite
+        .place_vote(&gauge_contract, whale, 0, Some(voters[0].to_owned()))
+        .unwrap();
+    for voter in voters {
+        suite
+            .place_vote(&gauge_contract, voter, 0, Some(voters[1].to_owned()))
+            .unwrap();
+    }
+
+    // the queries see the capped tallies just like the execution
+    assert_eq!(
+        suite.query_selected_set(&gauge_contract, 0).unwrap(),
+        vec![
+            (voters[1].to_owned(), Uint128::new(400)),
+            (voters[0].to_owned(), Uint128::new(100)),
+        ]
+    );
+
+    suite.advance_time(EPOCH);
+    suite.execute_options(&gauge_contract, whale, 0).unwrap();
+    // whale is scaled down to 100, so it has 20% of the 500 left
+    assert_eq!(suite.query_balance(voters[0], "ujuno").unwrap(), 200);
+    assert_eq!(suite.query_balance(voters[1], "ujuno").unwrap(), 800);
+}
+
+#[test]
+fn execute_condition_skips_epoch_below_price_floor() {
+    let voter1 = "voter1";
+    let voter2 = "voter2";
+    let mut suite = SuiteBuilder::new()
+        .with_voting_members(&[(voter1, 100), (voter2, 300)])
+        .with_core_balance((1000, "ujuno"))
+        .build();
+
+    let gauge_contract = init_gauge(&mut suite, &[voter1, voter2]);
+    let oracle = suite.instantiate_oracle(Decimal::percent(50)).unwrap();
+    let mut gauge_config = suite
+        .instantiate_adapter_and_return_config(&[voter1, voter2], (0, "ujuno"), None, None)
+        .unwrap();
+    gauge_config.to_distribute = Some(Asset::new_native("ujuno", 400));
+    gauge_config.execute_condition = Some(ExecuteCondition {
+        oracle,
+        min_price: Decimal::one(),
+    });
+    suite.create_gauge(&gauge_contract, gauge_config).unwrap();
+    suite
+        .place_vote(&gauge_contract, voter1, 0, Some(voter1.to_owned()))
+        .unwrap();
+    suite
+        .place_vote(&gauge_contract, voter2, 0, Some(voter2.to_owned()))
+        .unwrap();
+
+    // the price is too low, so the epoch is skipped without payout
+    suite.advance_time(EPOCH);
+    let res = suite.execute_options(&gauge_contract, voter1, 0).unwrap();
+    assert!(res.has_event(&Event::new("wasm").add_attribute("action", "skip_execution")));
+    assert_eq!(suite.query_balance(voter1, "ujuno").unwrap(), 0);
+    assert_eq!(suite.query_balance(voter2, "ujuno").unwrap(), 0);
+    let gauge = suite.query_gauge(gauge_contract.clone(), 0).unwrap();
+    assert_eq!(gauge.executed_epochs, 0);
+    // the clock moved on, so it cannot be retried right away
+    let err = suite
+        .execute_options(&gauge_contract, voter1, 0)
+        .unwrap_err();
+    assert!(matches!(
+        err.downcast().unwrap(),
+        ContractError::EpochNotReached { .. }
+    ));
+
+    let oracle = gauge.execute_condition.unwrap().oracle;
+    suite
+        .set_oracle_price(&oracle, Decimal::percent(150))
+        .unwrap();
+    suite.advance_time(EPOCH);
+    suite.execute_options(&gauge_contract, voter1, 0).unwrap();
+    assert_eq!(suite.query_balance(voter1, "ujuno").unwrap(), 100);
+    assert_eq!(suite.query_balance(voter2, "ujuno").unwrap(), 300);
+    let gauge = suite.query_gauge(gauge_contract, 0).unwrap();
+    assert_eq!(gauge.executed_epochs, 1);
+}
+
+#[test]
+fn swap_route_swaps_before_distribution() {
+    let voter1 = "voter1";
+    let voter2 = "voter2";
+    let mut suite = SuiteBuilder::new()
+        .with_voting_members(&[(voter1, 100), (voter2, 300)])
+        .with_core_balance((1000, "ujuno"))
+        .with_balance("owner", (1000, "uusdc"))
+        .build();
+
+    let gauge_contract = init_gauge(&mut suite, &[voter1, voter2]);
+    let router = suite
+        .instantiate_router(Decimal::percent(50), &coins(1000, "uusdc"))
+        .unwrap();
+    let mut gauge_config = suite
+        .instantiate_adapter_and_return_config(&[voter1, voter2], (0, "ujuno"), None, None)
+        .unwrap();
+    gauge_config.to_distribute = Some(Asset::new_native("ujuno", 400));
+    gauge_config.swap_route = Some(SwapRoute {
+        router: router.clone(),
+        to_denom: "uusdc".to_owned(),
+    });
+    suite.create_gauge(&gauge_contract, gauge_config).unwrap();
+    suite
+        .place_vote(&gauge_contract, voter1, 0, Some(voter1.to_owned()))
+        .unwrap();
+    suite
+        .place_vote(&gauge_contract, voter2, 0, Some(voter2.to_owned()))
+        .unwrap();
+
+    suite.advance_time(EPOCH);
+    let res = suite.execute_options(&gauge_contract, voter1, 0).unwrap();
+    // the swap is executed before any option is paid
+    let swap = res
+        .events
+        .iter()
+        .position(|event| {
+            event.ty == "wasm"
+                && event
+                    .attributes
+                    .iter()
+                    .any(|attr| attr.key == "action" && attr.value == "swap")
+        })
+        .unwrap();
+    let payout = res
+        .events
+        .iter()
+        .position(|event| {
+            event.ty == "transfer"
+                && event
+                    .attributes
+                    .iter()
+                    .any(|attr| attr.key == "recipient" && attr.value == voter1)
+        })
+        .unwrap();
+    assert!(swap < payout);
+
+    // 400 ujuno are swapped for 200 uusdc, split 25% to 75%
+    assert_eq!(
+        suite.query_balance(suite.core.as_str(), "ujuno").unwrap(),
+        600
+    );
+    assert_eq!(suite.query_balance(voter1, "uusdc").unwrap(), 50);
+    assert_eq!(suite.query_balance(voter2, "uusdc").unwrap(), 150);
+    assert_eq!(suite.query_balance(router.as_str(), "ujuno").unwrap(), 400);
+}
+
+#[test]
+fn shut_out_options_lists_dust() {
+    let voter1 = "voter1";
+    let voter2 = "voter2";
+    let voter3 = "voter3";
+    let mut suite = SuiteBuilder::new()
+        .with_voting_members(&[(voter1, 495), (voter2, 500), (voter3, 5)])
+        .with_core_balance((1000, "ujuno"))
+        .build();
+
+    let gauge_contract = init_gauge(&mut suite, &[voter1, voter2, voter3]);
+    let mut gauge_config = suite
+        .instantiate_adapter_and_return_config(&[voter1, voter2, voter3], (0, "ujuno"), None, None)
+        .unwrap();
+    gauge_config.to_distribute = Some(Asset::new_native("ujuno", 1000));
+    gauge_config.min_percent_selected = None;
+    gauge_config.min_distribution_per_option = Some(Uint128::new(10));
+    suite.create_gauge(&gauge_contract, gauge_config).unwrap();
+    let gauge_id = 0;
+
+    for voter in [voter1, voter2, voter3] {
+        suite
+            .place_vote(&gauge_contract, voter, gauge_id, Some(voter.to_owned()))
+            .unwrap();
+    }
+    let shut_out = suite
+        .query_shut_out_options(&gauge_contract, gauge_id)
+        .unwrap();
+    assert_eq!(shut_out.epoch, None);
+    assert!(shut_out.options.is_empty());
+
+    suite.advance_time(EPOCH);
+    suite
+        .execute_options(&gauge_contract, voter1, gauge_id)
+        .unwrap();
+
+    // the long tail option is voted, but its 5 ujuno are below the floor
+    assert_eq!(suite.query_balance(voter3, "ujuno").unwrap(), 0);
+    let shut_out = suite
+        .query_shut_out_options(&gauge_contract, gauge_id)
+        .unwrap();
+    assert_eq!(shut_out.epoch, Some(0));
+    assert_eq!(shut_out.options, vec![(voter3.to_owned(), Uint128::new(5))]);
+}
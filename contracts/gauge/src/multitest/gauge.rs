@@ -1,10 +1,16 @@
-use cosmwasm_std::{Addr, Decimal, Uint128};
+use cosmwasm_std::{coin, Addr, BankMsg, CosmosMsg, Decimal, Event, Timestamp, Uint128};
+use cw_utils::Expiration;
 use voting::Vote;
 
-use super::suite::{Suite, SuiteBuilder};
+use super::suite::{init_gauge, SuiteBuilder};
 
+use crate::contract::{CONTRACT_NAME, CONTRACT_VERSION};
 use crate::error::ContractError;
-use crate::msg::{GaugeMigrationConfig, GaugeResponse};
+use crate::msg::{
+    ExecutableGaugePreview, ExecutionComplexityResponse, GaugeConfig, GaugeMigrationConfig,
+    GaugeResponse, OptionDelta, OptionOrder, SelectionParamsResponse,
+};
+use crate::state::{DeprecatedVotePolicy, DistributionMode, EpochUnit, ExecutePolicy, Rounding};
 
 const EPOCH: u64 = 7 * 86_400;
 
@@ -40,12 +46,486 @@ fn create_gauge() {
             max_options_selected: 10,
             max_available_percentage: None,
             is_stopped: false,
+            voting_closed: false,
             next_epoch: suite.current_time() + 7 * 86400,
             reset: None,
+            to_distribute: None,
+            reward_schedule: vec![],
+            executed_epochs: 0,
+            max_voters: None,
+            validate_option_on_vote: false,
+            voter_restriction: None,
+            tenure_curve: None,
+            bootstrap_equal_split: false,
+            reclaim_votes_on_remove: false,
+            adapter_multipliers: false,
+            isolate_failures: false,
+            epoch_unit: EpochUnit::Time,
+            rounding: Rounding::Down,
+            tags: vec![],
+            escrow_balance: vec![],
+            extra_adapters: vec![],
+            min_absolute_tally: None,
+            distribution_mode: DistributionMode::Proportional,
+            boost_source: None,
+            flashloan_resistant: false,
+            distribution_hook: None,
+            auto_prune_zero_options: false,
+            require_full_allocation: false,
+            total_budget: None,
+            epoch_label: None,
+            adapter_reward: false,
+            vote_change_fee: None,
+            execute_policy: ExecutePolicy::Anyone,
+            max_paid_options: None,
+            deprecated_vote_policy: DeprecatedVotePolicy::Ignore,
+            min_distribution_per_option: None,
+            max_total_options: None,
+            post_execute_callback: None,
+            align_to: None,
+            max_voter_influence: None,
+            execute_condition: None,
+            options_frozen: false,
+            swap_route: None,
         }
     );
 }
 
+#[test]
+fn clone_gauge() {
+    let voter1 = "voter1";
+    let voter2 = "voter2";
+    let mut suite = SuiteBuilder::new()
+        .with_voting_members(&[(voter1, 100), (voter2, 100)])
+        .with_core_balance((1000, "ujuno"))
+        .build();
+    let owner = suite.owner.clone();
+
+    let gauge_contract = init_gauge(&mut suite, &[voter1, voter2]);
+    let mut gauge_config = suite
+        .instantiate_adapter_and_return_config(&[voter1, voter2], (1000, "ujuno"), None, None)
+        .unwrap();
+    gauge_config.max_options_selected = 3;
+    gauge_config.min_absolute_tally = Some(Uint128::new(10));
+    gauge_config.rounding = Rounding::Nearest;
+    gauge_config.tags = vec!["dex".to_owned()];
+    suite.create_gauge(&gauge_contract, gauge_config).unwrap();
+
+    suite
+        .place_vote(&gauge_contract, voter1, 0, Some(voter1.to_owned()))
+        .unwrap();
+    suite.advance_time(EPOCH);
+    suite.execute_options(&gauge_contract, voter1, 0).unwrap();
+
+    // only owner can clone
+    let err = suite
+        .clone_gauge(&gauge_contract, voter1, 0, None, None)
+        .unwrap_err();
+    assert_eq!(ContractError::Unauthorized {}, err.downcast().unwrap());
+    let err = suite
+        .clone_gauge(&gauge_contract, &owner, 5, None, None)
+        .unwrap_err();
+    assert_eq!(
+        ContractError::GaugeNotFound { gauge: 5 },
+        err.downcast().unwrap()
+    );
+
+    let new_adapter = suite
+        .instantiate_adapter(&["option1", "option2"], (500, "ujuno"))
+        .unwrap();
+    suite
+        .clone_gauge(
+            &gauge_contract,
+            &owner,
+            0,
+            Some(&new_adapter),
+            Some("sibling"),
+        )
+        .unwrap();
+
+    // config is the same, apart from the overrides and the fresh epoch state
+    let source = suite.query_gauge(gauge_contract.clone(), 0).unwrap();
+    let cloned = suite.query_gauge(gauge_contract.clone(), 1).unwrap();
+    assert_eq!(source.executed_epochs, 1);
+    assert_eq!(
+        cloned,
+        GaugeResponse {
+            id: 1,
+            title: "sibling".to_owned(),
+            adapter: new_adapter.to_string(),
+            next_epoch: suite.current_time() + EPOCH,
+            executed_epochs: 0,
+            ..source
+            distribution_mode: DistributionMode::Proportional,
+            boost_source: None,
+            flashloan_resistant: false,
+            distribution_hook: None,
+            auto_prune_zero_options: false,
+            require_full_allocation: false,
+            total_budget: None,
+            epoch_label: None,
+            adapter_reward: false,
+            vote_change_fee: None,
+            execute_policy: ExecutePolicy::Anyone,
+            max_paid_options: None,
+            deprecated_vote_policy: DeprecatedVotePolicy::Ignore,
+            min_distribution_per_option: None,
+            max_total_options: None,
+            post_execute_callback: None,
+            align_to: None,
+            max_voter_influence: None,
+            execute_condition: None,
+            options_frozen: false,
+            swap_route: None,
+        }
+    );
+    assert_eq!(
+        suite
+            .query_gauges_by_tag(&gauge_contract, "dex", None, None)
+            .unwrap()
+            .len(),
+        2
+    );
+
+    // options come from the new adapter, without any votes
+    assert_eq!(
+        suite.query_list_options(&gauge_contract, 1).unwrap(),
+        vec![
+            ("option1".to_owned(), Uint128::zero()),
+            ("option2".to_owned(), Uint128::zero()),
+        ]
+    );
+    assert!(suite
+        .query_list_votes(&gauge_contract, 1)
+        .unwrap()
+        .is_empty());
+    assert!(suite
+        .query_selected_set(&gauge_contract, 1)
+        .unwrap()
+        .is_empty());
+}
+
+#[test]
+fn split_gauge() {
+    let voter1 = "voter1";
+    let voter2 = "voter2";
+    let voter3 = "voter3";
+    let options = ["option1", "option2", "option3", "option4"];
+    let mut suite = SuiteBuilder::new()
+        .with_voting_members(&[(voter1, 100), (voter2, 200), (voter3, 300)])
+        .build();
+    let owner = suite.owner.clone();
+
+    let gauge_contract = init_gauge(&mut suite, &[voter1, voter2, voter3]);
+    suite
+        .instantiate_adapter_and_create_gauge(
+            gauge_contract.clone(),
+            &options,
+            (1000, "ujuno"),
+            None,
+            None,
+        )
+        .unwrap();
+
+    let split = |votes: &[(&str, u64)]| {
+        votes
+            .iter()
+            .map(|(option, percent)| (option.to_string(), Decimal::percent(*percent)))
+            .collect::<Vec<_>>()
+    };
+    suite
+        .place_votes(
+            &gauge_contract,
+            voter1,
+            0,
+            split(&[("option1", 50), ("option3", 50)]),
+        )
+        .unwrap();
+    suite
+        .place_votes(&gauge_contract, voter2, 0, split(&[("option3", 100)]))
+        .unwrap();
+    suite
+        .place_votes(
+            &gauge_contract,
+            voter3,
+            0,
+            split(&[("option2", 60), ("option4", 40)]),
+        )
+        .unwrap();
+
+    let new_config = suite
+        .instantiate_adapter_and_return_config(&["option3", "option4"], (1000, "ujuno"), None, None)
+        .unwrap();
+    let err = suite
+        .split_gauge(&gauge_contract, voter1, 0, &["option3"], new_config.clone())
+        .unwrap_err();
+    assert_eq!(ContractError::Unauthorized {}, err.downcast().unwrap());
+    let err = suite
+        .split_gauge(&gauge_contract, &owner, 0, &["option5"], new_config.clone())
+        .unwrap_err();
+    assert_eq!(
+        ContractError::OptionNotFound {
+            gauge: 0,
+            option: "option5".to_owned(),
+        },
+        err.downcast().unwrap()
+    );
+
+    suite
+        .split_gauge(
+            &gauge_contract,
+            &owner,
+            0,
+            &["option3", "option4"],
+            new_config,
+        )
+        .unwrap();
+
+    // tallies moved along with the options
+    assert_eq!(
+        suite.query_list_options(&gauge_contract, 0).unwrap(),
+        vec![
+            ("option1".to_owned(), Uint128::new(50)),
+            ("option2".to_owned(), Uint128::new(180)),
+        ]
+    );
+    assert_eq!(
+        suite.query_list_options(&gauge_contract, 1).unwrap(),
+        vec![
+            ("option3".to_owned(), Uint128::new(250)),
+            ("option4".to_owned(), Uint128::new(120)),
+        ]
+    );
+
+    // and so did the votes, keeping their weights
+    let votes_of = |gauge_id: u64, voter: &str| {
+        suite
+            .query_vote(&gauge_contract, gauge_id, voter)
+            .unwrap()
+            .map(|vote| {
+                vote.votes
+                    .into_iter()
+                    .map(|v| (v.option, v.weight))
+                    .collect::<Vec<_>>()
+            })
+    };
+    assert_eq!(votes_of(0, voter1), Some(split(&[("option1", 50)])));
+    assert_eq!(votes_of(1, voter1), Some(split(&[("option3", 50)])));
+    assert_eq!(votes_of(0, voter2), None);
+    assert_eq!(votes_of(1, voter2), Some(split(&[("option3", 100)])));
+    assert_eq!(votes_of(0, voter3), Some(split(&[("option2", 60)])));
+    assert_eq!(votes_of(1, voter3), Some(split(&[("option4", 40)])));
+
+    // the selected sets match the tallies
+    assert_eq!(
+        suite.query_selected_set(&gauge_contract, 1).unwrap(),
+        vec![
+            ("option3".to_owned(), Uint128::new(250)),
+            ("option4".to_owned(), Uint128::new(120)),
+        ]
+    );
+    assert_eq!(
+        suite.query_selected_set(&gauge_contract, 0).unwrap(),
+        vec![
+            ("option2".to_owned(), Uint128::new(180)),
+            ("option1".to_owned(), Uint128::new(50)),
+        ]
+    );
+}
+
+#[test]
+fn pause_all_freezes_mutations() {
+    let voter1 = "voter1";
+    let voter2 = "voter2";
+    let mut suite = SuiteBuilder::new()
+        .with_voting_members(&[(voter1, 100), (voter2, 100)])
+        .with_core_balance((1000, "ujuno"))
+        .build();
+    let owner = suite.owner.clone();
+
+    let gauge_contract = init_gauge(&mut suite, &[voter1, voter2]);
+    let gauge_adapter = suite
+        .instantiate_adapter_and_create_gauge(
+            gauge_contract.clone(),
+            &[voter1, voter2],
+            (1000, "ujuno"),
+            None,
+            None,
+        )
+        .unwrap();
+    let gauge_id = 0;
+    suite
+        .place_vote(&gauge_contract, voter1, gauge_id, Some(voter1.to_owned()))
+        .unwrap();
+    suite.add_valid_option(&gauge_adapter, "option1").unwrap();
+    let gauge_config = suite
+        .instantiate_adapter_and_return_config(&[voter1, voter2], (1000, "ujuno"), None, None)
+        .unwrap();
+    assert!(!suite.query_config(&gauge_contract).unwrap().paused);
+
+    // only owner can pause
+    let err = suite.pause_all(&gauge_contract, voter1).unwrap_err();
+    assert_eq!(ContractError::Unauthorized {}, err.downcast().unwrap());
+    suite.pause_all(&gauge_contract, &owner).unwrap();
+    assert!(suite.query_config(&gauge_contract).unwrap().paused);
+
+    suite.advance_time(EPOCH);
+    // keepers are not pointed to gauges that cannot be executed
+    assert!(suite
+        .query_executable_gauges(&gauge_contract, None)
+        .unwrap()
+        .is_empty());
+    assert!(suite
+        .query_executable_gauges_with_preview(&gauge_contract, None)
+        .unwrap()
+        .is_empty());
+    let err = suite
+        .create_gauge(&gauge_contract, gauge_config.clone())
+        .unwrap_err();
+    assert_eq!(ContractError::ContractPaused {}, err.downcast().unwrap());
+    let err = suite
+        .place_vote(&gauge_contract, voter2, gauge_id, Some(voter1.to_owned()))
+        .unwrap_err();
+    assert_eq!(ContractError::ContractPaused {}, err.downcast().unwrap());
+    let err = suite
+        .add_option(&gauge_contract, voter1, gauge_id, "option1")
+        .unwrap_err();
+    assert_eq!(ContractError::ContractPaused {}, err.downcast().unwrap());
+    let err = suite
+        .execute_options(&gauge_contract, voter1, gauge_id)
+        .unwrap_err();
+    assert_eq!(ContractError::ContractPaused {}, err.downcast().unwrap());
+
+    // everything works again after unpausing
+    let err = suite.unpause_all(&gauge_contract, voter1).unwrap_err();
+    assert_eq!(ContractError::Unauthorized {}, err.downcast().unwrap());
+    suite.unpause_all(&gauge_contract, &owner).unwrap();
+    assert!(!suite.query_config(&gauge_contract).unwrap().paused);
+    assert_eq!(
+        suite
+            .query_executable_gauges(&gauge_contract, None)
+            .unwrap(),
+        vec![gauge_id]
+    );
+
+    suite.create_gauge(&gauge_contract, gauge_config).unwrap();
+    suite
+        .place_vote(&gauge_contract, voter2, gauge_id, Some(voter1.to_owned()))
+        .unwrap();
+    suite
+        .add_option(&gauge_contract, voter1, gauge_id, "option1")
+        .unwrap();
+    suite
+        .execute_options(&gauge_contract, voter1, gauge_id)
+        .unwrap();
+    assert_eq!(suite.query_balance(voter1, "ujuno").unwrap(), 1000);
+}
+
+#[test]
+fn instantiate_with_gauges() {
+    let voter1 = "voter1";
+    let mut suite = SuiteBuilder::new()
+        .with_voting_members(&[(voter1, 100)])
+        .build();
+
+    let good_config = suite
+        .instantiate_adapter_and_return_config(&["option1", "option2"], (1000, "ujuno"), None, None)
+        .unwrap();
+    let gauge_contract = suite
+        .instantiate_gauge(vec![good_config.clone(), good_config.clone()])
+        .unwrap();
+    assert_eq!(suite.query_gauges(gauge_contract.clone()).unwrap().len(), 2);
+    assert_eq!(
+        suite.query_list_options(&gauge_contract, 1).unwrap(),
+        vec![
+            ("option1".to_owned(), Uint128::zero()),
+            ("option2".to_owned(), Uint128::zero())
+        ]
+    );
+
+    // the gauge contract itself does not answer adapter queries
+    let bad_config = GaugeConfig {
+        adapter: gauge_contract.to_string(),
+        ..good_config.clone()
+    };
+    let err = suite
+        .instantiate_gauge(vec![good_config.clone(), bad_config])
+        .unwrap_err();
+    assert_eq!(
+        ContractError::InvalidAdapter(gauge_contract.to_string()),
+        err.downcast().unwrap()
+    );
+
+    // not even a contract
+    let bad_config = GaugeConfig {
+        adapter: "notacontract".to_owned(),
+        ..good_config.clone()
+    };
+    let err = suite
+        .instantiate_gauge(vec![bad_config, good_config])
+        .unwrap_err();
+    assert_eq!(
+        ContractError::InvalidAdapter("notacontract".to_owned()),
+        err.downcast().unwrap()
+    );
+}
+
+#[test]
+fn gauge_ids_never_reused_and_title_lookup() {
+    let voter1 = "voter1";
+    let mut suite = SuiteBuilder::new()
+        .with_voting_members(&[(voter1, 100)])
+        .build();
+
+    let gauge_contract = init_gauge(&mut suite, &[voter1]);
+
+    for title in ["rewards", "grants", "rewards"] {
+        let mut config = suite
+            .instantiate_adapter_and_return_config(&["option1"], (1000, "ujuno"), None, None)
+            .unwrap();
+        config.title = title.to_owned();
+        suite.create_gauge(&gauge_contract, config).unwrap();
+    }
+
+    // stopping a gauge does not free its id
+    suite
+        .stop_gauge(&gauge_contract, suite.owner.clone(), 2)
+        .unwrap();
+    let mut config = suite
+        .instantiate_adapter_and_return_config(&["option1"], (1000, "ujuno"), None, None)
+        .unwrap();
+    config.title = "rewards".to_owned();
+    suite.create_gauge(&gauge_contract, config).unwrap();
+
+    let ids: Vec<u64> = suite
+        .query_gauges(gauge_contract.clone())
+        .unwrap()
+        .into_iter()
+        .map(|g| g.id)
+        .collect();
+    assert_eq!(ids, vec![0, 1, 2, 3]);
+
+    // all matches are returned, including the stopped gauge
+    let rewards = suite
+        .query_gauges_by_title(&gauge_contract, "rewards")
+        .unwrap();
+    assert_eq!(
+        rewards.iter().map(|g| g.id).collect::<Vec<_>>(),
+        vec![0, 2, 3]
+    );
+    assert!(rewards[1].is_stopped);
+    let grants = suite
+        .query_gauges_by_title(&gauge_contract, "grants")
+        .unwrap();
+    assert_eq!(grants.iter().map(|g| g.id).collect::<Vec<_>>(), vec![1]);
+    assert_eq!(
+        suite
+            .query_gauges_by_title(&gauge_contract, "unknown")
+            .unwrap(),
+        vec![]
+    );
+}
+
 #[test]
 fn gauge_can_upgrade_from_self() {
     let voter1 = "voter1";
@@ -80,8 +560,47 @@ fn gauge_can_upgrade_from_self() {
             max_options_selected: 10,
             max_available_percentage: None,
             is_stopped: false,
+            voting_closed: false,
             next_epoch: suite.current_time() + 7 * 86400,
             reset: None,
+            to_distribute: None,
+            reward_schedule: vec![],
+            executed_epochs: 0,
+            max_voters: None,
+            validate_option_on_vote: false,
+            voter_restriction: None,
+            tenure_curve: None,
+            bootstrap_equal_split: false,
+            reclaim_votes_on_remove: false,
+            adapter_multipliers: false,
+            isolate_failures: false,
+            epoch_unit: EpochUnit::Time,
+            rounding: Rounding::Down,
+            tags: vec![],
+            escrow_balance: vec![],
+            extra_adapters: vec![],
+            min_absolute_tally: None,
+            distribution_mode: DistributionMode::Proportional,
+            boost_source: None,
+            flashloan_resistant: false,
+            distribution_hook: None,
+            auto_prune_zero_options: false,
+            require_full_allocation: false,
+            total_budget: None,
+            epoch_label: None,
+            adapter_reward: false,
+            vote_change_fee: None,
+            execute_policy: ExecutePolicy::Anyone,
+            max_paid_options: None,
+            deprecated_vote_policy: DeprecatedVotePolicy::Ignore,
+            min_distribution_per_option: None,
+            max_total_options: None,
+            post_execute_callback: None,
+            align_to: None,
+            max_voter_influence: None,
+            execute_condition: None,
+            options_frozen: false,
+            swap_route: None,
         }
     );
 }
@@ -118,8 +637,47 @@ fn gauge_migrate_with_next_epochs() {
             max_options_selected: 10,
             max_available_percentage: None,
             is_stopped: false,
+            voting_closed: false,
             next_epoch: suite.current_time() + 7 * 86400,
             reset: None,
+            to_distribute: None,
+            reward_schedule: vec![],
+            executed_epochs: 0,
+            max_voters: None,
+            validate_option_on_vote: false,
+            voter_restriction: None,
+            tenure_curve: None,
+            bootstrap_equal_split: false,
+            reclaim_votes_on_remove: false,
+            adapter_multipliers: false,
+            isolate_failures: false,
+            epoch_unit: EpochUnit::Time,
+            rounding: Rounding::Down,
+            tags: vec![],
+            escrow_balance: vec![],
+            extra_adapters: vec![],
+            min_absolute_tally: None,
+            distribution_mode: DistributionMode::Proportional,
+            boost_source: None,
+            flashloan_resistant: false,
+            distribution_hook: None,
+            auto_prune_zero_options: false,
+            require_full_allocation: false,
+            total_budget: None,
+            epoch_label: None,
+            adapter_reward: false,
+            vote_change_fee: None,
+            execute_policy: ExecutePolicy::Anyone,
+            max_paid_options: None,
+            deprecated_vote_policy: DeprecatedVotePolicy::Ignore,
+            min_distribution_per_option: None,
+            max_total_options: None,
+            post_execute_callback: None,
+            align_to: None,
+            max_voter_influence: None,
+            execute_condition: None,
+            options_frozen: false,
+            swap_route: None,
         }
     );
 
@@ -151,8 +709,47 @@ fn gauge_migrate_with_next_epochs() {
             max_options_selected: 10,
             max_available_percentage: None,
             is_stopped: false,
+            voting_closed: false,
             next_epoch: suite.current_time() + 14 * 86400,
             reset: None,
+            to_distribute: None,
+            reward_schedule: vec![],
+            executed_epochs: 0,
+            max_voters: None,
+            validate_option_on_vote: false,
+            voter_restriction: None,
+            tenure_curve: None,
+            bootstrap_equal_split: false,
+            reclaim_votes_on_remove: false,
+            adapter_multipliers: false,
+            isolate_failures: false,
+            epoch_unit: EpochUnit::Time,
+            rounding: Rounding::Down,
+            tags: vec![],
+            escrow_balance: vec![],
+            extra_adapters: vec![],
+            min_absolute_tally: None,
+            distribution_mode: DistributionMode::Proportional,
+            boost_source: None,
+            flashloan_resistant: false,
+            distribution_hook: None,
+            auto_prune_zero_options: false,
+            require_full_allocation: false,
+            total_budget: None,
+            epoch_label: None,
+            adapter_reward: false,
+            vote_change_fee: None,
+            execute_policy: ExecutePolicy::Anyone,
+            max_paid_options: None,
+            deprecated_vote_policy: DeprecatedVotePolicy::Ignore,
+            min_distribution_per_option: None,
+            max_total_options: None,
+            post_execute_callback: None,
+            align_to: None,
+            max_voter_influence: None,
+            execute_condition: None,
+            options_frozen: false,
+            swap_route: None,
         }
     );
 
@@ -245,47 +842,20 @@ fn execute_gauge() {
     );
 }
 
-/// Small helper method to setup the gauge contract.
-/// Make sure that `voter` has voting power.
-fn init_gauge(suite: &mut Suite, voters: &[&str]) -> Addr {
-    suite.next_block();
-    suite
-        .propose_update_proposal_module(voters[0], None)
-        .unwrap();
-    suite.next_block();
-    let proposal = suite.list_proposals().unwrap()[0];
-    for voter in voters {
-        suite
-            .place_vote_single(*voter, proposal, Vote::Yes)
-            .unwrap();
-    }
-    suite.next_block();
-    suite.execute_single_proposal(voters[0], proposal).unwrap();
-    let proposal_modules = suite.query_proposal_modules().unwrap();
-
-    // Second proposal module is cw proposal single, first one is newly added gauge
-    assert_eq!(proposal_modules.len(), 2);
-    proposal_modules[0].clone()
-}
-
 #[test]
-fn query_last_execution() {
+fn adapter_sample_msgs_match_execution() {
     let voter1 = "voter1";
     let voter2 = "voter2";
-
-    let reward_to_distribute = (2000, "ujuno");
-
     let mut suite = SuiteBuilder::new()
-        .with_voting_members(&[(voter1, 100), (voter2, 100)])
-        .with_core_balance(reward_to_distribute)
+        .with_voting_members(&[(voter1, 300), (voter2, 100)])
+        .with_core_balance((1000, "ujuno"))
         .build();
 
     let gauge_contract = init_gauge(&mut suite, &[voter1, voter2]);
-
     suite
         .instantiate_adapter_and_create_gauge(
             gauge_contract.clone(),
-            &[voter1, voter2, gauge_contract.as_str()],
+            &[voter1, voter2],
             (1000, "ujuno"),
             None,
             None,
@@ -293,26 +863,161 @@ fn query_last_execution() {
         .unwrap();
     let gauge_id = 0;
 
-    assert_eq!(
-        suite
-            .query_last_executed_set(&gauge_contract, gauge_id)
-            .unwrap(),
-        None,
-        "not executed yet"
-    );
-
-    // vote
     suite
         .place_vote(&gauge_contract, voter1, gauge_id, Some(voter1.to_owned()))
         .unwrap();
     suite
-        .place_votes(
-            &gauge_contract,
-            voter2,
-            gauge_id,
-            vec![
-                (gauge_contract.to_string(), Decimal::percent(40)),
-                (voter2.to_owned(), Decimal::percent(60)),
+        .place_vote(&gauge_contract, voter2, gauge_id, Some(voter2.to_owned()))
+        .unwrap();
+
+    let selected = suite.query_selected_set(&gauge_contract, gauge_id).unwrap();
+    let sample = suite
+        .query_adapter_sample_msgs(&gauge_contract, gauge_id, selected)
+        .unwrap();
+    assert_eq!(
+        sample,
+        vec![
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: voter1.to_owned(),
+                amount: vec![coin(750, "ujuno")],
+            }),
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: voter2.to_owned(),
+                amount: vec![coin(250, "ujuno")],
+            }),
+        ]
+    );
+
+    // options without power are left out instead of dividing by a zero sum
+    let sample = suite
+        .query_adapter_sample_msgs(
+            &gauge_contract,
+            gauge_id,
+            vec![
+                (voter1.to_owned(), Uint128::zero()),
+                (voter2.to_owned(), Uint128::zero()),
+            ],
+        )
+        .unwrap();
+    assert_eq!(sample, vec![]);
+
+    // querying doesn't dispatch anything
+    assert_eq!(suite.query_balance(voter1, "ujuno").unwrap(), 0);
+
+    // execute sends exactly the sampled messages
+    suite.advance_time(EPOCH);
+    suite
+        .execute_options(&gauge_contract, voter1, gauge_id)
+        .unwrap();
+    assert_eq!(suite.query_balance(voter1, "ujuno").unwrap(), 750);
+    assert_eq!(suite.query_balance(voter2, "ujuno").unwrap(), 250);
+}
+
+#[test]
+fn query_execution_complexity() {
+    let voters = ["voter1", "voter2", "voter3"];
+    let mut suite = SuiteBuilder::new()
+        .with_voting_members(&[(voters[0], 100), (voters[1], 100), (voters[2], 100)])
+        .build();
+
+    let gauge_contract = init_gauge(&mut suite, &voters);
+    for isolate_failures in [false, true] {
+        let mut gauge_config = suite
+            .instantiate_adapter_and_return_config(&voters, (1000, "ujuno"), None, None)
+            .unwrap();
+        gauge_config.isolate_failures = isolate_failures;
+        suite.create_gauge(&gauge_contract, gauge_config).unwrap();
+    }
+
+    // nothing selected yet, so no payouts
+    assert_eq!(
+        suite
+            .query_execution_complexity(&gauge_contract, 1)
+            .unwrap(),
+        ExecutionComplexityResponse {
+            voter_count: 0,
+            option_count: 3,
+            estimated_submsgs: 0,
+        }
+    );
+
+    for gauge_id in [0, 1] {
+        for voter in &voters[..2] {
+            suite
+                .place_vote(&gauge_contract, *voter, gauge_id, Some(voter.to_string()))
+                .unwrap();
+        }
+    }
+
+    // all messages go through one proposal hook
+    assert_eq!(
+        suite
+            .query_execution_complexity(&gauge_contract, 0)
+            .unwrap(),
+        ExecutionComplexityResponse {
+            voter_count: 2,
+            option_count: 3,
+            estimated_submsgs: 1,
+        }
+    );
+    // one submessage per selected option
+    assert_eq!(
+        suite
+            .query_execution_complexity(&gauge_contract, 1)
+            .unwrap(),
+        ExecutionComplexityResponse {
+            voter_count: 2,
+            option_count: 3,
+            estimated_submsgs: 2,
+        }
+    );
+}
+
+#[test]
+fn query_last_execution() {
+    let voter1 = "voter1";
+    let voter2 = "voter2";
+
+    let reward_to_distribute = (2000, "ujuno");
+
+    let mut suite = SuiteBuilder::new()
+        .with_voting_members(&[(voter1, 100), (voter2, 100)])
+        .with_core_balance(reward_to_distribute)
+        .build();
+
+    let gauge_contract = init_gauge(&mut suite, &[voter1, voter2]);
+
+    suite
+        .instantiate_adapter_and_create_gauge(
+            gauge_contract.clone(),
+            &[voter1, voter2, gauge_contract.as_str()],
+            (1000, "ujuno"),
+            None,
+            None,
+        )
+        .unwrap();
+    let gauge_id = 0;
+
+    assert_eq!(
+        suite
+            .query_last_executed_set(&gauge_contract, gauge_id)
+            .unwrap(),
+        None,
+        "not executed yet"
+    );
+
+    // vote
+    suite
+        .place_vote(&gauge_contract, voter1, gauge_id, Some(voter1.to_owned()))
+        .unwrap();
+    suite
+        .place_votes(
+            &gauge_contract,
+            voter2,
+            gauge_id,
+            vec![
+                (gauge_contract.to_string(), Decimal::percent(40)),
+                (voter2.to_owned(), Decimal::percent(60)),
             ],
         )
         .unwrap();
@@ -369,6 +1074,198 @@ fn query_last_execution() {
     );
 }
 
+#[test]
+fn query_set_delta() {
+    let voter1 = "voter1";
+    let voter2 = "voter2";
+
+    let mut suite = SuiteBuilder::new()
+        .with_voting_members(&[(voter1, 100), (voter2, 100)])
+        .with_core_balance((2000, "ujuno"))
+        .build();
+
+    let gauge_contract = init_gauge(&mut suite, &[voter1, voter2]);
+
+    suite
+        .instantiate_adapter_and_create_gauge(
+            gauge_contract.clone(),
+            &[voter1, voter2],
+            (1000, "ujuno"),
+            None,
+            None,
+        )
+        .unwrap();
+    let gauge_id = 0;
+
+    suite
+        .place_vote(&gauge_contract, voter1, gauge_id, Some(voter1.to_owned()))
+        .unwrap();
+    suite
+        .place_vote(&gauge_contract, voter2, gauge_id, Some(voter2.to_owned()))
+        .unwrap();
+
+    // nothing executed yet, so everything is new
+    assert_eq!(
+        suite.query_set_delta(&gauge_contract, gauge_id).unwrap(),
+        vec![
+            OptionDelta {
+                option: voter2.to_owned(),
+                last_amount: Uint128::zero(),
+                projected_amount: Uint128::new(100),
+                delta: Uint128::new(100),
+                decreased: false,
+            },
+            OptionDelta {
+                option: voter1.to_owned(),
+                last_amount: Uint128::zero(),
+                projected_amount: Uint128::new(100),
+                delta: Uint128::new(100),
+                decreased: false,
+            },
+        ]
+    );
+
+    suite.advance_time(EPOCH);
+    suite
+        .execute_options(&gauge_contract, voter1, gauge_id)
+        .unwrap();
+
+    // voter1 shifts the vote to the other option
+    suite
+        .place_vote(&gauge_contract, voter1, gauge_id, Some(voter2.to_owned()))
+        .unwrap();
+
+    assert_eq!(
+        suite.query_set_delta(&gauge_contract, gauge_id).unwrap(),
+        vec![
+            OptionDelta {
+                option: voter2.to_owned(),
+                last_amount: Uint128::new(100),
+                projected_amount: Uint128::new(200),
+                delta: Uint128::new(100),
+                decreased: false,
+            },
+            OptionDelta {
+                option: voter1.to_owned(),
+                last_amount: Uint128::new(100),
+                projected_amount: Uint128::zero(),
+                delta: Uint128::new(100),
+                decreased: true,
+            },
+        ]
+    );
+}
+
+#[test]
+fn query_executable_gauges() {
+    let voter1 = "voter1";
+    let mut suite = SuiteBuilder::new()
+        .with_voting_members(&[(voter1, 100)])
+        .build();
+
+    let gauge_contract = init_gauge(&mut suite, &[voter1]);
+    for _ in 0..3 {
+        suite
+            .instantiate_adapter_and_create_gauge(
+                gauge_contract.clone(),
+                &[voter1],
+                (1000, "ujuno"),
+                None,
+                None,
+            )
+            .unwrap();
+    }
+    assert_eq!(
+        suite
+            .query_executable_gauges(&gauge_contract, None)
+            .unwrap(),
+        Vec::<u64>::new()
+    );
+
+    // gauges 0 and 2 are overdue, gauge 1 was executed recently
+    suite.advance_time(EPOCH);
+    suite.execute_options(&gauge_contract, voter1, 1).unwrap();
+    assert_eq!(
+        suite
+            .query_executable_gauges(&gauge_contract, None)
+            .unwrap(),
+        vec![0, 2]
+    );
+    assert_eq!(
+        suite.query_executable_gauges(&gauge_contract, 1).unwrap(),
+        vec![0]
+    );
+
+    // stopped gauges are not executable
+    suite
+        .stop_gauge(&gauge_contract, suite.owner.clone(), 0)
+        .unwrap();
+    assert_eq!(
+        suite
+            .query_executable_gauges(&gauge_contract, None)
+            .unwrap(),
+        vec![2]
+    );
+}
+
+#[test]
+fn query_executable_gauges_with_preview() {
+    let voter1 = "voter1";
+    let voter2 = "voter2";
+    let mut suite = SuiteBuilder::new()
+        .with_voting_members(&[(voter1, 100), (voter2, 100)])
+        .build();
+
+    let gauge_contract = init_gauge(&mut suite, &[voter1, voter2]);
+    for _ in 0..2 {
+        suite
+            .instantiate_adapter_and_create_gauge(
+                gauge_contract.clone(),
+                &[voter1, voter2],
+                (1000, "ujuno"),
+                None,
+                None,
+            )
+            .unwrap();
+    }
+    assert_eq!(
+        suite
+            .query_executable_gauges_with_preview(&gauge_contract, None)
+            .unwrap(),
+        vec![]
+    );
+
+    // only gauge 1 got votes, executing gauge 0 would not do anything
+    suite
+        .place_vote(&gauge_contract, voter1, 1, Some(voter2.to_owned()))
+        .unwrap();
+    suite.advance_time(EPOCH);
+    assert_eq!(
+        suite
+            .query_executable_gauges_with_preview(&gauge_contract, None)
+            .unwrap(),
+        vec![
+            ExecutableGaugePreview {
+                gauge_id: 0,
+                selected_set: vec![],
+                is_noop: true,
+            },
+            ExecutableGaugePreview {
+                gauge_id: 1,
+                selected_set: vec![(voter2.to_owned(), Uint128::new(100))],
+                is_noop: false,
+            },
+        ]
+    );
+    assert_eq!(
+        suite
+            .query_executable_gauges_with_preview(&gauge_contract, 1)
+            .unwrap()
+            .len(),
+        1
+    );
+}
+
 #[test]
 fn execute_gauge_twice_same_epoch() {
     let voter1 = "voter1";
@@ -562,15 +1459,16 @@ fn execute_stopped_gauge() {
 }
 
 #[test]
-fn update_gauge() {
+fn close_voting_allows_final_execution() {
     let voter1 = "voter1";
     let voter2 = "voter2";
+    let reward_to_distribute = (2000, "ujuno");
     let mut suite = SuiteBuilder::new()
         .with_voting_members(&[(voter1, 100), (voter2, 100)])
+        .with_core_balance(reward_to_distribute)
         .build();
 
     let gauge_contract = init_gauge(&mut suite, &[voter1, voter2]);
-
     let gauge_adapter = suite
         .instantiate_adapter_and_create_gauge(
             gauge_contract.clone(),
@@ -580,44 +1478,254 @@ fn update_gauge() {
             None,
         )
         .unwrap();
+    let gauge_id = 0;
 
-    let second_gauge_adapter = suite
-        .instantiate_adapter_and_create_gauge(
-            gauge_contract.clone(),
-            &[voter1, voter2],
-            (1000, "uusdc"),
-            None,
-            None,
-        )
+    suite
+        .place_vote(&gauge_contract, voter1, gauge_id, Some(voter1.to_owned()))
         .unwrap();
 
-    let response = suite.query_gauges(gauge_contract.clone()).unwrap();
+    // only owner can close voting
+    let err = suite
+        .close_voting(&gauge_contract, voter1, gauge_id)
+        .unwrap_err();
+    assert_eq!(ContractError::Unauthorized {}, err.downcast().unwrap());
+    suite
+        .close_voting(&gauge_contract, suite.owner.clone(), gauge_id)
+        .unwrap();
+
+    // no more votes or options
+    let err = suite
+        .place_vote(&gauge_contract, voter2, gauge_id, Some(voter1.to_owned()))
+        .unwrap_err();
     assert_eq!(
-        response,
-        vec![
-            GaugeResponse {
-                id: 0,
-                title: "gauge".to_owned(),
-                adapter: gauge_adapter.to_string(),
-                epoch_size: EPOCH,
-                min_percent_selected: Some(Decimal::percent(5)),
-                max_options_selected: 10,
-                max_available_percentage: None,
-                is_stopped: false,
-                next_epoch: suite.current_time() + 7 * 86400,
-                reset: None,
-            },
-            GaugeResponse {
-                id: 1,
-                title: "gauge".to_owned(),
-                adapter: second_gauge_adapter.to_string(),
-                epoch_size: EPOCH,
-                min_percent_selected: Some(Decimal::percent(5)),
-                max_options_selected: 10,
-                max_available_percentage: None,
-                is_stopped: false,
+        ContractError::VotingClosed(gauge_id),
+        err.downcast().unwrap()
+    );
+    suite.add_valid_option(&gauge_adapter, "option1").unwrap();
+    let err = suite
+        .add_option(&gauge_contract, voter1, gauge_id, "option1")
+        .unwrap_err();
+    assert_eq!(
+        ContractError::VotingClosed(gauge_id),
+        err.downcast().unwrap()
+    );
+
+    // but everything can still be read
+    let gauge = suite.query_gauge(gauge_contract.clone(), gauge_id).unwrap();
+    assert!(gauge.voting_closed);
+    assert!(!gauge.is_stopped);
+    assert_eq!(
+        suite.query_selected_set(&gauge_contract, gauge_id).unwrap(),
+        vec![(voter1.to_owned(), Uint128::new(100))]
+    );
+
+    // final execution distributes the rewards
+    suite.advance_time(EPOCH);
+    suite
+        .execute_options(&gauge_contract, voter2, gauge_id)
+        .unwrap();
+    assert_eq!(suite.query_balance(voter1, "ujuno").unwrap(), 1000);
+
+    // afterwards the gauge is stopped
+    let gauge = suite.query_gauge(gauge_contract.clone(), gauge_id).unwrap();
+    assert!(gauge.is_stopped);
+    suite.advance_time(EPOCH);
+    let err = suite
+        .execute_options(&gauge_contract, voter2, gauge_id)
+        .unwrap_err();
+    assert_eq!(
+        ContractError::GaugeStopped(gauge_id),
+        err.downcast().unwrap()
+    );
+    assert_eq!(
+        suite
+            .query_vote(&gauge_contract, gauge_id, voter1)
+            .unwrap()
+            .unwrap()
+            .voter,
+        voter1
+    );
+}
+
+#[test]
+fn gauge_state_change_events() {
+    let voter1 = "voter1";
+    let voter2 = "voter2";
+    let mut suite = SuiteBuilder::new()
+        .with_voting_members(&[(voter1, 100), (voter2, 100)])
+        .with_core_balance((2000, "ujuno"))
+        .build();
+    let owner = suite.owner.clone();
+    let state_change = |gauge_id: u64, from: &str, to: &str, by: &str| {
+        Event::new("wasm-gauge_state_change")
+            .add_attribute("gauge_id", gauge_id.to_string())
+            .add_attribute("from", from)
+            .add_attribute("to", to)
+            .add_attribute("by", by)
+    };
+
+    let gauge_contract = init_gauge(&mut suite, &[voter1, voter2]);
+    for gauge_id in 0..2 {
+        let gauge_config = suite
+            .instantiate_adapter_and_return_config(&[voter1, voter2], (1000, "ujuno"), None, None)
+            .unwrap();
+        let res = suite.create_gauge(&gauge_contract, gauge_config).unwrap();
+        assert!(res.has_event(&state_change(gauge_id, "none", "active", &owner)));
+    }
+
+    // stopping an active gauge
+    let res = suite.stop_gauge(&gauge_contract, &owner, 0).unwrap();
+    assert!(res.has_event(&state_change(0, "active", "stopped", &owner)));
+    // stopping it again is not a transition
+    let res = suite.stop_gauge(&gauge_contract, &owner, 0).unwrap();
+    assert!(!res.events.iter().any(|e| e.ty == "wasm-gauge_state_change"));
+
+    // closing voting, the final execution stops the gauge
+    suite
+        .place_vote(&gauge_contract, voter1, 1, Some(voter1.to_owned()))
+        .unwrap();
+    let res = suite.close_voting(&gauge_contract, &owner, 1).unwrap();
+    assert!(res.has_event(&state_change(1, "active", "closed", &owner)));
+    suite.advance_time(EPOCH);
+    let res = suite.execute_options(&gauge_contract, voter2, 1).unwrap();
+    assert!(res.has_event(&state_change(1, "closed", "stopped", voter2)));
+}
+
+#[test]
+fn update_gauge() {
+    let voter1 = "voter1";
+    let voter2 = "voter2";
+    let mut suite = SuiteBuilder::new()
+        .with_voting_members(&[(voter1, 100), (voter2, 100)])
+        .build();
+
+    let gauge_contract = init_gauge(&mut suite, &[voter1, voter2]);
+
+    let gauge_adapter = suite
+        .instantiate_adapter_and_create_gauge(
+            gauge_contract.clone(),
+            &[voter1, voter2],
+            (1000, "ujuno"),
+            None,
+            None,
+        )
+        .unwrap();
+
+    let second_gauge_adapter = suite
+        .instantiate_adapter_and_create_gauge(
+            gauge_contract.clone(),
+            &[voter1, voter2],
+            (1000, "uusdc"),
+            None,
+            None,
+        )
+        .unwrap();
+
+    let response = suite.query_gauges(gauge_contract.clone()).unwrap();
+    assert_eq!(
+        response,
+        vec![
+            GaugeResponse {
+                id: 0,
+                title: "gauge".to_owned(),
+                adapter: gauge_adapter.to_string(),
+                epoch_size: EPOCH,
+                min_percent_selected: Some(Decimal::percent(5)),
+                max_options_selected: 10,
+                max_available_percentage: None,
+                is_stopped: false,
+                voting_closed: false,
+                next_epoch: suite.current_time() + 7 * 86400,
+                reset: None,
+                to_distribute: None,
+                reward_schedule: vec![],
+                executed_epochs: 0,
+                max_voters: None,
+                validate_option_on_vote: false,
+                voter_restriction: None,
+                tenure_curve: None,
+                bootstrap_equal_split: false,
+                reclaim_votes_on_remove: false,
+                adapter_multipliers: false,
+                isolate_failures: false,
+                epoch_unit: EpochUnit::Time,
+                rounding: Rounding::Down,
+                tags: vec![],
+                escrow_balance: vec![],
+                extra_adapters: vec![],
+                min_absolute_tally: None,
+                distribution_mode: DistributionMode::Proportional,
+                boost_source: None,
+                flashloan_resistant: false,
+                distribution_hook: None,
+                auto_prune_zero_options: false,
+                require_full_allocation: false,
+                total_budget: None,
+                epoch_label: None,
+                adapter_reward: false,
+                vote_change_fee: None,
+                execute_policy: ExecutePolicy::Anyone,
+                max_paid_options: None,
+                deprecated_vote_policy: DeprecatedVotePolicy::Ignore,
+                min_distribution_per_option: None,
+                max_total_options: None,
+                post_execute_callback: None,
+                align_to: None,
+                max_voter_influence: None,
+                execute_condition: None,
+                options_frozen: false,
+                swap_route: None,
+            },
+            GaugeResponse {
+                id: 1,
+                title: "gauge".to_owned(),
+                adapter: second_gauge_adapter.to_string(),
+                epoch_size: EPOCH,
+                min_percent_selected: Some(Decimal::percent(5)),
+                max_options_selected: 10,
+                max_available_percentage: None,
+                is_stopped: false,
+                voting_closed: false,
                 next_epoch: suite.current_time() + 7 * 86400,
                 reset: None,
+                to_distribute: None,
+                reward_schedule: vec![],
+                executed_epochs: 0,
+                max_voters: None,
+                validate_option_on_vote: false,
+                voter_restriction: None,
+                tenure_curve: None,
+                bootstrap_equal_split: false,
+                reclaim_votes_on_remove: false,
+                adapter_multipliers: false,
+                isolate_failures: false,
+                epoch_unit: EpochUnit::Time,
+                rounding: Rounding::Down,
+                tags: vec![],
+                escrow_balance: vec![],
+                extra_adapters: vec![],
+                min_absolute_tally: None,
+                distribution_mode: DistributionMode::Proportional,
+                boost_source: None,
+                flashloan_resistant: false,
+                distribution_hook: None,
+                auto_prune_zero_options: false,
+                require_full_allocation: false,
+                total_budget: None,
+                epoch_label: None,
+                adapter_reward: false,
+                vote_change_fee: None,
+                execute_policy: ExecutePolicy::Anyone,
+                max_paid_options: None,
+                deprecated_vote_policy: DeprecatedVotePolicy::Ignore,
+                min_distribution_per_option: None,
+                max_total_options: None,
+                post_execute_callback: None,
+                align_to: None,
+                max_voter_influence: None,
+                execute_condition: None,
+                options_frozen: false,
+                swap_route: None,
             }
         ]
     );
@@ -653,8 +1761,47 @@ fn update_gauge() {
                 max_options_selected: new_max_options,
                 max_available_percentage: new_max_available_percentage,
                 is_stopped: false,
+                voting_closed: false,
                 next_epoch: suite.current_time() + 7 * 86400,
                 reset: None,
+                to_distribute: None,
+                reward_schedule: vec![],
+                executed_epochs: 0,
+                max_voters: None,
+                validate_option_on_vote: false,
+                voter_restriction: None,
+                tenure_curve: None,
+                bootstrap_equal_split: false,
+                reclaim_votes_on_remove: false,
+                adapter_multipliers: false,
+                isolate_failures: false,
+                epoch_unit: EpochUnit::Time,
+                rounding: Rounding::Down,
+                tags: vec![],
+                escrow_balance: vec![],
+                extra_adapters: vec![],
+                min_absolute_tally: None,
+                distribution_mode: DistributionMode::Proportional,
+                boost_source: None,
+                flashloan_resistant: false,
+                distribution_hook: None,
+                auto_prune_zero_options: false,
+                require_full_allocation: false,
+                total_budget: None,
+                epoch_label: None,
+                adapter_reward: false,
+                vote_change_fee: None,
+                execute_policy: ExecutePolicy::Anyone,
+                max_paid_options: None,
+                deprecated_vote_policy: DeprecatedVotePolicy::Ignore,
+                min_distribution_per_option: None,
+                max_total_options: None,
+                post_execute_callback: None,
+                align_to: None,
+                max_voter_influence: None,
+                execute_condition: None,
+                options_frozen: false,
+                swap_route: None,
             },
             GaugeResponse {
                 id: 1,
@@ -665,8 +1812,47 @@ fn update_gauge() {
                 max_options_selected: 10,
                 max_available_percentage: None,
                 is_stopped: false,
+                voting_closed: false,
                 next_epoch: suite.current_time() + 7 * 86400,
                 reset: None,
+                to_distribute: None,
+                reward_schedule: vec![],
+                executed_epochs: 0,
+                max_voters: None,
+                validate_option_on_vote: false,
+                voter_restriction: None,
+                tenure_curve: None,
+                bootstrap_equal_split: false,
+                reclaim_votes_on_remove: false,
+                adapter_multipliers: false,
+                isolate_failures: false,
+                epoch_unit: EpochUnit::Time,
+                rounding: Rounding::Down,
+                tags: vec![],
+                escrow_balance: vec![],
+                extra_adapters: vec![],
+                min_absolute_tally: None,
+                distribution_mode: DistributionMode::Proportional,
+                boost_source: None,
+                flashloan_resistant: false,
+                distribution_hook: None,
+                auto_prune_zero_options: false,
+                require_full_allocation: false,
+                total_budget: None,
+                epoch_label: None,
+                adapter_reward: false,
+                vote_change_fee: None,
+                execute_policy: ExecutePolicy::Anyone,
+                max_paid_options: None,
+                deprecated_vote_policy: DeprecatedVotePolicy::Ignore,
+                min_distribution_per_option: None,
+                max_total_options: None,
+                post_execute_callback: None,
+                align_to: None,
+                max_voter_influence: None,
+                execute_condition: None,
+                options_frozen: false,
+                swap_route: None,
             }
         ]
     );
@@ -697,8 +1883,47 @@ fn update_gauge() {
                 max_options_selected: new_max_options,
                 max_available_percentage: new_max_available_percentage,
                 is_stopped: false,
+                voting_closed: false,
                 next_epoch: suite.current_time() + 7 * 86400,
                 reset: None,
+                to_distribute: None,
+                reward_schedule: vec![],
+                executed_epochs: 0,
+                max_voters: None,
+                validate_option_on_vote: false,
+                voter_restriction: None,
+                tenure_curve: None,
+                bootstrap_equal_split: false,
+                reclaim_votes_on_remove: false,
+                adapter_multipliers: false,
+                isolate_failures: false,
+                epoch_unit: EpochUnit::Time,
+                rounding: Rounding::Down,
+                tags: vec![],
+                escrow_balance: vec![],
+                extra_adapters: vec![],
+                min_absolute_tally: None,
+                distribution_mode: DistributionMode::Proportional,
+                boost_source: None,
+                flashloan_resistant: false,
+                distribution_hook: None,
+                auto_prune_zero_options: false,
+                require_full_allocation: false,
+                total_budget: None,
+                epoch_label: None,
+                adapter_reward: false,
+                vote_change_fee: None,
+                execute_policy: ExecutePolicy::Anyone,
+                max_paid_options: None,
+                deprecated_vote_policy: DeprecatedVotePolicy::Ignore,
+                min_distribution_per_option: None,
+                max_total_options: None,
+                post_execute_callback: None,
+                align_to: None,
+                max_voter_influence: None,
+                execute_condition: None,
+                options_frozen: false,
+                swap_route: None,
             },
             GaugeResponse {
                 id: 1,
@@ -709,8 +1934,47 @@ fn update_gauge() {
                 max_options_selected: 10,
                 max_available_percentage: None,
                 is_stopped: false,
+                voting_closed: false,
                 next_epoch: suite.current_time() + 7 * 86400,
                 reset: None,
+                to_distribute: None,
+                reward_schedule: vec![],
+                executed_epochs: 0,
+                max_voters: None,
+                validate_option_on_vote: false,
+                voter_restriction: None,
+                tenure_curve: None,
+                bootstrap_equal_split: false,
+                reclaim_votes_on_remove: false,
+                adapter_multipliers: false,
+                isolate_failures: false,
+                epoch_unit: EpochUnit::Time,
+                rounding: Rounding::Down,
+                tags: vec![],
+                escrow_balance: vec![],
+                extra_adapters: vec![],
+                min_absolute_tally: None,
+                distribution_mode: DistributionMode::Proportional,
+                boost_source: None,
+                flashloan_resistant: false,
+                distribution_hook: None,
+                auto_prune_zero_options: false,
+                require_full_allocation: false,
+                total_budget: None,
+                epoch_label: None,
+                adapter_reward: false,
+                vote_change_fee: None,
+                execute_policy: ExecutePolicy::Anyone,
+                max_paid_options: None,
+                deprecated_vote_policy: DeprecatedVotePolicy::Ignore,
+                min_distribution_per_option: None,
+                max_total_options: None,
+                post_execute_callback: None,
+                align_to: None,
+                max_voter_influence: None,
+                execute_condition: None,
+                options_frozen: false,
+                swap_route: None,
             }
         ]
     );
@@ -790,3 +2054,1244 @@ fn update_gauge() {
         err.downcast().unwrap()
     );
 }
+
+#[test]
+fn update_gauge_flags_selection_changes() {
+    let voter1 = "voter1";
+    let mut suite = SuiteBuilder::new()
+        .with_voting_members(&[(voter1, 100)])
+        .build();
+
+    let gauge_contract = init_gauge(&mut suite, &[voter1]);
+    suite
+        .instantiate_adapter_and_create_gauge(
+            gauge_contract.clone(),
+            &[voter1],
+            (1000, "ujuno"),
+            None,
+            None,
+        )
+        .unwrap();
+    let owner = suite.owner.clone();
+    let changed = Event::new("wasm").add_attribute("selection_behavior_changed", "true");
+
+    // epoch size does not affect selection
+    let res = suite
+        .update_gauge(
+            &owner,
+            gauge_contract.clone(),
+            0,
+            EPOCH * 2,
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+    assert!(!res.has_event(&changed));
+
+    // setting the same value is not a change
+    let res = suite
+        .update_gauge(
+            &owner,
+            gauge_contract.clone(),
+            0,
+            None,
+            Some(Decimal::percent(5)),
+            None,
+            None,
+        )
+        .unwrap();
+    assert!(!res.has_event(&changed));
+
+    let res = suite
+        .update_gauge(
+            &owner,
+            gauge_contract.clone(),
+            0,
+            None,
+            Some(Decimal::percent(10)),
+            None,
+            None,
+        )
+        .unwrap();
+    assert!(res.has_event(&changed));
+
+    let res = suite
+        .update_gauge(&owner, gauge_contract, 0, None, None, 5, None)
+        .unwrap();
+    assert!(res.has_event(&changed));
+}
+
+#[test]
+fn new_gauge_starts_in_epoch_zero() {
+    let voter1 = "voter1";
+    let mut suite = SuiteBuilder::new()
+        .with_voting_members(&[(voter1, 100)])
+        .with_core_balance((1000, "ujuno"))
+        .build();
+
+    let gauge_contract = init_gauge(&mut suite, &[voter1]);
+    suite
+        .instantiate_adapter_and_create_gauge(
+            gauge_contract.clone(),
+            &[voter1],
+            (1000, "ujuno"),
+            None,
+            None,
+        )
+        .unwrap();
+    let gauge_id = 0;
+
+    let gauge = suite.query_gauge(gauge_contract.clone(), gauge_id).unwrap();
+    assert_eq!(gauge.executed_epochs, 0);
+    let next = suite
+        .query_next_execution_response(&gauge_contract, gauge_id)
+        .unwrap();
+    assert_eq!(next.epoch, 0);
+    // the first execution is possible one epoch after creation
+    assert_eq!(
+        next.next,
+        Expiration::AtTime(Timestamp::from_seconds(suite.current_time() + EPOCH))
+    );
+
+    suite
+        .place_vote(&gauge_contract, voter1, gauge_id, Some(voter1.to_owned()))
+        .unwrap();
+    suite.advance_time(EPOCH);
+    suite
+        .execute_options(&gauge_contract, voter1, gauge_id)
+        .unwrap();
+
+    let gauge = suite.query_gauge(gauge_contract.clone(), gauge_id).unwrap();
+    assert_eq!(gauge.executed_epochs, 1);
+    let next = suite
+        .query_next_execution_response(&gauge_contract, gauge_id)
+        .unwrap();
+    assert_eq!(next.epoch, 1);
+    // the execution inputs of epoch 0 are recorded under its number
+    assert!(suite
+        .query_execution_inputs(&gauge_contract, gauge_id, 0)
+        .unwrap()
+        .is_some());
+}
+
+#[test]
+fn executor_recorded_per_epoch() {
+    let voter1 = "voter1";
+    let voter2 = "voter2";
+    let mut suite = SuiteBuilder::new()
+        .with_voting_members(&[(voter1, 100), (voter2, 100)])
+        .with_core_balance((2000, "ujuno"))
+        .build();
+
+    let gauge_contract = init_gauge(&mut suite, &[voter1, voter2]);
+    suite
+        .instantiate_adapter_and_create_gauge(
+            gauge_contract.clone(),
+            &[voter1, voter2],
+            (1000, "ujuno"),
+            None,
+            None,
+        )
+        .unwrap();
+    let gauge_id = 0;
+    suite
+        .place_vote(&gauge_contract, voter1, gauge_id, Some(voter1.to_owned()))
+        .unwrap();
+    assert_eq!(
+        suite
+            .query_last_executed_by(&gauge_contract, gauge_id)
+            .unwrap(),
+        None
+    );
+
+    suite.advance_time(EPOCH);
+    let res = suite
+        .execute_options(&gauge_contract, voter1, gauge_id)
+        .unwrap();
+    assert!(res.has_event(&Event::new("wasm").add_attribute("executed_by", voter1)));
+    suite.advance_time(EPOCH);
+    suite
+        .execute_options(&gauge_contract, "keeper", gauge_id)
+        .unwrap();
+
+    assert_eq!(
+        suite
+            .query_last_executed_by(&gauge_contract, gauge_id)
+            .unwrap(),
+        Some(Addr::unchecked("keeper"))
+    );
+    let executors: Vec<_> = (0..2)
+        .map(|epoch| {
+            suite
+                .query_execution_inputs(&gauge_contract, gauge_id, epoch)
+                .unwrap()
+                .unwrap()
+                .executed_by
+        })
+        .collect();
+    assert_eq!(
+        executors,
+        vec![
+            Some(Addr::unchecked(voter1)),
+            Some(Addr::unchecked("keeper"))
+        ]
+    );
+}
+
+#[test]
+fn height_based_epochs() {
+    let voter1 = "voter1";
+    let voter2 = "voter2";
+    let mut suite = SuiteBuilder::new()
+        .with_voting_members(&[(voter1, 100), (voter2, 100)])
+        .with_core_balance((1000, "ujuno"))
+        .build();
+
+    let gauge_contract = init_gauge(&mut suite, &[voter1, voter2]);
+
+    let mut gauge_config = suite
+        .instantiate_adapter_and_return_config(&[voter1, voter2], (1000, "ujuno"), None, None)
+        .unwrap();
+    gauge_config.epoch_unit = EpochUnit::Height;
+    gauge_config.epoch_size = 0;
+    let err = suite
+        .create_gauge(&gauge_contract, gauge_config.clone())
+        .unwrap_err();
+    assert_eq!(
+        ContractError::EpochHeightTooShort {},
+        err.downcast().unwrap()
+    );
+
+    // a hundred blocks is far below the minimal time based epoch, but valid here
+    gauge_config.epoch_size = 100;
+    suite.create_gauge(&gauge_contract, gauge_config).unwrap();
+    let gauge_id = 0;
+
+    let start = suite.app.block_info().height;
+    assert_eq!(
+        suite
+            .query_next_execution(&gauge_contract, gauge_id)
+            .unwrap(),
+        Expiration::AtHeight(start + 100)
+    );
+
+    suite
+        .place_vote(&gauge_contract, voter1, gauge_id, Some(voter1.to_owned()))
+        .unwrap();
+
+    // time doesn't matter, only blocks
+    suite.advance_blocks(99);
+    let err = suite
+        .execute_options(&gauge_contract, voter1, gauge_id)
+        .unwrap_err();
+    assert_eq!(
+        ContractError::EpochNotReached {
+            gauge_id,
+            current_epoch: start + 99,
+            next_epoch: start + 100,
+        },
+        err.downcast().unwrap()
+    );
+
+    suite.advance_blocks(1);
+    suite
+        .execute_options(&gauge_contract, voter1, gauge_id)
+        .unwrap();
+    assert_eq!(suite.query_balance(voter1, "ujuno").unwrap(), 1000);
+    assert_eq!(
+        suite
+            .query_next_execution(&gauge_contract, gauge_id)
+            .unwrap(),
+        Expiration::AtHeight(start + 200)
+    );
+}
+
+#[test]
+fn list_gauges_by_tag() {
+    let voter1 = "voter1";
+    let voter2 = "voter2";
+    let mut suite = SuiteBuilder::new()
+        .with_voting_members(&[(voter1, 100), (voter2, 100)])
+        .build();
+
+    let gauge_contract = init_gauge(&mut suite, &[voter1, voter2]);
+    let owner = suite.owner.clone();
+
+    let gauge_config = suite
+        .instantiate_adapter_and_return_config(&[voter1, voter2], (1000, "ujuno"), None, None)
+        .unwrap();
+    let tags = |tags: &[&str]| tags.iter().map(|&t| t.to_owned()).collect::<Vec<_>>();
+
+    // too many and too long tags are rejected
+    let err = suite
+        .create_gauge(
+            &gauge_contract,
+            GaugeConfig {
+                tags: (0..11).map(|i| i.to_string()).collect(),
+                ..gauge_config.clone()
+            },
+        )
+        .unwrap_err();
+    assert_eq!(ContractError::TooManyTags(10), err.downcast().unwrap());
+    let long_tag = "x".repeat(33);
+    let err = suite
+        .create_gauge(
+            &gauge_contract,
+            GaugeConfig {
+                tags: tags(&["lp", &long_tag]),
+                ..gauge_config.clone()
+            },
+        )
+        .unwrap_err();
+    assert_eq!(
+        ContractError::InvalidTag(long_tag, 32),
+        err.downcast().unwrap()
+    );
+
+    // duplicates are removed
+    for gauge_tags in [
+        tags(&["lp", "incentives", "lp"]),
+        tags(&["grants"]),
+        tags(&["lp", "grants"]),
+    ] {
+        suite
+            .create_gauge(
+                &gauge_contract,
+                GaugeConfig {
+                    tags: gauge_tags,
+                    ..gauge_config.clone()
+                },
+            )
+            .unwrap();
+    }
+    let gauge = suite.query_gauge(gauge_contract.clone(), 0).unwrap();
+    assert_eq!(gauge.tags, tags(&["incentives", "lp"]));
+
+    let ids = |gauges: Vec<GaugeResponse>| gauges.into_iter().map(|g| g.id).collect::<Vec<_>>();
+    assert_eq!(
+        ids(suite
+            .query_gauges_by_tag(&gauge_contract, "lp", None, None)
+            .unwrap()),
+        vec![0, 2]
+    );
+    assert_eq!(
+        ids(suite
+            .query_gauges_by_tag(&gauge_contract, "grants", None, None)
+            .unwrap()),
+        vec![1, 2]
+    );
+    assert_eq!(
+        ids(suite
+            .query_gauges_by_tag(&gauge_contract, "lp", 0, 1)
+            .unwrap()),
+        vec![2]
+    );
+    assert!(suite
+        .query_gauges_by_tag(&gauge_contract, "bounties", None, None)
+        .unwrap()
+        .is_empty());
+
+    // updating replaces the tags and the index
+    suite
+        .update_gauge_tags(&owner, &gauge_contract, 0, &["bounties"])
+        .unwrap();
+    assert_eq!(
+        ids(suite
+            .query_gauges_by_tag(&gauge_contract, "lp", None, None)
+            .unwrap()),
+        vec![2]
+    );
+    assert_eq!(
+        ids(suite
+            .query_gauges_by_tag(&gauge_contract, "bounties", None, None)
+            .unwrap()),
+        vec![0]
+    );
+    let err = suite
+        .update_gauge_tags(voter1, &gauge_contract, 0, &["lp"])
+        .unwrap_err();
+    assert_eq!(ContractError::Unauthorized {}, err.downcast().unwrap());
+}
+
+#[test]
+fn query_gauges_for_adapter() {
+    let voter1 = "voter1";
+    let voter2 = "voter2";
+    let mut suite = SuiteBuilder::new()
+        .with_voting_members(&[(voter1, 100), (voter2, 100)])
+        .build();
+
+    let gauge_contract = init_gauge(&mut suite, &[voter1, voter2]);
+    let shared_config = suite
+        .instantiate_adapter_and_return_config(&[voter1, voter2], (1000, "ujuno"), None, None)
+        .unwrap();
+    let other_config = suite
+        .instantiate_adapter_and_return_config(&[voter1, voter2], (1000, "ujuno"), None, None)
+        .unwrap();
+    let shared_adapter = Addr::unchecked(&shared_config.adapter);
+    let other_adapter = Addr::unchecked(&other_config.adapter);
+
+    assert!(suite
+        .query_gauges_for_adapter(&gauge_contract, &shared_adapter)
+        .unwrap()
+        .is_empty());
+
+    suite
+        .create_gauge(&gauge_contract, shared_config.clone())
+        .unwrap();
+    suite.create_gauge(&gauge_contract, other_config).unwrap();
+    suite.create_gauge(&gauge_contract, shared_config).unwrap();
+
+    assert_eq!(
+        suite
+            .query_gauges_for_adapter(&gauge_contract, &shared_adapter)
+            .unwrap(),
+        vec![0, 2]
+    );
+    assert_eq!(
+        suite
+            .query_gauges_for_adapter(&gauge_contract, &other_adapter)
+            .unwrap(),
+        vec![1]
+    );
+}
+
+#[test]
+fn shrinking_epoch_size_keeps_current_boundary() {
+    let voter1 = "voter1";
+    let voter2 = "voter2";
+    let mut suite = SuiteBuilder::new()
+        .with_voting_members(&[(voter1, 100), (voter2, 100)])
+        .with_core_balance((1000, "ujuno"))
+        .build();
+
+    let gauge_contract = init_gauge(&mut suite, &[voter1, voter2]);
+
+    let gauge_config = suite
+        .instantiate_adapter_and_return_config(&[voter1, voter2], (100, "ujuno"), None, None)
+        .unwrap();
+    suite.create_gauge(&gauge_contract, gauge_config).unwrap();
+    let gauge_id = 0;
+    let next_epoch = suite
+        .query_gauge(gauge_contract.clone(), gauge_id)
+        .unwrap()
+        .next_epoch;
+
+    suite
+        .place_vote(&gauge_contract, voter1, gauge_id, Some(voter1.to_owned()))
+        .unwrap();
+
+    // shrink to a day in the middle of the first epoch, which already lasted longer than that
+    suite.advance_time(EPOCH / 2);
+    let owner = suite.owner.clone();
+    let res = suite
+        .update_gauge(
+            &owner,
+            gauge_contract.clone(),
+            gauge_id,
+            86_400,
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+    assert!(res.has_event(&Event::new("wasm").add_attribute("next_epoch", next_epoch.to_string())));
+
+    let err = suite
+        .execute_options(&gauge_contract, voter1, gauge_id)
+        .unwrap_err();
+    assert!(matches!(
+        err.downcast().unwrap(),
+        ContractError::EpochNotReached { .. }
+    ));
+
+    // the current epoch ends as planned, the next ones use the new size
+    suite.advance_time(EPOCH / 2);
+    suite
+        .execute_options(&gauge_contract, voter1, gauge_id)
+        .unwrap();
+    let gauge = suite.query_gauge(gauge_contract.clone(), gauge_id).unwrap();
+    assert_eq!(
+        gauge.next_epoch,
+        suite.app.block_info().time.seconds() + 86_400
+    );
+}
+
+#[test]
+fn max_available_percentage_validation() {
+    let voter1 = "voter1";
+    let voter2 = "voter2";
+    let mut suite = SuiteBuilder::new()
+        .with_voting_members(&[(voter1, 100), (voter2, 100)])
+        .build();
+
+    let gauge_contract = init_gauge(&mut suite, &[voter1, voter2]);
+    let owner = suite.owner.clone();
+
+    let gauge_config = suite
+        .instantiate_adapter_and_return_config(&[voter1, voter2], (1000, "ujuno"), None, None)
+        .unwrap();
+
+    // nothing could ever be distributed with a cap of zero
+    let err = suite
+        .create_gauge(
+            &gauge_contract,
+            GaugeConfig {
+                max_available_percentage: Some(Decimal::zero()),
+                ..gauge_config.clone()
+            },
+        )
+        .unwrap_err();
+    assert_eq!(
+        ContractError::InvalidMaxAvailablePercentage {},
+        err.downcast().unwrap()
+    );
+    let err = suite
+        .create_gauge(
+            &gauge_contract,
+            GaugeConfig {
+                max_available_percentage: Some(Decimal::percent(150)),
+                ..gauge_config.clone()
+            },
+        )
+        .unwrap_err();
+    assert_eq!(
+        ContractError::MaxAvailablePercentTooBig {},
+        err.downcast().unwrap()
+    );
+
+    suite
+        .create_gauge(
+            &gauge_contract,
+            GaugeConfig {
+                max_available_percentage: Some(Decimal::percent(30)),
+                ..gauge_config.clone()
+            },
+        )
+        .unwrap();
+    suite
+        .create_gauge(
+            &gauge_contract,
+            GaugeConfig {
+                max_available_percentage: None,
+                ..gauge_config
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        suite
+            .query_gauge(gauge_contract.clone(), 0)
+            .unwrap()
+            .max_available_percentage,
+        Some(Decimal::percent(30))
+    );
+    assert_eq!(
+        suite
+            .query_gauge(gauge_contract.clone(), 1)
+            .unwrap()
+            .max_available_percentage,
+        None
+    );
+
+    // same rules when updating
+    let err = suite
+        .update_gauge(
+            &owner,
+            gauge_contract.clone(),
+            0,
+            None,
+            None,
+            None,
+            Decimal::zero(),
+        )
+        .unwrap_err();
+    assert_eq!(
+        ContractError::InvalidMaxAvailablePercentage {},
+        err.downcast().unwrap()
+    );
+    let err = suite
+        .update_gauge(
+            &owner,
+            gauge_contract.clone(),
+            0,
+            None,
+            None,
+            None,
+            Decimal::percent(150),
+        )
+        .unwrap_err();
+    assert_eq!(
+        ContractError::MaxAvailablePercentTooBig {},
+        err.downcast().unwrap()
+    );
+    suite
+        .update_gauge(
+            &owner,
+            gauge_contract.clone(),
+            1,
+            None,
+            None,
+            None,
+            Decimal::percent(30),
+        )
+        .unwrap();
+    assert_eq!(
+        suite
+            .query_gauge(gauge_contract.clone(), 1)
+            .unwrap()
+            .max_available_percentage,
+        Some(Decimal::percent(30))
+    );
+
+    // the cap can only be removed explicitly
+    let err = suite
+        .clear_gauge_max_available_percentage(
+            &owner,
+            &gauge_contract,
+            1,
+            Some(Decimal::percent(40)),
+        )
+        .unwrap_err();
+    assert_eq!(
+        ContractError::ConflictingMaxAvailablePercentage {},
+        err.downcast().unwrap()
+    );
+    suite
+        .clear_gauge_max_available_percentage(&owner, &gauge_contract, 1, None)
+        .unwrap();
+    assert_eq!(
+        suite
+            .query_gauge(gauge_contract, 1)
+            .unwrap()
+            .max_available_percentage,
+        None
+    );
+}
+
+#[test]
+fn epoch_labels() {
+    let voter1 = "voter1";
+    let mut suite = SuiteBuilder::new()
+        .with_voting_members(&[(voter1, 100)])
+        .with_core_balance((1000, "ujuno"))
+        .build();
+
+    let gauge_contract = init_gauge(&mut suite, &[voter1]);
+    suite
+        .instantiate_adapter_and_create_gauge(
+            gauge_contract.clone(),
+            &[voter1],
+            (1000, "ujuno"),
+            None,
+            None,
+        )
+        .unwrap();
+    let gauge_id = 0;
+    let owner = suite.owner.clone();
+
+    let err = suite
+        .set_epoch_label(&gauge_contract, voter1, gauge_id, 0, "Launch week")
+        .unwrap_err();
+    assert_eq!(ContractError::Unauthorized {}, err.downcast().unwrap());
+    let err = suite
+        .set_epoch_label(&gauge_contract, &owner, gauge_id, 0, "x".repeat(65))
+        .unwrap_err();
+    assert_eq!(
+        ContractError::InvalidEpochLabel(64),
+        err.downcast().unwrap()
+    );
+
+    suite
+        .set_epoch_label(&gauge_contract, &owner, gauge_id, 0, "Launch week")
+        .unwrap();
+    suite
+        .set_epoch_label(&gauge_contract, &owner, gauge_id, 1, "Second week")
+        .unwrap();
+
+    let gauge = suite.query_gauge(gauge_contract.clone(), gauge_id).unwrap();
+    assert_eq!(gauge.epoch_label.as_deref(), Some("Launch week"));
+    assert_eq!(
+        suite
+            .query_next_execution_label(&gauge_contract, gauge_id)
+            .unwrap()
+            .as_deref(),
+        Some("Launch week")
+    );
+
+    // after executing, the label of the next epoch is current
+    suite
+        .place_vote(&gauge_contract, voter1, gauge_id, Some(voter1.to_owned()))
+        .unwrap();
+    suite.advance_time(EPOCH);
+    suite
+        .execute_options(&gauge_contract, voter1, gauge_id)
+        .unwrap();
+    let gauge = suite.query_gauge(gauge_contract.clone(), gauge_id).unwrap();
+    assert_eq!(gauge.epoch_label.as_deref(), Some("Second week"));
+
+    // an empty label removes it
+    suite
+        .set_epoch_label(&gauge_contract, &owner, gauge_id, 1, "")
+        .unwrap();
+    let gauge = suite.query_gauge(gauge_contract, gauge_id).unwrap();
+    assert_eq!(gauge.epoch_label, None);
+}
+
+#[test]
+fn execute_allowlist() {
+    let voter1 = "voter1";
+    let keeper = "keeper";
+    let mut suite = SuiteBuilder::new()
+        .with_voting_members(&[(voter1, 100)])
+        .with_core_balance((2000, "ujuno"))
+        .build();
+    let owner = suite.owner.clone();
+
+    let gauge_contract = init_gauge(&mut suite, &[voter1]);
+    let mut config = suite
+        .instantiate_adapter_and_return_config(&[voter1], (1000, "ujuno"), None, None)
+        .unwrap();
+    config.execute_policy = ExecutePolicy::Allowlist(vec![Addr::unchecked(keeper)]);
+    suite.create_gauge(&gauge_contract, config).unwrap();
+    let gauge_id = 0;
+
+    suite
+        .place_vote(&gauge_contract, voter1, gauge_id, Some(voter1.to_owned()))
+        .unwrap();
+    suite.advance_time(EPOCH);
+
+    // not listed, not even the owner
+    let err = suite
+        .execute_options(&gauge_contract, voter1, gauge_id)
+        .unwrap_err();
+    assert_eq!(
+        ContractError::ExecutorNotAllowed(voter1.to_owned(), gauge_id),
+        err.downcast().unwrap()
+    );
+    let err = suite
+        .execute_options(&gauge_contract, &owner, gauge_id)
+        .unwrap_err();
+    assert_eq!(
+        ContractError::ExecutorNotAllowed(owner.clone(), gauge_id),
+        err.downcast().unwrap()
+    );
+
+    // only the owner can change the allowlist
+    let err = suite
+        .update_execute_allowlist(&gauge_contract, keeper, gauge_id, &[voter1], &[])
+        .unwrap_err();
+    assert_eq!(ContractError::Unauthorized {}, err.downcast().unwrap());
+
+    suite
+        .execute_options(&gauge_contract, keeper, gauge_id)
+        .unwrap();
+    assert_eq!(
+        suite
+            .query_gauge(gauge_contract.clone(), gauge_id)
+            .unwrap()
+            .execute_policy,
+        ExecutePolicy::Allowlist(vec![Addr::unchecked(keeper)])
+    );
+
+    // swap the keeper for voter1
+    suite
+        .update_execute_allowlist(&gauge_contract, &owner, gauge_id, &[voter1], &[keeper])
+        .unwrap();
+    suite.advance_time(EPOCH);
+    let err = suite
+        .execute_options(&gauge_contract, keeper, gauge_id)
+        .unwrap_err();
+    assert_eq!(
+        ContractError::ExecutorNotAllowed(keeper.to_owned(), gauge_id),
+        err.downcast().unwrap()
+    );
+    suite
+        .execute_options(&gauge_contract, voter1, gauge_id)
+        .unwrap();
+}
+
+#[test]
+fn missing_gauge_and_option_errors() {
+    let voter1 = "voter1";
+    let mut suite = SuiteBuilder::new()
+        .with_voting_members(&[(voter1, 100)])
+        .build();
+
+    let gauge_contract = init_gauge(&mut suite, &[voter1]);
+    suite
+        .instantiate_adapter_and_create_gauge(
+            gauge_contract.clone(),
+            &[voter1],
+            (1000, "ujuno"),
+            None,
+            None,
+        )
+        .unwrap();
+    let gauge_id = 0;
+    let missing_gauge = ContractError::GaugeNotFound { gauge: 7 };
+    let missing_option = ContractError::OptionNotFound {
+        gauge: gauge_id,
+        option: "unknown".to_owned(),
+    };
+
+    let err = suite
+        .execute_options(&gauge_contract, voter1, 7)
+        .unwrap_err();
+    assert_eq!(missing_gauge, err.downcast().unwrap());
+    let err = suite
+        .place_vote(&gauge_contract, voter1, 7, Some(voter1.to_owned()))
+        .unwrap_err();
+    assert_eq!(missing_gauge, err.downcast().unwrap());
+    let err = suite
+        .place_vote(
+            &gauge_contract,
+            voter1,
+            gauge_id,
+            Some("unknown".to_owned()),
+        )
+        .unwrap_err();
+    assert_eq!(missing_option, err.downcast().unwrap());
+    let err = suite
+        .add_option(&gauge_contract, voter1, 7, "new_option")
+        .unwrap_err();
+    assert_eq!(missing_gauge, err.downcast().unwrap());
+
+    // queries fail with the same messages
+    let err = suite.query_gauge(gauge_contract.clone(), 7).unwrap_err();
+    assert!(err.to_string().contains(&missing_gauge.to_string()));
+    let err = suite.query_list_options(&gauge_contract, 7).unwrap_err();
+    assert!(err.to_string().contains(&missing_gauge.to_string()));
+    let err = suite
+        .query_option_voters(&gauge_contract, 7, voter1)
+        .unwrap_err();
+    assert!(err.to_string().contains(&missing_gauge.to_string()));
+    let err = suite
+        .query_option_voters(&gauge_contract, gauge_id, "unknown")
+        .unwrap_err();
+    assert!(err.to_string().contains(&missing_option.to_string()));
+    let err = suite
+        .query_option_tally_history(&gauge_contract, gauge_id, "unknown", None, None)
+        .unwrap_err();
+    assert!(err.to_string().contains(&missing_option.to_string()));
+}
+
+#[test]
+fn event_namespace_is_added_to_events() {
+    let voter1 = "voter1";
+    let mut suite = SuiteBuilder::new()
+        .with_voting_members(&[(voter1, 100)])
+        .with_core_balance((1000, "ujuno"))
+        .with_event_namespace("wynd-gauges")
+        .build();
+
+    let gauge_contract = init_gauge(&mut suite, &[voter1]);
+    let config = suite.query_config(&gauge_contract).unwrap();
+    assert_eq!(config.event_namespace, Some("wynd-gauges".to_owned()));
+
+    suite
+        .instantiate_adapter_and_create_gauge(
+            gauge_contract.clone(),
+            &[voter1],
+            (1000, "ujuno"),
+            None,
+            None,
+        )
+        .unwrap();
+    suite
+        .place_vote(&gauge_contract, voter1, 0, Some(voter1.to_owned()))
+        .unwrap();
+
+    suite.advance_time(EPOCH);
+    let res = suite.execute_options(&gauge_contract, voter1, 0).unwrap();
+    assert!(res.has_event(
+        &Event::new("wasm")
+            .add_attribute("action", "execute_tally")
+            .add_attribute("namespace", "wynd-gauges")
+    ));
+}
+
+#[test]
+fn max_total_options() {
+    let voter1 = "voter1";
+    let voter2 = "voter2";
+    let mut suite = SuiteBuilder::new()
+        .with_voting_members(&[(voter1, 100), (voter2, 100)])
+        .build();
+    let owner = suite.owner.clone();
+
+    let gauge_contract = init_gauge(&mut suite, &[voter1, voter2]);
+    let mut gauge_config = suite
+        .instantiate_adapter_and_return_config(&[voter1, voter2], (1000, "ujuno"), None, None)
+        .unwrap();
+    gauge_config.max_total_options = Some(3);
+    let gauge_adapter = Addr::unchecked(gauge_config.adapter.clone());
+    suite.create_gauge(&gauge_contract, gauge_config).unwrap();
+    let gauge_id = 0;
+    suite.add_valid_option(&gauge_adapter, "option1").unwrap();
+    suite.add_valid_option(&gauge_adapter, "option2").unwrap();
+
+    // fill up to the cap
+    suite
+        .add_option(&gauge_contract, voter1, gauge_id, "option1")
+        .unwrap();
+    let err = suite
+        .add_option(&gauge_contract, voter1, gauge_id, "option2")
+        .unwrap_err();
+    assert_eq!(
+        ContractError::OptionLimitReached {
+            gauge_id,
+            max_total_options: 3
+        },
+        err.downcast().unwrap()
+    );
+    // syncing from the adapter is limited as well
+    let err = suite
+        .sync_options(&gauge_contract, voter1, gauge_id, 10)
+        .unwrap_err();
+    assert_eq!(
+        ContractError::OptionLimitReached {
+            gauge_id,
+            max_total_options: 3
+        },
+        err.downcast().unwrap()
+    );
+
+    // only owner can raise the cap
+    let err = suite
+        .update_gauge_max_total_options(voter1, &gauge_contract, gauge_id, 4)
+        .unwrap_err();
+    assert_eq!(ContractError::Unauthorized {}, err.downcast().unwrap());
+    suite
+        .update_gauge_max_total_options(&owner, &gauge_contract, gauge_id, 4)
+        .unwrap();
+    assert_eq!(
+        suite
+            .query_gauge(gauge_contract.clone(), gauge_id)
+            .unwrap()
+            .max_total_options,
+        Some(4)
+    );
+    suite
+        .add_option(&gauge_contract, voter1, gauge_id, "option2")
+        .unwrap();
+}
+
+#[test]
+fn epoch_boundaries_aligned() {
+    let voter1 = "voter1";
+    let mut suite = SuiteBuilder::new()
+        .with_voting_members(&[(voter1, 100)])
+        .build();
+    const DAY: u64 = 86_400;
+
+    let gauge_contract = init_gauge(&mut suite, &[voter1]);
+    let mut gauge_config = suite
+        .instantiate_adapter_and_return_config(&[voter1], (1000, "ujuno"), None, None)
+        .unwrap();
+    // an epoch of 2.5 days, executed at midnight
+    gauge_config.epoch_size = 5 * DAY / 2;
+    gauge_config.align_to = Some(3 * DAY);
+    let err = suite
+        .create_gauge(&gauge_contract, gauge_config.clone())
+        .unwrap_err();
+    assert_eq!(ContractError::InvalidAlignTo {}, err.downcast().unwrap());
+    gauge_config.align_to = Some(DAY);
+    suite.create_gauge(&gauge_contract, gauge_config).unwrap();
+    let gauge_id = 0;
+
+    let now = suite.current_time();
+    let next_epoch = suite
+        .query_gauge(gauge_contract.clone(), gauge_id)
+        .unwrap()
+        .next_epoch;
+    assert_eq!(next_epoch % DAY, 0);
+    assert!(next_epoch > now + 3 * DAY / 2 && next_epoch <= now + 5 * DAY / 2);
+
+    suite
+        .place_vote(&gauge_contract, voter1, gauge_id, Some(voter1.to_owned()))
+        .unwrap();
+    suite.advance_time(next_epoch - now);
+    suite
+        .execute_options(&gauge_contract, voter1, gauge_id)
+        .unwrap();
+    // executed right on the boundary, the next one is 2 days later
+    assert_eq!(
+        suite
+            .query_gauge(gauge_contract.clone(), gauge_id)
+            .unwrap()
+            .next_epoch,
+        next_epoch + 2 * DAY
+    );
+}
+
+#[test]
+fn selection_params_reflect_update_gauge() {
+    let voter1 = "voter1";
+    let mut suite = SuiteBuilder::new()
+        .with_voting_members(&[(voter1, 100)])
+        .build();
+    let owner = suite.owner.clone();
+
+    let gauge_contract = init_gauge(&mut suite, &[voter1]);
+    let mut gauge_config = suite
+        .instantiate_adapter_and_return_config(&[voter1], (1000, "ujuno"), None, None)
+        .unwrap();
+    gauge_config.max_paid_options = Some(3);
+    suite.create_gauge(&gauge_contract, gauge_config).unwrap();
+    let gauge_id = 0;
+
+    suite
+        .update_gauge(
+            &owner,
+            gauge_contract.clone(),
+            gauge_id,
+            None,
+            Some(Decimal::percent(10)),
+            15,
+            Decimal::percent(50),
+        )
+        .unwrap();
+    assert_eq!(
+        suite
+            .query_selection_params(&gauge_contract, gauge_id)
+            .unwrap(),
+        SelectionParamsResponse {
+            min_percent_selected: Some(Decimal::percent(10)),
+            min_absolute_tally: None,
+            max_options_selected: 15,
+            max_available_percentage: Some(Decimal::percent(50)),
+            distribution_mode: DistributionMode::Proportional,
+            effective_max_options_selected: 15,
+            effective_max_available_percentage: Some(Decimal::percent(50)),
+            max_paid_options: Some(3),
+            deprecated_vote_policy: DeprecatedVotePolicy::Ignore,
+            bootstrap_equal_split: false,
+        }
+    );
+}
+
+#[test]
+fn query_gauges_for_option() {
+    let voter1 = "voter1";
+    let voter2 = "voter2";
+    let mut suite = SuiteBuilder::new()
+        .with_voting_members(&[(voter1, 100), (voter2, 100)])
+        .build();
+    let owner = suite.owner.clone();
+
+    let gauge_contract = init_gauge(&mut suite, &[voter1, voter2]);
+    for _ in 0..3 {
+        let adapter = suite
+            .instantiate_adapter_and_create_gauge(
+                gauge_contract.clone(),
+                &[voter1, voter2],
+                (1000, "ujuno"),
+                None,
+                None,
+            )
+            .unwrap();
+        suite.add_valid_option(&adapter, "pool").unwrap();
+    }
+    assert!(suite
+        .query_gauges_for_option(&gauge_contract, "pool", None, None)
+        .unwrap()
+        .is_empty());
+
+    // the same option in two gauges
+    suite
+        .add_option(&gauge_contract, voter1, 0, "pool")
+        .unwrap();
+    suite
+        .add_option(&gauge_contract, voter1, 2, "pool")
+        .unwrap();
+    assert_eq!(
+        suite
+            .query_gauges_for_option(&gauge_contract, "pool", None, None)
+            .unwrap(),
+        vec![0, 2]
+    );
+    assert_eq!(
+        suite
+            .query_gauges_for_option(&gauge_contract, "pool", 0, 1)
+            .unwrap(),
+        vec![2]
+    );
+    // options of the adapter are indexed on creation
+    assert_eq!(
+        suite
+            .query_gauges_for_option(&gauge_contract, voter1, None, None)
+            .unwrap(),
+        vec![0, 1, 2]
+    );
+
+    suite
+        .remove_option(&gauge_contract, &owner, 0, "pool")
+        .unwrap();
+    assert_eq!(
+        suite
+            .query_gauges_for_option(&gauge_contract, "pool", None, None)
+            .unwrap(),
+        vec![2]
+    );
+}
+
+#[test]
+fn config_reflects_instantiate() {
+    let voter1 = "voter1";
+    let mut suite = SuiteBuilder::new()
+        .with_voting_members(&[(voter1, 100)])
+        .build();
+
+    let gauge_contract = init_gauge(&mut suite, &[voter1]);
+    let config = suite.query_config(&gauge_contract).unwrap();
+    assert_eq!(config.owner, suite.owner);
+    assert_eq!(config.dao_core, suite.core.as_str());
+    assert!(!config.paused);
+    assert_eq!(config.event_namespace, None);
+    assert_eq!(config.contract, CONTRACT_NAME);
+    assert_eq!(config.version, CONTRACT_VERSION);
+}
+
+#[test]
+fn list_options_ordered() {
+    let voter1 = "voter1";
+    let voter2 = "voter2";
+    let mut suite = SuiteBuilder::new()
+        .with_voting_members(&[(voter1, 100), (voter2, 200)])
+        .build();
+
+    let gauge_contract = init_gauge(&mut suite, &[voter1, voter2]);
+    let adapter = suite
+        .instantiate_adapter_and_create_gauge(
+            gauge_contract.clone(),
+            &[voter1, voter2],
+            (1000, "ujuno"),
+            None,
+            None,
+        )
+        .unwrap();
+    for option in ["zeta", "alpha"] {
+        suite.add_valid_option(&adapter, option).unwrap();
+        suite
+            .add_option(&gauge_contract, voter1, 0, option)
+            .unwrap();
+    }
+    suite
+        .place_vote(&gauge_contract, voter1, 0, Some("zeta".to_owned()))
+        .unwrap();
+    suite
+        .place_vote(&gauge_contract, voter2, 0, Some(voter2.to_owned()))
+        .unwrap();
+
+    let names = |options: Vec<(String, Uint128)>| {
+        options
+            .into_iter()
+            .map(|(option, _)| option)
+            .collect::<Vec<_>>()
+    };
+    let list = |order_by, start_after: Option<&str>, limit: Option<u32>| {
+        names(
+            suite
+                .query_list_options_ordered(
+                    &gauge_contract,
+                    0,
+                    order_by,
+                    start_after.map(str::to_owned),
+                    limit,
+                )
+                .unwrap(),
+        )
+    };
+    assert_eq!(
+        list(OptionOrder::Key, None, None),
+        ["alpha", voter1, voter2, "zeta"]
+    );
+    // equal tallies come in reverse key order
+    assert_eq!(
+        list(OptionOrder::Tally, None, None),
+        [voter2, "zeta", voter1, "alpha"]
+    );
+    assert_eq!(
+        list(OptionOrder::AddedAt, None, None),
+        [voter1, voter2, "zeta", "alpha"]
+    );
+
+    // pagination continues in the chosen order
+    assert_eq!(list(OptionOrder::Tally, Some("zeta"), Some(1)), [voter1]);
+    assert_eq!(
+        list(OptionOrder::AddedAt, Some(voter2), None),
+        ["zeta", "alpha"]
+    );
+    assert_eq!(
+        suite
+            .query_list_options_ordered(&gauge_contract, 0, OptionOrder::Tally, None, 1)
+            .unwrap(),
+        vec![(voter2.to_owned(), Uint128::new(200))]
+    );
+}
+
+#[test]
+fn frozen_options_keep_voting_open() {
+    let voter1 = "voter1";
+    let voter2 = "voter2";
+    let mut suite = SuiteBuilder::new()
+        .with_voting_members(&[(voter1, 100), (voter2, 100)])
+        .build();
+    let owner = suite.owner.clone();
+
+    let gauge_contract = init_gauge(&mut suite, &[voter1, voter2]);
+    let adapter = suite
+        .instantiate_adapter_and_create_gauge(
+            gauge_contract.clone(),
+            &[voter1, voter2],
+            (1000, "ujuno"),
+            None,
+            None,
+        )
+        .unwrap();
+    suite.add_valid_option(&adapter, "newcomer").unwrap();
+
+    let err = suite
+        .freeze_options(&gauge_contract, voter1, 0, true)
+        .unwrap_err();
+    assert_eq!(ContractError::Unauthorized {}, err.downcast().unwrap());
+    suite
+        .freeze_options(&gauge_contract, &owner, 0, true)
+        .unwrap();
+    assert!(
+        suite
+            .query_gauge(gauge_contract.clone(), 0)
+            .unwrap()
+            .options_frozen
+    );
+
+    // no new entrants, neither added by voters nor synced from the adapter
+    let err = suite
+        .add_option(&gauge_contract, voter1, 0, "newcomer")
+        .unwrap_err();
+    assert_eq!(ContractError::OptionsFrozen(0), err.downcast().unwrap());
+    let err = suite
+        .sync_options(&gauge_contract, voter1, 0, 10)
+        .unwrap_err();
+    assert_eq!(ContractError::OptionsFrozen(0), err.downcast().unwrap());
+
+    // but the existing options can still be voted on
+    suite
+        .place_vote(&gauge_contract, voter1, 0, Some(voter2.to_owned()))
+        .unwrap();
+    assert_eq!(
+        suite.query_list_options(&gauge_contract, 0).unwrap(),
+        vec![
+            (voter1.to_owned(), Uint128::zero()),
+            (voter2.to_owned(), Uint128::new(100)),
+        ]
+    );
+
+    suite
+        .freeze_options(&gauge_contract, &owner, 0, false)
+        .unwrap();
+    suite
+        .add_option(&gauge_contract, voter1, 0, "newcomer")
+        .unwrap();
+}
@@ -0,0 +1,88 @@
+//! Distribution hook contract to mock in tests.
+//! It records every distribution it receives and splits the sent funds by the weights.
+
+use cosmwasm_std::{
+    to_binary, BankMsg, Binary, Coin, Decimal, Deps, DepsMut, Empty, Env, MessageInfo, Response,
+    StdError, StdResult, Uint128,
+};
+use cw_multi_test::{Contract, ContractWrapper};
+use cw_storage_plus::Item;
+use serde::{Deserialize, Serialize};
+
+use crate::msg::DistributionHookMsg;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Distribution {
+    pub gauge_id: u64,
+    pub epoch: u64,
+    pub selected: Vec<(String, Decimal)>,
+    pub funds: Vec<Coin>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    /// Returns all received distributions as `Vec<Distribution>`
+    Distributions {},
+}
+
+const DISTRIBUTIONS: Item<Vec<Distribution>> = Item::new("distributions");
+
+fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    _msg: Empty,
+) -> Result<Response, StdError> {
+    DISTRIBUTIONS.save(deps.storage, &vec![])?;
+    Ok(Response::default())
+}
+
+fn execute(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    msg: DistributionHookMsg,
+) -> StdResult<Response> {
+    match msg {
+        DistributionHookMsg::Distribute {
+            gauge_id,
+            epoch,
+            selected,
+        } => {
+            let mut response = Response::new();
+            for coin in &info.funds {
+                for (option, weight) in &selected {
+                    let amount = coin.amount * *weight;
+                    if amount != Uint128::zero() {
+                        response = response.add_message(BankMsg::Send {
+                            to_address: option.clone(),
+                            amount: vec![Coin::new(amount.u128(), &coin.denom)],
+                        });
+                    }
+                }
+            }
+            DISTRIBUTIONS.update(deps.storage, |mut distributions| -> StdResult<_> {
+                distributions.push(Distribution {
+                    gauge_id,
+                    epoch,
+                    selected,
+                    funds: info.funds,
+                });
+                Ok(distributions)
+            })?;
+            Ok(response)
+        }
+    }
+}
+
+fn query(deps: Deps, _env: Env, msg: QueryMsg) -> Result<Binary, StdError> {
+    match msg {
+        QueryMsg::Distributions {} => to_binary(&DISTRIBUTIONS.load(deps.storage)?),
+    }
+}
+
+pub fn contract() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new_with_empty(execute, instantiate, query);
+    Box::new(contract)
+}
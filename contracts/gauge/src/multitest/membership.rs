@@ -0,0 +1,48 @@
+//! Membership source contract to mock in tests.
+//! InstantiateMsg contains the members along with the time they joined.
+
+use cosmwasm_std::{
+    to_binary, Binary, Deps, DepsMut, Empty, Env, MessageInfo, Response, StdError, StdResult,
+};
+use cw_multi_test::{Contract, ContractWrapper};
+use cw_storage_plus::Map;
+use serde::{Deserialize, Serialize};
+
+use crate::msg::{MemberSinceResponse, MembershipQueryMsg};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InstantiateMsg {
+    /// `(address, UNIX time in seconds when they joined)` pairs
+    pub members: Vec<(String, u64)>,
+}
+
+const MEMBERS: Map<String, u64> = Map::new("members");
+
+fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, StdError> {
+    msg.members
+        .into_iter()
+        .try_for_each(|(member, since)| MEMBERS.save(deps.storage, member, &since))?;
+    Ok(Response::default())
+}
+
+fn execute(_deps: DepsMut, _env: Env, _info: MessageInfo, _msg: Empty) -> StdResult<Response> {
+    Ok(Response::new())
+}
+
+fn query(deps: Deps, _env: Env, msg: MembershipQueryMsg) -> Result<Binary, StdError> {
+    match msg {
+        MembershipQueryMsg::MemberSince { address } => to_binary(&MemberSinceResponse {
+            since: MEMBERS.may_load(deps.storage, address)?,
+        }),
+    }
+}
+
+pub fn contract() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new_with_empty(execute, instantiate, query);
+    Box::new(contract)
+}
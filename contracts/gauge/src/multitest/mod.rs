@@ -1,6 +1,14 @@
 mod adapter;
+mod boost;
+mod callback;
+mod distribution;
 mod gauge;
+mod hook;
+mod membership;
+mod oracle;
 mod reset;
+mod router;
+mod staking;
 mod suite;
 mod tally;
 mod voting;
@@ -0,0 +1,55 @@
+//! Price oracle contract to mock in tests.
+//! The price is set on instantiation and can be changed by anyone with `ExecuteMsg::SetPrice`.
+
+use cosmwasm_std::{
+    to_binary, Binary, Decimal, Deps, DepsMut, Empty, Env, MessageInfo, Response, StdError,
+    StdResult,
+};
+use cw_multi_test::{Contract, ContractWrapper};
+use cw_storage_plus::Item;
+use serde::{Deserialize, Serialize};
+
+use crate::msg::{OracleQueryMsg, PriceResponse};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InstantiateMsg {
+    pub price: Decimal,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    SetPrice { price: Decimal },
+}
+
+const PRICE: Item<Decimal> = Item::new("price");
+
+fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, StdError> {
+    PRICE.save(deps.storage, &msg.price)?;
+    Ok(Response::default())
+}
+
+fn execute(deps: DepsMut, _env: Env, _info: MessageInfo, msg: ExecuteMsg) -> StdResult<Response> {
+    match msg {
+        ExecuteMsg::SetPrice { price } => PRICE.save(deps.storage, &price)?,
+    }
+    Ok(Response::new())
+}
+
+fn query(deps: Deps, _env: Env, msg: OracleQueryMsg) -> Result<Binary, StdError> {
+    match msg {
+        OracleQueryMsg::Price {} => to_binary(&PriceResponse {
+            price: PRICE.load(deps.storage)?,
+        }),
+    }
+}
+
+pub fn contract() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new_with_empty(execute, instantiate, query);
+    Box::new(contract)
+}
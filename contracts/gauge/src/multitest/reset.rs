@@ -1,10 +1,11 @@
-use cosmwasm_std::{Addr, Decimal, StdError, Uint128};
+use cosmwasm_std::{Addr, Decimal, Event, StdError, Uint128};
 use cw_multi_test::{App, ContractWrapper, Executor};
 use voting::Vote;
 
 use crate::{
-    msg::{GaugeMigrationConfig, GaugeResponse, ResetMigrationConfig, VoteInfo},
+    msg::{GaugeMigrationConfig, GaugeResponse, OptionOrder, ResetMigrationConfig, VoteInfo},
     multitest::suite::SuiteBuilder,
+    state::{DeprecatedVotePolicy, DistributionMode, EpochUnit, ExecutePolicy, Rounding},
     ContractError,
 };
 
@@ -209,8 +210,47 @@ fn gauge_migrate_with_reset() {
             max_options_selected: 10,
             max_available_percentage: None,
             is_stopped: false,
+            voting_closed: false,
             next_epoch: suite.current_time() + 7 * 86400,
             reset: None,
+            to_distribute: None,
+            reward_schedule: vec![],
+            executed_epochs: 0,
+            max_voters: None,
+            validate_option_on_vote: false,
+            voter_restriction: None,
+            tenure_curve: None,
+            bootstrap_equal_split: false,
+            reclaim_votes_on_remove: false,
+            adapter_multipliers: false,
+            isolate_failures: false,
+            epoch_unit: EpochUnit::Time,
+            rounding: Rounding::Down,
+            tags: vec![],
+            escrow_balance: vec![],
+            extra_adapters: vec![],
+            min_absolute_tally: None,
+            distribution_mode: DistributionMode::Proportional,
+            boost_source: None,
+            flashloan_resistant: false,
+            distribution_hook: None,
+            auto_prune_zero_options: false,
+            require_full_allocation: false,
+            total_budget: None,
+            epoch_label: None,
+            adapter_reward: false,
+            vote_change_fee: None,
+            execute_policy: ExecutePolicy::Anyone,
+            max_paid_options: None,
+            deprecated_vote_policy: DeprecatedVotePolicy::Ignore,
+            min_distribution_per_option: None,
+            max_total_options: None,
+            post_execute_callback: None,
+            align_to: None,
+            max_voter_influence: None,
+            execute_condition: None,
+            options_frozen: false,
+            swap_route: None,
         }
     );
 
@@ -270,12 +310,51 @@ fn gauge_migrate_with_reset() {
             max_options_selected: 10,
             max_available_percentage: None,
             is_stopped: false,
+            voting_closed: false,
             next_epoch: suite.current_time() + 7 * 86400,
             reset: Some(crate::state::Reset {
                 last: None,
                 reset_each: RESET_EPOCH,
                 next: suite.current_time() + 100,
             }),
+            to_distribute: None,
+            reward_schedule: vec![],
+            executed_epochs: 0,
+            max_voters: None,
+            validate_option_on_vote: false,
+            voter_restriction: None,
+            tenure_curve: None,
+            bootstrap_equal_split: false,
+            reclaim_votes_on_remove: false,
+            adapter_multipliers: false,
+            isolate_failures: false,
+            epoch_unit: EpochUnit::Time,
+            rounding: Rounding::Down,
+            tags: vec![],
+            escrow_balance: vec![],
+            extra_adapters: vec![],
+            min_absolute_tally: None,
+            distribution_mode: DistributionMode::Proportional,
+            boost_source: None,
+            flashloan_resistant: false,
+            distribution_hook: None,
+            auto_prune_zero_options: false,
+            require_full_allocation: false,
+            total_budget: None,
+            epoch_label: None,
+            adapter_reward: false,
+            vote_change_fee: None,
+            execute_policy: ExecutePolicy::Anyone,
+            max_paid_options: None,
+            deprecated_vote_policy: DeprecatedVotePolicy::Ignore,
+            min_distribution_per_option: None,
+            max_total_options: None,
+            post_execute_callback: None,
+            align_to: None,
+            max_voter_influence: None,
+            execute_condition: None,
+            options_frozen: false,
+            swap_route: None,
         }
     );
 }
@@ -600,3 +679,251 @@ fn vote_migration() {
     let vote2 = suite.query_vote(&gauge_contract, gauge_id, voter2).unwrap();
     assert_eq!(vote2, None);
 }
+
+#[test]
+fn vote_index_backfill_migration() {
+    let voter1 = "voter1";
+    let voter2 = "voter2";
+    let voter3 = "voter3";
+    let mut suite = SuiteBuilder::new()
+        .with_voting_members(&[(voter1, 100), (voter2, 200), (voter3, 100)])
+        .build();
+
+    // setup old gauge version
+    let old_gauge = store_old_gauge(&mut suite.app);
+    let new_gauge = suite.gauge_code_id;
+    suite.gauge_code_id = old_gauge;
+    suite.next_block();
+    suite.propose_update_proposal_module(voter1, None).unwrap();
+    suite.next_block();
+    let proposal = suite.list_proposals().unwrap()[0];
+    suite
+        .place_vote_single(voter1, proposal, Vote::Yes)
+        .unwrap();
+    suite
+        .place_vote_single(voter2, proposal, Vote::Yes)
+        .unwrap();
+    suite.next_block();
+    suite.execute_single_proposal(voter1, proposal).unwrap();
+    let proposal_modules = suite.query_proposal_modules().unwrap();
+    let gauge_contract = proposal_modules[0].clone();
+    let gauge_id = 0;
+
+    let option = suite
+        .instantiate_adapter_and_return_config(&["option1", "option2"], (1000, "ujuno"), None, None)
+        .unwrap();
+    suite
+        .app
+        .execute_contract(
+            Addr::unchecked(&suite.owner),
+            gauge_contract.clone(),
+            &gauge_orchestrator_1_6::msg::ExecuteMsg::CreateGauge(
+                gauge_orchestrator_1_6::msg::GaugeConfig {
+                    title: option.title,
+                    adapter: option.adapter,
+                    epoch_size: option.epoch_size,
+                    min_percent_selected: option.min_percent_selected,
+                    max_options_selected: option.max_options_selected,
+                    max_available_percentage: option.max_available_percentage,
+                },
+            ),
+            &[],
+        )
+        .unwrap();
+
+    // place votes with the old layout
+    suite
+        .place_vote(
+            &gauge_contract,
+            voter1,
+            gauge_id,
+            Some("option1".to_owned()),
+        )
+        .unwrap();
+    suite
+        .place_votes(
+            &gauge_contract,
+            voter2,
+            gauge_id,
+            vec![
+                ("option1".to_owned(), Decimal::percent(50)),
+                ("option2".to_owned(), Decimal::percent(50)),
+            ],
+        )
+        .unwrap();
+    suite
+        .place_vote(
+            &gauge_contract,
+            voter3,
+            gauge_id,
+            Some("option2".to_owned()),
+        )
+        .unwrap();
+
+    // migrate without backfilling, the index is empty
+    suite.gauge_code_id = new_gauge;
+    suite.auto_migrate_gauge(&gauge_contract, None).unwrap();
+    assert_eq!(
+        suite
+            .query_option_voters(&gauge_contract, gauge_id, "option1")
+            .unwrap(),
+        vec![]
+    );
+
+    // backfill in batches of two votes
+    let res = suite
+        .migrate_backfill_vote_index(&gauge_contract, 2)
+        .unwrap();
+    assert!(res.has_event(
+        &Event::new("wasm")
+            .add_attribute("indexed_votes", "2")
+            .add_attribute("finished", "false")
+    ));
+    let res = suite
+        .migrate_backfill_vote_index(&gauge_contract, 2)
+        .unwrap();
+    assert!(res.has_event(
+        &Event::new("wasm")
+            .add_attribute("indexed_votes", "1")
+            .add_attribute("finished", "true")
+    ));
+
+    assert_eq!(
+        suite
+            .query_option_voters(&gauge_contract, gauge_id, "option1")
+            .unwrap(),
+        vec![
+            (voter1.to_owned(), Decimal::one()),
+            (voter2.to_owned(), Decimal::percent(50))
+        ]
+    );
+    assert_eq!(
+        suite
+            .query_option_voters(&gauge_contract, gauge_id, "option2")
+            .unwrap(),
+        vec![
+            (voter2.to_owned(), Decimal::percent(50)),
+            (voter3.to_owned(), Decimal::one())
+        ]
+    );
+
+    // new votes keep the index up to date
+    suite
+        .place_vote(
+            &gauge_contract,
+            voter1,
+            gauge_id,
+            Some("option2".to_owned()),
+        )
+        .unwrap();
+    assert_eq!(
+        suite
+            .query_option_voters(&gauge_contract, gauge_id, "option1")
+            .unwrap(),
+        vec![(voter2.to_owned(), Decimal::percent(50))]
+    );
+    assert_eq!(
+        suite
+            .query_option_voters(&gauge_contract, gauge_id, "option2")
+            .unwrap(),
+        vec![
+            (voter1.to_owned(), Decimal::one()),
+            (voter2.to_owned(), Decimal::percent(50)),
+            (voter3.to_owned(), Decimal::one())
+        ]
+    );
+}
+
+#[test]
+fn option_index_backfill_migration() {
+    let voter1 = "voter1";
+    let voter2 = "voter2";
+    let mut suite = SuiteBuilder::new()
+        .with_voting_members(&[(voter1, 100), (voter2, 200)])
+        .build();
+
+    // setup old gauge version
+    let old_gauge = store_old_gauge(&mut suite.app);
+    let new_gauge = suite.gauge_code_id;
+    suite.gauge_code_id = old_gauge;
+    suite.next_block();
+    suite.propose_update_proposal_module(voter1, None).unwrap();
+    suite.next_block();
+    let proposal = suite.list_proposals().unwrap()[0];
+    suite
+        .place_vote_single(voter2, proposal, Vote::Yes)
+        .unwrap();
+    suite.next_block();
+    suite.execute_single_proposal(voter1, proposal).unwrap();
+    let proposal_modules = suite.query_proposal_modules().unwrap();
+    let gauge_contract = proposal_modules[0].clone();
+
+    let option = suite
+        .instantiate_adapter_and_return_config(&["option1", "option2"], (1000, "ujuno"), None, None)
+        .unwrap();
+    suite
+        .app
+        .execute_contract(
+            Addr::unchecked(&suite.owner),
+            gauge_contract.clone(),
+            &gauge_orchestrator_1_6::msg::ExecuteMsg::CreateGauge(
+                gauge_orchestrator_1_6::msg::GaugeConfig {
+                    title: option.title,
+                    adapter: option.adapter,
+                    epoch_size: option.epoch_size,
+                    min_percent_selected: option.min_percent_selected,
+                    max_options_selected: option.max_options_selected,
+                    max_available_percentage: option.max_available_percentage,
+                },
+            ),
+            &[],
+        )
+        .unwrap();
+
+    // migrate without backfilling, the index is empty
+    suite.gauge_code_id = new_gauge;
+    suite.auto_migrate_gauge(&gauge_contract, None).unwrap();
+    assert_eq!(
+        suite
+            .query_gauges_for_option(&gauge_contract, "option1", None, None)
+            .unwrap(),
+        Vec::<u64>::new()
+    );
+
+    // backfill one option at a time
+    let res = suite
+        .migrate_backfill_option_index(&gauge_contract, 1)
+        .unwrap();
+    assert!(res.has_event(
+        &Event::new("wasm")
+            .add_attribute("indexed_options", "1")
+            .add_attribute("options_finished", "false")
+    ));
+    let res = suite
+        .migrate_backfill_option_index(&gauge_contract, 2)
+        .unwrap();
+    assert!(res.has_event(
+        &Event::new("wasm")
+            .add_attribute("indexed_options", "1")
+            .add_attribute("options_finished", "true")
+    ));
+
+    for option in ["option1", "option2"] {
+        assert_eq!(
+            suite
+                .query_gauges_for_option(&gauge_contract, option, None, None)
+                .unwrap(),
+            vec![0]
+        );
+    }
+    // the options of the old version were added in key order
+    assert_eq!(
+        suite
+            .query_list_options_ordered(&gauge_contract, 0, OptionOrder::AddedAt, None, None)
+            .unwrap(),
+        vec![
+            ("option1".to_owned(), Uint128::zero()),
+            ("option2".to_owned(), Uint128::zero())
+        ]
+    );
+}
@@ -0,0 +1,69 @@
+//! Swap router contract to mock in tests.
+//! It swaps any offered native funds to any denom it holds, at the rate given on instantiation.
+
+use cosmwasm_std::{
+    coins, to_binary, BankMsg, Binary, Decimal, Deps, DepsMut, Empty, Env, MessageInfo, Response,
+    StdError, StdResult, Uint128,
+};
+use cw_multi_test::{Contract, ContractWrapper};
+use cw_storage_plus::Item;
+use serde::{Deserialize, Serialize};
+
+use crate::msg::{RouterExecuteMsg, RouterQueryMsg, SimulateSwapResponse};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InstantiateMsg {
+    /// Amount of the returned denom per offered token
+    pub rate: Decimal,
+}
+
+const RATE: Item<Decimal> = Item::new("rate");
+
+fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, StdError> {
+    RATE.save(deps.storage, &msg.rate)?;
+    Ok(Response::default())
+}
+
+fn execute(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    msg: RouterExecuteMsg,
+) -> StdResult<Response> {
+    match msg {
+        RouterExecuteMsg::Swap {
+            to_denom,
+            minimum_receive,
+        } => {
+            let offered: Uint128 = info.funds.iter().map(|coin| coin.amount).sum();
+            let amount = offered * RATE.load(deps.storage)?;
+            if amount < minimum_receive {
+                return Err(StdError::generic_err("return below minimum"));
+            }
+            Ok(Response::new()
+                .add_attribute("action", "swap")
+                .add_message(BankMsg::Send {
+                    to_address: info.sender.to_string(),
+                    amount: coins(amount.u128(), to_denom),
+                }))
+        }
+    }
+}
+
+fn query(deps: Deps, _env: Env, msg: RouterQueryMsg) -> Result<Binary, StdError> {
+    match msg {
+        RouterQueryMsg::SimulateSwap { offer, .. } => to_binary(&SimulateSwapResponse {
+            return_amount: offer.amount * RATE.load(deps.storage)?,
+        }),
+    }
+}
+
+pub fn contract() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new_with_empty(execute, instantiate, query);
+    Box::new(contract)
+}
@@ -0,0 +1,61 @@
+//! Staking contract to mock in tests, as voting powers of `VotingQuery::StakedBalanceAtHeight`.
+//! InstantiateMsg contains the stakers along with their stake, which never changes.
+
+use cosmwasm_std::{
+    to_binary, Binary, Deps, DepsMut, Empty, Env, MessageInfo, Order, Response, StdError,
+    StdResult, Uint128,
+};
+use cw_multi_test::{Contract, ContractWrapper};
+use cw_storage_plus::Map;
+use serde::{Deserialize, Serialize};
+
+use crate::msg::{StakedBalanceAtHeightResponse, StakingQueryMsg, TotalStakedAtHeightResponse};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InstantiateMsg {
+    /// `(address, stake)` pairs, everyone else has nothing staked
+    pub stakes: Vec<(String, Uint128)>,
+}
+
+const STAKES: Map<String, Uint128> = Map::new("stakes");
+
+fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, StdError> {
+    msg.stakes
+        .into_iter()
+        .try_for_each(|(address, stake)| STAKES.save(deps.storage, address, &stake))?;
+    Ok(Response::default())
+}
+
+fn execute(_deps: DepsMut, _env: Env, _info: MessageInfo, _msg: Empty) -> StdResult<Response> {
+    Ok(Response::new())
+}
+
+fn query(deps: Deps, env: Env, msg: StakingQueryMsg) -> Result<Binary, StdError> {
+    match msg {
+        StakingQueryMsg::StakedBalanceAtHeight { address, height } => {
+            to_binary(&StakedBalanceAtHeightResponse {
+                balance: STAKES.may_load(deps.storage, address)?.unwrap_or_default(),
+                height: height.unwrap_or(env.block.height),
+            })
+        }
+        StakingQueryMsg::TotalStakedAtHeight { height } => {
+            to_binary(&TotalStakedAtHeightResponse {
+                total: STAKES
+                    .range(deps.storage, None, None, Order::Ascending)
+                    .map(|stake| stake.map(|(_, stake)| stake))
+                    .sum::<StdResult<Uint128>>()?,
+                height: height.unwrap_or(env.block.height),
+            })
+        }
+    }
+}
+
+pub fn contract() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new_with_empty(execute, instantiate, query);
+    Box::new(contract)
+}
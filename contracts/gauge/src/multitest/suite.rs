@@ -1,8 +1,13 @@
 use anyhow::Result as AnyResult;
 
-use cosmwasm_std::{coin, to_binary, Addr, Coin, CosmosMsg, Decimal, StdResult, Uint128, WasmMsg};
+use cosmwasm_std::{
+    coin, to_binary, Addr, Coin, CosmosMsg, Decimal, Empty, StdResult, Uint128, WasmMsg,
+};
+use cw20::{BalanceResponse, Cw20Coin, Cw20QueryMsg};
+use cw20_base::msg::InstantiateMsg as Cw20BaseInstantiateMsg;
 use cw4::Member;
-use cw4_voting::msg::InstantiateMsg as VotingInstantiateMsg;
+use cw4_group::msg::ExecuteMsg as Cw4GroupExecuteMsg;
+use cw4_voting::msg::{InstantiateMsg as VotingInstantiateMsg, QueryMsg as VotingQueryMsg};
 use cw_core::msg::{
     Admin, ExecuteMsg as CoreExecuteMsg, InstantiateMsg as CoreInstantiateMsg,
     ModuleInstantiateInfo, QueryMsg as CoreQueryMsg,
@@ -13,17 +18,48 @@ use cw_proposal_single::{
     msg::InstantiateMsg as ProposalSingleInstantiateMsg, msg::QueryMsg as ProposalSingleQueryMsg,
     query::ProposalListResponse, state::Executor as ProposalSingleExecutor,
 };
-use cw_utils::Duration;
+use cw_utils::{Duration, Expiration};
 use voting::{PercentageThreshold, Threshold, Vote};
+use wynd_stake::hook::{MemberChangedHookMsg, MemberDiff};
 
 use super::adapter::{
     contract as adapter_contract, ExecuteMsg as AdapterExecuteMsg,
     InstantiateMsg as AdapterInstantiateMsg,
 };
+use super::boost::{contract as boost_contract, InstantiateMsg as BoostInstantiateMsg};
+use super::callback::{
+    contract as callback_contract, InstantiateMsg as CallbackInstantiateMsg,
+    QueryMsg as CallbackQueryMsg,
+};
+use super::hook::{contract as hook_contract, Distribution, QueryMsg as HookQueryMsg};
+use super::membership::{
+    contract as membership_contract, InstantiateMsg as MembershipInstantiateMsg,
+};
+use super::oracle::{
+    contract as oracle_contract, ExecuteMsg as OracleExecuteMsg,
+    InstantiateMsg as OracleInstantiateMsg,
+};
+use super::router::{contract as router_contract, InstantiateMsg as RouterInstantiateMsg};
+use super::staking::{contract as staking_contract, InstantiateMsg as StakingInstantiateMsg};
 use crate::msg::{
-    ExecuteMsg, GaugeConfig, GaugeMigrationConfig, GaugeResponse, InstantiateMsg,
-    LastExecutedSetResponse, ListGaugesResponse, ListOptionsResponse, ListVotesResponse,
-    MigrateMsg, QueryMsg, SelectedSetResponse, VoteInfo, VoteResponse,
+    AggregateSelectedSetResponse, CanVoteResponse, ChangedVote, ConfigResponse,
+    ExecutableGaugePreview, ExecutableGaugesResponse, ExecutableGaugesWithPreviewResponse,
+    ExecuteMsg, ExecutionComplexityResponse, ExecutionFailuresResponse, ExecutionInputsResponse,
+    GaugeConfig, GaugeMigrationConfig, GaugeResponse, GaugesForAdapterResponse,
+    GaugesForOptionResponse, InstantiateMsg, LastExecutedSetResponse, LeaderboardEntry,
+    ListGaugesResponse, ListOptionsResponse, ListVotesResponse, MigrateMsg, NextExecutionResponse,
+    OptionDelta, OptionLeaderboardResponse, OptionLifetimeRewardsResponse, OptionOrder,
+    OptionRewardEfficiency, OptionTallyHistoryResponse, ParticipationHistoryResponse,
+    ParticipationResponse, PostExecuteCallbackMsg, QualificationThresholdResponse, QueryMsg,
+    RecentVotersResponse, RemainingBudgetResponse, RewardEfficiencyResponse,
+    SampleGaugeMsgsResponse, SelectedSetResponse, SelectionParamsResponse, SetDeltaResponse,
+    ShutOutOptionsResponse, UnvotedOptionsResponse, VoteDistributionResponse, VoteInfo,
+    VoteResponse, VoterActivityResponse, VoterMissedGaugesResponse, VoterOptionImpact,
+    VoterProjectedImpactResponse, VotesChangedSinceResponse, VotesToOvertakeResponse,
+};
+use crate::state::{
+    Asset, DeprecatedVotePolicy, DistributionMode, EpochParticipation, EpochUnit, ExecutePolicy,
+    ExecutionFailure, ExecutionInputs, OptionTallySnapshot, Rounding, VoterActivity, VotingQuery,
 };
 
 type GaugeId = u64;
@@ -37,7 +73,8 @@ fn store_gauge(app: &mut App) -> u64 {
             crate::contract::instantiate,
             crate::contract::query,
         )
-        .with_migrate(crate::contract::migrate),
+        .with_migrate(crate::contract::migrate)
+        .with_reply(crate::contract::reply),
     );
 
     app.store_code(contract)
@@ -76,6 +113,16 @@ fn store_proposal_single(app: &mut App) -> u64 {
     app.store_code(contract)
 }
 
+fn store_cw20(app: &mut App) -> u64 {
+    let contract = Box::new(ContractWrapper::new_with_empty(
+        cw20_base::contract::execute,
+        cw20_base::contract::instantiate,
+        cw20_base::contract::query,
+    ));
+
+    app.store_code(contract)
+}
+
 fn store_core(app: &mut App) -> u64 {
     let contract = Box::new(
         ContractWrapper::new_with_empty(
@@ -93,6 +140,8 @@ fn store_core(app: &mut App) -> u64 {
 pub struct SuiteBuilder {
     voting_members: Vec<Member>,
     initial_core_balance: Option<Coin>,
+    initial_balances: Vec<(String, Coin)>,
+    event_namespace: Option<String>,
 }
 
 impl SuiteBuilder {
@@ -100,6 +149,8 @@ impl SuiteBuilder {
         Self {
             voting_members: vec![],
             initial_core_balance: None,
+            initial_balances: vec![],
+            event_namespace: None,
         }
     }
 
@@ -108,6 +159,17 @@ impl SuiteBuilder {
         self
     }
 
+    pub fn with_balance(mut self, addr: &str, balance: (u128, &str)) -> Self {
+        self.initial_balances
+            .push((addr.to_owned(), coin(balance.0, balance.1)));
+        self
+    }
+
+    pub fn with_event_namespace(mut self, namespace: &str) -> Self {
+        self.event_namespace = Some(namespace.to_owned());
+        self
+    }
+
     pub fn with_voting_members(mut self, members: &[(&str, u64)]) -> Self {
         self.voting_members = members
             .iter()
@@ -190,6 +252,14 @@ impl SuiteBuilder {
             })
             .unwrap();
         }
+        for (addr, balance) in self.initial_balances {
+            app.init_modules(|router, _, storage| -> AnyResult<()> {
+                router
+                    .bank
+                    .init_balance(storage, &Addr::unchecked(addr), vec![balance])
+            })
+            .unwrap();
+        }
 
         let voting_contract: Addr = app
             .wrap()
@@ -208,6 +278,14 @@ impl SuiteBuilder {
 
         let gauge_code_id = store_gauge(&mut app);
         let gauge_adapter_code_id = app.store_code(adapter_contract());
+        let cw20_code_id = store_cw20(&mut app);
+        let membership_code_id = app.store_code(membership_contract());
+        let boost_code_id = app.store_code(boost_contract());
+        let hook_code_id = app.store_code(hook_contract());
+        let callback_code_id = app.store_code(callback_contract());
+        let oracle_code_id = app.store_code(oracle_contract());
+        let router_code_id = app.store_code(router_contract());
+        let staking_code_id = app.store_code(staking_contract());
 
         Suite {
             owner: owner.to_string(),
@@ -217,10 +295,42 @@ impl SuiteBuilder {
             proposal_single: proposal_single_contract[0].clone(),
             gauge_code_id,
             gauge_adapter_code_id,
+            cw20_code_id,
+            membership_code_id,
+            boost_code_id,
+            hook_code_id,
+            callback_code_id,
+            oracle_code_id,
+            router_code_id,
+            staking_code_id,
+            event_namespace: self.event_namespace,
         }
     }
 }
 
+/// Small helper method to setup the gauge contract.
+/// Make sure that `voter` has voting power.
+pub fn init_gauge(suite: &mut Suite, voters: &[&str]) -> Addr {
+    suite.next_block();
+    suite
+        .propose_update_proposal_module(voters[0], None)
+        .unwrap();
+    suite.next_block();
+    let proposal = suite.list_proposals().unwrap()[0];
+    for voter in voters {
+        suite
+            .place_vote_single(*voter, proposal, Vote::Yes)
+            .unwrap();
+    }
+    suite.next_block();
+    suite.execute_single_proposal(voters[0], proposal).unwrap();
+    let proposal_modules = suite.query_proposal_modules().unwrap();
+
+    // Second proposal module is cw proposal single, first one is newly added gauge
+    assert_eq!(proposal_modules.len(), 2);
+    proposal_modules[0].clone()
+}
+
 pub struct Suite {
     pub owner: String,
     pub app: App,
@@ -229,6 +339,15 @@ pub struct Suite {
     proposal_single: Addr,
     pub gauge_code_id: u64,
     gauge_adapter_code_id: u64,
+    cw20_code_id: u64,
+    membership_code_id: u64,
+    boost_code_id: u64,
+    hook_code_id: u64,
+    callback_code_id: u64,
+    oracle_code_id: u64,
+    router_code_id: u64,
+    staking_code_id: u64,
+    event_namespace: Option<String>,
 }
 
 impl Suite {
@@ -268,6 +387,54 @@ impl Suite {
         )
     }
 
+    pub fn stop_gauge_and_rollover(
+        &mut self,
+        gauge: &Addr,
+        sender: impl Into<String>,
+        gauge_id: u64,
+        successor_gauge: u64,
+    ) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            Addr::unchecked(sender),
+            gauge.clone(),
+            &ExecuteMsg::StopGaugeAndRollover {
+                gauge: gauge_id,
+                successor_gauge,
+            },
+            &[],
+        )
+    }
+
+    pub fn close_voting(
+        &mut self,
+        gauge: &Addr,
+        sender: impl Into<String>,
+        gauge_id: u64,
+    ) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            Addr::unchecked(sender),
+            gauge.clone(),
+            &ExecuteMsg::CloseVoting { gauge: gauge_id },
+            &[],
+        )
+    }
+
+    pub fn freeze_options(
+        &mut self,
+        gauge: &Addr,
+        sender: impl Into<String>,
+        gauge_id: u64,
+        frozen: bool,
+    ) -> AnyResult<AppResponse> {
+        let msg = if frozen {
+            ExecuteMsg::FreezeOptions { gauge: gauge_id }
+        } else {
+            ExecuteMsg::UnfreezeOptions { gauge: gauge_id }
+        };
+        self.app
+            .execute_contract(Addr::unchecked(sender), gauge.clone(), &msg, &[])
+    }
+
     pub fn add_option(
         &mut self,
         gauge: &Addr,
@@ -286,6 +453,44 @@ impl Suite {
         )
     }
 
+    pub fn deprecate_option(
+        &mut self,
+        gauge: &Addr,
+        sender: impl Into<String>,
+        gauge_id: u64,
+        option: impl Into<String>,
+    ) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            Addr::unchecked(sender),
+            gauge.clone(),
+            &ExecuteMsg::DeprecateOption {
+                gauge: gauge_id,
+                option: option.into(),
+            },
+            &[],
+        )
+    }
+
+    pub fn set_epoch_label(
+        &mut self,
+        gauge: &Addr,
+        sender: impl Into<String>,
+        gauge_id: u64,
+        epoch: u64,
+        label: impl Into<String>,
+    ) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            Addr::unchecked(sender),
+            gauge.clone(),
+            &ExecuteMsg::SetEpochLabel {
+                gauge: gauge_id,
+                epoch,
+                label: label.into(),
+            },
+            &[],
+        )
+    }
+
     pub fn remove_option(
         &mut self,
         gauge: &Addr,
@@ -304,6 +509,24 @@ impl Suite {
         )
     }
 
+    pub fn remove_options(
+        &mut self,
+        gauge: &Addr,
+        sender: impl Into<String>,
+        gauge_id: u64,
+        options: &[&str],
+    ) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            Addr::unchecked(sender),
+            gauge.clone(),
+            &ExecuteMsg::RemoveOptions {
+                gauge: gauge_id,
+                options: options.iter().map(|option| option.to_string()).collect(),
+            },
+            &[],
+        )
+    }
+
     /// Helper to remove an option from the test gauge adapter
     pub fn invalidate_option(
         &mut self,
@@ -320,6 +543,71 @@ impl Suite {
         )
     }
 
+    pub fn validate_options(
+        &mut self,
+        gauge: &Addr,
+        sender: impl Into<String>,
+        gauge_id: u64,
+    ) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            Addr::unchecked(sender),
+            gauge.clone(),
+            &ExecuteMsg::ValidateOptions { gauge: gauge_id },
+            &[],
+        )
+    }
+
+    pub fn sync_options(
+        &mut self,
+        gauge: &Addr,
+        sender: impl Into<String>,
+        gauge_id: u64,
+        limit: u32,
+    ) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            Addr::unchecked(sender),
+            gauge.clone(),
+            &ExecuteMsg::SyncOptions {
+                gauge: gauge_id,
+                limit,
+            },
+            &[],
+        )
+    }
+
+    /// Helper to set a reward multiplier for an option on the test gauge adapter
+    pub fn set_option_multiplier(
+        &mut self,
+        gauge_adapter: &Addr,
+        option: &str,
+        multiplier: Decimal,
+    ) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            Addr::unchecked(&self.owner),
+            gauge_adapter.clone(),
+            &AdapterExecuteMsg::SetMultiplier {
+                option: option.to_owned(),
+                multiplier,
+            },
+            &[],
+        )
+    }
+
+    /// Helper to set the reward of an epoch on the test gauge adapter
+    pub fn set_adapter_epoch_reward(
+        &mut self,
+        gauge_adapter: &Addr,
+        epoch: u64,
+        reward: Asset,
+    ) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            Addr::unchecked(&self.owner),
+            gauge_adapter.clone(),
+            &AdapterExecuteMsg::SetEpochReward { epoch, reward },
+            &[],
+        )
+    }
+
     /// Helper to add an option to the test gauge adapter
     pub fn add_valid_option(
         &mut self,
@@ -336,6 +624,20 @@ impl Suite {
         )
     }
 
+    /// Sends a member changed hook to the gauge as if it came from the voting powers contract
+    pub fn member_changed_hook(
+        &mut self,
+        gauge: &Addr,
+        diffs: Vec<MemberDiff>,
+    ) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            self.voting.clone(),
+            gauge.clone(),
+            &ExecuteMsg::MemberChangedHook(MemberChangedHookMsg { diffs }),
+            &[],
+        )
+    }
+
     /// Helper to vote for a single option
     pub fn place_vote(
         &mut self,
@@ -358,6 +660,17 @@ impl Suite {
         voter: impl Into<String>,
         gauge_id: u64,
         votes: impl Into<Option<Vec<(String, Decimal)>>>,
+    ) -> AnyResult<AppResponse> {
+        self.place_votes_with_proof(gauge, voter, gauge_id, votes, None)
+    }
+
+    pub fn place_votes_with_proof(
+        &mut self,
+        gauge: &Addr,
+        voter: impl Into<String>,
+        gauge_id: u64,
+        votes: impl Into<Option<Vec<(String, Decimal)>>>,
+        proof: impl Into<Option<Vec<String>>>,
     ) -> AnyResult<AppResponse> {
         let votes = votes.into().map(|v| {
             v.into_iter()
@@ -370,11 +683,87 @@ impl Suite {
             &ExecuteMsg::PlaceVotes {
                 gauge: gauge_id,
                 votes,
+                proof: proof.into(),
+                nonce: None,
+            },
+            &[],
+        )
+    }
+
+    pub fn place_vote_with_nonce(
+        &mut self,
+        gauge: &Addr,
+        voter: impl Into<String>,
+        gauge_id: u64,
+        option: impl Into<Option<String>>,
+        nonce: u64,
+    ) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            Addr::unchecked(voter),
+            gauge.clone(),
+            &ExecuteMsg::PlaceVotes {
+                gauge: gauge_id,
+                votes: option.into().map(|option| {
+                    vec![crate::state::Vote {
+                        option,
+                        weight: Decimal::one(),
+                    }]
+                }),
+                proof: None,
+                nonce: Some(nonce),
+            },
+            &[],
+        )
+    }
+
+    pub fn place_votes_bps(
+        &mut self,
+        gauge: &Addr,
+        voter: impl Into<String>,
+        gauge_id: u64,
+        votes: &[(&str, u16)],
+    ) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            Addr::unchecked(voter),
+            gauge.clone(),
+            &ExecuteMsg::PlaceVotesBps {
+                gauge: gauge_id,
+                votes: votes
+                    .iter()
+                    .map(|(option, bps)| (option.to_string(), *bps))
+                    .collect(),
+                proof: None,
             },
             &[],
         )
     }
 
+    pub fn place_vote_with_funds(
+        &mut self,
+        gauge: &Addr,
+        voter: impl Into<String>,
+        gauge_id: u64,
+        option: impl Into<Option<String>>,
+        funds: &[Coin],
+    ) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            Addr::unchecked(voter),
+            gauge.clone(),
+            &ExecuteMsg::PlaceVotes {
+                gauge: gauge_id,
+                votes: option.into().map(|option| {
+                    vec![crate::state::Vote {
+                        option,
+                        weight: Decimal::one(),
+                    }]
+                }),
+                proof: None,
+                nonce: None,
+            },
+            funds,
+        )
+    }
+
     pub fn execute_options(
         &mut self,
         gauge: &Addr,
@@ -389,6 +778,24 @@ impl Suite {
         )
     }
 
+    pub fn execute_epoch(
+        &mut self,
+        gauge: &Addr,
+        sender: impl Into<String>,
+        gauge_id: u64,
+        epoch: u64,
+    ) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            Addr::unchecked(sender),
+            gauge.clone(),
+            &ExecuteMsg::ExecuteEpoch {
+                gauge: gauge_id,
+                epoch,
+            },
+            &[],
+        )
+    }
+
     pub fn query_gauge(&self, gauge_contract: Addr, id: u64) -> StdResult<GaugeResponse> {
         self.app
             .wrap()
@@ -409,44 +816,505 @@ impl Suite {
             .gauges)
     }
 
-    pub fn query_selected_set(
-        &self,
-        gauge_contract: &Addr,
-        id: u64,
-    ) -> StdResult<Vec<(String, Uint128)>> {
-        let set: SelectedSetResponse = self
+    pub fn query_next_execution(&self, gauge_contract: &Addr, id: u64) -> StdResult<Expiration> {
+        let next: NextExecutionResponse = self
             .app
             .wrap()
-            .query_wasm_smart(gauge_contract, &QueryMsg::SelectedSet { gauge: id })?;
-        Ok(set.votes)
+            .query_wasm_smart(gauge_contract, &QueryMsg::NextExecution { gauge: id })?;
+        Ok(next.next)
     }
 
-    pub fn query_last_executed_set(
+    pub fn query_next_execution_label(
         &self,
         gauge_contract: &Addr,
         id: u64,
-    ) -> StdResult<Option<Vec<(String, Uint128)>>> {
-        let set: LastExecutedSetResponse = self
-            .app
-            .wrap()
-            .query_wasm_smart(gauge_contract, &QueryMsg::LastExecutedSet { gauge: id })?;
-        Ok(set.votes)
+    ) -> StdResult<Option<String>> {
+        Ok(self
+            .query_next_execution_response(gauge_contract, id)?
+            .epoch_label)
     }
 
-    pub fn query_list_options(
+    pub fn query_next_execution_response(
         &self,
         gauge_contract: &Addr,
         id: u64,
-    ) -> StdResult<Vec<(String, Uint128)>> {
-        let set: ListOptionsResponse = self.app.wrap().query_wasm_smart(
-            gauge_contract,
-            &QueryMsg::ListOptions {
-                gauge: id,
-                start_after: None,
-                limit: None,
-            },
-        )?;
-        Ok(set.options)
+    ) -> StdResult<NextExecutionResponse> {
+        self.app
+            .wrap()
+            .query_wasm_smart(gauge_contract, &QueryMsg::NextExecution { gauge: id })
+    }
+
+    pub fn query_executable_gauges_with_preview(
+        &self,
+        gauge_contract: &Addr,
+        limit: impl Into<Option<u32>>,
+    ) -> StdResult<Vec<ExecutableGaugePreview>> {
+        Ok(self
+            .app
+            .wrap()
+            .query_wasm_smart::<ExecutableGaugesWithPreviewResponse>(
+                gauge_contract,
+                &QueryMsg::ExecutableGaugesWithPreview {
+                    limit: limit.into(),
+                },
+            )?
+            .gauges)
+    }
+
+    pub fn query_executable_gauges(
+        &self,
+        gauge_contract: &Addr,
+        limit: impl Into<Option<u32>>,
+    ) -> StdResult<Vec<u64>> {
+        Ok(self
+            .app
+            .wrap()
+            .query_wasm_smart::<ExecutableGaugesResponse>(
+                gauge_contract,
+                &QueryMsg::ExecutableGauges {
+                    limit: limit.into(),
+                },
+            )?
+            .gauges)
+    }
+
+    pub fn query_gauges_by_title(
+        &self,
+        gauge_contract: &Addr,
+        title: &str,
+    ) -> StdResult<Vec<GaugeResponse>> {
+        Ok(self
+            .app
+            .wrap()
+            .query_wasm_smart::<ListGaugesResponse>(
+                gauge_contract,
+                &QueryMsg::GaugesByTitle {
+                    title: title.to_owned(),
+                },
+            )?
+            .gauges)
+    }
+
+    pub fn query_gauges_for_adapter(
+        &self,
+        gauge_contract: &Addr,
+        adapter: &Addr,
+    ) -> StdResult<Vec<u64>> {
+        let response: GaugesForAdapterResponse = self.app.wrap().query_wasm_smart(
+            gauge_contract,
+            &QueryMsg::GaugesForAdapter {
+                adapter: adapter.to_string(),
+            },
+        )?;
+        Ok(response.gauge_ids)
+    }
+
+    pub fn query_gauges_for_option(
+        &self,
+        gauge_contract: &Addr,
+        option: &str,
+        start_after: impl Into<Option<u64>>,
+        limit: impl Into<Option<u32>>,
+    ) -> StdResult<Vec<u64>> {
+        let response: GaugesForOptionResponse = self.app.wrap().query_wasm_smart(
+            gauge_contract,
+            &QueryMsg::GaugesForOption {
+                option: option.to_owned(),
+                start_after: start_after.into(),
+                limit: limit.into(),
+            },
+        )?;
+        Ok(response.gauge_ids)
+    }
+
+    pub fn query_gauges_by_tag(
+        &self,
+        gauge_contract: &Addr,
+        tag: &str,
+        start_after: impl Into<Option<u64>>,
+        limit: impl Into<Option<u32>>,
+    ) -> StdResult<Vec<GaugeResponse>> {
+        Ok(self
+            .app
+            .wrap()
+            .query_wasm_smart::<ListGaugesResponse>(
+                gauge_contract,
+                &QueryMsg::ListGaugesByTag {
+                    tag: tag.to_owned(),
+                    start_after: start_after.into(),
+                    limit: limit.into(),
+                },
+            )?
+            .gauges)
+    }
+
+    pub fn query_selected_set(
+        &self,
+        gauge_contract: &Addr,
+        id: u64,
+    ) -> StdResult<Vec<(String, Uint128)>> {
+        let set: SelectedSetResponse = self
+            .app
+            .wrap()
+            .query_wasm_smart(gauge_contract, &QueryMsg::SelectedSet { gauge: id })?;
+        Ok(set.votes)
+    }
+
+    pub fn query_option_leaderboard(
+        &self,
+        gauge_contract: &Addr,
+        id: u64,
+        limit: impl Into<Option<u32>>,
+    ) -> StdResult<Vec<LeaderboardEntry>> {
+        let leaderboard: OptionLeaderboardResponse = self.app.wrap().query_wasm_smart(
+            gauge_contract,
+            &QueryMsg::OptionLeaderboard {
+                gauge: id,
+                limit: limit.into(),
+            },
+        )?;
+        Ok(leaderboard.options)
+    }
+
+    pub fn query_last_executed_set(
+        &self,
+        gauge_contract: &Addr,
+        id: u64,
+    ) -> StdResult<Option<Vec<(String, Uint128)>>> {
+        let set: LastExecutedSetResponse = self
+            .app
+            .wrap()
+            .query_wasm_smart(gauge_contract, &QueryMsg::LastExecutedSet { gauge: id })?;
+        Ok(set.votes)
+    }
+
+    pub fn query_last_executed_by(
+        &self,
+        gauge_contract: &Addr,
+        id: u64,
+    ) -> StdResult<Option<Addr>> {
+        let set: LastExecutedSetResponse = self
+            .app
+            .wrap()
+            .query_wasm_smart(gauge_contract, &QueryMsg::LastExecutedSet { gauge: id })?;
+        Ok(set.executed_by)
+    }
+
+    pub fn query_execution_failures(
+        &self,
+        gauge_contract: &Addr,
+        id: u64,
+    ) -> StdResult<Vec<ExecutionFailure>> {
+        let failures: ExecutionFailuresResponse = self
+            .app
+            .wrap()
+            .query_wasm_smart(gauge_contract, &QueryMsg::ExecutionFailures { gauge: id })?;
+        Ok(failures.failures)
+    }
+
+    pub fn query_execution_inputs(
+        &self,
+        gauge_contract: &Addr,
+        id: u64,
+        epoch: u64,
+    ) -> StdResult<Option<ExecutionInputs>> {
+        let inputs: ExecutionInputsResponse = self.app.wrap().query_wasm_smart(
+            gauge_contract,
+            &QueryMsg::ExecutionInputs { gauge: id, epoch },
+        )?;
+        Ok(inputs.inputs)
+    }
+
+    pub fn query_preview_selected_set(
+        &self,
+        gauge_contract: &Addr,
+        inputs: &ExecutionInputs,
+    ) -> StdResult<Vec<(String, Uint128)>> {
+        let set: SelectedSetResponse = self.app.wrap().query_wasm_smart(
+            gauge_contract,
+            &QueryMsg::PreviewSelectedSet {
+                tallies: inputs.tallies.clone(),
+                min_percent_selected: inputs.min_percent_selected,
+                min_absolute_tally: inputs.min_absolute_tally,
+                max_options_selected: inputs.max_options_selected,
+                max_available_percentage: inputs.max_available_percentage,
+            },
+        )?;
+        Ok(set.votes)
+    }
+
+    pub fn query_set_delta(&self, gauge_contract: &Addr, id: u64) -> StdResult<Vec<OptionDelta>> {
+        let delta: SetDeltaResponse = self
+            .app
+            .wrap()
+            .query_wasm_smart(gauge_contract, &QueryMsg::SetDelta { gauge: id })?;
+        Ok(delta.deltas)
+    }
+
+    pub fn query_execution_complexity(
+        &self,
+        gauge_contract: &Addr,
+        id: u64,
+    ) -> StdResult<ExecutionComplexityResponse> {
+        self.app
+            .wrap()
+            .query_wasm_smart(gauge_contract, &QueryMsg::ExecutionComplexity { gauge: id })
+    }
+
+    pub fn query_remaining_budget(
+        &self,
+        gauge_contract: &Addr,
+        id: u64,
+    ) -> StdResult<RemainingBudgetResponse> {
+        self.app
+            .wrap()
+            .query_wasm_smart(gauge_contract, &QueryMsg::RemainingBudget { gauge: id })
+    }
+
+    pub fn query_unvoted_options(
+        &self,
+        gauge_contract: &Addr,
+        id: u64,
+        voter: &str,
+    ) -> StdResult<Vec<String>> {
+        let res: UnvotedOptionsResponse = self.app.wrap().query_wasm_smart(
+            gauge_contract,
+            &QueryMsg::UnvotedOptions {
+                gauge: id,
+                voter: voter.to_owned(),
+                start_after: None,
+                limit: None,
+            },
+        )?;
+        Ok(res.options)
+    }
+
+    pub fn query_vote_distribution(
+        &self,
+        gauge_contract: &Addr,
+        id: u64,
+        buckets: u32,
+    ) -> StdResult<VoteDistributionResponse> {
+        self.app.wrap().query_wasm_smart(
+            gauge_contract,
+            &QueryMsg::VoteDistribution { gauge: id, buckets },
+        )
+    }
+
+    pub fn query_selection_params(
+        &self,
+        gauge_contract: &Addr,
+        id: u64,
+    ) -> StdResult<SelectionParamsResponse> {
+        self.app
+            .wrap()
+            .query_wasm_smart(gauge_contract, &QueryMsg::SelectionParams { gauge: id })
+    }
+
+    pub fn query_qualification_threshold(
+        &self,
+        gauge_contract: &Addr,
+        id: u64,
+    ) -> StdResult<QualificationThresholdResponse> {
+        self.app.wrap().query_wasm_smart(
+            gauge_contract,
+            &QueryMsg::QualificationThreshold { gauge: id },
+        )
+    }
+
+    pub fn query_aggregate_selected_set(
+        &self,
+        gauge_contract: &Addr,
+        denom: &str,
+    ) -> StdResult<AggregateSelectedSetResponse> {
+        self.app.wrap().query_wasm_smart(
+            gauge_contract,
+            &QueryMsg::AggregateSelectedSet {
+                denom: denom.to_owned(),
+            },
+        )
+    }
+
+    pub fn query_adapter_sample_msgs(
+        &self,
+        gauge_contract: &Addr,
+        id: u64,
+        selected: Vec<(String, Uint128)>,
+    ) -> StdResult<Vec<CosmosMsg>> {
+        let msgs: SampleGaugeMsgsResponse = self.app.wrap().query_wasm_smart(
+            gauge_contract,
+            &QueryMsg::AdapterSampleMsgs {
+                gauge: id,
+                selected,
+            },
+        )?;
+        Ok(msgs.execute)
+    }
+
+    pub fn query_voter_projected_impact(
+        &self,
+        gauge_contract: &Addr,
+        id: u64,
+        voter: &str,
+    ) -> StdResult<Vec<VoterOptionImpact>> {
+        let impact: VoterProjectedImpactResponse = self.app.wrap().query_wasm_smart(
+            gauge_contract,
+            &QueryMsg::VoterProjectedImpact {
+                gauge: id,
+                voter: voter.to_owned(),
+            },
+        )?;
+        Ok(impact.options)
+    }
+
+    pub fn query_reward_efficiency(
+        &self,
+        gauge_contract: &Addr,
+        id: u64,
+    ) -> StdResult<Vec<OptionRewardEfficiency>> {
+        let efficiency: RewardEfficiencyResponse = self
+            .app
+            .wrap()
+            .query_wasm_smart(gauge_contract, &QueryMsg::RewardEfficiency { gauge: id })?;
+        Ok(efficiency.options)
+    }
+
+    pub fn query_list_options(
+        &self,
+        gauge_contract: &Addr,
+        id: u64,
+    ) -> StdResult<Vec<(String, Uint128)>> {
+        Ok(self
+            .query_list_options_response(gauge_contract, id)?
+            .options)
+    }
+
+    pub fn query_list_options_response(
+        &self,
+        gauge_contract: &Addr,
+        id: u64,
+    ) -> StdResult<ListOptionsResponse> {
+        self.app.wrap().query_wasm_smart(
+            gauge_contract,
+            &QueryMsg::ListOptions {
+                gauge: id,
+                start_after: None,
+                limit: None,
+                order_by: OptionOrder::Key,
+            },
+        )
+    }
+
+    pub fn query_list_options_ordered(
+        &self,
+        gauge_contract: &Addr,
+        id: u64,
+        order_by: OptionOrder,
+        start_after: impl Into<Option<String>>,
+        limit: impl Into<Option<u32>>,
+    ) -> StdResult<Vec<(String, Uint128)>> {
+        let response: ListOptionsResponse = self.app.wrap().query_wasm_smart(
+            gauge_contract,
+            &QueryMsg::ListOptions {
+                gauge: id,
+                start_after: start_after.into(),
+                limit: limit.into(),
+                order_by,
+            },
+        )?;
+        Ok(response.options)
+    }
+
+    pub fn query_shut_out_options(
+        &self,
+        gauge_contract: &Addr,
+        id: u64,
+    ) -> StdResult<ShutOutOptionsResponse> {
+        self.app
+            .wrap()
+            .query_wasm_smart(gauge_contract, &QueryMsg::ShutOutOptions { gauge: id })
+    }
+
+    pub fn query_recent_voters(
+        &self,
+        gauge_contract: &Addr,
+        id: u64,
+        limit: impl Into<Option<u32>>,
+    ) -> StdResult<Vec<(String, VoterActivity)>> {
+        let recent: RecentVotersResponse = self.app.wrap().query_wasm_smart(
+            gauge_contract,
+            &QueryMsg::RecentVoters {
+                gauge: id,
+                start_after: None,
+                limit: limit.into(),
+            },
+        )?;
+        Ok(recent.voters)
+    }
+
+    pub fn query_recent_voters_after(
+        &self,
+        gauge_contract: &Addr,
+        id: u64,
+        start_after: &str,
+        limit: impl Into<Option<u32>>,
+    ) -> StdResult<Vec<(String, VoterActivity)>> {
+        let recent: RecentVotersResponse = self.app.wrap().query_wasm_smart(
+            gauge_contract,
+            &QueryMsg::RecentVoters {
+                gauge: id,
+                start_after: Some(start_after.to_owned()),
+                limit: limit.into(),
+            },
+        )?;
+        Ok(recent.voters)
+    }
+
+    pub fn query_voter_activity(
+        &self,
+        gauge_contract: &Addr,
+        id: u64,
+        voter: &str,
+    ) -> StdResult<Option<VoterActivity>> {
+        let activity: VoterActivityResponse = self.app.wrap().query_wasm_smart(
+            gauge_contract,
+            &QueryMsg::VoterActivity {
+                gauge: id,
+                voter: voter.to_owned(),
+            },
+        )?;
+        Ok(activity.last_voted_at)
+    }
+
+    pub fn query_voter_missed_gauges(
+        &self,
+        gauge_contract: &Addr,
+        voter: &str,
+        limit: impl Into<Option<u32>>,
+    ) -> StdResult<Vec<u64>> {
+        let res: VoterMissedGaugesResponse = self.app.wrap().query_wasm_smart(
+            gauge_contract,
+            &QueryMsg::VoterMissedGauges {
+                voter: voter.to_owned(),
+                limit: limit.into(),
+            },
+        )?;
+        Ok(res.gauge_ids)
+    }
+
+    pub fn query_can_vote(
+        &self,
+        gauge_contract: &Addr,
+        id: u64,
+        voter: &str,
+    ) -> StdResult<CanVoteResponse> {
+        self.app.wrap().query_wasm_smart(
+            gauge_contract,
+            &QueryMsg::CanVote {
+                gauge: id,
+                voter: voter.to_owned(),
+            },
+        )
     }
 
     pub fn query_vote(
@@ -465,18 +1333,186 @@ impl Suite {
         Ok(vote.vote)
     }
 
+    /// Removes members from the cw4 group behind the voting module. The gauge is not notified,
+    /// so their votes stay in place
+    pub fn remove_voting_members(&mut self, members: &[&str]) -> AnyResult<AppResponse> {
+        let group: Addr = self
+            .app
+            .wrap()
+            .query_wasm_smart(&self.voting, &VotingQueryMsg::GroupContract {})?;
+        self.app.execute_contract(
+            self.core.clone(),
+            group,
+            &Cw4GroupExecuteMsg::UpdateMembers {
+                remove: members.iter().map(|&m| m.to_owned()).collect(),
+                add: vec![],
+            },
+            &[],
+        )
+    }
+
+    /// Sets the voting power of a member (adding them if needed)
+    pub fn set_voting_power(&mut self, member: &str, weight: u64) -> AnyResult<AppResponse> {
+        let group: Addr = self
+            .app
+            .wrap()
+            .query_wasm_smart(&self.voting, &VotingQueryMsg::GroupContract {})?;
+        self.app.execute_contract(
+            self.core.clone(),
+            group,
+            &Cw4GroupExecuteMsg::UpdateMembers {
+                remove: vec![],
+                add: vec![Member {
+                    addr: member.to_owned(),
+                    weight,
+                }],
+            },
+            &[],
+        )
+    }
+
+    pub fn query_participation(
+        &self,
+        gauge_contract: &Addr,
+        id: u64,
+    ) -> StdResult<ParticipationResponse> {
+        self.app
+            .wrap()
+            .query_wasm_smart(gauge_contract, &QueryMsg::Participation { gauge: id })
+    }
+
+    pub fn query_participation_history(
+        &self,
+        gauge_contract: &Addr,
+        id: u64,
+        start_after: impl Into<Option<u64>>,
+        limit: impl Into<Option<u32>>,
+    ) -> StdResult<Vec<EpochParticipation>> {
+        let history: ParticipationHistoryResponse = self.app.wrap().query_wasm_smart(
+            gauge_contract,
+            &QueryMsg::ParticipationHistory {
+                gauge: id,
+                start_after: start_after.into(),
+                limit: limit.into(),
+            },
+        )?;
+        Ok(history.history)
+    }
+
+    pub fn query_option_tally_history(
+        &self,
+        gauge_contract: &Addr,
+        id: u64,
+        option: &str,
+        start_after: impl Into<Option<u64>>,
+        limit: impl Into<Option<u32>>,
+    ) -> StdResult<Vec<OptionTallySnapshot>> {
+        let history: OptionTallyHistoryResponse = self.app.wrap().query_wasm_smart(
+            gauge_contract,
+            &QueryMsg::OptionTallyHistory {
+                gauge: id,
+                option: option.to_owned(),
+                start_after: start_after.into(),
+                limit: limit.into(),
+            },
+        )?;
+        Ok(history.history)
+    }
+
+    pub fn query_option_lifetime_rewards(
+        &self,
+        gauge_contract: &Addr,
+        id: u64,
+        option: &str,
+    ) -> StdResult<Vec<Asset>> {
+        let rewards: OptionLifetimeRewardsResponse = self.app.wrap().query_wasm_smart(
+            gauge_contract,
+            &QueryMsg::OptionLifetimeRewards {
+                gauge: id,
+                option: option.to_owned(),
+            },
+        )?;
+        Ok(rewards.rewards)
+    }
+
+    pub fn query_votes_to_overtake(
+        &self,
+        gauge_contract: &Addr,
+        id: u64,
+        option: &str,
+        target_option: &str,
+    ) -> StdResult<Uint128> {
+        let response: VotesToOvertakeResponse = self.app.wrap().query_wasm_smart(
+            gauge_contract,
+            &QueryMsg::VotesToOvertake {
+                gauge: id,
+                option: option.to_owned(),
+                target_option: target_option.to_owned(),
+            },
+        )?;
+        Ok(response.votes_needed)
+    }
+
+    pub fn query_option_voters(
+        &self,
+        gauge_contract: &Addr,
+        id: u64,
+        option: &str,
+    ) -> StdResult<Vec<(String, Decimal)>> {
+        let voters: OptionVotersResponse = self.app.wrap().query_wasm_smart(
+            gauge_contract,
+            &QueryMsg::OptionVoters {
+                gauge: id,
+                option: option.to_owned(),
+                start_after: None,
+                limit: None,
+            },
+        )?;
+        Ok(voters.voters)
+    }
+
     pub fn query_list_votes(&self, gauge_contract: &Addr, id: u64) -> StdResult<Vec<VoteInfo>> {
+        self.query_list_votes_filtered(gauge_contract, id, false)
+    }
+
+    pub fn query_list_votes_filtered(
+        &self,
+        gauge_contract: &Addr,
+        id: u64,
+        only_active: bool,
+    ) -> StdResult<Vec<VoteInfo>> {
         let vote: ListVotesResponse = self.app.wrap().query_wasm_smart(
             gauge_contract,
             &QueryMsg::ListVotes {
                 gauge: id,
                 start_after: None,
                 limit: None,
+                only_active,
             },
         )?;
         Ok(vote.votes)
     }
 
+    pub fn query_votes_changed_since(
+        &self,
+        gauge_contract: &Addr,
+        id: u64,
+        since_block: u64,
+        start_after: Option<&str>,
+        limit: Option<u32>,
+    ) -> StdResult<Vec<ChangedVote>> {
+        let res: VotesChangedSinceResponse = self.app.wrap().query_wasm_smart(
+            gauge_contract,
+            &QueryMsg::VotesChangedSince {
+                gauge: id,
+                since_block,
+                start_after: start_after.map(str::to_owned),
+                limit,
+            },
+        )?;
+        Ok(res.votes)
+    }
+
     // -----------------------------------------------------
 
     pub fn propose_update_proposal_module(
@@ -496,6 +1532,8 @@ impl Suite {
                             voting_powers: self.voting.to_string(),
                             owner: self.owner.clone(),
                             gauges: gauge_config.into(),
+                            event_namespace: self.event_namespace.clone(),
+                            voting_query: None,
                         })?,
                         admin: Admin::Address {
                             addr: self.owner.clone(),
@@ -515,6 +1553,68 @@ impl Suite {
         )
     }
 
+    /// Instantiates the gauge contract directly (not as a proposal module), with the DAO core
+    /// as sender.
+    pub fn instantiate_gauge(
+        &mut self,
+        gauge_config: impl Into<Option<Vec<GaugeConfig>>>,
+    ) -> AnyResult<Addr> {
+        self.app.instantiate_contract(
+            self.gauge_code_id,
+            self.core.clone(),
+            &InstantiateMsg {
+                voting_powers: self.voting.to_string(),
+                owner: self.owner.clone(),
+                gauges: gauge_config.into(),
+                event_namespace: self.event_namespace.clone(),
+                voting_query: None,
+            },
+            &[],
+            "gauge",
+            None,
+        )
+    }
+
+    /// Same as `instantiate_gauge`, with the voting powers of another contract
+    pub fn instantiate_gauge_with_voting_query(
+        &mut self,
+        voting_powers: &Addr,
+        voting_query: VotingQuery,
+    ) -> AnyResult<Addr> {
+        self.app.instantiate_contract(
+            self.gauge_code_id,
+            self.core.clone(),
+            &InstantiateMsg {
+                voting_powers: voting_powers.to_string(),
+                owner: self.owner.clone(),
+                gauges: None,
+                event_namespace: self.event_namespace.clone(),
+                voting_query: Some(voting_query),
+            },
+            &[],
+            "gauge",
+            None,
+        )
+    }
+
+    /// Instantiates a staking contract with given `(address, stake)` pairs, answering
+    /// the queries of `VotingQuery::StakedBalanceAtHeight`
+    pub fn instantiate_staking(&mut self, stakes: &[(&str, u128)]) -> AnyResult<Addr> {
+        self.app.instantiate_contract(
+            self.staking_code_id,
+            Addr::unchecked(&self.owner),
+            &StakingInstantiateMsg {
+                stakes: stakes
+                    .iter()
+                    .map(|(address, stake)| (address.to_string(), Uint128::new(*stake)))
+                    .collect(),
+            },
+            &[],
+            "staking",
+            None,
+        )
+    }
+
     pub fn instantiate_adapter_and_create_gauge(
         &mut self,
         gauge_contract: Addr,
@@ -530,23 +1630,113 @@ impl Suite {
             reset_epoch,
         )?;
         let gauge_adapter = option.adapter.clone();
+        self.create_gauge(&gauge_contract, option)?;
+        Ok(Addr::unchecked(gauge_adapter))
+    }
+
+    pub fn create_gauge(
+        &mut self,
+        gauge_contract: &Addr,
+        config: GaugeConfig,
+    ) -> AnyResult<AppResponse> {
         self.app.execute_contract(
             Addr::unchecked(&self.owner),
-            gauge_contract,
-            &ExecuteMsg::CreateGauge(option),
+            gauge_contract.clone(),
+            &ExecuteMsg::CreateGauge(config),
             &[],
-        )?;
-        Ok(Addr::unchecked(gauge_adapter))
+        )
     }
 
-    pub fn instantiate_adapter_and_return_config(
+    pub fn pause_all(&mut self, gauge_contract: &Addr, sender: &str) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            Addr::unchecked(sender),
+            gauge_contract.clone(),
+            &ExecuteMsg::PauseAll {},
+            &[],
+        )
+    }
+
+    pub fn unpause_all(&mut self, gauge_contract: &Addr, sender: &str) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            Addr::unchecked(sender),
+            gauge_contract.clone(),
+            &ExecuteMsg::UnpauseAll {},
+            &[],
+        )
+    }
+
+    pub fn query_config(&self, gauge_contract: &Addr) -> StdResult<ConfigResponse> {
+        self.app
+            .wrap()
+            .query_wasm_smart(gauge_contract, &QueryMsg::Config {})
+    }
+
+    pub fn split_gauge(
+        &mut self,
+        gauge_contract: &Addr,
+        sender: &str,
+        gauge: u64,
+        options_to_move: &[&str],
+        new_config: GaugeConfig,
+    ) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            Addr::unchecked(sender),
+            gauge_contract.clone(),
+            &ExecuteMsg::SplitGauge {
+                gauge,
+                options_to_move: options_to_move.iter().map(|o| o.to_string()).collect(),
+                new_config,
+            },
+            &[],
+        )
+    }
+
+    pub fn update_execute_allowlist(
+        &mut self,
+        gauge_contract: &Addr,
+        sender: &str,
+        gauge: u64,
+        add: &[&str],
+        remove: &[&str],
+    ) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            Addr::unchecked(sender),
+            gauge_contract.clone(),
+            &ExecuteMsg::UpdateExecuteAllowlist {
+                gauge,
+                add: add.iter().map(|a| a.to_string()).collect(),
+                remove: remove.iter().map(|r| r.to_string()).collect(),
+            },
+            &[],
+        )
+    }
+
+    pub fn clone_gauge(
+        &mut self,
+        gauge_contract: &Addr,
+        sender: &str,
+        source_gauge: u64,
+        new_adapter: Option<&Addr>,
+        new_title: Option<&str>,
+    ) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            Addr::unchecked(sender),
+            gauge_contract.clone(),
+            &ExecuteMsg::CloneGauge {
+                source_gauge,
+                new_adapter: new_adapter.map(Addr::to_string),
+                new_title: new_title.map(str::to_owned),
+            },
+            &[],
+        )
+    }
+
+    pub fn instantiate_adapter(
         &mut self,
         options: &[&str],
         to_distribute: (u128, &str),
-        max_available_percentage: impl Into<Option<Decimal>>,
-        reset_epoch: impl Into<Option<u64>>,
-    ) -> AnyResult<GaugeConfig> {
-        let gauge_adapter = self.app.instantiate_contract(
+    ) -> AnyResult<Addr> {
+        self.app.instantiate_contract(
             self.gauge_adapter_code_id,
             Addr::unchecked(&self.owner),
             &AdapterInstantiateMsg {
@@ -556,7 +1746,17 @@ impl Suite {
             &[],
             "gauge adapter",
             None,
-        )?;
+        )
+    }
+
+    pub fn instantiate_adapter_and_return_config(
+        &mut self,
+        options: &[&str],
+        to_distribute: (u128, &str),
+        max_available_percentage: impl Into<Option<Decimal>>,
+        reset_epoch: impl Into<Option<u64>>,
+    ) -> AnyResult<GaugeConfig> {
+        let gauge_adapter = self.instantiate_adapter(options, to_distribute)?;
 
         Ok(GaugeConfig {
             title: "gauge".to_owned(),
@@ -566,6 +1766,42 @@ impl Suite {
             max_options_selected: 10,
             max_available_percentage: max_available_percentage.into(),
             reset_epoch: reset_epoch.into(),
+            to_distribute: None,
+            reward_schedule: None,
+            max_voters: None,
+            validate_option_on_vote: false,
+            voter_allowlist: None,
+            voter_merkle_root: None,
+            tenure_weighting: false,
+            tenure_curve: None,
+            bootstrap_equal_split: false,
+            reclaim_votes_on_remove: false,
+            adapter_multipliers: false,
+            isolate_failures: false,
+            epoch_unit: EpochUnit::Time,
+            rounding: Rounding::Down,
+            tags: vec![],
+            extra_adapters: vec![],
+            min_absolute_tally: None,
+            distribution_mode: DistributionMode::Proportional,
+            boost_source: None,
+            flashloan_resistant: false,
+            distribution_hook: None,
+            auto_prune_zero_options: false,
+            require_full_allocation: false,
+            total_budget: None,
+            adapter_reward: false,
+            vote_change_fee: None,
+            execute_policy: ExecutePolicy::Anyone,
+            max_paid_options: None,
+            deprecated_vote_policy: DeprecatedVotePolicy::Ignore,
+            min_distribution_per_option: None,
+            max_total_options: None,
+            post_execute_callback: None,
+            align_to: None,
+            max_voter_influence: None,
+            execute_condition: None,
+            swap_route: None,
         })
     }
 
@@ -589,6 +1825,118 @@ impl Suite {
                 min_percent_selected,
                 max_options_selected: max_options_selected.into(),
                 max_available_percentage: max_available_percentage.into(),
+                clear_max_available_percentage: false,
+                tags: None,
+                max_total_options: None,
+            },
+            &[],
+        )
+    }
+
+    pub fn update_gauge_tags(
+        &mut self,
+        sender: &str,
+        gauge_contract: &Addr,
+        gauge_id: u64,
+        tags: &[&str],
+    ) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            Addr::unchecked(sender),
+            gauge_contract.clone(),
+            &ExecuteMsg::UpdateGauge {
+                gauge_id,
+                epoch_size: None,
+                min_percent_selected: None,
+                max_options_selected: None,
+                max_available_percentage: None,
+                clear_max_available_percentage: false,
+                tags: Some(tags.iter().map(|&t| t.to_owned()).collect()),
+                max_total_options: None,
+            },
+            &[],
+        )
+    }
+
+    pub fn update_gauge_max_total_options(
+        &mut self,
+        sender: &str,
+        gauge_contract: &Addr,
+        gauge_id: u64,
+        max_total_options: u32,
+    ) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            Addr::unchecked(sender),
+            gauge_contract.clone(),
+            &ExecuteMsg::UpdateGauge {
+                gauge_id,
+                epoch_size: None,
+                min_percent_selected: None,
+                max_options_selected: None,
+                max_available_percentage: None,
+                clear_max_available_percentage: false,
+                tags: None,
+                max_total_options: Some(max_total_options),
+            },
+            &[],
+        )
+    }
+
+    pub fn clear_gauge_max_available_percentage(
+        &mut self,
+        sender: &str,
+        gauge_contract: &Addr,
+        gauge_id: u64,
+        max_available_percentage: Option<Decimal>,
+    ) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            Addr::unchecked(sender),
+            gauge_contract.clone(),
+            &ExecuteMsg::UpdateGauge {
+                gauge_id,
+                epoch_size: None,
+                min_percent_selected: None,
+                max_options_selected: None,
+                max_available_percentage,
+                clear_max_available_percentage: true,
+                tags: None,
+                max_total_options: None,
+            },
+            &[],
+        )
+    }
+
+    pub fn fund_gauge(
+        &mut self,
+        sender: &str,
+        gauge_contract: &Addr,
+        gauge: u64,
+        funds: &[Coin],
+    ) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            Addr::unchecked(sender),
+            gauge_contract.clone(),
+            &ExecuteMsg::FundGauge { gauge },
+            funds,
+        )
+    }
+
+    pub fn emergency_withdraw(
+        &mut self,
+        sender: &str,
+        gauge_contract: &Addr,
+        denom: &str,
+        amount: impl Into<Option<u128>>,
+        recipient: &str,
+        gauge: impl Into<Option<u64>>,
+    ) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            Addr::unchecked(sender),
+            gauge_contract.clone(),
+            &ExecuteMsg::EmergencyWithdraw {
+                denom: denom.to_owned(),
+                amount: amount.into().map(Uint128::new),
+                recipient: recipient.to_owned(),
+                gauge: gauge.into(),
             },
             &[],
         )
@@ -662,6 +2010,147 @@ impl Suite {
         Ok(balance.amount.u128())
     }
 
+    /// Instantiates a cw20 token with given initial balances
+    pub fn instantiate_cw20(&mut self, balances: &[(&str, u128)]) -> AnyResult<Addr> {
+        self.app.instantiate_contract(
+            self.cw20_code_id,
+            Addr::unchecked(&self.owner),
+            &Cw20BaseInstantiateMsg {
+                name: "Reward Token".to_owned(),
+                symbol: "REWARD".to_owned(),
+                decimals: 6,
+                initial_balances: balances
+                    .iter()
+                    .map(|(address, amount)| Cw20Coin {
+                        address: address.to_string(),
+                        amount: Uint128::new(*amount),
+                    })
+                    .collect(),
+                mint: None,
+                marketing: None,
+            },
+            &[],
+            "reward token",
+            None,
+        )
+    }
+
+    /// Instantiates a membership source with given `(member, joined at)` pairs
+    pub fn instantiate_membership(&mut self, members: &[(&str, u64)]) -> AnyResult<Addr> {
+        self.app.instantiate_contract(
+            self.membership_code_id,
+            Addr::unchecked(&self.owner),
+            &MembershipInstantiateMsg {
+                members: members
+                    .iter()
+                    .map(|(member, since)| (member.to_string(), *since))
+                    .collect(),
+            },
+            &[],
+            "membership",
+            None,
+        )
+    }
+
+    /// Instantiates a boost source with given `(address, multiplier)` pairs
+    pub fn instantiate_boost_source(&mut self, boosts: &[(&str, Decimal)]) -> AnyResult<Addr> {
+        self.app.instantiate_contract(
+            self.boost_code_id,
+            Addr::unchecked(&self.owner),
+            &BoostInstantiateMsg {
+                boosts: boosts
+                    .iter()
+                    .map(|(address, multiplier)| (address.to_string(), *multiplier))
+                    .collect(),
+            },
+            &[],
+            "boost",
+            None,
+        )
+    }
+
+    /// Instantiates a distribution hook, which splits the funds it receives by the weights
+    pub fn instantiate_distribution_hook(&mut self) -> AnyResult<Addr> {
+        self.app.instantiate_contract(
+            self.hook_code_id,
+            Addr::unchecked(&self.owner),
+            &Empty {},
+            &[],
+            "distribution_hook",
+            None,
+        )
+    }
+
+    pub fn query_hook_distributions(&self, hook: &Addr) -> StdResult<Vec<Distribution>> {
+        self.app
+            .wrap()
+            .query_wasm_smart(hook, &HookQueryMsg::Distributions {})
+    }
+
+    /// Instantiates a post execute callback, which fails on every notification if `fail` is set
+    pub fn instantiate_post_execute_callback(&mut self, fail: bool) -> AnyResult<Addr> {
+        self.app.instantiate_contract(
+            self.callback_code_id,
+            Addr::unchecked(&self.owner),
+            &CallbackInstantiateMsg { fail },
+            &[],
+            "post_execute_callback",
+            None,
+        )
+    }
+
+    pub fn query_callback_notifications(
+        &self,
+        callback: &Addr,
+    ) -> StdResult<Vec<PostExecuteCallbackMsg>> {
+        self.app
+            .wrap()
+            .query_wasm_smart(callback, &CallbackQueryMsg::Notifications {})
+    }
+
+    /// Instantiates a price oracle reporting `price`
+    pub fn instantiate_oracle(&mut self, price: Decimal) -> AnyResult<Addr> {
+        self.app.instantiate_contract(
+            self.oracle_code_id,
+            Addr::unchecked(&self.owner),
+            &OracleInstantiateMsg { price },
+            &[],
+            "oracle",
+            None,
+        )
+    }
+
+    pub fn set_oracle_price(&mut self, oracle: &Addr, price: Decimal) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            Addr::unchecked(&self.owner),
+            oracle.clone(),
+            &OracleExecuteMsg::SetPrice { price },
+            &[],
+        )
+    }
+
+    /// Instantiates a swap router returning `rate` per offered token, funded by the owner
+    pub fn instantiate_router(&mut self, rate: Decimal, funds: &[Coin]) -> AnyResult<Addr> {
+        self.app.instantiate_contract(
+            self.router_code_id,
+            Addr::unchecked(&self.owner),
+            &RouterInstantiateMsg { rate },
+            funds,
+            "router",
+            None,
+        )
+    }
+
+    pub fn query_cw20_balance(&self, token: &Addr, account: &str) -> StdResult<u128> {
+        let balance: BalanceResponse = self.app.wrap().query_wasm_smart(
+            token,
+            &Cw20QueryMsg::Balance {
+                address: account.to_owned(),
+            },
+        )?;
+        Ok(balance.balance.u128())
+    }
+
     pub fn auto_migrate_gauge(
         &mut self,
         gauge: &Addr,
@@ -674,6 +2163,46 @@ impl Suite {
             gauge.clone(),
             &MigrateMsg {
                 gauge_config: gauge_config.into(),
+                backfill_vote_index: None,
+                backfill_option_index: None,
+            },
+            self.gauge_code_id,
+        )
+    }
+
+    pub fn migrate_backfill_vote_index(
+        &mut self,
+        gauge: &Addr,
+        batch_size: u32,
+    ) -> AnyResult<AppResponse> {
+        let sender = Addr::unchecked(&self.owner);
+
+        self.app.migrate_contract(
+            sender,
+            gauge.clone(),
+            &MigrateMsg {
+                gauge_config: None,
+                backfill_vote_index: Some(batch_size),
+                backfill_option_index: None,
+            },
+            self.gauge_code_id,
+        )
+    }
+
+    pub fn migrate_backfill_option_index(
+        &mut self,
+        gauge: &Addr,
+        batch_size: u32,
+    ) -> AnyResult<AppResponse> {
+        let sender = Addr::unchecked(&self.owner);
+
+        self.app.migrate_contract(
+            sender,
+            gauge.clone(),
+            &MigrateMsg {
+                gauge_config: None,
+                backfill_vote_index: None,
+                backfill_option_index: Some(batch_size),
             },
             self.gauge_code_id,
         )
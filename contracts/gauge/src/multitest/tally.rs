@@ -1,7 +1,11 @@
-use cosmwasm_std::Uint128;
+use cosmwasm_std::{Decimal, Uint128};
 use voting::Vote;
 
-use super::suite::SuiteBuilder;
+use super::suite::{init_gauge, SuiteBuilder};
+use crate::error::ContractError;
+use crate::state::{Asset, DeprecatedVotePolicy, OptionTallySnapshot, TenureCurve};
+
+const EPOCH: u64 = 7 * 86_400;
 
 #[test]
 fn multiple_options_one_gauge() {
@@ -331,3 +335,789 @@ fn not_voted_options_are_not_selected() {
         vec![("option2".to_owned(), Uint128::new(720)),]
     );
 }
+
+#[test]
+fn tenure_weighting_favors_long_term_members() {
+    let voter1 = "voter1";
+    let voter2 = "voter2";
+    let mut suite = SuiteBuilder::new()
+        .with_voting_members(&[(voter1, 100), (voter2, 100)])
+        .with_core_balance((1000, "ujuno"))
+        .build();
+
+    let gauge_contract = init_gauge(&mut suite, &[voter1, voter2]);
+
+    // voter1 is a member for a long time already, voter2 just joined
+    let now = suite.current_time();
+    let membership = suite
+        .instantiate_membership(&[(voter1, now - 8 * EPOCH), (voter2, now)])
+        .unwrap();
+
+    let mut gauge_config = suite
+        .instantiate_adapter_and_return_config(&[voter1, voter2], (0, "ujuno"), None, None)
+        .unwrap();
+    gauge_config.to_distribute = Some(Asset::new_native("ujuno", 1000));
+    gauge_config.tenure_weighting = true;
+
+    let err = suite
+        .create_gauge(&gauge_contract, gauge_config.clone())
+        .unwrap_err();
+    assert_eq!(
+        ContractError::TenureCurveMissing {},
+        err.downcast().unwrap()
+    );
+
+    let curve = TenureCurve {
+        source: membership.to_string(),
+        max_multiplier: Decimal::percent(300),
+        half_life: 3 * EPOCH,
+    };
+    gauge_config.tenure_curve = Some(curve.clone());
+    suite.create_gauge(&gauge_contract, gauge_config).unwrap();
+    let gauge_id = 0;
+    assert_eq!(
+        suite
+            .query_gauge(gauge_contract.clone(), gauge_id)
+            .unwrap()
+            .tenure_curve,
+        Some(curve)
+    );
+
+    suite
+        .place_vote(&gauge_contract, voter1, gauge_id, Some(voter1.to_owned()))
+        .unwrap();
+    suite
+        .place_vote(&gauge_contract, voter2, gauge_id, Some(voter2.to_owned()))
+        .unwrap();
+
+    // raw tallies are not affected
+    assert_eq!(
+        suite.query_selected_set(&gauge_contract, gauge_id).unwrap(),
+        vec![
+            (voter2.to_owned(), Uint128::new(100)),
+            (voter1.to_owned(), Uint128::new(100)),
+        ]
+    );
+
+    suite.advance_time(EPOCH);
+    suite
+        .execute_options(&gauge_contract, voter1, gauge_id)
+        .unwrap();
+
+    // on execution voter1 has a tenure of 9 epochs (multiplier 2.5),
+    // voter2 of 1 epoch (multiplier 1.5)
+    assert_eq!(
+        suite
+            .query_last_executed_set(&gauge_contract, gauge_id)
+            .unwrap(),
+        Some(vec![
+            (voter1.to_owned(), Uint128::new(250)),
+            (voter2.to_owned(), Uint128::new(150)),
+        ])
+    );
+    assert_eq!(suite.query_balance(voter1, "ujuno").unwrap(), 625);
+    assert_eq!(suite.query_balance(voter2, "ujuno").unwrap(), 375);
+}
+
+#[test]
+fn execution_inputs_reproduce_executed_set() {
+    let voter1 = "voter1";
+    let voter2 = "voter2";
+    let voter3 = "voter3";
+    let mut suite = SuiteBuilder::new()
+        .with_voting_members(&[(voter1, 300), (voter2, 200), (voter3, 100)])
+        .with_core_balance((1000, "ujuno"))
+        .build();
+
+    let gauge_contract = init_gauge(&mut suite, &[voter1, voter2, voter3]);
+
+    // votes above 40% are discarded
+    let mut gauge_config = suite
+        .instantiate_adapter_and_return_config(
+            &["option1", "option2", "option3"],
+            (1000, "ujuno"),
+            Decimal::percent(40),
+            None,
+        )
+        .unwrap();
+    gauge_config.max_options_selected = 2;
+    suite.create_gauge(&gauge_contract, gauge_config).unwrap();
+    let gauge_id = 0;
+
+    // nothing was executed yet
+    assert_eq!(
+        suite
+            .query_execution_inputs(&gauge_contract, gauge_id, 0)
+            .unwrap(),
+        None
+    );
+
+    suite
+        .place_vote(
+            &gauge_contract,
+            voter1,
+            gauge_id,
+            Some("option1".to_owned()),
+        )
+        .unwrap();
+    suite
+        .place_vote(
+            &gauge_contract,
+            voter2,
+            gauge_id,
+            Some("option2".to_owned()),
+        )
+        .unwrap();
+    suite
+        .place_vote(
+            &gauge_contract,
+            voter3,
+            gauge_id,
+            Some("option3".to_owned()),
+        )
+        .unwrap();
+
+    suite.advance_time(EPOCH);
+    suite
+        .execute_options(&gauge_contract, voter1, gauge_id)
+        .unwrap();
+
+    let inputs = suite
+        .query_execution_inputs(&gauge_contract, gauge_id, 0)
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        inputs.tallies,
+        vec![
+            ("option1".to_owned(), Uint128::new(300)),
+            ("option2".to_owned(), Uint128::new(200)),
+            ("option3".to_owned(), Uint128::new(100)),
+        ]
+    );
+    assert_eq!(inputs.total_power, Uint128::new(600));
+    assert_eq!(inputs.max_options_selected, 2);
+    assert_eq!(inputs.max_available_percentage, Some(Decimal::percent(40)));
+    assert_eq!(inputs.to_distribute, None);
+
+    // the stored inputs reproduce the executed set
+    let executed = suite
+        .query_last_executed_set(&gauge_contract, gauge_id)
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        executed,
+        vec![
+            ("option1".to_owned(), Uint128::new(240)),
+            ("option2".to_owned(), Uint128::new(200)),
+        ]
+    );
+    assert_eq!(
+        suite
+            .query_preview_selected_set(&gauge_contract, &inputs)
+            .unwrap(),
+        executed
+    );
+}
+
+#[test]
+fn boost_source_multiplies_locked_voters_power() {
+    let voter1 = "voter1";
+    let voter2 = "voter2";
+    let voter3 = "voter3";
+    let mut suite = SuiteBuilder::new()
+        .with_voting_members(&[(voter1, 100), (voter2, 100), (voter3, 100)])
+        .with_core_balance((1100, "ujuno"))
+        .build();
+
+    let gauge_contract = init_gauge(&mut suite, &[voter1, voter2, voter3]);
+
+    // voter1 locked tokens, voter2 didn't, voter3 gets more than the maximum boost
+    let boost_source = suite
+        .instantiate_boost_source(&[
+            (voter1, Decimal::percent(200)),
+            (voter3, Decimal::percent(1000)),
+        ])
+        .unwrap();
+
+    let mut gauge_config = suite
+        .instantiate_adapter_and_return_config(&[voter1, voter2, voter3], (0, "ujuno"), None, None)
+        .unwrap();
+    gauge_config.to_distribute = Some(Asset::new_native("ujuno", 1100));
+    gauge_config.boost_source = Some(boost_source.to_string());
+    suite.create_gauge(&gauge_contract, gauge_config).unwrap();
+    let gauge_id = 0;
+    assert_eq!(
+        suite
+            .query_gauge(gauge_contract.clone(), gauge_id)
+            .unwrap()
+            .boost_source,
+        Some(boost_source.to_string())
+    );
+
+    for voter in [voter1, voter2, voter3] {
+        suite
+            .place_vote(&gauge_contract, voter, gauge_id, Some(voter.to_owned()))
+            .unwrap();
+    }
+
+    suite.advance_time(EPOCH);
+    suite
+        .execute_options(&gauge_contract, voter1, gauge_id)
+        .unwrap();
+
+    // voter3's boost is capped at 2.5
+    assert_eq!(
+        suite
+            .query_last_executed_set(&gauge_contract, gauge_id)
+            .unwrap(),
+        Some(vec![
+            (voter3.to_owned(), Uint128::new(250)),
+            (voter1.to_owned(), Uint128::new(200)),
+            (voter2.to_owned(), Uint128::new(100)),
+        ])
+    );
+    assert_eq!(suite.query_balance(voter1, "ujuno").unwrap(), 400);
+    assert_eq!(suite.query_balance(voter2, "ujuno").unwrap(), 200);
+    assert_eq!(suite.query_balance(voter3, "ujuno").unwrap(), 500);
+}
+
+#[test]
+fn option_at_min_percent_selected_everywhere() {
+    let voter1 = "voter1";
+    let voter2 = "voter2";
+    let voter3 = "voter3";
+    let mut suite = SuiteBuilder::new()
+        .with_voting_members(&[(voter1, 100), (voter2, 197), (voter3, 3)])
+        .with_core_balance((1000, "ujuno"))
+        .build();
+
+    let gauge_contract = init_gauge(&mut suite, &[voter1, voter2, voter3]);
+    let mut gauge_config = suite
+        .instantiate_adapter_and_return_config(
+            &[voter1, voter2, voter3],
+            (300, "ujuno"),
+            None,
+            None,
+        )
+        .unwrap();
+    // not representable exactly, voter1's option has exactly a third of the votes
+    gauge_config.min_percent_selected = Some(Decimal::from_ratio(1u128, 3u128));
+    suite.create_gauge(&gauge_contract, gauge_config).unwrap();
+    let gauge_id = 0;
+
+    for voter in [voter1, voter2, voter3] {
+        suite
+            .place_vote(&gauge_contract, voter, gauge_id, Some(voter.to_owned()))
+            .unwrap();
+    }
+    let expected = vec![
+        (voter2.to_owned(), Uint128::new(197)),
+        (voter1.to_owned(), Uint128::new(100)),
+    ];
+    assert_eq!(
+        suite.query_selected_set(&gauge_contract, gauge_id).unwrap(),
+        expected
+    );
+
+    suite.advance_time(EPOCH);
+    suite
+        .execute_options(&gauge_contract, voter1, gauge_id)
+        .unwrap();
+    assert_eq!(
+        suite
+            .query_last_executed_set(&gauge_contract, gauge_id)
+            .unwrap(),
+        Some(expected.clone())
+    );
+
+    let inputs = suite
+        .query_execution_inputs(&gauge_contract, gauge_id, 0)
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        suite
+            .query_preview_selected_set(&gauge_contract, &inputs)
+            .unwrap(),
+        expected
+    );
+}
+
+#[test]
+fn qualification_threshold() {
+    let voter1 = "voter1";
+    let voter2 = "voter2";
+    let voter3 = "voter3";
+    let mut suite = SuiteBuilder::new()
+        .with_voting_members(&[(voter1, 100), (voter2, 300), (voter3, 600)])
+        .build();
+
+    let gauge_contract = init_gauge(&mut suite, &[voter1, voter2, voter3]);
+    let mut gauge_config = suite
+        .instantiate_adapter_and_return_config(
+            &[voter1, voter2, voter3],
+            (1000, "ujuno"),
+            None,
+            None,
+        )
+        .unwrap();
+    gauge_config.min_percent_selected = Some(Decimal::percent(15));
+    suite
+        .create_gauge(&gauge_contract, gauge_config.clone())
+        .unwrap();
+    // the absolute minimum is higher than the percentage here
+    gauge_config.min_percent_selected = Some(Decimal::percent(10));
+    gauge_config.min_absolute_tally = Some(Uint128::new(200));
+    suite.create_gauge(&gauge_contract, gauge_config).unwrap();
+
+    // nothing was voted yet
+    let res = suite
+        .query_qualification_threshold(&gauge_contract, 0)
+        .unwrap();
+    assert_eq!(res.total_tally, Uint128::zero());
+    assert!(suite
+        .query_selected_set(&gauge_contract, 0)
+        .unwrap()
+        .is_empty());
+
+    for gauge_id in [0, 1] {
+        for voter in [voter1, voter2, voter3] {
+            suite
+                .place_vote(&gauge_contract, voter, gauge_id, Some(voter.to_owned()))
+                .unwrap();
+        }
+    }
+
+    let res = suite
+        .query_qualification_threshold(&gauge_contract, 0)
+        .unwrap();
+    assert_eq!(res.total_tally, Uint128::new(1000));
+    assert_eq!(res.threshold, Uint128::new(1000) * Decimal::percent(15));
+    let res = suite
+        .query_qualification_threshold(&gauge_contract, 1)
+        .unwrap();
+    assert_eq!(res.threshold, Uint128::new(200));
+
+    // voter1 is below both thresholds, so they are not selected
+    let selected = suite.query_selected_set(&gauge_contract, 0).unwrap();
+    assert_eq!(
+        selected,
+        vec![
+            (voter3.to_owned(), Uint128::new(600)),
+            (voter2.to_owned(), Uint128::new(300)),
+        ]
+    );
+}
+
+#[test]
+fn flashloan_resistant_ignores_power_spikes() {
+    let voter1 = "voter1";
+    let voter2 = "voter2";
+    let mut suite = SuiteBuilder::new()
+        .with_voting_members(&[(voter1, 100), (voter2, 100)])
+        .with_core_balance((1000, "ujuno"))
+        .build();
+
+    let gauge_contract = init_gauge(&mut suite, &[voter1, voter2]);
+    let mut gauge_config = suite
+        .instantiate_adapter_and_return_config(&[voter1, voter2], (0, "ujuno"), None, None)
+        .unwrap();
+    gauge_config.to_distribute = Some(Asset::new_native("ujuno", 1000));
+    gauge_config.flashloan_resistant = true;
+    suite.create_gauge(&gauge_contract, gauge_config).unwrap();
+    let gauge_id = 0;
+    assert!(
+        suite
+            .query_gauge(gauge_contract.clone(), gauge_id)
+            .unwrap()
+            .flashloan_resistant
+    );
+
+    // voter2 borrows a lot of power right before voting
+    suite.next_block();
+    suite.set_voting_power(voter2, 900).unwrap();
+    suite.next_block();
+    suite
+        .place_vote(&gauge_contract, voter1, gauge_id, Some(voter1.to_owned()))
+        .unwrap();
+    suite
+        .place_vote(&gauge_contract, voter2, gauge_id, Some(voter2.to_owned()))
+        .unwrap();
+    // the raw tally counts the spike
+    assert_eq!(
+        suite.query_selected_set(&gauge_contract, gauge_id).unwrap(),
+        vec![
+            (voter2.to_owned(), Uint128::new(900)),
+            (voter1.to_owned(), Uint128::new(100)),
+        ]
+    );
+
+    // but execution only counts the power voter2 had when the epoch started
+    suite.advance_time(EPOCH);
+    suite
+        .execute_options(&gauge_contract, voter1, gauge_id)
+        .unwrap();
+    assert_eq!(
+        suite
+            .query_last_executed_set(&gauge_contract, gauge_id)
+            .unwrap(),
+        Some(vec![
+            (voter2.to_owned(), Uint128::new(100)),
+            (voter1.to_owned(), Uint128::new(100)),
+        ])
+    );
+    assert_eq!(suite.query_balance(voter1, "ujuno").unwrap(), 500);
+    assert_eq!(suite.query_balance(voter2, "ujuno").unwrap(), 500);
+}
+
+#[test]
+fn option_leaderboard_ranks_options() {
+    let voters = ["voter1", "voter2", "voter3", "voter4", "voter5"];
+    let mut suite = SuiteBuilder::new()
+        .with_voting_members(&[
+            (voters[0], 100),
+            (voters[1], 400),
+            (voters[2], 200),
+            (voters[3], 400),
+            (voters[4], 500),
+        ])
+        .build();
+
+    let gauge_contract = init_gauge(&mut suite, &voters);
+
+    let gauge_config = suite
+        .instantiate_adapter_and_return_config(&voters, (1000, "ujuno"), None, None)
+        .unwrap();
+    suite.create_gauge(&gauge_contract, gauge_config).unwrap();
+    let gauge_id = 0;
+
+    assert!(suite
+        .query_option_leaderboard(&gauge_contract, gauge_id, None)
+        .unwrap()
+        .is_empty());
+
+    // everyone votes for their own option
+    for voter in voters {
+        suite
+            .place_vote(&gauge_contract, voter, gauge_id, Some(voter.to_owned()))
+            .unwrap();
+    }
+
+    let leaderboard = suite
+        .query_option_leaderboard(&gauge_contract, gauge_id, None)
+        .unwrap();
+    let ranked: Vec<_> = leaderboard
+        .iter()
+        .map(|entry| (entry.rank, entry.option.as_str(), entry.tally.u128()))
+        .collect();
+    // equal tallies share their rank, the next rank is skipped
+    assert_eq!(
+        ranked,
+        vec![
+            (1, voters[4], 500),
+            (2, voters[3], 400),
+            (2, voters[1], 400),
+            (4, voters[2], 200),
+            (5, voters[0], 100),
+        ]
+    );
+    assert_eq!(
+        leaderboard[0].tally_fraction,
+        Decimal::from_ratio(500u128, 1600u128)
+    );
+    assert_eq!(
+        leaderboard[4].tally_fraction,
+        Decimal::from_ratio(100u128, 1600u128)
+    );
+
+    let top = suite
+        .query_option_leaderboard(&gauge_contract, gauge_id, 2)
+        .unwrap();
+    assert_eq!(top, leaderboard[..2]);
+}
+
+#[test]
+fn deprecated_option_keeps_votes_but_is_not_selected() {
+    let voter1 = "voter1";
+    let voter2 = "voter2";
+    let voter3 = "voter3";
+    let mut suite = SuiteBuilder::new()
+        .with_voting_members(&[(voter1, 100), (voter2, 300), (voter3, 100)])
+        .with_core_balance((1000, "ujuno"))
+        .build();
+
+    let gauge_contract = init_gauge(&mut suite, &[voter1, voter2, voter3]);
+    suite
+        .instantiate_adapter_and_create_gauge(
+            gauge_contract.clone(),
+            &[voter1, voter2],
+            (1000, "ujuno"),
+            None,
+            None,
+        )
+        .unwrap();
+    let gauge_id = 0;
+    let owner = suite.owner.clone();
+
+    suite
+        .place_vote(&gauge_contract, voter1, gauge_id, Some(voter1.to_owned()))
+        .unwrap();
+    suite
+        .place_vote(&gauge_contract, voter2, gauge_id, Some(voter2.to_owned()))
+        .unwrap();
+
+    let err = suite
+        .deprecate_option(&gauge_contract, voter1, gauge_id, voter2)
+        .unwrap_err();
+    assert_eq!(ContractError::Unauthorized {}, err.downcast().unwrap());
+    let err = suite
+        .deprecate_option(&gauge_contract, &owner, gauge_id, "unknown")
+        .unwrap_err();
+    assert_eq!(
+        ContractError::OptionNotFound {
+            gauge: gauge_id,
+            option: "unknown".to_owned(),
+        },
+        err.downcast().unwrap()
+    );
+
+    // the most popular option is deprecated, so the other one gets everything
+    suite
+        .deprecate_option(&gauge_contract, &owner, gauge_id, voter2)
+        .unwrap();
+    assert_eq!(
+        suite.query_selected_set(&gauge_contract, gauge_id).unwrap(),
+        vec![(voter1.to_owned(), Uint128::new(100))]
+    );
+
+    // its votes are still there
+    let options = suite
+        .query_list_options_response(&gauge_contract, gauge_id)
+        .unwrap();
+    assert_eq!(
+        options.options,
+        vec![
+            (voter1.to_owned(), Uint128::new(100)),
+            (voter2.to_owned(), Uint128::new(300)),
+        ]
+    );
+    assert_eq!(options.deprecated, vec![voter2.to_owned()]);
+    assert_eq!(
+        suite
+            .query_vote(&gauge_contract, gauge_id, voter2)
+            .unwrap()
+            .unwrap()
+            .votes[0]
+            .option,
+        voter2
+    );
+
+    // nobody new can vote for it
+    let err = suite
+        .place_vote(&gauge_contract, voter3, gauge_id, Some(voter2.to_owned()))
+        .unwrap_err();
+    assert_eq!(
+        ContractError::OptionDeprecated {
+            option: voter2.to_owned(),
+            gauge_id
+        },
+        err.downcast().unwrap()
+    );
+
+    suite.advance_time(EPOCH);
+    suite
+        .execute_options(&gauge_contract, voter1, gauge_id)
+        .unwrap();
+    assert_eq!(suite.query_balance(voter1, "ujuno").unwrap(), 1000);
+    assert_eq!(suite.query_balance(voter2, "ujuno").unwrap(), 0);
+
+    // voter2 can move their votes whenever they like
+    suite
+        .place_vote(&gauge_contract, voter2, gauge_id, Some(voter1.to_owned()))
+        .unwrap();
+    assert_eq!(
+        suite.query_selected_set(&gauge_contract, gauge_id).unwrap(),
+        vec![(voter1.to_owned(), Uint128::new(400))]
+    );
+}
+
+#[test]
+fn min_absolute_tally_excludes_small_options() {
+    let voter1 = "voter1";
+    let voter2 = "voter2";
+    let mut suite = SuiteBuilder::new()
+        .with_voting_members(&[(voter1, 940), (voter2, 60)])
+        .build();
+
+    let gauge_contract = init_gauge(&mut suite, &[voter1, voter2]);
+    let mut gauge_config = suite
+        .instantiate_adapter_and_return_config(&[voter1, voter2], (1000, "ujuno"), None, None)
+        .unwrap();
+    gauge_config.min_absolute_tally = Some(Uint128::new(100));
+    suite.create_gauge(&gauge_contract, gauge_config).unwrap();
+    let gauge_id = 0;
+
+    suite
+        .place_vote(&gauge_contract, voter1, gauge_id, Some(voter1.to_owned()))
+        .unwrap();
+    suite
+        .place_vote(&gauge_contract, voter2, gauge_id, Some(voter2.to_owned()))
+        .unwrap();
+
+    // voter2 has 6% of the votes, which is above 5% required, but below the absolute floor
+    assert_eq!(
+        suite.query_selected_set(&gauge_contract, gauge_id).unwrap(),
+        vec![(voter1.to_owned(), Uint128::new(940))]
+    );
+    let gauge = suite.query_gauge(gauge_contract, gauge_id).unwrap();
+    assert_eq!(gauge.min_absolute_tally, Some(Uint128::new(100)));
+}
+
+#[test]
+fn option_tally_history_over_epochs() {
+    let voter1 = "voter1";
+    let voter2 = "voter2";
+    let mut suite = SuiteBuilder::new()
+        .with_voting_members(&[(voter1, 100), (voter2, 100)])
+        .with_core_balance((3000, "ujuno"))
+        .build();
+
+    let gauge_contract = init_gauge(&mut suite, &[voter1, voter2]);
+    suite
+        .instantiate_adapter_and_create_gauge(
+            gauge_contract.clone(),
+            &[voter1, voter2],
+            (1000, "ujuno"),
+            None,
+            None,
+        )
+        .unwrap();
+    let gauge_id = 0;
+    let snapshot = |epoch: u64, tally: u128| OptionTallySnapshot {
+        epoch,
+        tally: Uint128::new(tally),
+    };
+
+    // epoch 0: both options get one vote
+    suite
+        .place_vote(&gauge_contract, voter1, gauge_id, Some(voter1.to_owned()))
+        .unwrap();
+    suite
+        .place_vote(&gauge_contract, voter2, gauge_id, Some(voter2.to_owned()))
+        .unwrap();
+    suite.advance_time(EPOCH);
+    suite
+        .execute_options(&gauge_contract, voter1, gauge_id)
+        .unwrap();
+
+    // epoch 1: everyone votes for voter1
+    suite
+        .place_vote(&gauge_contract, voter2, gauge_id, Some(voter1.to_owned()))
+        .unwrap();
+    suite.advance_time(EPOCH);
+    suite
+        .execute_options(&gauge_contract, voter1, gauge_id)
+        .unwrap();
+
+    // epoch 2: everyone votes for voter2
+    suite
+        .place_vote(&gauge_contract, voter1, gauge_id, Some(voter2.to_owned()))
+        .unwrap();
+    suite
+        .place_vote(&gauge_contract, voter2, gauge_id, Some(voter2.to_owned()))
+        .unwrap();
+    suite.advance_time(EPOCH);
+    suite
+        .execute_options(&gauge_contract, voter1, gauge_id)
+        .unwrap();
+
+    assert_eq!(
+        suite
+            .query_option_tally_history(&gauge_contract, gauge_id, voter1, None, None)
+            .unwrap(),
+        vec![snapshot(0, 100), snapshot(1, 200)]
+    );
+    // not selected in epoch 1, so there is no snapshot
+    assert_eq!(
+        suite
+            .query_option_tally_history(&gauge_contract, gauge_id, voter2, None, None)
+            .unwrap(),
+        vec![snapshot(0, 100), snapshot(2, 200)]
+    );
+    assert_eq!(
+        suite
+            .query_option_tally_history(&gauge_contract, gauge_id, voter2, 0, 1)
+            .unwrap(),
+        vec![snapshot(2, 200)]
+    );
+}
+
+#[test]
+fn deprecated_vote_policy() {
+    let voter1 = "voter1";
+    let voter2 = "voter2";
+    let options = ["option1", "option2", "option3"];
+
+    let selected_with = |policy: DeprecatedVotePolicy| {
+        let mut suite = SuiteBuilder::new()
+            .with_voting_members(&[(voter1, 100), (voter2, 100)])
+            .build();
+        let owner = suite.owner.clone();
+        let gauge_contract = init_gauge(&mut suite, &[voter1, voter2]);
+        let mut config = suite
+            .instantiate_adapter_and_return_config(&options, (1000, "ujuno"), None, None)
+            .unwrap();
+        config.deprecated_vote_policy = policy;
+        suite.create_gauge(&gauge_contract, config).unwrap();
+        let gauge_id = 0;
+
+        // voter1 splits between an active and a soon deprecated option
+        suite
+            .place_votes(
+                &gauge_contract,
+                voter1,
+                gauge_id,
+                vec![
+                    ("option1".to_owned(), Decimal::percent(40)),
+                    ("option2".to_owned(), Decimal::percent(60)),
+                ],
+            )
+            .unwrap();
+        suite
+            .place_vote(
+                &gauge_contract,
+                voter2,
+                gauge_id,
+                Some("option3".to_owned()),
+            )
+            .unwrap();
+        suite
+            .deprecate_option(&gauge_contract, &owner, gauge_id, "option2")
+            .unwrap();
+
+        suite.advance_time(EPOCH);
+        suite
+            .execute_options(&gauge_contract, voter1, gauge_id)
+            .unwrap();
+        suite
+            .query_last_executed_set(&gauge_contract, gauge_id)
+            .unwrap()
+            .unwrap()
+    };
+
+    // the power on option2 is left out
+    assert_eq!(
+        selected_with(DeprecatedVotePolicy::Ignore),
+        vec![
+            ("option3".to_owned(), Uint128::new(100)),
+            ("option1".to_owned(), Uint128::new(40)),
+        ]
+    );
+    // the power on option2 goes to option1, the only other option of voter1
+    assert_eq!(
+        selected_with(DeprecatedVotePolicy::Redistribute),
+        vec![
+            ("option3".to_owned(), Uint128::new(100)),
+            ("option1".to_owned(), Uint128::new(100)),
+        ]
+    );
+}
@@ -1,9 +1,14 @@
-use cosmwasm_std::{Decimal, Uint128};
+use cosmwasm_std::{coin, Addr, Decimal, Event, OverflowError, OverflowOperation, Uint128};
+use sha2::{Digest, Sha256};
 use voting::Vote;
+use wynd_stake::hook::MemberDiff;
 
-use super::suite::SuiteBuilder;
+use super::suite::{init_gauge, SuiteBuilder};
 use crate::error::ContractError;
-use crate::msg::VoteInfo;
+use crate::msg::{CanVoteResponse, ChangedVote, GaugeConfig, PowerBucket, VoteInfo};
+use crate::state::{
+    EpochParticipation, VoterActivity, VoterRestriction, VotingQuery, ABSTAIN_OPTION,
+};
 
 const EPOCH: u64 = 7 * 86_400;
 
@@ -211,6 +216,236 @@ fn remove_option() {
     );
 }
 
+#[test]
+fn remove_options_skips_voted_ones() {
+    let owner = "owner";
+    let voter1 = "voter1";
+    let voter2 = "voter2";
+    let mut suite = SuiteBuilder::new()
+        .with_voting_members(&[(voter1, 100), (voter2, 200)])
+        .build();
+
+    let gauge_contract = init_gauge(&mut suite, &[voter1, voter2]);
+    suite
+        .instantiate_adapter_and_create_gauge(
+            gauge_contract.clone(),
+            &[voter1, voter2, "dead1", "dead2"],
+            (1000, "ujuno"),
+            None,
+            None,
+        )
+        .unwrap();
+    let gauge_id = 0;
+
+    suite
+        .place_vote(&gauge_contract, voter1, gauge_id, Some(voter1.to_owned()))
+        .unwrap();
+
+    let err = suite
+        .remove_options(&gauge_contract, voter1, gauge_id, &["dead1"])
+        .unwrap_err();
+    assert_eq!(ContractError::Unauthorized {}, err.downcast().unwrap());
+    let err = suite
+        .remove_options(&gauge_contract, owner, gauge_id, &["dead1", "unknown"])
+        .unwrap_err();
+    assert_eq!(
+        ContractError::OptionNotFound {
+            gauge: gauge_id,
+            option: "unknown".to_owned(),
+        },
+        err.downcast().unwrap()
+    );
+
+    let res = suite
+        .remove_options(
+            &gauge_contract,
+            owner,
+            gauge_id,
+            &["dead1", voter1, "dead2", voter2],
+        )
+        .unwrap();
+    assert!(res.has_event(
+        &Event::new("wasm")
+            .add_attribute("removed", "dead1,dead2,voter2")
+            .add_attribute("skipped", "voter1")
+    ));
+
+    // only the voted option is left
+    assert_eq!(
+        suite.query_list_options(&gauge_contract, gauge_id).unwrap(),
+        vec![(voter1.to_owned(), Uint128::new(100))]
+    );
+}
+
+#[test]
+fn auto_prune_long_idle_options() {
+    let voter1 = "voter1";
+    let voter2 = "voter2";
+    let mut suite = SuiteBuilder::new()
+        .with_voting_members(&[(voter1, 100), (voter2, 200)])
+        .with_core_balance((1000, "ujuno"))
+        .build();
+
+    let gauge_contract = init_gauge(&mut suite, &[voter1, voter2]);
+    let mut gauge_config = suite
+        .instantiate_adapter_and_return_config(&[voter1, "idle"], (10, "ujuno"), None, None)
+        .unwrap();
+    gauge_config.auto_prune_zero_options = true;
+    let gauge_adapter = Addr::unchecked(&gauge_config.adapter);
+    suite.create_gauge(&gauge_contract, gauge_config).unwrap();
+    let gauge_id = 0;
+
+    suite
+        .place_vote(&gauge_contract, voter1, gauge_id, Some(voter1.to_owned()))
+        .unwrap();
+
+    // "idle" has no votes from the first execution on
+    for _ in 0..3 {
+        suite.advance_time(EPOCH);
+        suite
+            .execute_options(&gauge_contract, voter1, gauge_id)
+            .unwrap();
+    }
+
+    // a new option is added later on, without votes either
+    suite.add_valid_option(&gauge_adapter, "fresh").unwrap();
+    suite
+        .add_option(&gauge_contract, voter1, gauge_id, "fresh")
+        .unwrap();
+    for _ in 0..2 {
+        suite.advance_time(EPOCH);
+        let res = suite
+            .execute_options(&gauge_contract, voter1, gauge_id)
+            .unwrap();
+        assert!(!res.has_event(&Event::new("wasm").add_attribute("pruned", "idle")));
+    }
+    assert_eq!(
+        suite.query_list_options(&gauge_contract, gauge_id).unwrap(),
+        vec![
+            ("fresh".to_owned(), Uint128::zero()),
+            ("idle".to_owned(), Uint128::zero()),
+            (voter1.to_owned(), Uint128::new(100)),
+        ]
+    );
+
+    // after more than 4 epochs without votes, "idle" is pruned, while "fresh" survives
+    suite.advance_time(EPOCH);
+    let res = suite
+        .execute_options(&gauge_contract, voter1, gauge_id)
+        .unwrap();
+    assert!(res.has_event(&Event::new("wasm").add_attribute("pruned", "idle")));
+    assert_eq!(
+        suite.query_list_options(&gauge_contract, gauge_id).unwrap(),
+        vec![
+            ("fresh".to_owned(), Uint128::zero()),
+            (voter1.to_owned(), Uint128::new(100)),
+        ]
+    );
+
+    // options with votes again are not idle anymore
+    suite
+        .place_vote(&gauge_contract, voter2, gauge_id, Some("fresh".to_owned()))
+        .unwrap();
+    suite
+        .place_vote(&gauge_contract, voter2, gauge_id, None)
+        .unwrap();
+    for _ in 0..5 {
+        suite.advance_time(EPOCH);
+        let res = suite
+            .execute_options(&gauge_contract, voter1, gauge_id)
+            .unwrap();
+        assert!(!res.has_event(&Event::new("wasm").add_attribute("pruned", "fresh")));
+    }
+    suite.advance_time(EPOCH);
+    let res = suite
+        .execute_options(&gauge_contract, voter1, gauge_id)
+        .unwrap();
+    assert!(res.has_event(&Event::new("wasm").add_attribute("pruned", "fresh")));
+}
+
+#[test]
+fn reclaim_votes_on_remove() {
+    let owner = "owner";
+    let voter1 = "voter1";
+    let voter2 = "voter2";
+    let option = "option";
+    let mut suite = SuiteBuilder::new()
+        .with_voting_members(&[(voter1, 100), (voter2, 200)])
+        .build();
+
+    let gauge_contract = init_gauge(&mut suite, &[voter1, voter2]);
+    let mut gauge_config = suite
+        .instantiate_adapter_and_return_config(
+            &[voter1, voter2, option],
+            (1000, "ujuno"),
+            None,
+            None,
+        )
+        .unwrap();
+    gauge_config.reclaim_votes_on_remove = true;
+    suite.create_gauge(&gauge_contract, gauge_config).unwrap();
+    let gauge_id = 0;
+
+    suite
+        .place_votes(
+            &gauge_contract,
+            voter1,
+            gauge_id,
+            vec![
+                (option.to_owned(), Decimal::percent(50)),
+                (voter1.to_owned(), Decimal::percent(30)),
+            ],
+        )
+        .unwrap();
+    suite
+        .place_vote(&gauge_contract, voter2, gauge_id, Some(option.to_owned()))
+        .unwrap();
+    let cast = suite.current_time();
+
+    suite.next_block();
+    suite
+        .remove_option(&gauge_contract, owner, gauge_id, option)
+        .unwrap();
+
+    // voter1 keeps the rest of the split, voter2 has nothing left
+    assert_eq!(
+        suite.query_vote(&gauge_contract, gauge_id, voter1).unwrap(),
+        Some(simple_vote(voter1, voter1, 30, cast))
+    );
+    assert_eq!(
+        suite.query_vote(&gauge_contract, gauge_id, voter2).unwrap(),
+        None
+    );
+    assert_eq!(
+        suite.query_list_options(&gauge_contract, gauge_id).unwrap(),
+        vec![
+            (voter1.to_owned(), Uint128::new(30)),
+            (voter2.to_owned(), Uint128::zero())
+        ]
+    );
+    assert_eq!(
+        suite
+            .query_option_voters(&gauge_contract, gauge_id, option)
+            .unwrap(),
+        vec![]
+    );
+
+    // both can vote again as usual
+    suite
+        .place_vote(&gauge_contract, voter1, gauge_id, Some(voter2.to_owned()))
+        .unwrap();
+    suite
+        .place_vote(&gauge_contract, voter2, gauge_id, Some(voter2.to_owned()))
+        .unwrap();
+    assert_eq!(
+        suite.query_list_options(&gauge_contract, gauge_id).unwrap(),
+        vec![
+            (voter1.to_owned(), Uint128::zero()),
+            (voter2.to_owned(), Uint128::new(300))
+        ]
+    );
+}
+
 fn simple_vote(
     voter: &str,
     option: &str,
@@ -375,9 +610,9 @@ fn vote_for_option() {
         )
         .unwrap_err();
     assert_eq!(
-        ContractError::OptionDoesNotExists {
+        ContractError::OptionNotFound {
+            gauge: gauge_id,
             option: "random option".to_owned(),
-            gauge_id
         },
         err.downcast().unwrap()
     );
@@ -479,197 +714,1883 @@ fn remove_vote() {
 }
 
 #[test]
-fn votes_stays_the_same_after_execution() {
+fn abstain_counts_towards_participation_only() {
     let voter1 = "voter1";
     let voter2 = "voter2";
-    let reward_to_distribute = (1000, "ujuno");
+    let voter3 = "voter3";
     let mut suite = SuiteBuilder::new()
-        .with_voting_members(&[(voter1, 100), (voter2, 100)])
-        .with_core_balance(reward_to_distribute)
+        .with_voting_members(&[(voter1, 100), (voter2, 100), (voter3, 200)])
         .build();
 
-    suite.next_block();
-    let gauge_config = suite
-        .instantiate_adapter_and_return_config(&[voter1, voter2], reward_to_distribute, None, None)
-        .unwrap();
-    suite
-        .propose_update_proposal_module(voter1.to_string(), vec![gauge_config])
-        .unwrap();
-
-    suite.next_block();
-    let proposal = suite.list_proposals().unwrap()[0];
-    suite
-        .place_vote_single(voter1, proposal, Vote::Yes)
-        .unwrap();
-    suite
-        .place_vote_single(voter2, proposal, Vote::Yes)
-        .unwrap();
-
-    suite.next_block();
+    let gauge_contract = init_gauge(&mut suite, &[voter1, voter2, voter3]);
     suite
-        .execute_single_proposal(voter1.to_string(), proposal)
+        .instantiate_adapter_and_create_gauge(
+            gauge_contract.clone(),
+            &[voter1, voter2],
+            (1000, "ujuno"),
+            None,
+            None,
+        )
         .unwrap();
-    let proposal_modules = suite.query_proposal_modules().unwrap();
-    let gauge_contract = proposal_modules[0].clone();
-
     let gauge_id = 0;
 
-    // vote for one of the options in gauge
+    // abstain option cannot be added as a regular option
+    let err = suite
+        .add_option(&gauge_contract, voter1, gauge_id, ABSTAIN_OPTION)
+        .unwrap_err();
+    assert_eq!(
+        ContractError::ReservedOption(ABSTAIN_OPTION.to_owned()),
+        err.downcast().unwrap()
+    );
+
+    // voter3 abstains completely, voter1 abstains half of the power and leaves the rest unused
     suite
         .place_vote(
             &gauge_contract,
-            voter1.to_owned(),
+            voter3,
             gauge_id,
-            Some(voter1.to_owned()), // option to vote for
+            Some(ABSTAIN_OPTION.to_owned()),
         )
         .unwrap();
     suite
-        .place_vote(
+        .place_votes(
             &gauge_contract,
-            voter2.to_owned(),
+            voter1,
             gauge_id,
-            Some(voter1.to_owned()),
+            vec![(ABSTAIN_OPTION.to_owned(), Decimal::percent(50))],
         )
         .unwrap();
 
-    let selected_set = suite.query_selected_set(&gauge_contract, gauge_id).unwrap();
-    // voter1 was option voted for with two 100 voting powers combined
-    assert_eq!(selected_set, vec![("voter1".to_owned(), Uint128::new(200))]);
-
-    // before advancing specified epoch tally won't get sampled
-    suite.advance_time(EPOCH);
-
-    assert_eq!(
-        vec![
-            simple_vote(voter1, voter1, 100, suite.current_time() - EPOCH),
-            simple_vote(voter2, voter1, 100, suite.current_time() - EPOCH)
-        ],
-        suite.query_list_votes(&gauge_contract, gauge_id).unwrap()
-    );
-    suite
-        .execute_options(&gauge_contract, voter1, gauge_id)
+    // participation is over a 60% quorum, but no option gets any weight
+    let participation = suite
+        .query_participation(&gauge_contract, gauge_id)
         .unwrap();
-
+    assert_eq!(participation.votes_cast, Uint128::zero());
+    assert_eq!(participation.abstained, Uint128::new(250));
+    assert_eq!(participation.total_power, Uint128::new(400));
+    assert_eq!(participation.participation, Decimal::permille(625));
+    assert!(participation.participation > Decimal::percent(60));
     assert_eq!(
-        vec![
-            simple_vote(voter1, voter1, 100, suite.current_time() - EPOCH),
-            simple_vote(voter2, voter1, 100, suite.current_time() - EPOCH)
-        ],
-        suite.query_list_votes(&gauge_contract, gauge_id).unwrap()
+        suite.query_selected_set(&gauge_contract, gauge_id).unwrap(),
+        vec![]
     );
     assert_eq!(
-        suite.query_vote(&gauge_contract, gauge_id, voter1).unwrap(),
-        Some(simple_vote(
-            voter1,
-            voter1,
-            100,
-            suite.current_time() - EPOCH
-        )),
+        suite.query_list_options(&gauge_contract, gauge_id).unwrap(),
+        vec![
+            ("voter1".to_owned(), Uint128::zero()),
+            ("voter2".to_owned(), Uint128::zero())
+        ]
     );
+
+    // regular votes are selected as before
+    suite
+        .place_vote(&gauge_contract, voter2, gauge_id, Some(voter1.to_owned()))
+        .unwrap();
+    let participation = suite
+        .query_participation(&gauge_contract, gauge_id)
+        .unwrap();
+    assert_eq!(participation.votes_cast, Uint128::new(100));
+    assert_eq!(participation.abstained, Uint128::new(250));
+    assert_eq!(participation.participation, Decimal::permille(875));
     assert_eq!(
-        suite.query_vote(&gauge_contract, gauge_id, voter2).unwrap(),
-        Some(simple_vote(
-            voter2,
-            voter1,
-            100,
-            suite.current_time() - EPOCH
-        )),
+        suite.query_selected_set(&gauge_contract, gauge_id).unwrap(),
+        vec![("voter1".to_owned(), Uint128::new(100))]
     );
+
+    // removing the abstain vote lowers participation again
+    suite
+        .place_vote(&gauge_contract, voter3, gauge_id, None)
+        .unwrap();
+    let participation = suite
+        .query_participation(&gauge_contract, gauge_id)
+        .unwrap();
+    assert_eq!(participation.abstained, Uint128::new(50));
+    assert_eq!(participation.participation, Decimal::permille(375));
 }
 
 #[test]
-fn vote_for_max_capped_option() {
+fn participation_history_is_recorded_per_epoch() {
     let voter1 = "voter1";
     let voter2 = "voter2";
+    let voter3 = "voter3";
     let mut suite = SuiteBuilder::new()
-        .with_voting_members(&[(voter1, 100), (voter2, 100)])
+        .with_voting_members(&[(voter1, 100), (voter2, 100), (voter3, 200)])
         .build();
 
-    suite.next_block();
+    let gauge_contract = init_gauge(&mut suite, &[voter1, voter2, voter3]);
     suite
-        .propose_update_proposal_module(voter1.to_string(), None)
+        .instantiate_adapter_and_create_gauge(
+            gauge_contract.clone(),
+            &[voter1, voter2],
+            (1000, "ujuno"),
+            None,
+            None,
+        )
         .unwrap();
+    let gauge_id = 0;
 
-    suite.next_block();
-    let proposal = suite.list_proposals().unwrap()[0];
-    suite
-        .place_vote_single(voter1, proposal, Vote::Yes)
-        .unwrap();
-    suite
-        .place_vote_single(voter2, proposal, Vote::Yes)
-        .unwrap();
+    // participation grows with each epoch: 100, 200, 400 out of 400
+    for (voter, option) in [
+        (voter1, voter1.to_owned()),
+        (voter2, voter2.to_owned()),
+        (voter3, ABSTAIN_OPTION.to_owned()),
+    ] {
+        suite
+            .place_vote(&gauge_contract, voter, gauge_id, Some(option))
+            .unwrap();
+        suite.advance_time(EPOCH);
+        suite
+            .execute_options(&gauge_contract, voter1, gauge_id)
+            .unwrap();
+    }
 
+    let history = suite
+        .query_participation_history(&gauge_contract, gauge_id, None, None)
+        .unwrap();
+    assert_eq!(
+        history,
+        vec![
+            EpochParticipation {
+                epoch: 0,
+                votes_power: Uint128::new(100),
+                total_power: Uint128::new(400),
+            },
+            EpochParticipation {
+                epoch: 1,
+                votes_power: Uint128::new(200),
+                total_power: Uint128::new(400),
+            },
+            EpochParticipation {
+                epoch: 2,
+                votes_power: Uint128::new(400),
+                total_power: Uint128::new(400),
+            },
+        ]
+    );
+
+    // paginate over the history
+    let page = suite
+        .query_participation_history(&gauge_contract, gauge_id, 0, 1)
+        .unwrap();
+    assert_eq!(page, vec![history[1].clone()]);
+}
+
+#[test]
+fn sync_options_paginates_over_adapter() {
+    let voter1 = "voter1";
+    let mut suite = SuiteBuilder::new()
+        .with_voting_members(&[(voter1, 100)])
+        .build();
+
+    let gauge_contract = init_gauge(&mut suite, &[voter1]);
+    let gauge_adapter = suite
+        .instantiate_adapter_and_create_gauge(
+            gauge_contract.clone(),
+            &[voter1],
+            (1000, "ujuno"),
+            None,
+            None,
+        )
+        .unwrap();
+    let gauge_id = 0;
+
+    // adapter now has 250 options, only one of them is known to the gauge
+    for i in 0..249 {
+        suite
+            .add_valid_option(&gauge_adapter, format!("option{:03}", i))
+            .unwrap();
+    }
+    suite
+        .place_vote(
+            &gauge_contract,
+            voter1,
+            gauge_id,
+            Some("option248".to_owned()),
+        )
+        .unwrap_err();
+
+    // sync in batches of 100, the last one also contains the already known option
+    for (added, finished) in [(100, false), (100, false), (49, true)] {
+        let res = suite
+            .sync_options(&gauge_contract, voter1, gauge_id, 100)
+            .unwrap();
+        assert!(res.has_event(
+            &Event::new("wasm")
+                .add_attribute("added", added.to_string())
+                .add_attribute("finished", finished.to_string())
+        ));
+    }
+    suite
+        .place_vote(
+            &gauge_contract,
+            voter1,
+            gauge_id,
+            Some("option248".to_owned()),
+        )
+        .unwrap();
+
+    // syncing again starts over and adds nothing
+    let res = suite
+        .sync_options(&gauge_contract, voter1, gauge_id, 100)
+        .unwrap();
+    assert!(res.has_event(
+        &Event::new("wasm")
+            .add_attribute("added", "0")
+            .add_attribute("finished", "false")
+    ));
+}
+
+#[test]
+fn validate_options_deprecates_delisted_ones() {
+    let voter1 = "voter1";
+    let voter2 = "voter2";
+    let mut suite = SuiteBuilder::new()
+        .with_voting_members(&[(voter1, 100), (voter2, 200)])
+        .build();
+
+    let gauge_contract = init_gauge(&mut suite, &[voter1, voter2]);
+    let gauge_adapter = suite
+        .instantiate_adapter_and_create_gauge(
+            gauge_contract.clone(),
+            &[voter1, voter2, "pool"],
+            (1000, "ujuno"),
+            None,
+            None,
+        )
+        .unwrap();
+    let gauge_id = 0;
+
+    // the pool gets delisted
+    suite.invalidate_option(&gauge_adapter, "pool").unwrap();
+
+    // anyone can trigger the sweep
+    let res = suite
+        .validate_options(&gauge_contract, voter1, gauge_id)
+        .unwrap();
+    assert!(res.has_event(
+        &Event::new("wasm")
+            .add_attribute("deprecated", "pool")
+            .add_attribute("finished", "true")
+    ));
+    assert_eq!(
+        suite
+            .query_list_options_response(&gauge_contract, gauge_id)
+            .unwrap()
+            .deprecated,
+        vec!["pool".to_owned()]
+    );
+
+    // a new sweep has to wait a day
+    let err = suite
+        .validate_options(&gauge_contract, voter2, gauge_id)
+        .unwrap_err();
+    let next = suite.current_time() + 86_400;
+    assert_eq!(
+        ContractError::ValidationSweepTooSoon(gauge_id, next),
+        err.downcast().unwrap()
+    );
+
+    suite.advance_time(86_400);
+    let res = suite
+        .validate_options(&gauge_contract, voter2, gauge_id)
+        .unwrap();
+    assert!(res.has_event(
+        &Event::new("wasm")
+            .add_attribute("deprecated", "")
+            .add_attribute("finished", "true")
+    ));
+}
+
+#[test]
+fn validate_option_on_vote() {
+    let voter1 = "voter1";
+    let voter2 = "voter2";
+    let mut suite = SuiteBuilder::new()
+        .with_voting_members(&[(voter1, 100), (voter2, 100)])
+        .build();
+
+    let gauge_contract = init_gauge(&mut suite, &[voter1, voter2]);
+    let mut gauge_config = suite
+        .instantiate_adapter_and_return_config(&[voter1, voter2], (1000, "ujuno"), None, None)
+        .unwrap();
+    let gauge_adapter = Addr::unchecked(&gauge_config.adapter);
+    gauge_config.validate_option_on_vote = true;
+    suite.create_gauge(&gauge_contract, gauge_config).unwrap();
+    let gauge_id = 0;
+
+    suite
+        .place_vote(&gauge_contract, voter1, gauge_id, Some(voter1.to_owned()))
+        .unwrap();
+
+    // option gets delisted by the adapter, but stays in the gauge
+    suite.invalidate_option(&gauge_adapter, voter1).unwrap();
+    let err = suite
+        .place_vote(&gauge_contract, voter2, gauge_id, Some(voter1.to_owned()))
+        .unwrap_err();
+    assert_eq!(
+        ContractError::OptionInvalidByAdapter {
+            option: voter1.to_owned(),
+            gauge_id
+        },
+        err.downcast().unwrap()
+    );
+
+    // still valid options can be voted for
+    suite
+        .place_vote(&gauge_contract, voter2, gauge_id, Some(voter2.to_owned()))
+        .unwrap();
+}
+
+#[test]
+fn voter_allowlist() {
+    let voter1 = "voter1";
+    let voter2 = "voter2";
+    let mut suite = SuiteBuilder::new()
+        .with_voting_members(&[(voter1, 100), (voter2, 100)])
+        .build();
+
+    let gauge_contract = init_gauge(&mut suite, &[voter1, voter2]);
+    let mut gauge_config = suite
+        .instantiate_adapter_and_return_config(&[voter1, voter2], (1000, "ujuno"), None, None)
+        .unwrap();
+    gauge_config.voter_allowlist = Some(vec![voter1.to_owned()]);
+
+    // allowlist and merkle root cannot be combined
+    let bad_config = GaugeConfig {
+        voter_merkle_root: Some(hex::encode([0u8; 32])),
+        ..gauge_config.clone()
+    };
+    let err = suite.create_gauge(&gauge_contract, bad_config).unwrap_err();
+    assert_eq!(
+        ContractError::MultipleVoterRestrictions {},
+        err.downcast().unwrap()
+    );
+
+    suite.create_gauge(&gauge_contract, gauge_config).unwrap();
+    let gauge_id = 0;
+    assert_eq!(
+        suite
+            .query_gauge(gauge_contract.clone(), gauge_id)
+            .unwrap()
+            .voter_restriction,
+        Some(VoterRestriction::Allowlist {})
+    );
+
+    suite
+        .place_vote(&gauge_contract, voter1, gauge_id, Some(voter1.to_owned()))
+        .unwrap();
+    let err = suite
+        .place_vote(&gauge_contract, voter2, gauge_id, Some(voter1.to_owned()))
+        .unwrap_err();
+    assert_eq!(
+        ContractError::VoterNotAllowed(voter2.to_owned()),
+        err.downcast().unwrap()
+    );
+}
+
+fn sha256(data: &[u8]) -> [u8; 32] {
+    Sha256::digest(data).as_slice().try_into().unwrap()
+}
+
+fn hash_pair(a: [u8; 32], b: [u8; 32]) -> [u8; 32] {
+    let mut hashes = [a, b];
+    hashes.sort_unstable();
+    sha256(&hashes.concat())
+}
+
+#[test]
+fn voter_merkle_proof() {
+    let voter1 = "voter1";
+    let voter2 = "voter2";
+    let outsider = "voter3";
+    let mut suite = SuiteBuilder::new()
+        .with_voting_members(&[(voter1, 100), (voter2, 100), (outsider, 100)])
+        .build();
+
+    // merkle tree with voter1, voter2 and two other addresses
+    let leaves: Vec<[u8; 32]> = [voter1, voter2, "other1", "other2"]
+        .iter()
+        .map(|addr| sha256(addr.as_bytes()))
+        .collect();
+    let left = hash_pair(leaves[0], leaves[1]);
+    let right = hash_pair(leaves[2], leaves[3]);
+    let root = hash_pair(left, right);
+    let voter1_proof = vec![hex::encode(leaves[1]), hex::encode(right)];
+
+    let gauge_contract = init_gauge(&mut suite, &[voter1, voter2, outsider]);
+    let mut gauge_config = suite
+        .instantiate_adapter_and_return_config(&[voter1, voter2], (1000, "ujuno"), None, None)
+        .unwrap();
+
+    // root must be a valid hash
+    gauge_config.voter_merkle_root = Some("nothex".to_owned());
+    let err = suite
+        .create_gauge(&gauge_contract, gauge_config.clone())
+        .unwrap_err();
+    assert_eq!(ContractError::InvalidMerkleRoot {}, err.downcast().unwrap());
+
+    gauge_config.voter_merkle_root = Some(hex::encode(root));
+    suite.create_gauge(&gauge_contract, gauge_config).unwrap();
+    let gauge_id = 0;
+    let votes = || Some(vec![(voter1.to_owned(), Decimal::one())]);
+
+    // proof is required
+    let err = suite
+        .place_votes(&gauge_contract, voter1, gauge_id, votes())
+        .unwrap_err();
+    assert_eq!(
+        ContractError::VoterNotAllowed(voter1.to_owned()),
+        err.downcast().unwrap()
+    );
+
+    // invalid proof is rejected
+    let err = suite
+        .place_votes_with_proof(
+            &gauge_contract,
+            voter1,
+            gauge_id,
+            votes(),
+            vec![hex::encode(leaves[2]), hex::encode(right)],
+        )
+        .unwrap_err();
+    assert_eq!(
+        ContractError::InvalidMerkleProof {},
+        err.downcast().unwrap()
+    );
+
+    // someone else's proof is rejected
+    let err = suite
+        .place_votes_with_proof(
+            &gauge_contract,
+            outsider,
+            gauge_id,
+            votes(),
+            voter1_proof.clone(),
+        )
+        .unwrap_err();
+    assert_eq!(
+        ContractError::InvalidMerkleProof {},
+        err.downcast().unwrap()
+    );
+
+    // valid proof, later votes don't need it anymore
+    suite
+        .place_votes_with_proof(&gauge_contract, voter1, gauge_id, votes(), voter1_proof)
+        .unwrap();
+    suite
+        .place_vote(&gauge_contract, voter1, gauge_id, Some(voter2.to_owned()))
+        .unwrap();
+    assert_eq!(
+        suite.query_selected_set(&gauge_contract, gauge_id).unwrap(),
+        vec![(voter2.to_owned(), Uint128::new(100))]
+    );
+}
+
+#[test]
+fn can_vote_reports_reason() {
+    let voter1 = "voter1";
+    let voter2 = "voter2";
+    let nobody = "nobody";
+    let mut suite = SuiteBuilder::new()
+        .with_voting_members(&[(voter1, 100), (voter2, 100)])
+        .build();
+
+    let gauge_contract = init_gauge(&mut suite, &[voter1, voter2]);
+    let gauge_config = suite
+        .instantiate_adapter_and_return_config(&[voter1, voter2], (1000, "ujuno"), None, None)
+        .unwrap();
+    let blocked_by = |err: ContractError| CanVoteResponse {
+        can_vote: false,
+        reason: Some(err.to_string()),
+    };
+
+    // gauge 0 allows only one voter
+    let limited = GaugeConfig {
+        max_voters: Some(1),
+        ..gauge_config.clone()
+    };
+    suite.create_gauge(&gauge_contract, limited).unwrap();
+    assert_eq!(
+        suite.query_can_vote(&gauge_contract, 0, voter1).unwrap(),
+        CanVoteResponse {
+            can_vote: true,
+            reason: None
+        }
+    );
+    assert_eq!(
+        suite.query_can_vote(&gauge_contract, 0, nobody).unwrap(),
+        blocked_by(ContractError::NoVotingPower(nobody.to_owned()))
+    );
+    suite
+        .place_vote(&gauge_contract, voter1, 0, Some(voter1.to_owned()))
+        .unwrap();
+    assert_eq!(
+        suite.query_can_vote(&gauge_contract, 0, voter2).unwrap(),
+        blocked_by(ContractError::VoterLimitReached(0))
+    );
+    // voter1 can still change the vote
+    assert!(
+        suite
+            .query_can_vote(&gauge_contract, 0, voter1)
+            .unwrap()
+            .can_vote
+    );
+
+    // gauge 1 has an allowlist
+    let allowlisted = GaugeConfig {
+        voter_allowlist: Some(vec![voter1.to_owned()]),
+        ..gauge_config.clone()
+    };
+    suite.create_gauge(&gauge_contract, allowlisted).unwrap();
+    assert_eq!(
+        suite.query_can_vote(&gauge_contract, 1, voter2).unwrap(),
+        blocked_by(ContractError::VoterNotAllowed(voter2.to_owned()))
+    );
+
+    // gauge 2 is closed for voting
+    suite
+        .create_gauge(&gauge_contract, gauge_config.clone())
+        .unwrap();
+    suite
+        .close_voting(&gauge_contract, suite.owner.clone(), 2)
+        .unwrap();
+    assert_eq!(
+        suite.query_can_vote(&gauge_contract, 2, voter1).unwrap(),
+        blocked_by(ContractError::VotingClosed(2))
+    );
+
+    // gauge 3 is resetting
+    let resettable = GaugeConfig {
+        reset_epoch: Some(EPOCH),
+        ..gauge_config
+    };
+    suite.create_gauge(&gauge_contract, resettable).unwrap();
+    suite
+        .place_vote(&gauge_contract, voter1, 3, Some(voter1.to_owned()))
+        .unwrap();
+    suite
+        .place_vote(&gauge_contract, voter2, 3, Some(voter2.to_owned()))
+        .unwrap();
+    suite.advance_time(EPOCH);
+    suite.reset_gauge("someone", &gauge_contract, 3, 1).unwrap();
+    assert_eq!(
+        suite.query_can_vote(&gauge_contract, 3, voter1).unwrap(),
+        blocked_by(ContractError::GaugeResetting(3))
+    );
+
+    assert_eq!(
+        suite.query_can_vote(&gauge_contract, 4, voter1).unwrap(),
+        blocked_by(ContractError::GaugeNotFound { gauge: 4 })
+    );
+}
+
+#[test]
+fn max_voters_limits_new_voters() {
+    let voter1 = "voter1";
+    let voter2 = "voter2";
+    let voter3 = "voter3";
+    let mut suite = SuiteBuilder::new()
+        .with_voting_members(&[(voter1, 100), (voter2, 100), (voter3, 100)])
+        .build();
+
+    let gauge_contract = init_gauge(&mut suite, &[voter1, voter2, voter3]);
+    let mut gauge_config = suite
+        .instantiate_adapter_and_return_config(&[voter1, voter2], (1000, "ujuno"), None, None)
+        .unwrap();
+    gauge_config.max_voters = Some(2);
+    suite.create_gauge(&gauge_contract, gauge_config).unwrap();
+    let gauge_id = 0;
+
+    suite
+        .place_vote(&gauge_contract, voter1, gauge_id, Some(voter1.to_owned()))
+        .unwrap();
+    suite
+        .place_vote(&gauge_contract, voter2, gauge_id, Some(voter1.to_owned()))
+        .unwrap();
+
+    // limit reached, no new voters
+    let err = suite
+        .place_vote(&gauge_contract, voter3, gauge_id, Some(voter2.to_owned()))
+        .unwrap_err();
+    assert_eq!(
+        ContractError::VoterLimitReached(gauge_id),
+        err.downcast().unwrap()
+    );
+
+    // existing voters can still change their votes
+    suite
+        .place_vote(&gauge_contract, voter1, gauge_id, Some(voter2.to_owned()))
+        .unwrap();
+
+    // removing a vote frees a place
+    suite
+        .place_vote(&gauge_contract, voter2, gauge_id, None)
+        .unwrap();
+    suite
+        .place_vote(&gauge_contract, voter3, gauge_id, Some(voter2.to_owned()))
+        .unwrap();
+    assert_eq!(
+        suite.query_selected_set(&gauge_contract, gauge_id).unwrap(),
+        vec![("voter2".to_owned(), Uint128::new(200))]
+    );
+}
+
+#[test]
+fn require_full_allocation() {
+    let voter1 = "voter1";
+    let voter2 = "voter2";
+    let mut suite = SuiteBuilder::new()
+        .with_voting_members(&[(voter1, 100), (voter2, 100)])
+        .build();
+
+    let gauge_contract = init_gauge(&mut suite, &[voter1, voter2]);
+    let mut gauge_config = suite
+        .instantiate_adapter_and_return_config(&[voter1, voter2], (1000, "ujuno"), None, None)
+        .unwrap();
+    gauge_config.require_full_allocation = true;
+    suite.create_gauge(&gauge_contract, gauge_config).unwrap();
+    let gauge_id = 0;
+
+    let err = suite
+        .place_votes(
+            &gauge_contract,
+            voter1,
+            gauge_id,
+            vec![
+                (voter1.to_owned(), Decimal::percent(50)),
+                (voter2.to_owned(), Decimal::percent(40)),
+            ],
+        )
+        .unwrap_err();
+    assert_eq!(
+        ContractError::IncompleteAllocation(Decimal::percent(90)),
+        err.downcast().unwrap()
+    );
+
+    suite
+        .place_votes(
+            &gauge_contract,
+            voter1,
+            gauge_id,
+            vec![
+                (voter1.to_owned(), Decimal::percent(50)),
+                (voter2.to_owned(), Decimal::percent(50)),
+            ],
+        )
+        .unwrap();
+    assert_eq!(
+        suite.query_vote(&gauge_contract, gauge_id, voter1).unwrap(),
+        Some(multi_vote(
+            voter1,
+            &[(voter1, 50), (voter2, 50)],
+            suite.current_time()
+        ))
+    );
+
+    // the vote can still be removed
+    suite
+        .place_vote(&gauge_contract, voter1, gauge_id, None)
+        .unwrap();
+    assert_eq!(
+        suite.query_vote(&gauge_contract, gauge_id, voter1).unwrap(),
+        None
+    );
+}
+
+#[test]
+fn zero_weight_votes_are_ignored() {
+    let voter1 = "voter1";
+    let voter2 = "voter2";
+    let mut suite = SuiteBuilder::new()
+        .with_voting_members(&[(voter1, 100), (voter2, 100)])
+        .build();
+
+    let gauge_contract = init_gauge(&mut suite, &[voter1, voter2]);
+    suite
+        .instantiate_adapter_and_create_gauge(
+            gauge_contract.clone(),
+            &[voter1, voter2],
+            (1000, "ujuno"),
+            None,
+            None,
+        )
+        .unwrap();
+    let gauge_id = 0;
+
+    // only the nonzero vote is kept
+    suite
+        .place_votes(
+            &gauge_contract,
+            voter1,
+            gauge_id,
+            vec![
+                (voter1.to_owned(), Decimal::zero()),
+                (voter2.to_owned(), Decimal::percent(60)),
+            ],
+        )
+        .unwrap();
+    assert_eq!(
+        suite.query_vote(&gauge_contract, gauge_id, voter1).unwrap(),
+        Some(simple_vote(voter1, voter2, 60, suite.current_time()))
+    );
+    assert_eq!(
+        suite.query_list_options(&gauge_contract, gauge_id).unwrap(),
+        vec![
+            ("voter1".to_owned(), Uint128::zero()),
+            ("voter2".to_owned(), Uint128::new(60))
+        ]
+    );
+
+    // only zero weights remove the vote
+    suite
+        .place_votes(
+            &gauge_contract,
+            voter1,
+            gauge_id,
+            vec![(voter2.to_owned(), Decimal::zero())],
+        )
+        .unwrap();
+    assert_eq!(
+        suite.query_vote(&gauge_contract, gauge_id, voter1).unwrap(),
+        None
+    );
+    assert_eq!(
+        suite.query_list_options(&gauge_contract, gauge_id).unwrap(),
+        vec![
+            ("voter1".to_owned(), Uint128::zero()),
+            ("voter2".to_owned(), Uint128::zero())
+        ]
+    );
+
+    // nothing to remove for a voter without a vote
+    let err = suite
+        .place_votes(
+            &gauge_contract,
+            voter2,
+            gauge_id,
+            vec![(voter1.to_owned(), Decimal::zero())],
+        )
+        .unwrap_err();
+    assert_eq!(
+        ContractError::CannotRemoveNonexistingVote {},
+        err.downcast().unwrap()
+    );
+}
+
+#[test]
+fn votes_stays_the_same_after_execution() {
+    let voter1 = "voter1";
+    let voter2 = "voter2";
+    let reward_to_distribute = (1000, "ujuno");
+    let mut suite = SuiteBuilder::new()
+        .with_voting_members(&[(voter1, 100), (voter2, 100)])
+        .with_core_balance(reward_to_distribute)
+        .build();
+
+    suite.next_block();
+    let gauge_config = suite
+        .instantiate_adapter_and_return_config(&[voter1, voter2], reward_to_distribute, None, None)
+        .unwrap();
+    suite
+        .propose_update_proposal_module(voter1.to_string(), vec![gauge_config])
+        .unwrap();
+
+    suite.next_block();
+    let proposal = suite.list_proposals().unwrap()[0];
+    suite
+        .place_vote_single(voter1, proposal, Vote::Yes)
+        .unwrap();
+    suite
+        .place_vote_single(voter2, proposal, Vote::Yes)
+        .unwrap();
+
+    suite.next_block();
+    suite
+        .execute_single_proposal(voter1.to_string(), proposal)
+        .unwrap();
+    let proposal_modules = suite.query_proposal_modules().unwrap();
+    let gauge_contract = proposal_modules[0].clone();
+
+    let gauge_id = 0;
+
+    // vote for one of the options in gauge
+    suite
+        .place_vote(
+            &gauge_contract,
+            voter1.to_owned(),
+            gauge_id,
+            Some(voter1.to_owned()), // option to vote for
+        )
+        .unwrap();
+    suite
+        .place_vote(
+            &gauge_contract,
+            voter2.to_owned(),
+            gauge_id,
+            Some(voter1.to_owned()),
+        )
+        .unwrap();
+
+    let selected_set = suite.query_selected_set(&gauge_contract, gauge_id).unwrap();
+    // voter1 was option voted for with two 100 voting powers combined
+    assert_eq!(selected_set, vec![("voter1".to_owned(), Uint128::new(200))]);
+
+    // before advancing specified epoch tally won't get sampled
+    suite.advance_time(EPOCH);
+
+    assert_eq!(
+        vec![
+            simple_vote(voter1, voter1, 100, suite.current_time() - EPOCH),
+            simple_vote(voter2, voter1, 100, suite.current_time() - EPOCH)
+        ],
+        suite.query_list_votes(&gauge_contract, gauge_id).unwrap()
+    );
+    suite
+        .execute_options(&gauge_contract, voter1, gauge_id)
+        .unwrap();
+
+    assert_eq!(
+        vec![
+            simple_vote(voter1, voter1, 100, suite.current_time() - EPOCH),
+            simple_vote(voter2, voter1, 100, suite.current_time() - EPOCH)
+        ],
+        suite.query_list_votes(&gauge_contract, gauge_id).unwrap()
+    );
+    assert_eq!(
+        suite.query_vote(&gauge_contract, gauge_id, voter1).unwrap(),
+        Some(simple_vote(
+            voter1,
+            voter1,
+            100,
+            suite.current_time() - EPOCH
+        )),
+    );
+    assert_eq!(
+        suite.query_vote(&gauge_contract, gauge_id, voter2).unwrap(),
+        Some(simple_vote(
+            voter2,
+            voter1,
+            100,
+            suite.current_time() - EPOCH
+        )),
+    );
+}
+
+#[test]
+fn vote_for_max_capped_option() {
+    let voter1 = "voter1";
+    let voter2 = "voter2";
+    let mut suite = SuiteBuilder::new()
+        .with_voting_members(&[(voter1, 100), (voter2, 100)])
+        .build();
+
+    suite.next_block();
+    suite
+        .propose_update_proposal_module(voter1.to_string(), None)
+        .unwrap();
+
+    suite.next_block();
+    let proposal = suite.list_proposals().unwrap()[0];
+    suite
+        .place_vote_single(voter1, proposal, Vote::Yes)
+        .unwrap();
+    suite
+        .place_vote_single(voter2, proposal, Vote::Yes)
+        .unwrap();
+
+    suite.next_block();
+    suite
+        .execute_single_proposal(voter1.to_string(), proposal)
+        .unwrap();
+    let proposal_modules = suite.query_proposal_modules().unwrap();
+
+    let gauge_contract = proposal_modules[0].clone();
+
+    let gauge_adapter = suite
+        .instantiate_adapter_and_create_gauge(
+            gauge_contract.clone(),
+            &[voter1, voter2],
+            (1000, "ujuno"),
+            Some(Decimal::percent(10)),
+            None,
+        )
+        .unwrap();
+
+    let gauge_id = 0; // first created gauge
+
+    // wait until epoch passes
+    suite.advance_time(EPOCH);
+
+    // add more valid options to gauge adapter
+    suite.add_valid_option(&gauge_adapter, "option1").unwrap();
+    suite.add_valid_option(&gauge_adapter, "option2").unwrap();
+
+    // change vote for option added through gauge
+    suite
+        .add_option(&gauge_contract, voter1, gauge_id, "option1")
+        .unwrap();
+    suite
+        .add_option(&gauge_contract, voter1, gauge_id, "option2")
+        .unwrap();
+
+    // vote 100% voting power on 'voter1' option (100 weight)
+    suite
+        .place_vote(
+            &gauge_contract,
+            voter1,
+            gauge_id,
+            Some("option1".to_owned()),
+        )
+        .unwrap();
+    // vote 10% voting power on 'voter2' option (10 weight)
+    suite
+        .place_votes(
+            &gauge_contract,
+            voter2,
+            gauge_id,
+            vec![("option2".to_owned(), Decimal::percent(10))],
+        )
+        .unwrap();
+
+    assert_eq!(
+        vec![
+            multi_vote(voter1, &[("option1", 100)], suite.current_time()),
+            multi_vote(voter2, &[("option2", 10)], suite.current_time()),
+        ],
+        suite.query_list_votes(&gauge_contract, gauge_id).unwrap()
+    );
+
+    let selected_set = suite.query_selected_set(&gauge_contract, gauge_id).unwrap();
+    // Despite 'option1' having 100 voting power and option2 having 10 voting power,
+    // because of max vote cap set to 10% now 'option1' will have its power decreased to 10% * 110
+    // 'option2' stays at 10 voting power as it was below 10% of total votes
+    assert_eq!(
+        selected_set,
+        vec![
+            ("option1".to_owned(), Uint128::new(11)),
+            ("option2".to_owned(), Uint128::new(10))
+        ]
+    );
+}
+
+#[test]
+fn voter_activity_tracks_last_vote() {
+    let voter1 = "voter1";
+    let voter2 = "voter2";
+    let mut suite = SuiteBuilder::new()
+        .with_voting_members(&[(voter1, 100), (voter2, 100)])
+        .build();
+
+    let gauge_contract = init_gauge(&mut suite, &[voter1, voter2]);
+
+    let gauge_config = suite
+        .instantiate_adapter_and_return_config(&[voter1, voter2], (1000, "ujuno"), None, None)
+        .unwrap();
+    suite.create_gauge(&gauge_contract, gauge_config).unwrap();
+    let gauge_id = 0;
+
+    assert_eq!(
+        suite
+            .query_voter_activity(&gauge_contract, gauge_id, voter1)
+            .unwrap(),
+        None
+    );
+
+    let res = suite
+        .place_vote(&gauge_contract, voter1, gauge_id, Some(voter1.to_owned()))
+        .unwrap();
+    let first = suite.app.block_info();
+    assert!(res.has_event(
+        &Event::new("wasm").add_attribute("last_voted_at", first.time.seconds().to_string())
+    ));
+    assert_eq!(
+        suite
+            .query_voter_activity(&gauge_contract, gauge_id, voter1)
+            .unwrap(),
+        Some(VoterActivity {
+            height: first.height,
+            time: first.time,
+        })
+    );
+
+    suite.advance_time(EPOCH);
+    suite
+        .place_vote(&gauge_contract, voter1, gauge_id, Some(voter2.to_owned()))
+        .unwrap();
+    let second = suite.app.block_info();
+    assert!(second.time > first.time);
+    assert_eq!(
+        suite
+            .query_voter_activity(&gauge_contract, gauge_id, voter1)
+            .unwrap(),
+        Some(VoterActivity {
+            height: second.height,
+            time: second.time,
+        })
+    );
+
+    // voter2 never voted
+    assert_eq!(
+        suite
+            .query_voter_activity(&gauge_contract, gauge_id, voter2)
+            .unwrap(),
+        None
+    );
+}
+
+#[test]
+fn votes_changed_since_checkpoint() {
+    let voter1 = "voter1";
+    let voter2 = "voter2";
+    let voter3 = "voter3";
+    let mut suite = SuiteBuilder::new()
+        .with_voting_members(&[(voter1, 100), (voter2, 200), (voter3, 300)])
+        .build();
+
+    let gauge_contract = init_gauge(&mut suite, &[voter1, voter2, voter3]);
+
+    let gauge_config = suite
+        .instantiate_adapter_and_return_config(&[voter1, voter2], (1000, "ujuno"), None, None)
+        .unwrap();
+    suite.create_gauge(&gauge_contract, gauge_config).unwrap();
+    let gauge_id = 0;
+
+    for voter in [voter1, voter2, voter3] {
+        suite
+            .place_vote(&gauge_contract, voter, gauge_id, Some(voter1.to_owned()))
+            .unwrap();
+    }
+    let first = suite.app.block_info().height;
+    assert_eq!(
+        suite
+            .query_votes_changed_since(&gauge_contract, gauge_id, first, None, None)
+            .unwrap()
+            .len(),
+        3
+    );
+
+    // indexer takes a checkpoint, then voter3 and voter1 change their votes in later blocks
+    suite.next_block();
+    let checkpoint = suite.app.block_info().height;
+    suite
+        .place_vote(&gauge_contract, voter3, gauge_id, Some(voter2.to_owned()))
+        .unwrap();
     suite.next_block();
     suite
-        .execute_single_proposal(voter1.to_string(), proposal)
+        .place_vote(&gauge_contract, voter1, gauge_id, Some(voter2.to_owned()))
+        .unwrap();
+    let last = suite.app.block_info().height;
+
+    let changed = suite
+        .query_votes_changed_since(&gauge_contract, gauge_id, checkpoint, None, None)
+        .unwrap();
+    assert_eq!(
+        changed,
+        vec![
+            ChangedVote {
+                voter: voter3.to_owned(),
+                votes: vec![crate::state::Vote {
+                    option: voter2.to_owned(),
+                    weight: Decimal::one()
+                }],
+                power: Uint128::new(300),
+                last_updated_block: checkpoint,
+            },
+            ChangedVote {
+                voter: voter1.to_owned(),
+                votes: vec![crate::state::Vote {
+                    option: voter2.to_owned(),
+                    weight: Decimal::one()
+                }],
+                power: Uint128::new(100),
+                last_updated_block: last,
+            },
+        ]
+    );
+
+    // paginated by the voter of the last vote
+    let page = suite
+        .query_votes_changed_since(&gauge_contract, gauge_id, checkpoint, None, Some(1))
+        .unwrap();
+    assert_eq!(page, changed[..1]);
+    let page = suite
+        .query_votes_changed_since(&gauge_contract, gauge_id, checkpoint, Some(voter3), Some(1))
+        .unwrap();
+    assert_eq!(page, changed[1..]);
+    let page = suite
+        .query_votes_changed_since(&gauge_contract, gauge_id, checkpoint, Some(voter1), None)
+        .unwrap();
+    assert_eq!(page, vec![]);
+}
+
+#[test]
+fn participation_capped_when_total_power_shrinks() {
+    let voter1 = "voter1";
+    let voter2 = "voter2";
+    let voter3 = "voter3";
+    let mut suite = SuiteBuilder::new()
+        .with_voting_members(&[(voter1, 100), (voter2, 100), (voter3, 200)])
+        .with_core_balance((1000, "ujuno"))
+        .build();
+
+    let gauge_contract = init_gauge(&mut suite, &[voter1, voter2, voter3]);
+    suite
+        .instantiate_adapter_and_create_gauge(
+            gauge_contract.clone(),
+            &[voter1, voter2],
+            (1000, "ujuno"),
+            None,
+            None,
+        )
+        .unwrap();
+    let gauge_id = 0;
+
+    suite
+        .place_vote(&gauge_contract, voter1, gauge_id, Some(voter1.to_owned()))
+        .unwrap();
+    suite
+        .place_vote(&gauge_contract, voter2, gauge_id, Some(voter2.to_owned()))
+        .unwrap();
+    let participation = suite
+        .query_participation(&gauge_contract, gauge_id)
+        .unwrap();
+    assert_eq!(participation.participation, Decimal::percent(50));
+    assert!(!participation.votes_exceed_power);
+
+    // the votes of voter2 stay, but only voter1 is left
+    suite.remove_voting_members(&[voter2, voter3]).unwrap();
+    let participation = suite
+        .query_participation(&gauge_contract, gauge_id)
+        .unwrap();
+    assert_eq!(participation.votes_cast, Uint128::new(200));
+    assert_eq!(participation.total_power, Uint128::new(100));
+    assert_eq!(participation.participation, Decimal::one());
+    assert!(participation.votes_exceed_power);
+
+    suite.advance_time(EPOCH);
+    let res = suite
+        .execute_options(&gauge_contract, voter1, gauge_id)
+        .unwrap();
+    assert!(res.has_event(&Event::new("wasm").add_attribute("votes_exceed_power", "true")));
+}
+
+#[test]
+fn vote_change_fee() {
+    let voter1 = "voter1";
+    let voter2 = "voter2";
+    let mut suite = SuiteBuilder::new()
+        .with_voting_members(&[(voter1, 100), (voter2, 100)])
+        .with_core_balance((1000, "ujuno"))
+        .with_balance(voter1, (100, "ujuno"))
+        .build();
+
+    let gauge_contract = init_gauge(&mut suite, &[voter1, voter2]);
+    let mut gauge_config = suite
+        .instantiate_adapter_and_return_config(&[voter1, voter2], (1000, "ujuno"), None, None)
+        .unwrap();
+    gauge_config.vote_change_fee = Some(coin(10, "ujuno"));
+    suite.create_gauge(&gauge_contract, gauge_config).unwrap();
+    let gauge_id = 0;
+    let core = suite.core.to_string();
+
+    // first vote of the epoch is free
+    suite
+        .place_vote(&gauge_contract, voter1, gauge_id, Some(voter1.to_owned()))
+        .unwrap();
+
+    // changing it requires the fee
+    let err = suite
+        .place_vote(&gauge_contract, voter1, gauge_id, Some(voter2.to_owned()))
+        .unwrap_err();
+    assert_eq!(
+        ContractError::VoteChangeFeeRequired("10ujuno".to_owned()),
+        err.downcast().unwrap()
+    );
+    let err = suite
+        .place_vote_with_funds(
+            &gauge_contract,
+            voter1,
+            gauge_id,
+            Some(voter2.to_owned()),
+            &[coin(5, "ujuno")],
+        )
+        .unwrap_err();
+    assert_eq!(
+        ContractError::VoteChangeFeeRequired("10ujuno".to_owned()),
+        err.downcast().unwrap()
+    );
+    suite
+        .place_vote_with_funds(
+            &gauge_contract,
+            voter1,
+            gauge_id,
+            Some(voter2.to_owned()),
+            &[coin(10, "ujuno")],
+        )
+        .unwrap();
+    assert_eq!(suite.query_balance(voter1, "ujuno").unwrap(), 90);
+    assert_eq!(suite.query_balance(&core, "ujuno").unwrap(), 1010);
+
+    // others still vote for free, removing is free as well
+    suite
+        .place_vote(&gauge_contract, voter2, gauge_id, Some(voter2.to_owned()))
+        .unwrap();
+    suite
+        .place_vote(&gauge_contract, voter2, gauge_id, None)
+        .unwrap();
+
+    // in the next epoch, the first vote is free again
+    suite.advance_time(EPOCH);
+    suite
+        .execute_options(&gauge_contract, voter1, gauge_id)
+        .unwrap();
+    suite
+        .place_vote(&gauge_contract, voter1, gauge_id, Some(voter1.to_owned()))
+        .unwrap();
+    assert_eq!(suite.query_balance(voter1, "ujuno").unwrap(), 90);
+}
+
+#[test]
+fn vote_change_fee_refunds_overpayment() {
+    let voter1 = "voter1";
+    let voter2 = "voter2";
+    let mut suite = SuiteBuilder::new()
+        .with_voting_members(&[(voter1, 100), (voter2, 100)])
+        .with_core_balance((1000, "ujuno"))
+        .with_balance(voter1, (100, "ujuno"))
+        .build();
+
+    let gauge_contract = init_gauge(&mut suite, &[voter1, voter2]);
+    let mut gauge_config = suite
+        .instantiate_adapter_and_return_config(&[voter1, voter2], (1000, "ujuno"), None, None)
+        .unwrap();
+    gauge_config.vote_change_fee = Some(coin(10, "ujuno"));
+    suite.create_gauge(&gauge_contract, gauge_config).unwrap();
+    let gauge_id = 0;
+    let core = suite.core.to_string();
+
+    // no fee is due for the first vote, the funds are sent back
+    suite
+        .place_vote_with_funds(
+            &gauge_contract,
+            voter1,
+            gauge_id,
+            Some(voter1.to_owned()),
+            &[coin(20, "ujuno")],
+        )
+        .unwrap();
+    assert_eq!(suite.query_balance(voter1, "ujuno").unwrap(), 100);
+
+    // only the fee is taken from an overpayment
+    suite
+        .place_vote_with_funds(
+            &gauge_contract,
+            voter1,
+            gauge_id,
+            Some(voter2.to_owned()),
+            &[coin(25, "ujuno")],
+        )
+        .unwrap();
+    assert_eq!(suite.query_balance(voter1, "ujuno").unwrap(), 90);
+    assert_eq!(suite.query_balance(&core, "ujuno").unwrap(), 1010);
+    assert_eq!(
+        suite
+            .query_balance(gauge_contract.as_str(), "ujuno")
+            .unwrap(),
+        0
+    );
+}
+
+#[test]
+fn vote_distribution_histogram() {
+    let voters = ["voter1", "voter2", "voter3", "voter4", "voter5"];
+    let mut suite = SuiteBuilder::new()
+        .with_voting_members(&[
+            (voters[0], 100),
+            (voters[1], 200),
+            (voters[2], 300),
+            (voters[3], 1000),
+            (voters[4], 50),
+        ])
+        .build();
+
+    let gauge_contract = init_gauge(&mut suite, &voters);
+    suite
+        .instantiate_adapter_and_create_gauge(
+            gauge_contract.clone(),
+            &voters,
+            (1000, "ujuno"),
+            None,
+            None,
+        )
+        .unwrap();
+    let gauge_id = 0;
+
+    let res = suite
+        .query_vote_distribution(&gauge_contract, gauge_id, 4)
+        .unwrap();
+    assert_eq!(res.buckets, vec![]);
+
+    // voter5 does not vote
+    for voter in &voters[..4] {
+        suite
+            .place_vote(&gauge_contract, *voter, gauge_id, Some(voter.to_string()))
+            .unwrap();
+    }
+
+    let res = suite
+        .query_vote_distribution(&gauge_contract, gauge_id, 4)
+        .unwrap();
+    let bucket = |min: u128, max: u128, voters: u32| PowerBucket {
+        min: Uint128::new(min),
+        max: Uint128::new(max),
+        voters,
+    };
+    assert_eq!(
+        res.buckets,
+        vec![
+            bucket(0, 250, 2),
+            bucket(251, 501, 1),
+            bucket(502, 752, 0),
+            bucket(753, 1000, 1),
+        ]
+    );
+    assert!(!res.truncated);
+
+    // all voters in a single bucket
+    let res = suite
+        .query_vote_distribution(&gauge_contract, gauge_id, 0)
+        .unwrap();
+    assert_eq!(res.buckets, vec![bucket(0, 1000, 4)]);
+}
+
+#[test]
+fn unvoted_options() {
+    let voter1 = "voter1";
+    let voter2 = "voter2";
+    let voter3 = "voter3";
+    let mut suite = SuiteBuilder::new()
+        .with_voting_members(&[(voter1, 100), (voter2, 100)])
+        .build();
+
+    let gauge_contract = init_gauge(&mut suite, &[voter1, voter2]);
+    suite
+        .instantiate_adapter_and_create_gauge(
+            gauge_contract.clone(),
+            &[voter1, voter2, voter3],
+            (1000, "ujuno"),
+            None,
+            None,
+        )
+        .unwrap();
+    let gauge_id = 0;
+
+    // nothing voted yet
+    assert_eq!(
+        suite
+            .query_unvoted_options(&gauge_contract, gauge_id, voter1)
+            .unwrap(),
+        vec![voter1, voter2, voter3]
+    );
+
+    suite
+        .place_vote(&gauge_contract, voter1, gauge_id, Some(voter2.to_owned()))
+        .unwrap();
+    assert_eq!(
+        suite
+            .query_unvoted_options(&gauge_contract, gauge_id, voter1)
+            .unwrap(),
+        vec![voter1, voter3]
+    );
+    // other voters are not affected
+    assert_eq!(
+        suite
+            .query_unvoted_options(&gauge_contract, gauge_id, voter2)
+            .unwrap(),
+        vec![voter1, voter2, voter3]
+    );
+}
+
+#[test]
+fn recent_voters_of_current_epoch() {
+    let voter1 = "voter1";
+    let voter2 = "voter2";
+    let mut suite = SuiteBuilder::new()
+        .with_voting_members(&[(voter1, 100), (voter2, 100)])
+        .build();
+
+    let gauge_contract = init_gauge(&mut suite, &[voter1, voter2]);
+    let gauge_config = suite
+        .instantiate_adapter_and_return_config(&[voter1, voter2], (1000, "ujuno"), None, None)
+        .unwrap();
+    suite.create_gauge(&gauge_contract, gauge_config).unwrap();
+    let gauge_id = 0;
+
+    suite
+        .place_vote(&gauge_contract, voter1, gauge_id, Some(voter1.to_owned()))
+        .unwrap();
+    suite
+        .place_vote(&gauge_contract, voter2, gauge_id, Some(voter1.to_owned()))
+        .unwrap();
+    let voters = suite
+        .query_recent_voters(&gauge_contract, gauge_id, None)
+        .unwrap();
+    assert_eq!(
+        voters
+            .iter()
+            .map(|(voter, _)| voter.as_str())
+            .collect::<Vec<_>>(),
+        vec![voter1, voter2]
+    );
+    assert_eq!(
+        suite
+            .query_recent_voters(&gauge_contract, gauge_id, 1)
+            .unwrap()
+            .len(),
+        1
+    );
+    // the next page continues after the given voter
+    let page = suite
+        .query_recent_voters_after(&gauge_contract, gauge_id, voter1, None)
+        .unwrap();
+    assert_eq!(page.len(), 1);
+    assert_eq!(page[0].0, voter2);
+    assert!(suite
+        .query_recent_voters_after(&gauge_contract, gauge_id, voter2, None)
+        .unwrap()
+        .is_empty());
+
+    // a new epoch starts with the execution, only voter2 votes again
+    suite.advance_time(EPOCH);
+    suite
+        .execute_options(&gauge_contract, voter1, gauge_id)
+        .unwrap();
+    assert!(suite
+        .query_recent_voters(&gauge_contract, gauge_id, None)
+        .unwrap()
+        .is_empty());
+    suite.advance_blocks(1);
+    suite
+        .place_vote(&gauge_contract, voter2, gauge_id, Some(voter2.to_owned()))
         .unwrap();
-    let proposal_modules = suite.query_proposal_modules().unwrap();
+    let block = suite.app.block_info();
+    assert_eq!(
+        suite
+            .query_recent_voters(&gauge_contract, gauge_id, None)
+            .unwrap(),
+        vec![(
+            voter2.to_owned(),
+            VoterActivity {
+                height: block.height,
+                time: block.time,
+            }
+        )]
+    );
+}
 
-    let gauge_contract = proposal_modules[0].clone();
+#[test]
+fn votes_to_overtake() {
+    let voter1 = "voter1";
+    let voter2 = "voter2";
+    let mut suite = SuiteBuilder::new()
+        .with_voting_members(&[(voter1, 100), (voter2, 250)])
+        .build();
 
-    let gauge_adapter = suite
+    let gauge_contract = init_gauge(&mut suite, &[voter1, voter2]);
+    let gauge_config = suite
+        .instantiate_adapter_and_return_config(&[voter1, voter2], (1000, "ujuno"), None, None)
+        .unwrap();
+    suite.create_gauge(&gauge_contract, gauge_config).unwrap();
+    let gauge_id = 0;
+
+    suite
+        .place_vote(&gauge_contract, voter1, gauge_id, Some(voter1.to_owned()))
+        .unwrap();
+    suite
+        .place_vote(&gauge_contract, voter2, gauge_id, Some(voter2.to_owned()))
+        .unwrap();
+
+    // 100 votes need 151 more to exceed 250
+    let needed = suite
+        .query_votes_to_overtake(&gauge_contract, gauge_id, voter1, voter2)
+        .unwrap();
+    assert_eq!(needed, Uint128::new(151));
+    // already ahead
+    let needed = suite
+        .query_votes_to_overtake(&gauge_contract, gauge_id, voter2, voter1)
+        .unwrap();
+    assert_eq!(needed, Uint128::zero());
+
+    let err = suite
+        .query_votes_to_overtake(&gauge_contract, gauge_id, voter1, "unknown")
+        .unwrap_err();
+    assert!(err
+        .to_string()
+        .contains("Option unknown not found in gauge 0"));
+}
+
+#[test]
+fn list_only_active_votes() {
+    let voter1 = "voter1";
+    let voter2 = "voter2";
+    let mut suite = SuiteBuilder::new()
+        .with_voting_members(&[(voter1, 100), (voter2, 100)])
+        .build();
+
+    let gauge_contract = init_gauge(&mut suite, &[voter1, voter2]);
+    suite
         .instantiate_adapter_and_create_gauge(
             gauge_contract.clone(),
             &[voter1, voter2],
             (1000, "ujuno"),
-            Some(Decimal::percent(10)),
+            None,
             None,
         )
         .unwrap();
+    let gauge_id = 0;
 
-    let gauge_id = 0; // first created gauge
-
-    // wait until epoch passes
-    suite.advance_time(EPOCH);
+    suite
+        .place_vote(&gauge_contract, voter1, gauge_id, Some(voter1.to_owned()))
+        .unwrap();
+    suite
+        .place_vote(&gauge_contract, voter2, gauge_id, Some(voter2.to_owned()))
+        .unwrap();
 
-    // add more valid options to gauge adapter
-    suite.add_valid_option(&gauge_adapter, "option1").unwrap();
-    suite.add_valid_option(&gauge_adapter, "option2").unwrap();
+    // voter2 loses all power, but the vote stays
+    suite.remove_voting_members(&[voter2]).unwrap();
+    suite.next_block();
 
-    // change vote for option added through gauge
-    suite
-        .add_option(&gauge_contract, voter1, gauge_id, "option1")
+    let voters = |votes: Vec<VoteInfo>| votes.into_iter().map(|v| v.voter).collect::<Vec<_>>();
+    let votes = suite
+        .query_list_votes_filtered(&gauge_contract, gauge_id, false)
+        .unwrap();
+    assert_eq!(voters(votes), vec![voter1, voter2]);
+    let votes = suite
+        .query_list_votes_filtered(&gauge_contract, gauge_id, true)
         .unwrap();
+    assert_eq!(voters(votes), vec![voter1]);
+}
+
+#[test]
+fn place_votes_in_basis_points() {
+    let voter1 = "voter1";
+    let voter2 = "voter2";
+    let mut suite = SuiteBuilder::new()
+        .with_voting_members(&[(voter1, 100), (voter2, 100)])
+        .build();
+
+    let gauge_contract = init_gauge(&mut suite, &[voter1, voter2]);
     suite
-        .add_option(&gauge_contract, voter1, gauge_id, "option2")
+        .instantiate_adapter_and_create_gauge(
+            gauge_contract.clone(),
+            &[voter1, voter2],
+            (1000, "ujuno"),
+            None,
+            None,
+        )
         .unwrap();
+    let gauge_id = 0;
 
-    // vote 100% voting power on 'voter1' option (100 weight)
     suite
-        .place_vote(
+        .place_votes(
             &gauge_contract,
             voter1,
             gauge_id,
-            Some("option1".to_owned()),
+            vec![
+                (voter1.to_owned(), Decimal::percent(50)),
+                (voter2.to_owned(), Decimal::percent(50)),
+            ],
         )
         .unwrap();
-    // vote 10% voting power on 'voter2' option (10 weight)
     suite
-        .place_votes(
+        .place_votes_bps(
             &gauge_contract,
             voter2,
             gauge_id,
-            vec![("option2".to_owned(), Decimal::percent(10))],
+            &[(voter1, 5000), (voter2, 5000)],
+        )
+        .unwrap();
+    let decimal_vote = suite
+        .query_vote(&gauge_contract, gauge_id, voter1)
+        .unwrap()
+        .unwrap();
+    let bps_vote = suite
+        .query_vote(&gauge_contract, gauge_id, voter2)
+        .unwrap()
+        .unwrap();
+    assert_eq!(decimal_vote.votes, bps_vote.votes);
+    assert_eq!(
+        suite.query_list_options(&gauge_contract, gauge_id).unwrap(),
+        vec![
+            (voter1.to_owned(), Uint128::new(100)),
+            (voter2.to_owned(), Uint128::new(100)),
+        ]
+    );
+
+    let err = suite
+        .place_votes_bps(
+            &gauge_contract,
+            voter2,
+            gauge_id,
+            &[(voter1, 6000), (voter2, 5000)],
         )
+        .unwrap_err();
+    assert_eq!(
+        ContractError::TooMuchVotingWeight(Decimal::percent(110)),
+        err.downcast().unwrap()
+    );
+}
+
+#[test]
+fn resubmitted_nonce_is_ignored() {
+    let voter1 = "voter1";
+    let voter2 = "voter2";
+    let mut suite = SuiteBuilder::new()
+        .with_voting_members(&[(voter1, 100), (voter2, 100)])
+        .build();
+
+    let gauge_contract = init_gauge(&mut suite, &[voter1, voter2]);
+    suite
+        .instantiate_adapter_and_create_gauge(
+            gauge_contract.clone(),
+            &[voter1, voter2],
+            (1000, "ujuno"),
+            None,
+            None,
+        )
+        .unwrap();
+    let gauge_id = 0;
+
+    suite
+        .place_vote_with_nonce(&gauge_contract, voter1, gauge_id, voter1.to_owned(), 1)
+        .unwrap();
+    // the same nonce again is a no-op success, even with other votes
+    let res = suite
+        .place_vote_with_nonce(&gauge_contract, voter1, gauge_id, voter2.to_owned(), 1)
+        .unwrap();
+    assert!(res.has_event(&Event::new("wasm").add_attribute("duplicate_nonce", "1")));
+    let vote = suite
+        .query_vote(&gauge_contract, gauge_id, voter1)
+        .unwrap()
+        .unwrap();
+    assert_eq!(vote.votes[0].option, voter1);
+
+    // a new nonce is applied
+    suite
+        .place_vote_with_nonce(&gauge_contract, voter1, gauge_id, voter2.to_owned(), 2)
+        .unwrap();
+    let vote = suite
+        .query_vote(&gauge_contract, gauge_id, voter1)
+        .unwrap()
+        .unwrap();
+    assert_eq!(vote.votes[0].option, voter2);
+
+    // a resubmission is rejected like any other vote after voting was closed
+    let owner = suite.owner.clone();
+    suite
+        .close_voting(&gauge_contract, owner, gauge_id)
+        .unwrap();
+    let err = suite
+        .place_vote_with_nonce(&gauge_contract, voter1, gauge_id, voter2.to_owned(), 2)
+        .unwrap_err();
+    assert_eq!(
+        ContractError::VotingClosed(gauge_id),
+        err.downcast().unwrap()
+    );
+}
+
+#[test]
+fn voting_powers_with_custom_query() {
+    let voter1 = "voter1";
+    let voter2 = "voter2";
+    let voter3 = "voter3";
+    let mut suite = SuiteBuilder::new()
+        .with_voting_members(&[(voter1, 100), (voter2, 100)])
+        .build();
+
+    // voter2 is a DAO member, but has nothing staked
+    let staking = suite
+        .instantiate_staking(&[(voter1, 100), (voter3, 200)])
+        .unwrap();
+    let gauge_contract = suite
+        .instantiate_gauge_with_voting_query(&staking, VotingQuery::StakedBalanceAtHeight)
+        .unwrap();
+    assert_eq!(
+        suite.query_config(&gauge_contract).unwrap().voting_query,
+        VotingQuery::StakedBalanceAtHeight
+    );
+    let gauge_config = suite
+        .instantiate_adapter_and_return_config(&[voter1, voter2], (1000, "ujuno"), None, None)
+        .unwrap();
+    suite.create_gauge(&gauge_contract, gauge_config).unwrap();
+
+    suite
+        .place_vote(&gauge_contract, voter1, 0, Some(voter1.to_owned()))
+        .unwrap();
+    let err = suite
+        .place_vote(&gauge_contract, voter2, 0, Some(voter2.to_owned()))
+        .unwrap_err();
+    assert_eq!(
+        ContractError::NoVotingPower(voter2.to_owned()),
+        err.downcast().unwrap()
+    );
+    suite
+        .place_vote(&gauge_contract, voter3, 0, Some(voter2.to_owned()))
         .unwrap();
 
     assert_eq!(
+        suite.query_list_options(&gauge_contract, 0).unwrap(),
         vec![
-            multi_vote(voter1, &[("option1", 100)], suite.current_time()),
-            multi_vote(voter2, &[("option2", 10)], suite.current_time()),
-        ],
-        suite.query_list_votes(&gauge_contract, gauge_id).unwrap()
+            (voter1.to_owned(), Uint128::new(100)),
+            (voter2.to_owned(), Uint128::new(200)),
+        ]
     );
+    let participation = suite.query_participation(&gauge_contract, 0).unwrap();
+    assert_eq!(participation.total_power, Uint128::new(300));
+}
 
-    let selected_set = suite.query_selected_set(&gauge_contract, gauge_id).unwrap();
-    // Despite 'option1' having 100 voting power and option2 having 10 voting power,
-    // because of max vote cap set to 10% now 'option1' will have its power decreased to 10% * 110
-    // 'option2' stays at 10 voting power as it was below 10% of total votes
+#[test]
+fn tally_overflow_is_an_error() {
+    let voter1 = "voter1";
+    let voter2 = "voter2";
+    let mut suite = SuiteBuilder::new()
+        .with_voting_members(&[(voter1, 100), (voter2, 100)])
+        .build();
+
+    // together, both stakes exceed a Uint128
+    let half = u128::MAX / 2 + 1;
+    let staking = suite
+        .instantiate_staking(&[(voter1, half), (voter2, half)])
+        .unwrap();
+    let gauge_contract = suite
+        .instantiate_gauge_with_voting_query(&staking, VotingQuery::StakedBalanceAtHeight)
+        .unwrap();
+    let gauge_config = suite
+        .instantiate_adapter_and_return_config(&[voter1, voter2], (1000, "ujuno"), None, None)
+        .unwrap();
+    suite.create_gauge(&gauge_contract, gauge_config).unwrap();
+
+    suite
+        .place_vote(&gauge_contract, voter1, 0, Some(voter1.to_owned()))
+        .unwrap();
+    let err = suite
+        .place_vote(&gauge_contract, voter2, 0, Some(voter1.to_owned()))
+        .unwrap_err();
     assert_eq!(
-        selected_set,
+        ContractError::Overflow(OverflowError::new(OverflowOperation::Add, half, half)),
+        err.downcast().unwrap()
+    );
+
+    // the first vote is unchanged
+    assert_eq!(
+        suite.query_list_options(&gauge_contract, 0).unwrap(),
         vec![
-            ("option1".to_owned(), Uint128::new(11)),
-            ("option2".to_owned(), Uint128::new(10))
+            (voter1.to_owned(), Uint128::new(half)),
+            (voter2.to_owned(), Uint128::zero()),
+        ]
+    );
+}
+
+#[test]
+fn voter_missed_gauges() {
+    let voter1 = "voter1";
+    let voter2 = "voter2";
+    let mut suite = SuiteBuilder::new()
+        .with_voting_members(&[(voter1, 100), (voter2, 100)])
+        .build();
+
+    let gauge_contract = init_gauge(&mut suite, &[voter1, voter2]);
+    let gauge_config = suite
+        .instantiate_adapter_and_return_config(&[voter1, voter2], (1000, "ujuno"), None, None)
+        .unwrap();
+    // voter1 is eligible for gauges 0 and 1, but not 2
+    suite
+        .create_gauge(&gauge_contract, gauge_config.clone())
+        .unwrap();
+    suite
+        .create_gauge(&gauge_contract, gauge_config.clone())
+        .unwrap();
+    let allowlisted = GaugeConfig {
+        voter_allowlist: Some(vec![voter2.to_owned()]),
+        ..gauge_config.clone()
+    };
+    suite.create_gauge(&gauge_contract, allowlisted).unwrap();
+    // stopped gauges are left out
+    suite.create_gauge(&gauge_contract, gauge_config).unwrap();
+    suite
+        .stop_gauge(&gauge_contract, suite.owner.clone(), 3)
+        .unwrap();
+
+    suite
+        .place_vote(&gauge_contract, voter1, 0, Some(voter1.to_owned()))
+        .unwrap();
+    assert_eq!(
+        suite
+            .query_voter_missed_gauges(&gauge_contract, voter1, None)
+            .unwrap(),
+        vec![1]
+    );
+    assert_eq!(
+        suite
+            .query_voter_missed_gauges(&gauge_contract, voter2, None)
+            .unwrap(),
+        vec![0, 1, 2]
+    );
+    assert_eq!(
+        suite
+            .query_voter_missed_gauges(&gauge_contract, voter2, 2)
+            .unwrap(),
+        vec![0, 1]
+    );
+}
+
+#[test]
+fn member_changed_overflow_is_an_error() {
+    let voter1 = "voter1";
+    let voter2 = "voter2";
+    let mut suite = SuiteBuilder::new()
+        .with_voting_members(&[(voter1, 100), (voter2, 200)])
+        .build();
+
+    let gauge_contract = init_gauge(&mut suite, &[voter1, voter2]);
+    let gauge_config = suite
+        .instantiate_adapter_and_return_config(&[voter1, voter2], (1000, "ujuno"), None, None)
+        .unwrap();
+    suite.create_gauge(&gauge_contract, gauge_config).unwrap();
+
+    suite
+        .place_vote(&gauge_contract, voter1, 0, Some(voter1.to_owned()))
+        .unwrap();
+    suite
+        .place_vote(&gauge_contract, voter2, 0, Some(voter1.to_owned()))
+        .unwrap();
+
+    // growing voter2's power to the maximum pushes the tally past a Uint128
+    let err = suite
+        .member_changed_hook(
+            &gauge_contract,
+            vec![MemberDiff::new(
+                voter2,
+                Some(Uint128::new(200)),
+                Some(Uint128::MAX),
+            )],
+        )
+        .unwrap_err();
+    assert_eq!(
+        ContractError::Overflow(OverflowError::new(
+            OverflowOperation::Add,
+            300u128,
+            u128::MAX - 200
+        )),
+        err.downcast().unwrap()
+    );
+
+    // the tally is unchanged
+    assert_eq!(
+        suite.query_list_options(&gauge_contract, 0).unwrap(),
+        vec![
+            (voter1.to_owned(), Uint128::new(300)),
+            (voter2.to_owned(), Uint128::zero()),
         ]
     );
 }
@@ -1,18 +1,37 @@
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Addr, Decimal, Deps, Env, Order, StdResult, Storage, Uint128};
+use cosmwasm_std::{
+    coins, to_binary, Addr, BankMsg, BlockInfo, Coin, CosmosMsg, Decimal, Deps, Env, Order,
+    OverflowError, QuerierWrapper, StdResult, Storage, Timestamp, Uint128, WasmMsg,
+};
+use cw20::{BalanceResponse, Cw20ExecuteMsg, Cw20QueryMsg, TokenInfoResponse};
+use cw_core_interface::voting::{
+    Query as DaoQuery, TotalPowerAtHeightResponse, VotingPowerAtHeightResponse,
+};
 use cw_storage_plus::{Bound, Index, IndexList, IndexedMap, Item, Map, MultiIndex};
-use cw_utils::maybe_addr;
+use cw_utils::{maybe_addr, Expiration};
 
-use crate::msg::VoteInfo;
+use crate::msg::{
+    ChangedVote, StakedBalanceAtHeightResponse, StakingQueryMsg, TotalStakedAtHeightResponse,
+    VoteInfo,
+};
 
 /// Type alias for u64 to make the map types a bit more self-explanatory
 pub type GaugeId = u64;
 
 pub const CONFIG: Item<Config> = Item::new("config");
 pub const GAUGES: Map<GaugeId, Gauge> = Map::new("gauges");
+/// Native funds sent to a gauge with `FundGauge`, by gauge and denom
+pub const ESCROW: Map<(GaugeId, &str), Uint128> = Map::new("escrow");
+/// Index of gauges by their tags - data field is a placeholder
+pub const GAUGES_BY_TAG: Map<(&str, GaugeId), u8> = Map::new("gauges_by_tag");
+/// Index of gauges by their (main) adapter - data field is a placeholder
+pub const GAUGES_BY_ADAPTER: Map<(&Addr, GaugeId), u8> = Map::new("gauges_by_adapter");
+/// Index of gauges by their options - data field is a placeholder
+pub const GAUGES_BY_OPTION: Map<(&str, GaugeId), u8> = Map::new("gauges_by_option");
 const LAST_ID: Item<GaugeId> = Item::new("last_id");
 
-/// Get ID for gauge registration and increment value in storage
+/// Get ID for gauge registration and increment value in storage.
+/// The counter only ever increases, so IDs are never reused, even if a gauge is stopped.
 pub fn fetch_last_id(storage: &mut dyn Storage) -> StdResult<u64> {
     let last_id = LAST_ID.load(storage).unwrap_or_default();
     LAST_ID.save(storage, &(last_id + 1u64))?;
@@ -27,6 +46,16 @@ pub fn votes() -> Votes<'static> {
     Votes::new("votes", "votes__gaugeid")
 }
 
+/// Secondary index of votes by option, `(gauge, option, voter) -> weight`.
+/// Maintained by `Votes`, it can be backfilled for older votes with a migration.
+pub const VOTES_BY_OPTION: Map<(GaugeId, &str, &Addr), Decimal> = Map::new("votes_by_option");
+/// Secondary index of votes by the block they were last changed at, `(gauge, block, voter)`.
+/// Maintained by `Votes`, votes of older versions are only added once they change again.
+/// Data field is a placeholder
+pub const VOTES_BY_UPDATE: Map<(GaugeId, u64, &Addr), u8> = Map::new("votes_by_update");
+/// Last vote added to `VOTES_BY_OPTION` by an unfinished backfill migration
+pub const BACKFILL_CURSOR: Item<(Addr, GaugeId)> = Item::new("backfill_cursor");
+
 // settings for pagination
 const MAX_LIMIT: u32 = 100;
 const DEFAULT_LIMIT: u32 = 30;
@@ -39,6 +68,106 @@ pub struct Config {
     pub owner: Addr,
     /// Address of DAO core module resposible for instantiation and execution of messages
     pub dao_core: Addr,
+    /// True if all gauges are frozen, see `ExecuteMsg::PauseAll`
+    #[serde(default)]
+    pub paused: bool,
+    /// Added as `namespace` attribute to all emitted events, see `InstantiateMsg`
+    pub event_namespace: Option<String>,
+    /// Shape of the queries sent to `voting_powers`
+    #[serde(default)]
+    pub voting_query: VotingQuery,
+}
+
+/// Queries the gauge sends to its voting powers contract
+#[cw_serde]
+pub enum VotingQuery {
+    /// `VotingPowerAtHeight` and `TotalPowerAtHeight` of DAO voting modules, eg. cw4 voting
+    VotingPowerAtHeight,
+    /// `StakedBalanceAtHeight` and `TotalStakedAtHeight` of a staking contract
+    StakedBalanceAtHeight,
+    /// cw20 `Balance` and the total supply of `TokenInfo`.
+    /// Balances have no history, so the current one is used for every height
+    Cw20Balance,
+}
+
+impl Default for VotingQuery {
+    fn default() -> Self {
+        VotingQuery::VotingPowerAtHeight
+    }
+}
+
+impl VotingQuery {
+    /// Voting power of `address` at `height`, the current one for `None`
+    pub fn power(
+        &self,
+        querier: &QuerierWrapper,
+        voting_powers: &Addr,
+        address: &str,
+        height: Option<u64>,
+    ) -> StdResult<Uint128> {
+        let address = address.to_owned();
+        Ok(match self {
+            VotingQuery::VotingPowerAtHeight => {
+                querier
+                    .query_wasm_smart::<VotingPowerAtHeightResponse>(
+                        voting_powers,
+                        &DaoQuery::VotingPowerAtHeight { address, height },
+                    )?
+                    .power
+            }
+            VotingQuery::StakedBalanceAtHeight => {
+                querier
+                    .query_wasm_smart::<StakedBalanceAtHeightResponse>(
+                        voting_powers,
+                        &StakingQueryMsg::StakedBalanceAtHeight { address, height },
+                    )?
+                    .balance
+            }
+            VotingQuery::Cw20Balance => {
+                querier
+                    .query_wasm_smart::<BalanceResponse>(
+                        voting_powers,
+                        &Cw20QueryMsg::Balance { address },
+                    )?
+                    .balance
+            }
+        })
+    }
+
+    /// Total voting power at `height`, the current one for `None`
+    pub fn total_power(
+        &self,
+        querier: &QuerierWrapper,
+        voting_powers: &Addr,
+        height: Option<u64>,
+    ) -> StdResult<Uint128> {
+        Ok(match self {
+            VotingQuery::VotingPowerAtHeight => {
+                querier
+                    .query_wasm_smart::<TotalPowerAtHeightResponse>(
+                        voting_powers,
+                        &DaoQuery::TotalPowerAtHeight { height },
+                    )?
+                    .power
+            }
+            VotingQuery::StakedBalanceAtHeight => {
+                querier
+                    .query_wasm_smart::<TotalStakedAtHeightResponse>(
+                        voting_powers,
+                        &StakingQueryMsg::TotalStakedAtHeight { height },
+                    )?
+                    .total
+            }
+            VotingQuery::Cw20Balance => {
+                querier
+                    .query_wasm_smart::<TokenInfoResponse>(
+                        voting_powers,
+                        &Cw20QueryMsg::TokenInfo {},
+                    )?
+                    .total_supply
+            }
+        })
+    }
 }
 
 #[cw_serde]
@@ -47,7 +176,7 @@ pub struct Gauge {
     pub title: String,
     /// Address of contract to serve gauge-specific info (AdapterQueryMsg)
     pub adapter: Addr,
-    /// Frequency (in seconds) the gauge executes messages, typically something like 7*86400
+    /// Frequency (in `epoch_unit`) the gauge executes messages, typically something like 7*86400 seconds
     pub epoch: u64,
     /// Minimum percentage of votes needed by a given option to be in the selected set
     pub min_percent_selected: Option<Decimal>,
@@ -58,12 +187,366 @@ pub struct Gauge {
     pub max_available_percentage: Option<Decimal>,
     /// True if the gauge is stopped
     pub is_stopped: bool,
-    /// UNIX time (seconds) when next epoch can be executed. If < env.block.time then Execute can be called
+    /// UNIX time (seconds) or block height (depending on `epoch_unit`) when next epoch can be executed.
+    /// If < current time/height then Execute can be called
     pub next_epoch: u64,
     /// The last set of options selected by the gauge, `None` before the first execution
     pub last_executed_set: Option<Vec<(String, Uint128)>>,
     /// Set this in migration if the gauge should be periodically reset
     pub reset: Option<Reset>,
+    /// If set, the gauge creates the transfers of this asset to the selected options itself,
+    /// instead of asking the adapter for the messages to execute.
+    /// Allow `None` for 0-cost migration from current data
+    pub to_distribute: Option<Asset>,
+    /// True if voting was closed. The gauge can execute one more time, then it is stopped
+    #[serde(default)]
+    pub voting_closed: bool,
+    /// True if no new options can be added, while voting continues
+    #[serde(default)]
+    pub options_frozen: bool,
+    /// `(epoch, reward)` pairs sorted by epoch, each replacing `to_distribute` from that epoch on
+    #[serde(default)]
+    pub reward_schedule: Vec<(u64, Asset)>,
+    /// Number of times the gauge was executed. Gauges migrated from older versions start at 0
+    #[serde(default)]
+    pub executed_epochs: u64,
+    /// Maximum number of distinct voters, `None` if unlimited
+    pub max_voters: Option<u64>,
+    /// True if voted options are checked with the adapter again when voting
+    #[serde(default)]
+    pub validate_option_on_vote: bool,
+    /// Restricts who can vote, `None` if everyone with voting power can
+    pub voter_restriction: Option<VoterRestriction>,
+    /// If set, voting powers are weighted by the voters' tenure when executing
+    pub tenure_curve: Option<TenureCurve>,
+    /// True if the reward is split equally between the options when executing without any votes
+    #[serde(default)]
+    pub bootstrap_equal_split: bool,
+    /// True if removing an option also removes it from the votes of its voters
+    #[serde(default)]
+    pub reclaim_votes_on_remove: bool,
+    /// True if the weights of the selected options are multiplied by the adapter's multipliers
+    #[serde(default)]
+    pub adapter_multipliers: bool,
+    /// True if the adapter may override the reward of every epoch
+    #[serde(default)]
+    pub adapter_reward: bool,
+    /// True if every message of an execution is sent on its own, so failing ones are just recorded
+    #[serde(default)]
+    pub isolate_failures: bool,
+    /// Unit of `epoch` and `next_epoch`. Gauges migrated from older versions are time based
+    #[serde(default)]
+    pub epoch_unit: EpochUnit,
+    /// How the share of each option is rounded when the gauge distributes `to_distribute` itself
+    #[serde(default)]
+    pub rounding: Rounding,
+    /// Tags used to group gauges, sorted and without duplicates
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Further adapters asked for execute messages along with `adapter`.
+    /// Options, their validation and multipliers only come from `adapter`
+    #[serde(default)]
+    pub extra_adapters: Vec<Addr>,
+    /// Minimum absolute power needed by an option to be selected, on top of `min_percent_selected`
+    pub min_absolute_tally: Option<Uint128>,
+    /// How the reward is split between the qualifying options
+    #[serde(default)]
+    pub distribution_mode: DistributionMode,
+    /// Contract answering `BoostQueryMsg::Boost`, multiplying voting powers when executing
+    pub boost_source: Option<Addr>,
+    /// True if voting powers are capped at the voter's power at the start of the epoch when executing
+    #[serde(default)]
+    pub flashloan_resistant: bool,
+    /// Block height the current epoch started at, `None` for gauges of older versions
+    pub epoch_start_height: Option<u64>,
+    /// Contract receiving the whole selected set in one `DistributionHookMsg::Distribute`
+    /// instead of the adapter messages or transfers
+    pub distribution_hook: Option<Addr>,
+    /// True if options without votes for a long time are removed when executing
+    #[serde(default)]
+    pub auto_prune_zero_options: bool,
+    /// True if votes must allocate all of the voter's power
+    #[serde(default)]
+    pub require_full_allocation: bool,
+    /// Total amount the gauge may ever distribute, it is stopped once this is paid out
+    pub total_budget: Option<Coin>,
+    /// Amount of the `total_budget` denom distributed so far
+    #[serde(default)]
+    pub distributed: Uint128,
+    /// Fee for every vote after the first one of a voter in the same epoch, `None` if free
+    pub vote_change_fee: Option<Coin>,
+    /// Who may execute the gauge, anyone for gauges of older versions
+    #[serde(default)]
+    pub execute_policy: ExecutePolicy,
+    /// Maximum number of selected options that are paid, `None` if all of them
+    pub max_paid_options: Option<u32>,
+    /// What happens to votes for deprecated options on `Execute`
+    #[serde(default)]
+    pub deprecated_vote_policy: DeprecatedVotePolicy,
+    /// Smallest amount paid to an option, smaller shares go to the other options
+    pub min_distribution_per_option: Option<Uint128>,
+    /// Maximum number of options the gauge can have
+    pub max_total_options: Option<u32>,
+    /// Contract notified with the results after every `Execute`
+    pub post_execute_callback: Option<Addr>,
+    /// Epoch boundaries are snapped down to a multiple of this
+    pub align_to: Option<u64>,
+    /// Maximum share of a single voter in the tallies the selection is done with
+    pub max_voter_influence: Option<Decimal>,
+    /// Oracle price condition checked on `Execute`, the epoch is skipped if it fails
+    pub execute_condition: Option<ExecuteCondition>,
+    /// Router the native reward is swapped with on `Execute`, before it is distributed
+    pub swap_route: Option<SwapRoute>,
+}
+
+/// Highest boost multiplier a voter can get from the boost source (2.5)
+pub const MAX_BOOST_MULTIPLIER: Decimal = Decimal::raw(2_500_000_000_000_000_000);
+
+/// Allocated weight a vote may miss on gauges with `require_full_allocation` (0.0001%),
+/// so weights like `3 x 0.333333` are accepted
+pub const FULL_ALLOCATION_TOLERANCE: Decimal = Decimal::raw(1_000_000_000_000);
+
+/// Unit the epochs of a gauge are measured in, mirroring `cw_utils::Duration`
+#[cw_serde]
+pub enum EpochUnit {
+    /// Epochs are measured in seconds
+    Time,
+    /// Epochs are measured in blocks
+    Height,
+}
+
+impl Default for EpochUnit {
+    fn default() -> Self {
+        EpochUnit::Time
+    }
+}
+
+impl EpochUnit {
+    /// Current block time (in seconds) or height, depending on the unit
+    pub fn now(&self, block: &BlockInfo) -> u64 {
+        match self {
+            EpochUnit::Time => block.time.seconds(),
+            EpochUnit::Height => block.height,
+        }
+    }
+
+    /// Expiration at the given time (in seconds) or height, depending on the unit
+    pub fn expiration(&self, at: u64) -> Expiration {
+        match self {
+            EpochUnit::Time => Expiration::AtTime(Timestamp::from_seconds(at)),
+            EpochUnit::Height => Expiration::AtHeight(at),
+        }
+    }
+}
+
+/// How the selected set is built from the qualifying options
+#[cw_serde]
+pub enum DistributionMode {
+    /// Up to `max_options_selected` options, weighted by their votes
+    Proportional,
+    /// Only the option with the most votes is selected and gets everything.
+    /// `max_options_selected` and `max_available_percentage` are ignored
+    WinnerTakeAll,
+    /// Every selected option gets the same `amount` of the reward, as far as the reward covers
+    /// it. The tallies only rank the options, `max_available_percentage` is ignored
+    EqualFixed { amount: Uint128 },
+}
+
+impl Default for DistributionMode {
+    fn default() -> Self {
+        DistributionMode::Proportional
+    }
+}
+
+/// What happens on `Execute` to the power voters still give to deprecated options
+#[cw_serde]
+pub enum DeprecatedVotePolicy {
+    /// The power is left out
+    Ignore,
+    /// The power is moved to the voter's other options, proportionally to their weights.
+    /// It is left out for voters without other options
+    Redistribute,
+}
+
+impl Default for DeprecatedVotePolicy {
+    fn default() -> Self {
+        DeprecatedVotePolicy::Ignore
+    }
+}
+
+/// Who may trigger `Execute` on a gauge
+#[cw_serde]
+pub enum ExecutePolicy {
+    /// Anyone, once the epoch is over
+    Anyone,
+    /// Only the owner of the contract
+    Owner,
+    /// Only the listed addresses, eg. a trusted keeper
+    Allowlist(Vec<Addr>),
+}
+
+impl Default for ExecutePolicy {
+    fn default() -> Self {
+        ExecutePolicy::Anyone
+    }
+}
+
+impl ExecutePolicy {
+    pub fn allows(&self, sender: &Addr, owner: &Addr) -> bool {
+        match self {
+            ExecutePolicy::Anyone => true,
+            ExecutePolicy::Owner => sender == owner,
+            ExecutePolicy::Allowlist(allowed) => allowed.contains(sender),
+        }
+    }
+}
+
+/// Price condition that must hold for `Execute` to distribute
+#[cw_serde]
+pub struct ExecuteCondition {
+    /// Contract answering `OracleQueryMsg::Price`
+    pub oracle: Addr,
+    /// The epoch is skipped while the price is below this
+    pub min_price: Decimal,
+}
+
+/// Router converting the native reward, before it is distributed
+#[cw_serde]
+pub struct SwapRoute {
+    /// Contract answering `RouterQueryMsg::SimulateSwap` and executing `RouterExecuteMsg::Swap`
+    pub router: Addr,
+    /// Native denom the options are paid in
+    pub to_denom: String,
+}
+
+/// Rounding of the per-option reward amounts
+#[cw_serde]
+pub enum Rounding {
+    /// Amounts are truncated, the remainder stays with the DAO
+    Down,
+    /// Amounts are rounded to the nearest unit. If this exceeds the reward,
+    /// the biggest shares are reduced to make up for it
+    Nearest,
+}
+
+impl Default for Rounding {
+    fn default() -> Self {
+        Rounding::Down
+    }
+}
+
+impl Rounding {
+    /// Share of `amount` for the given weight
+    pub fn share(&self, amount: Uint128, weight: Decimal) -> StdResult<Uint128> {
+        let floor = amount * weight;
+        match self {
+            Rounding::Down => Ok(floor),
+            // floor(x + 1/2) == floor(2x) - floor(x)
+            Rounding::Nearest => Ok(amount.checked_mul(Uint128::new(2))? * weight - floor),
+        }
+    }
+}
+
+#[cw_serde]
+pub struct TenureCurve {
+    /// Contract answering `MembershipQueryMsg::MemberSince`
+    pub source: String,
+    /// Multiplier approached by voters with a very long tenure, must be at least 1.0
+    pub max_multiplier: Decimal,
+    /// Tenure (in seconds) at which voters get half of the bonus
+    pub half_life: u64,
+}
+
+impl TenureCurve {
+    /// Multiplier for a voter who is a member for `tenure` seconds.
+    /// It starts at 1.0 and grows towards `max_multiplier`, with the gain decaying over time.
+    pub fn multiplier(&self, tenure: u64) -> Decimal {
+        let bonus = self.max_multiplier - Decimal::one();
+        Decimal::one() + bonus * Decimal::from_ratio(tenure, tenure + self.half_life)
+    }
+}
+
+#[cw_serde]
+pub enum VoterRestriction {
+    /// Only addresses in `VOTER_ALLOWLIST` can vote
+    Allowlist {},
+    /// Only addresses proving to be part of the merkle tree with this (hex encoded) root can vote.
+    /// Once verified, they are added to `VOTER_ALLOWLIST`
+    MerkleRoot(String),
+}
+
+#[cw_serde]
+pub enum AssetType {
+    Native(String),
+    Cw20(String),
+}
+
+#[cw_serde]
+pub struct Asset {
+    pub denom: AssetType,
+    pub amount: Uint128,
+}
+
+impl Asset {
+    pub fn new_native(denom: &str, amount: u128) -> Self {
+        Self {
+            denom: AssetType::Native(denom.to_owned()),
+            amount: amount.into(),
+        }
+    }
+
+    pub fn new_cw20(denom: &str, amount: u128) -> Self {
+        Self {
+            denom: AssetType::Cw20(denom.to_owned()),
+            amount: amount.into(),
+        }
+    }
+}
+
+impl AssetType {
+    /// Native denom or address of the cw20 token
+    pub fn denom(&self) -> &str {
+        match self {
+            AssetType::Native(denom) => denom,
+            AssetType::Cw20(address) => address,
+        }
+    }
+
+    /// Amount of this asset held by `address`
+    pub fn query_balance(&self, querier: &QuerierWrapper, address: &Addr) -> StdResult<Uint128> {
+        match self {
+            AssetType::Native(denom) => Ok(querier.query_balance(address, denom)?.amount),
+            AssetType::Cw20(token) => {
+                let res: BalanceResponse = querier.query_wasm_smart(
+                    token,
+                    &Cw20QueryMsg::Balance {
+                        address: address.to_string(),
+                    },
+                )?;
+                Ok(res.balance)
+            }
+        }
+    }
+
+    /// Creates the message transferring `amount` of this asset to `recipient`
+    pub fn transfer_msg(&self, recipient: &Addr, amount: Uint128) -> StdResult<CosmosMsg> {
+        match self {
+            AssetType::Native(denom) => Ok(BankMsg::Send {
+                to_address: recipient.to_string(),
+                amount: coins(amount.u128(), denom),
+            }
+            .into()),
+            AssetType::Cw20(address) => Ok(WasmMsg::Execute {
+                contract_addr: address.to_string(),
+                msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                    recipient: recipient.to_string(),
+                    amount,
+                })?,
+                funds: vec![],
+            }
+            .into()),
+        }
+    }
 }
 
 #[cw_serde]
@@ -84,6 +567,54 @@ impl Gauge {
             .map(|r| r.last == Some(r.next))
             .unwrap_or_default()
     }
+
+    /// Returns the asset distributed in the current epoch, if the gauge distributes it itself.
+    /// That is the latest started schedule entry, or `to_distribute` if there is none.
+    pub fn current_reward(&self) -> Option<&Asset> {
+        self.reward_schedule
+            .iter()
+            .rev()
+            .find(|(epoch, _)| *epoch <= self.executed_epochs)
+            .map(|(_, reward)| reward)
+            .or(self.to_distribute.as_ref())
+    }
+
+    /// Returns what is left of the `total_budget`, if the gauge has one
+    pub fn remaining_budget(&self) -> Option<Uint128> {
+        self.total_budget
+            .as_ref()
+            .map(|budget| budget.amount.saturating_sub(self.distributed))
+    }
+
+    /// Returns the reward paid out in the current epoch.
+    /// This is the `current_reward`, limited to the remaining budget
+    pub fn epoch_reward(&self) -> Option<Asset> {
+        self.current_reward().map(|reward| Asset {
+            denom: reward.denom.clone(),
+            amount: match self.remaining_budget() {
+                Some(remaining) => reward.amount.min(remaining),
+                None => reward.amount,
+            },
+        })
+    }
+
+    /// `max_options_selected` and `max_available_percentage` to select the options with,
+    /// depending on the distribution mode
+    pub fn selection_limits(&self) -> (u32, Option<Decimal>) {
+        match self.distribution_mode {
+            DistributionMode::Proportional => {
+                (self.max_options_selected, self.max_available_percentage)
+            }
+            // on equal tallies, the order of `OPTION_BY_POINTS` decides the winner
+            DistributionMode::WinnerTakeAll => (1, None),
+            DistributionMode::EqualFixed { .. } => (self.max_options_selected, None),
+        }
+    }
+
+    /// All adapters creating the execute messages of the gauge, `adapter` first
+    pub fn adapters(&self) -> impl Iterator<Item = &Addr> {
+        std::iter::once(&self.adapter).chain(self.extra_adapters.iter())
+    }
 }
 
 #[cw_serde]
@@ -97,6 +628,8 @@ pub struct WeightedVotes {
     /// Timestamp when vote was cast.
     /// Allow `None` for 0-cost migration from current data
     pub cast: Option<u64>,
+    /// Block the votes or their power were changed at last, `None` for votes of older versions
+    pub last_updated_block: Option<u64>,
 }
 
 impl WeightedVotes {
@@ -125,6 +658,7 @@ impl Default for WeightedVotes {
             power: Uint128::zero(),
             votes: vec![],
             cast: None,
+            last_updated_block: None,
         }
     }
 }
@@ -169,6 +703,10 @@ impl<'a> Votes<'a> {
         gauge_id: GaugeId,
         vote: &WeightedVotes,
     ) -> StdResult<()> {
+        if let Some(old) = self.votes.may_load(storage, (voter, gauge_id))? {
+            remove_update_index(storage, voter, &old);
+        }
+        save_update_index(storage, voter, vote)?;
         self.votes.save(storage, (voter, gauge_id), vote)
     }
 
@@ -182,16 +720,19 @@ impl<'a> Votes<'a> {
         power: impl Into<Uint128>,
     ) -> StdResult<()> {
         let power = power.into();
-        self.votes.save(
-            storage,
-            (voter, gauge_id),
-            &WeightedVotes {
-                gauge_id,
-                power,
-                votes,
-                cast: Some(env.block.time.seconds()),
-            },
-        )
+        self.remove_indexes(storage, voter, gauge_id)?;
+        for vote in &votes {
+            VOTES_BY_OPTION.save(storage, (gauge_id, &vote.option, voter), &vote.weight)?;
+        }
+        let vote = WeightedVotes {
+            gauge_id,
+            power,
+            votes,
+            cast: Some(env.block.time.seconds()),
+            last_updated_block: Some(env.block.height),
+        };
+        save_update_index(storage, voter, &vote)?;
+        self.votes.save(storage, (voter, gauge_id), &vote)
     }
 
     pub fn remove_votes(
@@ -200,9 +741,122 @@ impl<'a> Votes<'a> {
         voter: &'a Addr,
         gauge_id: GaugeId,
     ) -> StdResult<()> {
+        self.remove_indexes(storage, voter, gauge_id)?;
         self.votes.remove(storage, (voter, gauge_id))
     }
 
+    /// Removes the stored votes of the voter from `VOTES_BY_OPTION` and `VOTES_BY_UPDATE`,
+    /// even if they are expired
+    fn remove_indexes(
+        &self,
+        storage: &mut dyn Storage,
+        voter: &'a Addr,
+        gauge_id: GaugeId,
+    ) -> StdResult<()> {
+        if let Some(old) = self.votes.may_load(storage, (voter, gauge_id))? {
+            for vote in &old.votes {
+                VOTES_BY_OPTION.remove(storage, (gauge_id, &vote.option, voter));
+            }
+            remove_update_index(storage, voter, &old);
+        }
+        Ok(())
+    }
+
+    /// Drops `option` from the votes of everyone who voted for it, keeping the rest of their split.
+    /// Voters without any other option lose their vote. Returns the number of those voters.
+    pub fn remove_option_from_votes(
+        &self,
+        storage: &mut dyn Storage,
+        gauge_id: GaugeId,
+        option: &str,
+    ) -> StdResult<u64> {
+        let voters = VOTES_BY_OPTION
+            .prefix((gauge_id, option))
+            .keys(storage, None, None, Order::Ascending)
+            .collect::<StdResult<Vec<Addr>>>()?;
+
+        let mut removed = 0;
+        for voter in &voters {
+            VOTES_BY_OPTION.remove(storage, (gauge_id, option, voter));
+            let mut vote = self.votes.load(storage, (voter, gauge_id))?;
+            vote.votes.retain(|v| v.option != option);
+            if vote.votes.is_empty() {
+                remove_update_index(storage, voter, &vote);
+                self.votes.remove(storage, (voter, gauge_id))?;
+                removed += 1;
+            } else {
+                self.votes.save(storage, (voter, gauge_id), &vote)?;
+            }
+        }
+        Ok(removed)
+    }
+
+    /// Adds up to `batch_size` of the stored votes to `VOTES_BY_OPTION`, continuing after
+    /// the last call. Returns the number of added votes and if all votes are indexed now.
+    pub fn backfill_option_index(
+        &self,
+        storage: &mut dyn Storage,
+        batch_size: u32,
+    ) -> StdResult<(u32, bool)> {
+        let cursor = BACKFILL_CURSOR.may_load(storage)?;
+        let start = cursor
+            .as_ref()
+            .map(|(voter, gauge_id)| Bound::exclusive((voter, *gauge_id)));
+        let batch = self
+            .votes
+            .range(storage, start, None, Order::Ascending)
+            .take(batch_size as usize)
+            .collect::<StdResult<Vec<_>>>()?;
+
+        for ((voter, gauge_id), vote) in &batch {
+            for v in &vote.votes {
+                VOTES_BY_OPTION.save(storage, (*gauge_id, &v.option, voter), &v.weight)?;
+            }
+        }
+
+        let finished = (batch.len() as u32) < batch_size;
+        match batch.last() {
+            Some((last, _)) if !finished => BACKFILL_CURSOR.save(storage, last)?,
+            _ => BACKFILL_CURSOR.remove(storage),
+        }
+        Ok((batch.len() as u32, finished))
+    }
+
+    /// Iterates over all votes of a gauge, including expired ones
+    pub fn range_by_gauge<'c>(
+        &self,
+        storage: &'c dyn Storage,
+        gauge_id: GaugeId,
+    ) -> Box<dyn Iterator<Item = StdResult<(Addr, WeightedVotes)>> + 'c> {
+        Box::new(
+            self.votes
+                .idx
+                .vote
+                .prefix(gauge_id)
+                .range(storage, None, None, Order::Ascending)
+                .map(|r| r.map(|((voter, _gauge), vote)| (voter, vote))),
+        )
+    }
+
+    pub fn query_voters_by_option(
+        &self,
+        deps: Deps,
+        gauge_id: GaugeId,
+        option: &str,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<Vec<(Addr, Decimal)>> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let addr = maybe_addr(deps.api, start_after)?;
+        let start = addr.as_ref().map(Bound::exclusive);
+
+        VOTES_BY_OPTION
+            .prefix((gauge_id, option))
+            .range(deps.storage, start, None, Order::Ascending)
+            .take(limit)
+            .collect()
+    }
+
     pub fn load(
         &self,
         storage: &dyn Storage,
@@ -276,16 +930,314 @@ impl<'a> Votes<'a> {
             // NIT: collect and into_iter is a bit inefficient... guess it was too complex/confusing otherwise, so fine
             .collect()
     }
+
+    /// Lists the valid votes of a gauge changed at or after `since_block`, ordered by the block
+    /// they were changed at. `start_after` is the voter of the last vote of the previous page
+    pub fn query_votes_changed_since(
+        &self,
+        deps: Deps,
+        gauge_id: GaugeId,
+        since_block: u64,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<Vec<ChangedVote>> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let addr = maybe_addr(deps.api, start_after)?;
+        let last_block = match &addr {
+            Some(addr) => self
+                .may_load(deps.storage, addr, gauge_id)?
+                .and_then(|vote| vote.last_updated_block),
+            None => None,
+        };
+        // the empty address sorts before all others of the same block
+        let empty = Addr::unchecked("");
+        let start = match (&addr, last_block) {
+            (Some(addr), Some(block)) if block >= since_block => Bound::exclusive((block, addr)),
+            _ => Bound::inclusive((since_block, &empty)),
+        };
+
+        let gauge = GAUGES.load(deps.storage, gauge_id)?;
+        VOTES_BY_UPDATE
+            .sub_prefix(gauge_id)
+            .keys(deps.storage, Some(start), None, Order::Ascending)
+            .map(|key| {
+                let (block, voter) = key?;
+                let vote = self.load(deps.storage, &voter, gauge_id)?;
+                Ok((block, voter, vote))
+            })
+            .filter(|r| match r {
+                Ok((_, _, vote)) => !vote.is_expired(&gauge),
+                Err(_) => true,
+            })
+            .take(limit)
+            .map(|r| {
+                let (block, voter, vote) = r?;
+                Ok(ChangedVote {
+                    voter: voter.into_string(),
+                    votes: vote.votes,
+                    power: vote.power,
+                    last_updated_block: block,
+                })
+            })
+            .collect()
+    }
+}
+
+fn save_update_index(
+    storage: &mut dyn Storage,
+    voter: &Addr,
+    vote: &WeightedVotes,
+) -> StdResult<()> {
+    if let Some(block) = vote.last_updated_block {
+        VOTES_BY_UPDATE.save(storage, (vote.gauge_id, block, voter), &1)?;
+    }
+    Ok(())
+}
+
+fn remove_update_index(storage: &mut dyn Storage, voter: &Addr, vote: &WeightedVotes) {
+    if let Some(block) = vote.last_updated_block {
+        VOTES_BY_UPDATE.remove(storage, (vote.gauge_id, block, voter));
+    }
 }
 
 /// Total amount of votes in all options, used to calculate min percentage.
 pub const TOTAL_CAST: Map<GaugeId, u128> = Map::new("total_power");
 
+/// Reserved option used to abstain. It counts towards participation, but never gets selected.
+pub const ABSTAIN_OPTION: &str = "__abstain__";
+/// Total amount of abstained votes, kept out of `TOTAL_CAST` so it doesn't affect selection.
+pub const TOTAL_ABSTAIN: Map<GaugeId, u128> = Map::new("total_abstain");
+
+/// Number of distinct voters with a valid vote per gauge, used to enforce `max_voters`
+pub const VOTER_COUNT: Map<GaugeId, u64> = Map::new("voter_count");
+
+/// Block of the last `PlaceVotes` of a voter, by gauge and voter.
+/// It is kept when the vote is removed, as removing is a change too.
+pub const LAST_VOTED_AT: Map<(GaugeId, &Addr), VoterActivity> = Map::new("last_voted_at");
+/// Voters by gauge and the block of their last `PlaceVotes`, the same as in `LAST_VOTED_AT`.
+/// Lets `RecentVoters` start at the current epoch instead of going through all voters
+pub const VOTERS_BY_HEIGHT: Map<(GaugeId, u64, &Addr), u8> = Map::new("voters_by_height");
+
+#[cw_serde]
+pub struct VoterActivity {
+    pub height: u64,
+    pub time: Timestamp,
+}
+
+/// Nonce of the last `PlaceVotes` of a voter that sent one, by gauge and voter
+pub const VOTE_NONCES: Map<(GaugeId, &Addr), u64> = Map::new("vote_nonces");
+
+/// Addresses allowed to vote on gauges with a `VoterRestriction` - data field is a placeholder
+pub const VOTER_ALLOWLIST: Map<(GaugeId, &Addr), u8> = Map::new("voter_allowlist");
+
+/// Last adapter option processed by an unfinished `SyncOptions`, by gauge
+pub const SYNC_CURSOR: Map<GaugeId, String> = Map::new("sync_cursor");
+
+/// Maximum number of options checked by one `ValidateOptions`
+pub const VALIDATE_OPTIONS_BATCH: usize = 30;
+/// Seconds between the starts of two `ValidateOptions` sweeps of a gauge (1 day)
+pub const VALIDATION_SWEEP_INTERVAL: u64 = 86_400;
+/// Last option checked by an unfinished `ValidateOptions` sweep, by gauge
+pub const VALIDATION_CURSOR: Map<GaugeId, String> = Map::new("validation_cursor");
+/// Time the last `ValidateOptions` sweep started at in seconds, by gauge
+pub const LAST_VALIDATION_SWEEP: Map<GaugeId, u64> = Map::new("last_validation_sweep");
+
+/// Number of executed epochs an option must have had a zero tally for, before it is pruned
+pub const AUTO_PRUNE_IDLE_EPOCHS: u64 = 4;
+/// Maximum number of options pruned by one `Execute`
+pub const AUTO_PRUNE_MAX_OPTIONS: usize = 10;
+/// Maximum number of zero tally options checked by one `Execute`
+pub const AUTO_PRUNE_SCAN_LIMIT: usize = 50;
+/// Epoch an option was first seen with a zero tally when pruning, by gauge and option.
+/// It is cleared as soon as the option gets votes again
+pub const ZERO_TALLY_SINCE: Map<(GaugeId, &str), u64> = Map::new("zero_tally_since");
+/// Last zero tally option checked by the pruning of the last `Execute`, by gauge
+pub const PRUNE_CURSOR: Map<GaugeId, String> = Map::new("prune_cursor");
+
+/// Number of votes placed in the epoch of gauges with a `vote_change_fee`, by gauge and voter.
+/// The epoch is the number of executed epochs when voting, older counts are outdated
+pub const VOTES_IN_EPOCH: Map<(GaugeId, &Addr), (u64, u32)> = Map::new("votes_in_epoch");
+
+/// Snapshot of everything the selected set and the reward of an epoch were computed from
+#[cw_serde]
+pub struct ExecutionInputs {
+    /// All voted options with their (tenure weighted, if enabled) power, sorted by power descending
+    pub tallies: Vec<(String, Uint128)>,
+    /// Total voting power at the time of execution
+    pub total_power: Uint128,
+    pub min_percent_selected: Option<Decimal>,
+    pub min_absolute_tally: Option<Uint128>,
+    pub max_options_selected: u32,
+    pub max_available_percentage: Option<Decimal>,
+    /// Reward distributed by the gauge itself, `None` if the adapter created the messages
+    pub to_distribute: Option<Asset>,
+    /// Address which called `Execute`, `None` for executions of older versions
+    pub executed_by: Option<Addr>,
+    /// Number of options of the selected set which were paid, `None` for all of them
+    pub max_paid_options: Option<u32>,
+    #[serde(default)]
+    pub distribution_mode: DistributionMode,
+    #[serde(default)]
+    pub rounding: Rounding,
+}
+
+/// Inputs of every execution, by gauge and epoch
+pub const EXECUTION_INPUTS: Map<(GaugeId, u64), ExecutionInputs> = Map::new("execution_inputs");
+
+/// What the execution of an epoch paid to the options
+#[cw_serde]
+pub enum EpochPayouts {
+    /// The gauge paid the reward itself, `(option, amount)` after the split and floor
+    Reward(Vec<(String, Uint128)>),
+    /// The adapters (or the distribution hook) created the messages for these `(option, weight)`
+    Weights(Vec<(String, Decimal)>),
+}
+
+/// Payouts of every execution, by gauge and epoch. Not recorded by older versions
+pub const EPOCH_PAYOUTS: Map<(GaugeId, u64), EpochPayouts> = Map::new("epoch_payouts");
+
+/// Number of whole epochs that passed without an execution before an epoch was executed,
+/// by gauge and epoch. They are counted down by `ExecuteEpoch` and removed at zero
+pub const MISSED_EPOCHS: Map<(GaugeId, u64), u64> = Map::new("missed_epochs");
+
+/// Message sent by an execution with `isolate_failures`, waiting for its reply
+#[cw_serde]
+pub struct PendingPayout {
+    pub gauge_id: GaugeId,
+    pub epoch: u64,
+    /// Index of the message among the messages of the execution
+    pub index: u64,
+    pub msg: CosmosMsg,
+    /// Rewards the message pays to the options, they are taken back from the records if it fails
+    pub rewards: Vec<(String, Asset)>,
+    /// Whether the rewards count towards the `distributed` budget of the gauge
+    pub counts_to_budget: bool,
+    /// Whether the rewards are part of the recorded `EPOCH_PAYOUTS` of the epoch
+    pub recorded: bool,
+}
+
+/// Pending messages by reply id, they are removed again once the reply arrives
+pub const PENDING_PAYOUTS: Map<u64, PendingPayout> = Map::new("pending_payouts");
+/// Reply id of the next pending message, unique across all gauges and executions
+pub const NEXT_PAYOUT_REPLY_ID: Item<u64> = Item::new("next_payout_reply_id");
+
+#[cw_serde]
+pub struct ExecutionFailure {
+    /// Epoch of the execution
+    pub epoch: u64,
+    /// The message that failed
+    pub msg: CosmosMsg,
+    pub error: String,
+}
+
+/// Failed messages of executions with `isolate_failures`, by gauge, epoch and message index
+pub const EXECUTION_FAILURES: Map<(GaugeId, u64, u64), ExecutionFailure> =
+    Map::new("execution_failures");
+
+/// Participation at every execution, by gauge and epoch
+pub const PARTICIPATION_HISTORY: Map<(GaugeId, u64), EpochParticipation> =
+    Map::new("participation_history");
+
+#[cw_serde]
+pub struct EpochParticipation {
+    /// Number of the executed epoch, starting at 0
+    pub epoch: u64,
+    /// Power cast on options, including abstained power
+    pub votes_power: Uint128,
+    /// Total voting power of the DAO at the time of execution
+    pub total_power: Uint128,
+}
+
+/// Number of epochs the tally history of an option is kept for
+pub const OPTION_TALLY_HISTORY_EPOCHS: u64 = 52;
+
+/// Tally of every selected option at execution, by gauge, option and slot.
+/// Slots are `epoch % OPTION_TALLY_HISTORY_EPOCHS`, so old snapshots get overwritten
+pub const OPTION_TALLY_HISTORY: Map<(GaugeId, &str, u64), OptionTallySnapshot> =
+    Map::new("option_tally_history");
+
+/// Rewards the gauge ever distributed to an option, one entry per denom, by gauge and option.
+/// They are kept when the option is removed
+pub const OPTION_LIFETIME_REWARDS: Map<(GaugeId, &str), Vec<Asset>> =
+    Map::new("option_lifetime_rewards");
+
+#[cw_serde]
+pub struct OptionTallySnapshot {
+    /// Number of the executed epoch, starting at 0
+    pub epoch: u64,
+    /// Power voted for the option when the epoch was executed
+    pub tally: Uint128,
+}
+
+/// Maximum length of an epoch label in characters
+pub const MAX_EPOCH_LABEL_LENGTH: usize = 64;
+/// Labels given to the epochs by the owner, by gauge and number of the executed epoch
+pub const EPOCH_LABELS: Map<(GaugeId, u64), String> = Map::new("epoch_labels");
+
+/// Options which cannot be selected anymore, but keep their votes - data field is a placeholder
+pub const DEPRECATED_OPTIONS: Map<(GaugeId, &str), u8> = Map::new("deprecated_options");
+
 /// Count how many points each option has per gauge
 pub const TALLY: Map<(GaugeId, &str), u128> = Map::new("tally");
 /// Sorted index of options by points, separated by gauge - data field is a placeholder
 pub const OPTION_BY_POINTS: Map<(GaugeId, u128, &str), u8> = Map::new("tally_points");
 
+/// Last option added to the option indexes by an unfinished backfill migration
+pub const OPTION_BACKFILL_CURSOR: Item<(GaugeId, String)> = Item::new("option_backfill_cursor");
+
+/// Adds up to `batch_size` of the options of all gauges to `GAUGES_BY_OPTION` and, if missing,
+/// to `OPTION_ADDED`, continuing after the last call.
+/// Returns the number of visited options and if all options are indexed now.
+pub fn backfill_option_indexes(
+    storage: &mut dyn Storage,
+    batch_size: u32,
+) -> StdResult<(u32, bool)> {
+    let cursor = OPTION_BACKFILL_CURSOR.may_load(storage)?;
+    let start = cursor
+        .as_ref()
+        .map(|(gauge, option)| Bound::exclusive((*gauge, option.as_str())));
+    let batch = TALLY
+        .keys(storage, start, None, Order::Ascending)
+        .take(batch_size as usize)
+        .collect::<StdResult<Vec<_>>>()?;
+
+    for (gauge, option) in &batch {
+        GAUGES_BY_OPTION.save(storage, (option, *gauge), &1u8)?;
+        if !OPTION_ADDED.has(storage, (*gauge, option)) {
+            index_added_option(storage, *gauge, option)?;
+        }
+    }
+
+    let finished = (batch.len() as u32) < batch_size;
+    match batch.last() {
+        Some(last) if !finished => OPTION_BACKFILL_CURSOR.save(storage, last)?,
+        _ => OPTION_BACKFILL_CURSOR.remove(storage),
+    }
+    Ok((batch.len() as u32, finished))
+}
+
+/// Position of every option in the order they were added to the gauge, by gauge and option.
+/// Options of older versions are appended in key order by the backfill migration
+pub const OPTION_ADDED: Map<(GaugeId, &str), u64> = Map::new("option_added");
+/// Options by their position in `OPTION_ADDED`
+pub const OPTION_BY_ADDED: Map<(GaugeId, u64), String> = Map::new("option_by_added");
+
+/// Appends a new option to the insertion order of the gauge
+pub fn index_added_option(
+    storage: &mut dyn Storage,
+    gauge: GaugeId,
+    option: &str,
+) -> StdResult<()> {
+    let position = OPTION_BY_ADDED
+        .prefix(gauge)
+        .keys(storage, None, None, Order::Descending)
+        .next()
+        .transpose()?
+        .map_or(0, |last| last + 1);
+    OPTION_ADDED.save(storage, (gauge, option), &position)?;
+    OPTION_BY_ADDED.save(storage, (gauge, position), &option.to_owned())
+}
+
 /// Updates the tally for one option.
 /// The first time a user votes, they get `{old_vote: 0, new_vote: power}`
 /// If they change options, call old option with `{old_vote: power, new_vote: 0}` and new option with `{old_vote: 0, new_vote: power}`
@@ -306,11 +1258,17 @@ pub fn remove_tally(storage: &mut dyn Storage, gauge: GaugeId, option: &str) ->
 
     // update main index
     TALLY.remove(storage, (gauge, option));
+    ZERO_TALLY_SINCE.remove(storage, (gauge, option));
+    GAUGES_BY_OPTION.remove(storage, (option, gauge));
+    if let Some(position) = OPTION_ADDED.may_load(storage, (gauge, option))? {
+        OPTION_ADDED.remove(storage, (gauge, option));
+        OPTION_BY_ADDED.remove(storage, (gauge, position));
+    }
 
     if let Some(old_vote) = old_vote {
         let total_cast = TOTAL_CAST.may_load(storage, gauge)?.unwrap_or_default();
         // update total cast
-        TOTAL_CAST.save(storage, gauge, &(total_cast - old_vote))?;
+        TOTAL_CAST.save(storage, gauge, &apply_vote(total_cast, old_vote, 0)?)?;
 
         // update sorted index
         OPTION_BY_POINTS.remove(storage, (gauge, old_vote, option));
@@ -329,16 +1287,21 @@ pub fn update_tallies(
     // (option, old, new)
     updates: Vec<(&str, u128, u128)>,
 ) -> StdResult<()> {
-    let mut old_votes = 0u128;
-    let mut new_votes = 0u128;
+    let mut total = TOTAL_CAST.may_load(storage, gauge)?.unwrap_or_default();
 
     for (option, old_vote, new_vote) in updates {
-        old_votes += old_vote;
-        new_votes += new_vote;
+        // abstained votes are only tracked in total, not as an option
+        if option == ABSTAIN_OPTION {
+            let abstain = TOTAL_ABSTAIN.may_load(storage, gauge)?.unwrap_or_default();
+            TOTAL_ABSTAIN.save(storage, gauge, &apply_vote(abstain, old_vote, new_vote)?)?;
+            continue;
+        }
+
+        total = apply_vote(total, old_vote, new_vote)?;
 
         // get old and new values
         let old_count = TALLY.may_load(storage, (gauge, option))?;
-        let count = old_count.unwrap_or_default() + new_vote - old_vote;
+        let count = apply_vote(old_count.unwrap_or_default(), old_vote, new_vote)?;
 
         // update main index
         TALLY.save(storage, (gauge, option), &count)?;
@@ -346,21 +1309,39 @@ pub fn update_tallies(
         // delete old secondary index (if any)
         if let Some(old) = old_count {
             OPTION_BY_POINTS.remove(storage, (gauge, old, option));
+            // the option is not idle anymore
+            if old == 0 && count > 0 {
+                ZERO_TALLY_SINCE.remove(storage, (gauge, option));
+            }
+        } else {
+            // new option
+            GAUGES_BY_OPTION.save(storage, (option, gauge), &1u8)?;
+            index_added_option(storage, gauge, option)?;
         }
         // add new secondary index
         OPTION_BY_POINTS.save(storage, (gauge, count, option), &1u8)?;
     }
 
     // update total count
-    let total = TOTAL_CAST.may_load(storage, gauge)?.unwrap_or_default();
-    let total = total + new_votes - old_votes;
     TOTAL_CAST.save(storage, gauge, &total)
 }
 
+/// Replaces `old_vote` in `count` with `new_vote`, failing instead of panicking if the
+/// result does not fit
+fn apply_vote(count: u128, old_vote: u128, new_vote: u128) -> Result<u128, OverflowError> {
+    let count = Uint128::new(count);
+    let count = if new_vote >= old_vote {
+        count.checked_add(Uint128::new(new_vote - old_vote))?
+    } else {
+        count.checked_sub(Uint128::new(old_vote - new_vote))?
+    };
+    Ok(count.u128())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use cosmwasm_std::Order;
+    use cosmwasm_std::{Order, OverflowOperation, StdError};
 
     use cosmwasm_std::testing::{mock_dependencies, mock_env};
 
@@ -418,6 +1399,89 @@ mod tests {
         assert_eq!(total, 750u128);
     }
 
+    #[test]
+    fn gauge_ids_are_monotonic() {
+        let mut deps = mock_dependencies();
+
+        assert_eq!(fetch_last_id(&mut deps.storage).unwrap(), 0);
+        assert_eq!(fetch_last_id(&mut deps.storage).unwrap(), 1);
+        assert_eq!(fetch_last_id(&mut deps.storage).unwrap(), 2);
+        assert_eq!(LAST_ID.load(&deps.storage).unwrap(), 3);
+    }
+
+    #[test]
+    fn tenure_multiplier_grows_towards_max() {
+        let curve = TenureCurve {
+            source: "membership".to_owned(),
+            max_multiplier: Decimal::percent(300),
+            half_life: 100,
+        };
+
+        assert_eq!(curve.multiplier(0), Decimal::one());
+        assert_eq!(curve.multiplier(100), Decimal::percent(200));
+        assert_eq!(curve.multiplier(300), Decimal::percent(250));
+        assert!(curve.multiplier(1_000_000) < Decimal::percent(300));
+    }
+
+    #[test]
+    fn rounding_share() {
+        let amount = Uint128::new(1000);
+        let third = Decimal::from_ratio(1u128, 3u128);
+        let two_thirds = Decimal::from_ratio(2u128, 3u128);
+
+        assert_eq!(Rounding::Down.share(amount, third).unwrap().u128(), 333);
+        assert_eq!(
+            Rounding::Down.share(amount, two_thirds).unwrap().u128(),
+            666
+        );
+        assert_eq!(Rounding::Nearest.share(amount, third).unwrap().u128(), 333);
+        assert_eq!(
+            Rounding::Nearest.share(amount, two_thirds).unwrap().u128(),
+            667
+        );
+        // halves are rounded up
+        assert_eq!(
+            Rounding::Nearest
+                .share(Uint128::new(5), Decimal::percent(50))
+                .unwrap()
+                .u128(),
+            3
+        );
+    }
+
+    #[test]
+    fn abstain_not_tallied() {
+        let mut mock_deps = mock_dependencies();
+        let deps = mock_deps.as_mut();
+
+        update_tally(deps.storage, GAUGE, OPTION1, 0, 250).unwrap();
+        update_tally(deps.storage, GAUGE, ABSTAIN_OPTION, 0, 100).unwrap();
+        update_tally(deps.storage, GAUGE, ABSTAIN_OPTION, 100, 40).unwrap();
+
+        assert!(!TALLY.has(deps.storage, (GAUGE, ABSTAIN_OPTION)));
+        assert_eq!(TOTAL_CAST.load(deps.storage, GAUGE).unwrap(), 250u128);
+        assert_eq!(TOTAL_ABSTAIN.load(deps.storage, GAUGE).unwrap(), 40u128);
+    }
+
+    #[test]
+    fn tally_overflow_fails_cleanly() {
+        let mut mock_deps = mock_dependencies();
+        let deps = mock_deps.as_mut();
+
+        let near_max = u128::MAX - 10;
+        update_tally(deps.storage, GAUGE, OPTION1, 0, near_max).unwrap();
+        // only the difference is added, so raising a near max vote works
+        update_tally(deps.storage, GAUGE, OPTION1, near_max, u128::MAX).unwrap();
+        update_tally(deps.storage, GAUGE, OPTION1, u128::MAX, near_max).unwrap();
+
+        let err = update_tally(deps.storage, GAUGE, OPTION2, 0, 20).unwrap_err();
+        assert_eq!(
+            err,
+            StdError::overflow(OverflowError::new(OverflowOperation::Add, near_max, 20))
+        );
+        assert_eq!(TOTAL_CAST.load(deps.storage, GAUGE).unwrap(), near_max);
+    }
+
     fn to_vote_info(voter: &Addr, votes: &[Vote], cast: impl Into<Option<u64>>) -> VoteInfo {
         VoteInfo {
             voter: voter.to_string(),
@@ -449,6 +1513,45 @@ mod tests {
                         next_epoch: env.block.time.seconds(),
                         last_executed_set: None,
                         reset: None,
+                        to_distribute: None,
+                        voting_closed: false,
+                        reward_schedule: vec![],
+                        executed_epochs: 0,
+                        max_voters: None,
+                        validate_option_on_vote: false,
+                        voter_restriction: None,
+                        tenure_curve: None,
+                        bootstrap_equal_split: false,
+                        reclaim_votes_on_remove: false,
+                        adapter_multipliers: false,
+                        adapter_reward: false,
+                        isolate_failures: false,
+                        epoch_unit: EpochUnit::Time,
+                        rounding: Rounding::Down,
+                        tags: vec![],
+                        extra_adapters: vec![],
+                        min_absolute_tally: None,
+                        distribution_mode: DistributionMode::Proportional,
+                        boost_source: None,
+                        flashloan_resistant: false,
+                        epoch_start_height: None,
+                        distribution_hook: None,
+                        auto_prune_zero_options: false,
+                        require_full_allocation: false,
+                        total_budget: None,
+                        distributed: Uint128::zero(),
+                        vote_change_fee: None,
+                        execute_policy: ExecutePolicy::Anyone,
+                        max_paid_options: None,
+                        deprecated_vote_policy: DeprecatedVotePolicy::Ignore,
+                        min_distribution_per_option: None,
+                        max_total_options: None,
+                        post_execute_callback: None,
+                        align_to: None,
+                        max_voter_influence: None,
+                        execute_condition: None,
+                        options_frozen: false,
+                        swap_route: None,
                     },
                 )
                 .unwrap();
@@ -464,6 +1567,7 @@ mod tests {
             power: Uint128::new(3),
             votes: votes1.clone(),
             cast: Some(env.block.time.seconds()),
+            last_updated_block: None,
         };
         votes
             .votes
@@ -480,6 +1584,7 @@ mod tests {
             power: Uint128::new(6),
             votes: votes2.clone(),
             cast: Some(env.block.time.seconds()),
+            last_updated_block: None,
         };
         votes
             .votes
@@ -496,6 +1601,7 @@ mod tests {
             power: Uint128::new(9),
             votes: votes3.clone(),
             cast: Some(env.block.time.seconds()),
+            last_updated_block: None,
         };
         votes
             .votes
@@ -511,6 +1617,7 @@ mod tests {
             power: Uint128::new(12),
             votes: votes4,
             cast: Some(env.block.time.seconds()),
+            last_updated_block: None,
         };
         votes
             .votes
@@ -526,6 +1633,7 @@ mod tests {
             power: Uint128::new(15),
             votes: votes5,
             cast: Some(env.block.time.seconds()),
+            last_updated_block: None,
         };
         votes
             .votes
@@ -576,6 +1684,45 @@ mod tests {
                     next_epoch: env.block.time.seconds(),
                     last_executed_set: None,
                     reset: None,
+                    to_distribute: None,
+                    voting_closed: false,
+                    reward_schedule: vec![],
+                    executed_epochs: 0,
+                    max_voters: None,
+                    validate_option_on_vote: false,
+                    voter_restriction: None,
+                    tenure_curve: None,
+                    bootstrap_equal_split: false,
+                    reclaim_votes_on_remove: false,
+                    adapter_multipliers: false,
+                    adapter_reward: false,
+                    isolate_failures: false,
+                    epoch_unit: EpochUnit::Time,
+                    rounding: Rounding::Down,
+                    tags: vec![],
+                    extra_adapters: vec![],
+                    min_absolute_tally: None,
+                    distribution_mode: DistributionMode::Proportional,
+                    boost_source: None,
+                    flashloan_resistant: false,
+                    epoch_start_height: None,
+                    distribution_hook: None,
+                    auto_prune_zero_options: false,
+                    require_full_allocation: false,
+                    total_budget: None,
+                    distributed: Uint128::zero(),
+                    vote_change_fee: None,
+                    execute_policy: ExecutePolicy::Anyone,
+                    max_paid_options: None,
+                    deprecated_vote_policy: DeprecatedVotePolicy::Ignore,
+                    min_distribution_per_option: None,
+                    max_total_options: None,
+                    post_execute_callback: None,
+                    align_to: None,
+                    max_voter_influence: None,
+                    execute_condition: None,
+                    options_frozen: false,
+                    swap_route: None,
                 },
             )
             .unwrap();
@@ -590,6 +1737,7 @@ mod tests {
             power: Uint128::new(3),
             votes: votes1.clone(),
             cast: Some(env.block.time.seconds()),
+            last_updated_block: None,
         };
         votes
             .votes
@@ -606,6 +1754,7 @@ mod tests {
             power: Uint128::new(6),
             votes: votes2.clone(),
             cast: Some(env.block.time.seconds()),
+            last_updated_block: None,
         };
         votes
             .votes
@@ -622,6 +1771,7 @@ mod tests {
             power: Uint128::new(9),
             votes: votes3.clone(),
             cast: Some(env.block.time.seconds()),
+            last_updated_block: None,
         };
         votes
             .votes
@@ -668,6 +1818,7 @@ mod tests {
                 weight: Decimal::percent(100),
             }],
             cast: Some(env.block.time.seconds()),
+            last_updated_block: None,
         };
         votes
             .votes
@@ -682,6 +1833,7 @@ mod tests {
                 weight: Decimal::percent(100),
             }],
             cast: Some(env.block.time.seconds()),
+            last_updated_block: None,
         };
         votes
             .votes
@@ -696,6 +1848,7 @@ mod tests {
                 weight: Decimal::percent(100),
             }],
             cast: Some(env.block.time.seconds()),
+            last_updated_block: None,
         };
         votes
             .votes